@@ -5,10 +5,27 @@ use criterion::{criterion_group, criterion_main, Criterion};
 
 use rinex::prelude::Rinex;
 
+#[cfg(feature = "rayon")]
+use std::{fs::File, io::BufReader};
+
+#[cfg(feature = "rayon")]
+use flate2::read::GzDecoder;
+
 fn rinex_parsing(path: &str) {
     let _ = Rinex::from_file(path).unwrap();
 }
 
+fn rinex_gzip_parsing(path: &str) {
+    let _ = Rinex::from_gzip_file(path).unwrap();
+}
+
+#[cfg(feature = "rayon")]
+fn rinex_gzip_parallel_parsing(path: &str) {
+    let fd = File::open(path).unwrap();
+    let mut reader = BufReader::new(GzDecoder::new(fd));
+    let _ = Rinex::parse_parallel(&mut reader).unwrap();
+}
+
 fn benchmark(c: &mut Criterion) {
     let mut parsing_grp = c.benchmark_group("parsing");
 
@@ -26,6 +43,21 @@ fn benchmark(c: &mut Criterion) {
         })
     });
 
+    // Daily multi GNSS RINEX NAV (V3)
+    parsing_grp.bench_function("NAV/V3", |b| {
+        b.iter(|| {
+            rinex_gzip_parsing("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz");
+        })
+    });
+
+    // Daily multi GNSS RINEX NAV (V3), decoded in parallel
+    #[cfg(feature = "rayon")]
+    parsing_grp.bench_function("NAV/V3/parallel", |b| {
+        b.iter(|| {
+            rinex_gzip_parallel_parsing("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz");
+        })
+    });
+
     parsing_grp.finish();
 }
 
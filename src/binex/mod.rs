@@ -1,5 +1,8 @@
 //! RINEX to BINEX serialization
-use crate::prelude::{Epoch, Header, Rinex};
+use crate::{
+    navigation::SkipReason,
+    prelude::{Epoch, Header, Rinex, SV},
+};
 use binex::prelude::{Message, Meta, MonumentGeoMetadata, MonumentGeoRecord};
 
 mod nav;
@@ -145,6 +148,15 @@ impl<'a> Iterator for RNX2BIN<'a> {
 }
 
 impl<'a> RNX2BIN<'a> {
+    /// Returns the record entries that could not be converted to BINEX so far,
+    /// as (Epoch, SV, reason). Inspect this report once the stream has been
+    /// consumed: these entries are not part of the stream.
+    pub fn skipped_report(&self) -> &[(Epoch, SV, SkipReason)] {
+        match &self.streamer {
+            TypeDependentStreamer::Nav(streamer) => streamer.skipped_report(),
+        }
+    }
+
     fn forge_monument_geo(&self) -> MonumentGeoRecord {
         let mut geo = MonumentGeoRecord::default();
         geo.epoch = self.t0;
@@ -1,6 +1,6 @@
 use crate::{
-    navigation::{Ephemeris, NavKey},
-    prelude::Rinex,
+    navigation::{Ephemeris, NavKey, SkipReason},
+    prelude::{Constellation, Epoch, Rinex, SV},
 };
 
 use binex::prelude::{Message, Meta, Record};
@@ -9,6 +9,7 @@ use binex::prelude::{Message, Meta, Record};
 pub struct Streamer<'a> {
     meta: Meta,
     ephemeris_iter: Box<dyn Iterator<Item = (&'a NavKey, &'a Ephemeris)> + 'a>,
+    skipped: Vec<(Epoch, SV, SkipReason)>,
 }
 
 impl<'a> Streamer<'a> {
@@ -16,19 +17,40 @@ impl<'a> Streamer<'a> {
         Self {
             meta: meta,
             ephemeris_iter: rinex.nav_ephemeris_frames_iter(),
+            skipped: Vec::new(),
         }
     }
+
+    /// Frames that were skipped so far.
+    pub fn skipped_report(&self) -> &[(Epoch, SV, SkipReason)] {
+        &self.skipped
+    }
 }
 
 impl<'a> Iterator for Streamer<'a> {
     type Item = Message;
     fn next(&mut self) -> Option<Self::Item> {
-        let (key, eph) = self.ephemeris_iter.next()?;
-        let frame = eph.to_binex(key.epoch, key.sv)?;
+        loop {
+            let (key, eph) = self.ephemeris_iter.next()?;
 
-        Some(Message {
-            meta: self.meta,
-            record: Record::new_ephemeris_frame(frame),
-        })
+            match eph.to_binex(key.epoch, key.sv) {
+                Some(frame) => {
+                    return Some(Message {
+                        meta: self.meta,
+                        record: Record::new_ephemeris_frame(frame),
+                    });
+                },
+                None => {
+                    let reason = match key.sv.constellation {
+                        Constellation::GPS | Constellation::Glonass | Constellation::Galileo => {
+                            SkipReason::MissingFields
+                        },
+                        constellation if constellation.is_sbas() => SkipReason::MissingFields,
+                        _ => SkipReason::NonSupportedConstellation,
+                    };
+                    self.skipped.push((key.epoch, key.sv, reason));
+                },
+            }
+        }
     }
 }
@@ -190,8 +190,8 @@ impl Carrier {
             Self::G1(None) => 1602.000_f64,
             Self::G1(Some(c)) => 1602.000_f64 + (*c as f64 * 9.0 / 16.0),
             Self::G2a => 1248.060_f64,
-            Self::G2(None) => 1246.060_f64,
-            Self::G2(Some(c)) => 1246.060_f64 + (*c as f64 * 7.0 / 16.0),
+            Self::G2(None) => 1246.000_f64,
+            Self::G2(Some(c)) => 1246.000_f64 + (*c as f64 * 7.0 / 16.0),
             Self::G3 => 1202.025_f64,
             Self::S1 => 2036.250,
             Self::U2 => 401.250,
@@ -330,8 +330,8 @@ impl Carrier {
     /// Converts to exact Glonass carrier
     pub fn with_glonass_offset(&self, offset: i8) -> Self {
         match self {
-            Self::L1 => Self::G1(Some(offset)),
-            Self::L2 => Self::G2(Some(offset)),
+            Self::L1 | Self::G1(_) => Self::G1(Some(offset)),
+            Self::L2 | Self::G2(_) => Self::G2(Some(offset)),
             other => *other,
         }
     }
@@ -1048,4 +1048,30 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn glonass_fdma_frequencies() {
+        // GLONASS ICD (edition 5.1), 3.3.1.4:
+        // f(K,L1) = 1602 MHz + K * 9/16 MHz
+        // f(K,L2) = 1246 MHz + K * 7/16 MHz
+        for (channel, l1_mhz, l2_mhz) in [
+            (-7, 1598.0625, 1242.9375),
+            (0, 1602.0, 1246.0),
+            (6, 1605.375, 1248.625),
+        ] {
+            let g1 = Carrier::G1(Some(channel));
+            let g2 = Carrier::G2(Some(channel));
+            assert_eq!(g1.frequency_mega_hz(), l1_mhz);
+            assert_eq!(g2.frequency_mega_hz(), l2_mhz);
+        }
+
+        assert_eq!(Carrier::G2(None).frequency_mega_hz(), 1246.0);
+
+        // channel may be redefined
+        assert_eq!(
+            Carrier::G2(Some(1)).with_glonass_offset(-3),
+            Carrier::G2(Some(-3))
+        );
+        assert_eq!(Carrier::L1.with_glonass_offset(2), Carrier::G1(Some(2)));
+    }
 }
@@ -1,7 +1,8 @@
 //! const value of Gnss
 
-#[cfg(feature = "nav")]
-use crate::prelude::{Constellation, SV};
+/// Speed of light in vacuum (m.s⁻¹)
+#[allow(dead_code)]
+pub(crate) const SPEED_OF_LIGHT_M_S: f64 = 299_792_458.0;
 
 #[cfg(feature = "nav")]
 pub(crate) struct GM;
@@ -12,6 +13,7 @@ impl GM {
     pub const BDS: f64 = 3.986004418E14;
     pub const GLO: f64 = 3.9860044E14;
     pub const GAL: f64 = 3.986004418E14;
+    pub const IRN: f64 = 3.986005E14;
 }
 
 #[cfg(feature = "nav")]
@@ -23,6 +25,65 @@ impl Omega {
     pub const BDS: f64 = 7.292115E-5;
     pub const GLO: f64 = 7.292115E-5;
     pub const GAL: f64 = 7.2921151467E-5;
+    pub const IRN: f64 = 7.2921151467E-5;
+}
+
+/// WGS84 reference ellipsoid
+pub(crate) struct Wgs84;
+
+impl Wgs84 {
+    /// Semi major axis (equatorial radius) in km
+    pub const SEMI_MAJOR_AXIS_KM: f64 = 6378.137;
+    /// Flattening
+    pub const FLATTENING: f64 = 1.0 / 298.257223563;
+
+    /// Semi minor axis (polar radius) in km
+    pub const fn semi_minor_axis_km() -> f64 {
+        Self::SEMI_MAJOR_AXIS_KM * (1.0 - Self::FLATTENING)
+    }
+
+    /// Converts geodetic coordinates (latitude and longitude in degrees,
+    /// altitude in meters) to ECEF coordinates in km.
+    pub fn geodetic2ecef_km(lat_deg: f64, lon_deg: f64, alt_m: f64) -> (f64, f64, f64) {
+        let e2 = Self::FLATTENING * (2.0 - Self::FLATTENING);
+        let alt_km = alt_m / 1000.0;
+
+        let (sin_lat, cos_lat) = lat_deg.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = lon_deg.to_radians().sin_cos();
+
+        // prime vertical radius of curvature
+        let n_km = Self::SEMI_MAJOR_AXIS_KM / (1.0 - e2 * sin_lat.powi(2)).sqrt();
+
+        (
+            (n_km + alt_km) * cos_lat * cos_lon,
+            (n_km + alt_km) * cos_lat * sin_lon,
+            (n_km * (1.0 - e2) + alt_km) * sin_lat,
+        )
+    }
+
+    /// Converts ECEF coordinates in km to geodetic coordinates
+    /// (latitude and longitude in degrees, altitude in meters).
+    /// Latitude is resolved iteratively, which converges to sub-millimeter
+    /// accuracy within a few iterations for terrestrial receivers.
+    pub fn ecef2geodetic_km(x_km: f64, y_km: f64, z_km: f64) -> (f64, f64, f64) {
+        let e2 = Self::FLATTENING * (2.0 - Self::FLATTENING);
+
+        let lon_rad = y_km.atan2(x_km);
+        let p_km = (x_km.powi(2) + y_km.powi(2)).sqrt();
+
+        let mut lat_rad = z_km.atan2(p_km * (1.0 - e2));
+        let mut alt_km = 0.0;
+
+        for _ in 0..10 {
+            let (sin_lat, cos_lat) = lat_rad.sin_cos();
+            let n_km = Self::SEMI_MAJOR_AXIS_KM / (1.0 - e2 * sin_lat.powi(2)).sqrt();
+
+            alt_km = p_km / cos_lat - n_km;
+            lat_rad = z_km.atan2(p_km * (1.0 - e2 * n_km / (n_km + alt_km)));
+        }
+
+        (lat_rad.to_degrees(), lon_rad.to_degrees(), alt_km * 1.0E3)
+    }
 }
 
 /// - 2 * sqrt(gm) / c / c
@@ -43,32 +104,4 @@ pub(crate) struct Constants;
 impl Constants {
     /// Maximal iteration in the iterative Kepler solver
     pub const MAX_KEPLER_ITER: u8 = 30;
-
-    /// Earth gravitation.
-    pub const fn gm(sv: SV) -> f64 {
-        match sv.constellation {
-            Constellation::BeiDou => GM::BDS,
-            Constellation::Galileo => GM::GAL,
-            Constellation::Glonass => GM::GLO,
-            _ => GM::GPS,
-        }
-    }
-    /// Earth rotation rate
-    pub const fn omega(sv: SV) -> f64 {
-        match sv.constellation {
-            Constellation::BeiDou => Omega::BDS,
-            Constellation::Galileo => Omega::GAL,
-            Constellation::Glonass => Omega::GLO,
-            _ => Omega::GPS,
-        }
-    }
-    /// Auxiliary Quantities for Calculating Relativistic Effects in Clock Correction
-    pub const fn dtr_f(sv: SV) -> f64 {
-        match sv.constellation {
-            Constellation::GPS => DtrF::GPS,
-            Constellation::BeiDou => DtrF::BDS,
-            Constellation::Galileo => DtrF::GAL,
-            _ => DtrF::GPS,
-        }
-    }
 }
@@ -1,7 +1,7 @@
 //! Epoch parsing helper
 
 use crate::{
-    prelude::{Epoch, ParsingError, TimeScale},
+    prelude::{Duration, Epoch, ParsingError, TimeScale},
     types::Type,
 };
 
@@ -213,6 +213,27 @@ pub(crate) fn parse_utc(s: &str) -> Result<Epoch, ParsingError> {
     parse_in_timescale(s, TimeScale::UTC)
 }
 
+/// Resolves the week counter of this time of week (in nanoseconds) expressed
+/// in [TimeScale], so it lies within half a week of the reference [Epoch].
+pub(crate) fn resolve_time_of_week(
+    reference: Epoch,
+    tow_nanos: u64,
+    timescale: TimeScale,
+) -> Epoch {
+    let half_week = Duration::from_days(3.5);
+    let (week, _) = reference.to_time_scale(timescale).to_time_of_week();
+
+    let t = Epoch::from_time_of_week(week, tow_nanos, timescale);
+
+    if t - reference > half_week {
+        Epoch::from_time_of_week(week.saturating_sub(1), tow_nanos, timescale)
+    } else if reference - t > half_week {
+        Epoch::from_time_of_week(week + 1, tow_nanos, timescale)
+    } else {
+        t
+    }
+}
+
 pub(crate) fn epoch_decompose(epoch: Epoch) -> (i32, u8, u8, u8, u8, u8, u32) {
     epoch.to_gregorian(epoch.time_scale)
 }
@@ -18,6 +18,10 @@ pub enum ParsingError {
     HeaderLineTooShort,
     #[error("empty epoch")]
     EmptyEpoch,
+    #[error("truncated epoch")]
+    TruncatedEpoch,
+    #[error("duplicate epoch")]
+    DuplicateEpoch,
     #[error("invalid epoch flag")]
     EpochFlag,
     #[error("number of sat")]
@@ -160,12 +164,14 @@ pub enum ParsingError {
     IonexModel,
     #[error("antex: calibration method")]
     AntexCalibrationMethod,
-    #[error("obs: hardware events not supported yet")]
+    #[error("obs: event epoch is not defined")]
     ObsHardwareEvent,
     #[error("obs: bad v2 satellites description")]
     BadV2SatellitesDescription,
     #[error("obs: numsat parsing")]
     NumSatParsing,
+    #[error("obs: unparseable signal observation line")]
+    BadSignalObservationLine,
     #[error("CRINEX error: {0}")]
     CRINEX(HatanakaError),
     #[error("bad utf-8 generated by CRINEX recovering process")]
@@ -176,6 +182,28 @@ pub enum ParsingError {
     DorisClockParsing,
     #[error("ionex scaling exponent")]
     IonexScalingExponent,
+    #[error("input error: {0}")]
+    InputError(#[from] IoError),
+    #[error("rtcm: non supported rinex type")]
+    NonSupportedRtcmType,
+}
+
+/// [ParseWarning] describes a record line (or entry) that could not be parsed
+/// and was skipped, when parsing leniently. Refer to
+/// [Rinex::from_file_lenient](crate::Rinex::from_file_lenient).
+#[derive(Debug)]
+pub struct ParseWarning {
+    /// Line number (starting at 1) in the parsed file.
+    /// For entries spanning several lines, this is the first line of the entry.
+    pub line: usize,
+    /// [ParsingError] that caused this line to be skipped
+    pub error: ParsingError,
+}
+
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
 }
 
 /// Errors that may rise in Formatting process
@@ -201,6 +229,40 @@ pub enum FormattingError {
 
     #[error("nav: missing grid defs")]
     NoGridDefinition,
+
+    #[error("output format not supported for this rinex type")]
+    UnsupportedOutputFormat,
+
+    #[error("binary message encoding error")]
+    BinaryEncoding,
+
+    #[error("failed to parse existing header")]
+    ExistingHeaderParsing,
+
+    #[error("incompatible with existing header")]
+    IncompatibleHeader,
+
+    #[error("crinex revision does not apply to this rinex revision")]
+    IncompatibleCrinexVersion,
+}
+
+/// Errors that may rise when exploiting an [Ephemeris](crate::navigation::Ephemeris) frame
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum EphemerisError {
+    #[error("missing keplerian parameters")]
+    MissingKeplerParameters,
+
+    #[error("non physical eccentricity e={0} (expecting 0 <= e < 1)")]
+    NonPhysicalEccentricity(f64),
+
+    #[error("non physical semi major axis a={0}m (expecting a > 0)")]
+    NonPhysicalSemiMajorAxis(f64),
+
+    #[error("invalid finite difference step dt={0}s (expecting dt > 0)")]
+    InvalidFiniteDifferenceStep(f64),
+
+    #[error("invalid solver tolerance {0} (expecting tolerance > 0)")]
+    InvalidSolverTolerance(f64),
 }
 
 /// General error (processing, analysis..)
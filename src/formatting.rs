@@ -0,0 +1,142 @@
+//! Formatting options
+use std::io::Write;
+
+#[cfg(feature = "binex")]
+use binex::prelude::Meta;
+
+/// Line ending style, used when generating RINEX content.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum LineEnding {
+    /// Unix style line ending (`\n`)
+    #[default]
+    LF,
+    /// Windows style line ending (`\r\n`)
+    CRLF,
+}
+
+/// [FormattingOptions] allow customizing the content generated by
+/// [Rinex::format_with_options](crate::Rinex::format_with_options),
+/// to match the output of other tools or archive requirements.
+/// The default options follow the standard RINEX specifications.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FormattingOptions {
+    /// Number of decimals of Observation RINEX signal values.
+    /// Standard is 3 (F14.3 format). Field width is not modified,
+    /// so large values may not fit with a higher precision.
+    pub observation_decimals: usize,
+    /// Use the fortran 'D' exponent for Navigation RINEX floating point values,
+    /// as the standard describes them (default), instead of 'E'.
+    /// Both are accepted when parsing.
+    pub nav_d_exponent: bool,
+    /// [LineEnding] style
+    pub line_ending: LineEnding,
+}
+
+impl Default for FormattingOptions {
+    fn default() -> Self {
+        Self {
+            observation_decimals: 3,
+            nav_d_exponent: true,
+            line_ending: LineEnding::default(),
+        }
+    }
+}
+
+impl FormattingOptions {
+    /// Copies and returns [FormattingOptions] with desired number of decimals
+    /// for Observation RINEX signal values.
+    pub fn with_observation_decimals(&self, decimals: usize) -> Self {
+        let mut s = *self;
+        s.observation_decimals = decimals;
+        s
+    }
+
+    /// Copies and returns [FormattingOptions] with either 'D' (true) or 'E' (false)
+    /// exponent for Navigation RINEX floating point values.
+    pub fn with_nav_d_exponent(&self, d_exponent: bool) -> Self {
+        let mut s = *self;
+        s.nav_d_exponent = d_exponent;
+        s
+    }
+
+    /// Copies and returns [FormattingOptions] with desired [LineEnding].
+    pub fn with_line_ending(&self, line_ending: LineEnding) -> Self {
+        let mut s = *self;
+        s.line_ending = line_ending;
+        s
+    }
+}
+
+/// [OutputFormat] describes all the formats a [Rinex](crate::Rinex) may be
+/// converted to, using [Rinex::convert](crate::Rinex::convert).
+/// Binary formats are feature dependent.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum OutputFormat {
+    /// Readable RINEX, in the revision described by the [Header](crate::prelude::Header)
+    Rinex,
+    /// Compact (Hatanaka compressed) RINEX, only applies to Observation RINEX
+    Crinex,
+    /// Stream of BINEX messages, encoded with the provided [Meta]
+    #[cfg(feature = "binex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "binex")))]
+    Binex(Meta),
+    /// Stream of RTCM messages
+    #[cfg(feature = "rtcm")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rtcm")))]
+    Rtcm,
+    /// Stream of UBX frames
+    #[cfg(feature = "ublox")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ublox")))]
+    Ubx,
+}
+
+/// [LineEndingWriter] converts `\n` line endings
+/// to the desired [LineEnding], on the fly.
+pub(crate) struct LineEndingWriter<W: Write> {
+    inner: W,
+    line_ending: LineEnding,
+}
+
+impl<W: Write> LineEndingWriter<W> {
+    pub fn new(inner: W, line_ending: LineEnding) -> Self {
+        Self { inner, line_ending }
+    }
+}
+
+impl<W: Write> Write for LineEndingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self.line_ending {
+            LineEnding::LF => self.inner.write_all(buf)?,
+            LineEnding::CRLF => {
+                for (nth, line) in buf.split(|b| *b == b'\n').enumerate() {
+                    if nth > 0 {
+                        self.inner.write_all(b"\r\n")?;
+                    }
+                    self.inner.write_all(line)?;
+                }
+            },
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LineEnding, LineEndingWriter};
+    use std::io::Write;
+
+    #[test]
+    fn crlf_line_endings() {
+        let mut writer = LineEndingWriter::new(Vec::new(), LineEnding::CRLF);
+        write!(writer, "line 1\nline 2\n\nline 4").unwrap();
+        assert_eq!(writer.inner, b"line 1\r\nline 2\r\n\r\nline 4");
+
+        let mut writer = LineEndingWriter::new(Vec::new(), LineEnding::LF);
+        write!(writer, "line 1\nline 2\n").unwrap();
+        assert_eq!(writer.inner, b"line 1\nline 2\n");
+    }
+}
@@ -78,6 +78,13 @@ impl<const M: usize> CompressorExpert<M> {
                 .sorted()
                 .collect::<Vec<_>>();
 
+            // special events describe the number of records to follow
+            let numsat = if k.flag.is_event() {
+                v.events.len()
+            } else {
+                svnn.len()
+            };
+
             if !self.epoch_compression {
                 if self.v3 {
                     write!(w, "> ")?;
@@ -103,7 +110,7 @@ impl<const M: usize> CompressorExpert<M> {
                     ss,
                     ns / 100,
                     k.flag,
-                    svnn.len(),
+                    numsat,
                 ));
             } else {
                 self.epoch_buf.push_str(&format!(
@@ -116,10 +123,24 @@ impl<const M: usize> CompressorExpert<M> {
                     ss,
                     ns / 100,
                     k.flag,
-                    svnn.len(),
+                    numsat,
                 ));
             }
 
+            // Special events are not compressed (like RNX2CRX):
+            // epoch description is followed by the special records, as is.
+            // Epoch compression restarts on the following epoch.
+            if k.flag.is_event() {
+                writeln!(w, "{}", self.epoch_buf.trim_end())?;
+
+                for event in v.events.iter() {
+                    writeln!(w, "{}", event)?;
+                }
+
+                self.epoch_buf.clear();
+                continue;
+            }
+
             // Append each SV to epoch description
             for sv in svnn.iter() {
                 self.epoch_buf.push_str(&format!("{:x}", sv));
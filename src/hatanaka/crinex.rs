@@ -10,6 +10,59 @@ use std::io::{BufWriter, Write};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+/// [CrinexVersion] describes the revision of the Hatanaka compression format.
+/// Each revision goes with one family of Observation RINEX: CRINEX1 compresses
+/// RINEX V2, while CRINEX3 compresses RINEX V3 and later.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CrinexVersion {
+    /// CRINEX 1.0, for RINEX V2
+    V1,
+    /// CRINEX 3.0, for RINEX V3 and V4
+    #[default]
+    V3,
+}
+
+impl CrinexVersion {
+    /// Returns the [CrinexVersion] that goes with this Observation RINEX [Version].
+    pub fn from_rinex_version(version: Version) -> Self {
+        if version.major < 3 {
+            Self::V1
+        } else {
+            Self::V3
+        }
+    }
+
+    /// Returns the [CrinexVersion] that this (CRINEX) [Version] describes,
+    /// if we support it.
+    pub fn from_version(version: Version) -> Option<Self> {
+        match version.major {
+            1 => Some(Self::V1),
+            3 => Some(Self::V3),
+            _ => None,
+        }
+    }
+
+    /// Returns the (CRINEX) [Version] we declare for this revision.
+    pub fn version(&self) -> Version {
+        match self {
+            Self::V1 => Version::new(1, 0),
+            Self::V3 => Version::new(3, 0),
+        }
+    }
+
+    /// Returns true if this revision is compatible with this Observation RINEX [Version].
+    pub fn is_compatible(&self, version: Version) -> bool {
+        *self == Self::from_rinex_version(version)
+    }
+}
+
+impl std::fmt::Display for CrinexVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "CRINEX{}", self.version().major)
+    }
+}
+
 /// CRINEX specifications
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -24,6 +77,34 @@ pub struct CRINEX {
     pub date: Epoch,
 }
 
+/// [CrinexStats] summarizes the decompression of a CRINEX record section.
+/// An unexpected epoch count typically indicates a truncated or corrupt file,
+/// because the decompressor cannot resynchronize past an error.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CrinexStats {
+    /// Number of compressed bytes submitted to the decompressor (record section only)
+    pub input_bytes: usize,
+
+    /// Number of bytes recovered by the decompressor
+    pub output_bytes: usize,
+
+    /// Number of epochs recovered by the decompressor
+    pub epochs: usize,
+}
+
+impl CrinexStats {
+    /// Returns the compression ratio that was achieved (recovered bytes
+    /// per compressed byte), or None if nothing was consumed.
+    pub fn compression_ratio(&self) -> Option<f64> {
+        if self.input_bytes > 0 {
+            Some(self.output_bytes as f64 / self.input_bytes as f64)
+        } else {
+            None
+        }
+    }
+}
+
 impl CRINEX {
     /// Formats [CRINEX] into [BufWriter]
     pub fn format<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
@@ -200,6 +281,7 @@ impl std::str::FromStr for CRINEX {
 
 #[cfg(test)]
 mod test {
+    use super::CrinexVersion;
     use crate::prelude::{Epoch, Version, CRINEX};
     use std::str::FromStr;
 
@@ -249,4 +331,22 @@ test                                    20-Oct-15 09:08     CRINEX PROG / DATE";
             .with_prog_date("RNX2CRX ver.4.0.7                       28-Dec-21 00:17     ")
             .unwrap();
     }
+
+    #[test]
+    fn crinex_revisions() {
+        for (rinex, revision, crinex) in [
+            (Version::new(2, 11), CrinexVersion::V1, Version::new(1, 0)),
+            (Version::new(3, 5), CrinexVersion::V3, Version::new(3, 0)),
+            (Version::new(4, 0), CrinexVersion::V3, Version::new(3, 0)),
+        ] {
+            assert_eq!(CrinexVersion::from_rinex_version(rinex), revision);
+            assert_eq!(revision.version(), crinex);
+            assert_eq!(CrinexVersion::from_version(crinex), Some(revision));
+            assert!(revision.is_compatible(rinex));
+        }
+
+        assert!(!CrinexVersion::V1.is_compatible(Version::new(3, 0)));
+        assert_eq!(CrinexVersion::from_version(Version::new(2, 0)), None);
+        assert_eq!(CrinexVersion::V1.to_string(), "CRINEX1");
+    }
 }
@@ -1,6 +1,7 @@
 //! CRINEX decompression module
 use crate::{
     hatanaka::{Error, NumDiff, TextDiff},
+    observation::EpochFlag,
     prelude::{Constellation, Observable, SV},
 };
 
@@ -46,6 +47,9 @@ pub enum State {
 
     /// Observations gathering and recovering.
     Observation,
+
+    /// Special event records, that are not compressed.
+    Event,
 }
 
 impl State {
@@ -94,7 +98,8 @@ impl State {
                 }
             },
             // Other states do not generate any data
-            // we need to consume lines to progress to states that actually produce something
+            // we need to consume lines to progress to states that actually produce something.
+            // Special events (rare) verify the buffer size themselves.
             _ => 0,
         }
     }
@@ -240,6 +245,17 @@ impl<const M: usize> DecompressorExpert<M> {
         }
     }
 
+    /// Macro to directly parse [EpochFlag] from recovered descriptor
+    fn epoch_flag(&self) -> Option<EpochFlag> {
+        let start = if self.v3 {
+            Self::V3_NUMSAT_OFFSET
+        } else {
+            Self::V1_NUMSAT_OFFSET
+        };
+
+        EpochFlag::from_str(self.epoch_descriptor[start - 1..start].trim()).ok()
+    }
+
     /// Macro to directly parse numsat from recovered descriptor
     fn epoch_numsat(&self) -> Option<usize> {
         let start = if self.v3 {
@@ -315,14 +331,15 @@ impl<const M: usize> DecompressorExpert<M> {
         }
 
         match self.state {
-            State::Epoch => self.run_epoch(line, len),
+            State::Epoch => self.run_epoch(line, len, buf),
             State::Clock => self.run_clock(line, len, buf),
             State::Observation => self.run_observation(line, len, buf),
+            State::Event => self.run_event(line, buf),
         }
     }
 
     /// Process the given line, during [State::Epoch] state.
-    fn run_epoch(&mut self, line: &str, len: usize) -> Result<usize, Error> {
+    fn run_epoch(&mut self, line: &str, len: usize, buf: &mut [u8]) -> Result<usize, Error> {
         let min_len = if self.v3 {
             State::MIN_COMPRESSED_EPOCH_SIZE_V3
         } else {
@@ -373,6 +390,19 @@ impl<const M: usize> DecompressorExpert<M> {
 
             // proceed
             self.numsat = numsat;
+
+            if self.epoch_flag().map_or(false, |flag| flag.is_event()) {
+                // Special events are not compressed: numsat describes
+                // the number of special records to follow, there is no clock line.
+                self.sv_ptr = 0;
+
+                if numsat > 0 {
+                    self.state = State::Event;
+                }
+
+                return self.format_event(buf);
+            }
+
             self.state = State::Clock;
             Ok(0)
         } else {
@@ -474,6 +504,46 @@ impl<const M: usize> DecompressorExpert<M> {
         produced
     }
 
+    /// Fills user buffer with special event description (no satellites, no clock).
+    fn format_event(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        let (marker, end) = if self.v3 {
+            (b'>', Self::V3_NUMSAT_OFFSET + 3)
+        } else {
+            (b' ', Self::V1_NUMSAT_OFFSET + 3)
+        };
+
+        if buf.len() < end + 1 {
+            return Err(Error::BufferOverflow);
+        }
+
+        let bytes = self.epoch_descriptor.as_bytes();
+
+        buf[0] = marker;
+        buf[1..end + 1].copy_from_slice(&bytes[..end]);
+        Ok(end + 1)
+    }
+
+    /// Process following line, in [State::Event].
+    /// Special records are forwarded as is.
+    fn run_event(&mut self, line: &str, buf: &mut [u8]) -> Result<usize, Error> {
+        let line = line.trim_end();
+        let size = line.len();
+
+        if size > buf.len() {
+            return Err(Error::BufferOverflow);
+        }
+
+        buf[..size].copy_from_slice(line.as_bytes());
+
+        self.sv_ptr += 1;
+
+        if self.sv_ptr == self.numsat {
+            self.state = State::Epoch;
+        }
+
+        Ok(size)
+    }
+
     /// Process following line, in [State::Clock]
     fn run_clock(&mut self, line: &str, len: usize, buf: &mut [u8]) -> Result<usize, Error> {
         let mut clock_data = Option::<i64>::None;
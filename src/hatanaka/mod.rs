@@ -23,7 +23,7 @@ mod numdiff;
 mod textdiff;
 
 pub use compressor::Compressor;
-pub use crinex::CRINEX;
+pub use crinex::{CrinexStats, CrinexVersion, CRINEX};
 
 pub use decompressor::{
     io::{DecompressorExpertIO, DecompressorIO},
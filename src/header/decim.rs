@@ -3,7 +3,7 @@ use crate::{
     prelude::{Epoch, Header, TimeScale},
 };
 
-use qc_traits::{Decimate, DecimationFilter};
+use qc_traits::{Decimate, DecimationFilter, DecimationFilterType};
 
 impl Decimate for Header {
     fn decimate(&self, f: &DecimationFilter) -> Self {
@@ -11,7 +11,22 @@ impl Decimate for Header {
         s.decimate_mut(f);
         s
     }
-    fn decimate_mut(&mut self, _: &DecimationFilter) {
+    fn decimate_mut(&mut self, f: &DecimationFilter) {
+        // declared interval should reflect the decimated record
+        if let Some(interval) = self.sampling_interval {
+            match f.filter {
+                DecimationFilterType::Modulo(r) => {
+                    self.sampling_interval = Some(interval * r as f64);
+                },
+                DecimationFilterType::Duration(dt) => {
+                    if dt > interval {
+                        let ratio = (dt.to_seconds() / interval.to_seconds()).ceil();
+                        self.sampling_interval = Some(interval * ratio);
+                    }
+                },
+            }
+        }
+
         self.program = Some(format!(
             "rs-rinex v{}",
             Self::format_pkg_version(env!("CARGO_PKG_VERSION"),)
@@ -3,7 +3,7 @@
 use crate::{
     fmt_comment, fmt_rinex,
     header::Header,
-    prelude::{Constellation, FormattingError},
+    prelude::{Constellation, FormattingError, FormattingOptions},
     types::Type,
 };
 
@@ -12,6 +12,15 @@ use std::io::{BufWriter, Write};
 impl Header {
     /// Formats [Header] into [Write]able interface, using efficient buffering.
     pub fn format<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
+        self.format_with_options(w, &FormattingOptions::default())
+    }
+
+    /// Formats [Header] like [Self::format], with custom [FormattingOptions].
+    pub fn format_with_options<W: Write>(
+        &self,
+        w: &mut BufWriter<W>,
+        options: &FormattingOptions,
+    ) -> Result<(), FormattingError> {
         const NUM_GLO_CHANNELS_PER_LINE: usize = 8;
 
         let major = self.version.major;
@@ -33,7 +42,7 @@ impl Header {
         }
 
         self.format_comments(w)?;
-        self.format_rinex_dependent(w)?;
+        self.format_rinex_dependent(w, options)?;
 
         if let Some(rcvr) = &self.rcvr {
             rcvr.format(w)?;
@@ -98,13 +107,13 @@ impl Header {
         for (index, (constellation, model)) in self.ionod_corrections.iter().enumerate() {
             if let Some(kb) = model.as_klobuchar() {
                 if major == 2 && index == 0 {
-                    kb.format_v2_header(w)?;
+                    kb.format_v2_header(w, options.nav_d_exponent)?;
                 } else if major == 3 {
-                    kb.format_v3_header(w, *constellation)?;
+                    kb.format_v3_header(w, *constellation, options.nav_d_exponent)?;
                 }
             } else if let Some(ng) = model.as_nequick_g() {
                 if major == 3 {
-                    ng.format_header(w, constellation)?;
+                    ng.format_header(w, constellation, options.nav_d_exponent)?;
                 }
             }
         }
@@ -226,11 +235,12 @@ impl Header {
     fn format_rinex_dependent<W: Write>(
         &self,
         w: &mut BufWriter<W>,
+        options: &FormattingOptions,
     ) -> Result<(), FormattingError> {
         if let Some(obs) = &self.obs {
             obs.format(w, self.version.major)
         } else if let Some(nav) = &self.nav {
-            nav.format(w, self.version.major)
+            nav.format(w, self.version.major, options.nav_d_exponent)
         } else if let Some(meteo) = &self.meteo {
             meteo.format(w)
         } else if let Some(clock) = &self.clock {
@@ -300,7 +310,7 @@ impl Header {
             writeln!(
                 w,
                 "{}",
-                fmt_rinex(&format!("{:6}", interval.to_seconds()), "INTERVAL")
+                fmt_rinex(&format!("{:10.3}", interval.to_seconds()), "INTERVAL")
             )?;
         }
         Ok(())
@@ -0,0 +1,120 @@
+//! Guarded mutable [Header] access
+
+#[cfg(feature = "log")]
+use log::error;
+
+use crate::{header::Header, types::Type, version::Version};
+
+use std::ops::{Deref, DerefMut};
+
+/// [HeaderMut] gives mutable access to the [Header] of a [Rinex](crate::Rinex),
+/// obtained with [Rinex::header_mut](crate::Rinex::header_mut).
+/// The record that follows is not modified, so
+/// the fields that define how this record should be interpreted are protected:
+/// - the [Type] cannot be modified and is restored when this guard is dropped
+/// - the [Version] may be modified, as long as it remains within the same
+///   family (V1/V2 or V3/V4): moving from one family to the other requires
+///   a different record layout and the previous [Version] is then restored.
+///
+/// The verification happens when the guard is dropped: incompatible values
+/// are silently reverted (an error is logged with the `log` feature), while
+/// all other modifications made through the guard are retained.
+pub struct HeaderMut<'a> {
+    header: &'a mut Header,
+    rinex_type: Type,
+    version: Version,
+}
+
+impl<'a> HeaderMut<'a> {
+    pub(crate) fn new(header: &'a mut Header) -> Self {
+        Self {
+            rinex_type: header.rinex_type,
+            version: header.version,
+            header,
+        }
+    }
+}
+
+impl Deref for HeaderMut<'_> {
+    type Target = Header;
+    fn deref(&self) -> &Header {
+        self.header
+    }
+}
+
+impl DerefMut for HeaderMut<'_> {
+    fn deref_mut(&mut self) -> &mut Header {
+        self.header
+    }
+}
+
+/// Reverts the [Type] and incompatible [Version] modifications.
+impl Drop for HeaderMut<'_> {
+    fn drop(&mut self) {
+        if self.header.rinex_type != self.rinex_type {
+            #[cfg(feature = "log")]
+            error!(
+                "header_mut: {} cannot be converted to {}",
+                self.rinex_type, self.header.rinex_type
+            );
+            self.header.rinex_type = self.rinex_type;
+        }
+
+        let (prev_major, major) = (self.version.major, self.header.version.major);
+
+        if (prev_major < 3) != (major < 3) {
+            #[cfg(feature = "log")]
+            error!(
+                "header_mut: incompatible revision V{} (record is V{})",
+                major, prev_major
+            );
+            self.header.version = self.version;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HeaderMut;
+    use crate::{header::Header, types::Type, version::Version};
+
+    #[test]
+    fn header_mut_drop() {
+        let mut header = Header::basic_obs().with_version(Version::new(3, 5));
+
+        // compatible modifications are retained
+        {
+            let mut guard = HeaderMut::new(&mut header);
+            guard.version = Version::new(4, 0);
+            guard.observer = Some("test".to_string());
+        }
+
+        assert_eq!(header.version, Version::new(4, 0));
+        assert_eq!(header.observer, Some("test".to_string()));
+
+        // incompatible modifications are reverted, others are retained
+        {
+            let mut guard = HeaderMut::new(&mut header);
+            guard.rinex_type = Type::MeteoData;
+            guard.version = Version::new(2, 11);
+            guard.agency = Some("agency".to_string());
+        }
+
+        assert_eq!(header.rinex_type, Type::ObservationData);
+        assert_eq!(header.version, Version::new(4, 0));
+        assert_eq!(header.agency, Some("agency".to_string()));
+
+        // reverted to the value at guard creation, not the original one
+        {
+            let mut guard = HeaderMut::new(&mut header);
+            guard.version = Version::new(3, 0);
+        }
+
+        {
+            let mut guard = HeaderMut::new(&mut header);
+            guard.version = Version::new(1, 0);
+        }
+
+        assert_eq!(header.version, Version::new(3, 0));
+    }
+}
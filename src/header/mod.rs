@@ -4,7 +4,7 @@ use crate::{
     antex::HeaderFields as AntexHeader,
     clock::HeaderFields as ClockHeader,
     hardware::{Antenna, Receiver, SvAntenna},
-    hatanaka::CRINEX,
+    hatanaka::{CrinexVersion, CRINEX},
     leap::Leap,
     marker::GeodeticMarker,
     meteo::HeaderFields as MeteoHeader,
@@ -20,8 +20,11 @@ use itertools::Itertools;
 use std::collections::HashMap;
 
 mod formatting;
+mod guard;
 mod parsing;
 
+pub use guard::HeaderMut;
+
 #[cfg(feature = "qc")]
 mod qc;
 
@@ -219,6 +222,85 @@ impl Header {
         }
     }
 
+    /// Returns true if a record described by this [Header] may be appended
+    /// to a file described by the `existing` [Header], without rewriting it.
+    /// Both must share the same [Type], major revision and [Constellation].
+    /// Each observable (per [Constellation]) or meteo sensor that we describe must
+    /// already be described by the `existing` [Header].
+    /// CRINEX files are never compatible, because the compression kernels
+    /// of the existing record cannot be recovered from the header alone.
+    pub fn is_append_compatible(&self, existing: &Self) -> bool {
+        if self.rinex_type != existing.rinex_type
+            || self.version.major != existing.version.major
+            || self.constellation != existing.constellation
+        {
+            return false;
+        }
+
+        if self.is_crinex() || existing.is_crinex() {
+            return false;
+        }
+
+        if let Some(obs) = &self.obs {
+            let existing = match &existing.obs {
+                Some(existing) => existing,
+                None => return false,
+            };
+
+            for (constellation, observables) in obs.codes.iter() {
+                match existing.codes.get(constellation) {
+                    Some(codes) => {
+                        if observables.iter().any(|obs| !codes.contains(obs)) {
+                            return false;
+                        }
+                    },
+                    None => return false,
+                }
+            }
+        }
+
+        if let Some(meteo) = &self.meteo {
+            let existing = match &existing.meteo {
+                Some(existing) => existing,
+                None => return false,
+            };
+
+            if meteo.codes.iter().any(|obs| !existing.codes.contains(obs)) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns the [CRINEX] (compression program) [Version], when this [Header]
+    /// describes a Compact (Hatanaka compressed) RINEX.
+    pub fn crinex_version(&self) -> Option<Version> {
+        let crinex = self.obs.as_ref()?.crinex.as_ref()?;
+        Some(crinex.version)
+    }
+
+    /// Returns the [CrinexVersion] (compression revision) when this [Header]
+    /// describes a Compact (Hatanaka compressed) RINEX we know how to decompress.
+    pub fn crinex_revision(&self) -> Option<CrinexVersion> {
+        CrinexVersion::from_version(self.crinex_version()?)
+    }
+
+    /// Returns the number of leap seconds declared by this [Header] ("LEAP SECONDS"),
+    /// which is the offset between GNSS timescales and UTC at the time of production.
+    /// Refer to [Rinex::leap_seconds_at](crate::Rinex::leap_seconds_at) to also take
+    /// updates that happen within the record into account.
+    pub fn leap_seconds(&self) -> Option<u32> {
+        self.leap.map(|leap| leap.leap)
+    }
+
+    /// Returns the nominal sampling interval declared by this [Header] ("INTERVAL").
+    /// Refer to [Rinex::dominant_sampling_interval](crate::Rinex::dominant_sampling_interval)
+    /// for the interval obtained by actual data analysis.
+    pub fn sampling_interval(&self) -> Option<Duration> {
+        self.sampling_interval
+    }
+
     /// Builds a basic [Header] to describe a Multi-GNSS Navigation RINEX
     pub fn basic_nav() -> Self {
         Self::default()
@@ -359,6 +441,13 @@ impl Header {
         s
     }
 
+    /// Copies and returns [Header] with specified sampling interval
+    pub fn with_sampling_interval(&self, interval: Duration) -> Self {
+        let mut s = self.clone();
+        s.sampling_interval = Some(interval);
+        s
+    }
+
     /// Copies and returns [Header] with a new comment
     pub fn with_comment(&self, c: &str) -> Self {
         let mut s = self.clone();
@@ -704,7 +704,7 @@ impl Header {
                         // KB requires two lines
                         if let Some(ionod_model) = ionod_corrections.get_mut(&constell) {
                             let kb_model = ionod_model.as_klobuchar_mut().unwrap();
-                            if model_id.ends_with('A') {
+                            if model_id.trim_end().ends_with('A') {
                                 kb_model.alpha = alpha;
                                 kb_model.region = region;
                             } else {
@@ -48,11 +48,14 @@ pub mod version;
 mod bibliography;
 mod constants;
 mod epoch;
+mod formatting;
 mod iterators;
 mod leap;
 mod linspace;
 mod observable;
+mod parsing;
 mod sampling;
+mod timeseries;
 
 #[cfg(feature = "qc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "qc")))]
@@ -78,9 +81,9 @@ mod ublox;
 mod tests;
 
 use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufReader, BufWriter, Read, Write},
+    collections::{BTreeSet, HashMap},
+    fs::{File, OpenOptions},
+    io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write},
     path::Path,
     str::FromStr,
 };
@@ -88,6 +91,7 @@ use std::{
 use itertools::Itertools;
 
 use antex::{Antenna, FrequencyDependentData};
+use parsing::LineEndingNormalizer;
 
 #[cfg(feature = "antex")]
 use antex::{AntennaMatcher, AntennaSpecific};
@@ -101,6 +105,7 @@ use std::collections::BTreeMap;
 use crate::{
     epoch::epoch_decompose,
     hatanaka::CRINEX,
+    header::HeaderMut,
     observable::Observable,
     production::{DataSource, DetailedProductionAttributes, ProductionAttributes, FFU, PPU},
 };
@@ -110,13 +115,17 @@ pub mod prelude {
     // export
     pub use crate::{
         carrier::Carrier,
-        error::{Error, FormattingError, ParsingError},
+        error::{EphemerisError, Error, FormattingError, ParseWarning, ParsingError},
+        formatting::{FormattingOptions, LineEnding, OutputFormat},
         hatanaka::{
-            Decompressor, DecompressorExpert, DecompressorExpertIO, DecompressorIO, CRINEX,
+            CrinexStats, CrinexVersion, Decompressor, DecompressorExpert, DecompressorExpertIO,
+            DecompressorIO, CRINEX,
         },
         header::Header,
         leap::Leap,
         observable::Observable,
+        parsing::{DuplicateEpochPolicy, ParsingOptions},
+        timeseries::SatelliteTimeSeries,
         types::Type as RinexType,
         version::Version,
         Rinex,
@@ -145,8 +154,8 @@ pub mod prelude {
         pub use crate::carrier::Carrier;
 
         pub use crate::observation::{
-            ClockObservation, Combination, CombinationKey, EpochFlag, LliFlags, ObsKey,
-            Observations, SignalObservation, SNR,
+            ClockObservation, Combination, CombinationKey, EpochFlag, LliCounts, LliFlags,
+            LliStats, ObsKey, Observations, SignalObservation, WeightModel, SNR,
         };
     }
 
@@ -182,6 +191,7 @@ pub mod prelude {
     #[cfg(feature = "qc")]
     #[cfg_attr(docsrs, doc(cfg(feature = "qc")))]
     pub mod qc {
+        pub use crate::qc::MergePolicy;
         pub use qc_traits::{Merge, MergeError};
     }
 
@@ -189,9 +199,11 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "processing")))]
     pub mod processing {
         pub use qc_traits::{
-            Decimate, DecimationFilter, Filter, MaskFilter, Masking, Preprocessing, Split,
-            TimeCorrection, TimeCorrectionError, TimeCorrectionsDB, Timeshift,
+            Decimate, DecimationFilter, Filter, MaskFilter, MaskOperand, Masking, Preprocessing,
+            Split, TimeCorrection, TimeCorrectionError, TimeCorrectionsDB, Timeshift,
         };
+
+        pub use crate::observation::ObservableSNRMask;
     }
 
     #[cfg(feature = "rtcm")]
@@ -218,9 +230,13 @@ use qc_traits::{MaskFilter, Masking};
 
 #[cfg(feature = "processing")]
 use crate::{
-    clock::record::clock_mask_mut, header::processing::header_mask_mut,
-    meteo::mask::mask_mut as meteo_mask_mut, navigation::mask::mask_mut as navigation_mask_mut,
-    observation::mask::mask_mut as observation_mask_mut,
+    clock::record::clock_mask_mut,
+    header::processing::header_mask_mut,
+    meteo::mask::mask_mut as meteo_mask_mut,
+    navigation::mask::mask_mut as navigation_mask_mut,
+    observation::mask::{
+        mask_mut as observation_mask_mut, observable_snr_mask_mut, ObservableSNRMask,
+    },
 };
 
 #[cfg(docsrs)]
@@ -313,6 +329,8 @@ pub struct Rinex {
     pub record: Record,
     /// [ProductionAttributes] filled
     pub production: ProductionAttributes,
+    /// [CrinexStats] gathered while parsing a CRINEX record
+    pub(crate) crinex_stats: Option<CrinexStats>,
 }
 
 impl Rinex {
@@ -323,6 +341,7 @@ impl Rinex {
             record,
             comments: Comments::new(),
             production: ProductionAttributes::default(),
+            crinex_stats: None,
         }
     }
 
@@ -332,6 +351,7 @@ impl Rinex {
             header: Header::basic_nav(),
             comments: Default::default(),
             production: ProductionAttributes::default(),
+            crinex_stats: None,
             record: Record::NavRecord(Default::default()),
         }
     }
@@ -342,6 +362,7 @@ impl Rinex {
             header: Header::basic_obs(),
             comments: Default::default(),
             production: ProductionAttributes::default(),
+            crinex_stats: None,
             record: Record::ObsRecord(Default::default()),
         }
     }
@@ -352,6 +373,7 @@ impl Rinex {
             comments: Default::default(),
             header: Header::basic_crinex(),
             production: ProductionAttributes::default(),
+            crinex_stats: None,
             record: Record::ObsRecord(Default::default()),
         }
     }
@@ -363,6 +385,7 @@ impl Rinex {
             record: self.record.clone(),
             comments: self.comments.clone(),
             production: self.production.clone(),
+            crinex_stats: self.crinex_stats,
         }
     }
 
@@ -371,6 +394,159 @@ impl Rinex {
         self.header = header.clone();
     }
 
+    /// Returns mutable access to the [Header], wrapped in a [HeaderMut] guard.
+    /// The [RinexType] cannot be modified, and the [Version] cannot move between
+    /// V2 and V3 families, because the [Record] would no longer match:
+    /// such modifications are reverted when the guard is dropped.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let mut rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// rinex.header_mut().observer = Some("Someone".to_string());
+    /// assert_eq!(rinex.header.observer, Some("Someone".to_string()));
+    ///
+    /// // incompatible modifications are not retained
+    /// rinex.header_mut().rinex_type = RinexType::NavigationData;
+    /// assert_eq!(rinex.header.rinex_type, RinexType::ObservationData);
+    /// ```
+    pub fn header_mut(&mut self) -> HeaderMut<'_> {
+        HeaderMut::new(&mut self.header)
+    }
+
+    /// Renames this station, with mutable access: updates the [GeodeticMarker]
+    /// name and, when provided, its number (standardized "XXXXXMXXX" format).
+    /// The [GeodeticMarker] is created if this file did not define one.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let mut rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// rinex.rename_station_mut("ESBC", Some("10118M001"));
+    ///
+    /// let marker = rinex.header.geodetic_marker.as_ref().unwrap();
+    /// assert_eq!(marker.name, "ESBC");
+    /// assert_eq!(marker.number(), Some("10118M001".to_string()));
+    /// ```
+    pub fn rename_station_mut(&mut self, name: &str, number: Option<&str>) {
+        self.set_marker_name_mut(name);
+        if let Some(number) = number {
+            if let Some(marker) = &mut self.header.geodetic_marker {
+                *marker = marker.with_number(number);
+            }
+        }
+    }
+
+    /// Updates the [GeodeticMarker] name, with mutable access.
+    /// The name is truncated to the 60 characters that RINEX allows.
+    pub fn set_marker_name_mut(&mut self, name: &str) {
+        let name = name.chars().take(60).collect::<String>();
+        let marker = self
+            .header
+            .geodetic_marker
+            .get_or_insert_with(Default::default);
+        *marker = marker.with_name(name.trim());
+    }
+
+    /// Updates the agency in charge of this station, with mutable access.
+    /// The name is truncated to the 40 characters that RINEX allows.
+    pub fn set_agency_mut(&mut self, agency: &str) {
+        let agency = agency.chars().take(40).collect::<String>();
+        self.header.agency = Some(agency.trim().to_string());
+    }
+
+    /// Updates the observer (operator) name, with mutable access.
+    /// The name is truncated to the 20 characters that RINEX allows.
+    pub fn set_operator_mut(&mut self, operator: &str) {
+        let operator = operator.chars().take(20).collect::<String>();
+        self.header.observer = Some(operator.trim().to_string());
+    }
+
+    /// Updates the [hardware::Receiver] description, with mutable access.
+    pub fn set_receiver_mut(&mut self, receiver: hardware::Receiver) {
+        self.header.rcvr = Some(receiver);
+    }
+
+    /// Updates the receiver [hardware::Antenna] description, with mutable access.
+    pub fn set_antenna_mut(&mut self, antenna: hardware::Antenna) {
+        self.header.rcvr_antenna = Some(antenna);
+    }
+
+    /// Regenerates the [Header] fields that describe the [Record], from the
+    /// [Record] itself. This is typically needed after the [Record] was modified directly.
+    /// - the [Constellation] definition is updated (or turned to [Constellation::Mixed])
+    /// - Observation RINEX: observables are regenerated, per [Constellation].
+    ///   Existing observables retain their header order, new ones are appended.
+    ///   Time of first and last observation are updated.
+    /// - Meteo RINEX: observables are regenerated.
+    pub fn rebuild_header_from_record(&mut self) {
+        let constellations = self
+            .constellations_iter()
+            .map(|c| if c.is_sbas() { Constellation::SBAS } else { c })
+            .unique()
+            .collect::<Vec<_>>();
+
+        match constellations.len() {
+            0 => {},
+            1 => self.header.constellation = Some(constellations[0]),
+            _ => self.header.constellation = Some(Constellation::Mixed),
+        }
+
+        if self.is_observation_rinex() {
+            let mut codes = HashMap::<Constellation, Vec<Observable>>::new();
+
+            for (_, signal) in self.signal_observations_iter() {
+                let constellation = if signal.sv.constellation.is_sbas() {
+                    Constellation::SBAS
+                } else {
+                    signal.sv.constellation
+                };
+
+                let observables = codes.entry(constellation).or_default();
+                if !observables.contains(&signal.observable) {
+                    observables.push(signal.observable.clone());
+                }
+            }
+
+            let (t_first, t_last) = (self.first_epoch(), self.last_epoch());
+            let obs_header = self.header.obs.get_or_insert_with(Default::default);
+
+            for (constellation, observables) in codes.iter_mut() {
+                if let Some(prev) = obs_header.codes.get(constellation) {
+                    observables.sort_by_key(|observable| {
+                        prev.iter()
+                            .position(|p| p == observable)
+                            .unwrap_or(usize::MAX)
+                    });
+                }
+            }
+
+            obs_header.codes = codes;
+            obs_header.timeof_first_obs = t_first;
+            obs_header.timeof_last_obs = t_last;
+        } else if self.is_meteo_rinex() {
+            let mut codes = Vec::<Observable>::new();
+
+            for (k, _) in self.meteo_observations_iter() {
+                if !codes.contains(&k.observable) {
+                    codes.push(k.observable.clone());
+                }
+            }
+
+            let meteo_header = self.header.meteo.get_or_insert_with(Default::default);
+
+            codes.sort_by_key(|observable| {
+                meteo_header
+                    .codes
+                    .iter()
+                    .position(|p| p == observable)
+                    .unwrap_or(usize::MAX)
+            });
+
+            meteo_header.codes = codes;
+        }
+    }
+
     /// Copy and return this [Rinex] with updated [Record]
     pub fn with_record(&self, record: Record) -> Self {
         Rinex {
@@ -378,6 +554,7 @@ impl Rinex {
             header: self.header.clone(),
             comments: self.comments.clone(),
             production: self.production.clone(),
+            crinex_stats: None,
         }
     }
 
@@ -406,21 +583,55 @@ impl Rinex {
         s
     }
 
-    /// Mutable [Self::rnx2crnx] implementation
+    /// Mutable [Self::rnx2crnx] implementation.
+    /// This has no effect if this [Rinex] is already compressed:
+    /// the original [CRINEX] specifications are retained.
     pub fn rnx2crnx_mut(&mut self) {
-        if self.is_observation_rinex() {
-            let mut crinex = CRINEX::default();
-            crinex.version.major = match self.header.version.major {
-                1 | 2 => 1,
-                _ => 3,
-            };
-            crinex.date = epoch::now();
-            crinex.prog = format!(
+        if self.is_observation_rinex() && !self.is_hatanaka() {
+            self.with_crinex_revision_mut(CrinexVersion::from_rinex_version(self.header.version));
+        }
+    }
+
+    /// Converts this Observation [Rinex] to the desired [CrinexVersion], with mutable access.
+    /// Unlike [Self::rnx2crnx_mut], the CRINEX revision is not deduced from the RINEX revision:
+    /// - RINEX V2 is first converted to V3 (see [Self::rnx2v3_mut]) when targeting [CrinexVersion::V3].
+    /// - [CrinexVersion::V1] only applies to RINEX V2: we do not support the conversion
+    ///   to RINEX V2, so this returns [FormattingError::IncompatibleCrinexVersion] for V3+ data.
+    ///
+    /// When this [Rinex] is already compressed to the desired revision,
+    /// the original [CRINEX] specifications are retained.
+    /// This has no effect if this is not an Observation [Rinex].
+    pub fn rnx2crnx_version_mut(&mut self, revision: CrinexVersion) -> Result<(), FormattingError> {
+        if !self.is_observation_rinex() {
+            return Ok(());
+        }
+
+        if revision == CrinexVersion::V3 {
+            self.rnx2v3_mut();
+        }
+
+        if !revision.is_compatible(self.header.version) {
+            return Err(FormattingError::IncompatibleCrinexVersion);
+        }
+
+        if self.header.crinex_revision() != Some(revision) {
+            self.with_crinex_revision_mut(revision);
+        }
+
+        Ok(())
+    }
+
+    /// Defines our own [CRINEX] specifications, for this revision.
+    fn with_crinex_revision_mut(&mut self, revision: CrinexVersion) {
+        let crinex = CRINEX::default()
+            .with_version(revision.version())
+            .with_date(epoch::now())
+            .with_prog(&format!(
                 "rs-rinex v{}",
                 Header::format_pkg_version(env!("CARGO_PKG_VERSION"))
-            );
-            self.header = self.header.with_crinex(crinex);
-        }
+            ));
+
+        self.header = self.header.with_crinex(crinex);
     }
 
     /// Copies and convert this supposedly Compact (compressed) [Rinex] into
@@ -453,6 +664,149 @@ impl Rinex {
         }
     }
 
+    /// Copies and converts this RINEX V2 Observation [Rinex] to RINEX V3 (3.05).
+    /// Observables are converted with [Observable::v2_to_v3], which follows the
+    /// IGS conventions: refer to its documentation for the complete mapping.
+    /// This has no effect on other RINEX formats, or on RINEX V3+ data.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    ///
+    /// let rinex = Rinex::from_file("data/OBS/V2/aopr0010.17o")
+    ///     .unwrap();
+    ///
+    /// let v3 = rinex.rnx2v3();
+    /// assert_eq!(v3.header.version.major, 3);
+    ///
+    /// let c1w = Observable::from_str("C1W").unwrap();
+    ///
+    /// assert!(v3.signal_observations_iter()
+    ///     .any(|(_, sig)| sig.observable == c1w));
+    /// ```
+    pub fn rnx2v3(&self) -> Self {
+        let mut s = self.clone();
+        s.rnx2v3_mut();
+        s
+    }
+
+    /// Mutable [Self::rnx2v3] implementation.
+    pub fn rnx2v3_mut(&mut self) {
+        if !self.is_observation_rinex() || self.header.version.major > 2 {
+            return;
+        }
+
+        self.header = self.header.clone().with_version(Version::new(3, 5));
+
+        if let Some(obs) = &mut self.header.obs {
+            for (constellation, observables) in obs.codes.iter_mut() {
+                for observable in observables.iter_mut() {
+                    *observable = observable.v2_to_v3(*constellation);
+                }
+            }
+
+            obs.scaling = obs
+                .scaling
+                .iter()
+                .map(|((c, observable), scaling)| ((*c, observable.v2_to_v3(*c)), *scaling))
+                .collect();
+        }
+
+        for (_, signal) in self.signal_observations_iter_mut() {
+            signal.observable = signal.observable.v2_to_v3(signal.sv.constellation);
+        }
+    }
+
+    /// Copies and returns a new [Rinex] that only contains data from
+    /// the desired [Constellation]s. See [Self::retain_constellations_mut].
+    pub fn retain_constellations(&self, constellations: &[Constellation]) -> Self {
+        let mut s = self.clone();
+        s.retain_constellations_mut(constellations);
+        s
+    }
+
+    /// Only retains data from the desired [Constellation]s.
+    /// This is a shortcut to a [Constellation] mask and applies to Observation
+    /// and Navigation [Rinex]. For the latter, ephemerides, ionosphere models
+    /// and header corrections of other [Constellation]s are dropped as well.
+    /// ```
+    /// use rinex::prelude::*;
+    ///
+    /// let mut rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// rinex.retain_constellations_mut(&[Constellation::GPS]);
+    ///
+    /// assert!(rinex.sv_iter().all(|sv| sv.constellation == Constellation::GPS));
+    /// ```
+    pub fn retain_constellations_mut(&mut self, constellations: &[Constellation]) {
+        // SBAS definitions are gathered under [Constellation::SBAS] in the header
+        let normalize = |c: Constellation| {
+            if c.is_sbas() {
+                Constellation::SBAS
+            } else {
+                c
+            }
+        };
+
+        let constellations = constellations
+            .iter()
+            .copied()
+            .map(normalize)
+            .unique()
+            .collect::<Vec<_>>();
+
+        self.retain_sv_mut(|sv| constellations.contains(&normalize(sv.constellation)));
+
+        self.header
+            .ionod_corrections
+            .retain(|c, _| constellations.contains(&normalize(*c)));
+
+        if let Some(obs) = &mut self.header.obs {
+            obs.codes
+                .retain(|c, _| constellations.contains(&normalize(*c)));
+            obs.scaling
+                .retain(|(c, _), _| constellations.contains(&normalize(*c)));
+        }
+    }
+
+    /// Copies and returns a new [Rinex] that only contains data from
+    /// the desired satellites. See [Self::retain_satellites_mut].
+    pub fn retain_satellites(&self, satellites: &[SV]) -> Self {
+        let mut s = self.clone();
+        s.retain_satellites_mut(satellites);
+        s
+    }
+
+    /// Only retains data from the desired satellites ([SV]).
+    /// This is a shortcut to a [SV] mask and applies to Observation and Navigation [Rinex].
+    /// Header fields of [Constellation]s that are no longer represented are dropped too.
+    pub fn retain_satellites_mut(&mut self, satellites: &[SV]) {
+        self.retain_sv_mut(|sv| satellites.contains(&sv));
+
+        let constellations = satellites
+            .iter()
+            .map(|sv| sv.constellation)
+            .unique()
+            .collect::<Vec<_>>();
+
+        self.retain_constellations_mut(&constellations);
+    }
+
+    /// Drops all [SV] dependent data that does not match the predicate.
+    fn retain_sv_mut<F: Fn(SV) -> bool>(&mut self, retain: F) {
+        self.header.glo_channels.retain(|sv, _| retain(*sv));
+
+        if let Some(rec) = self.record.as_mut_obs() {
+            rec.retain(|_, v| {
+                v.signals.retain(|sig| retain(sig.sv));
+                !v.signals.is_empty() || !v.events.is_empty()
+            });
+            self.recompute_time_bounds_mut();
+        } else if let Some(rec) = self.record.as_mut_nav() {
+            rec.retain(|k, _| retain(k.sv));
+        }
+    }
+
     /// Returns a file name that would describe this [Rinex] according to standard naming conventions.
     /// For this information to be 100% complete, this [Rinex] must originate a file that
     /// followed standard naming conventions itself.
@@ -794,18 +1148,95 @@ impl Rinex {
     /// Attributes potentially described by a file name need to be provided either
     /// manually / externally, or guessed when parsing has been completed.
     pub fn parse<R: Read>(reader: &mut BufReader<R>) -> Result<Self, ParsingError> {
+        Self::parse_with_options(reader, &ParsingOptions::default())
+    }
+
+    /// Parses [Rinex] from [Read]able I/O, like [Self::parse],
+    /// but with custom [ParsingOptions].
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::{fs::File, io::BufReader};
+    ///
+    /// let fd = File::open("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// // reject files with duplicate epochs
+    /// let options = ParsingOptions::default()
+    ///     .with_duplicate_epoch_policy(DuplicateEpochPolicy::Error);
+    ///
+    /// let mut reader = BufReader::new(fd);
+    /// let rinex = Rinex::parse_with_options(&mut reader, &options)
+    ///     .unwrap();
+    /// ```
+    pub fn parse_with_options<R: Read>(
+        reader: &mut BufReader<R>,
+        options: &ParsingOptions,
+    ) -> Result<Self, ParsingError> {
+        // CR bytes would shift our fixed-column parsers
+        let mut reader = BufReader::new(LineEndingNormalizer::new(reader));
+
         // Parses Header section (=consumes header until this point)
-        let mut header = Header::parse(reader)?;
+        let mut header = Header::parse(&mut reader)?;
 
         // Parse record (=consumes rest of this resource)
         // Comments are preserved and store "as is"
-        let (record, comments) = Record::parse(&mut header, reader)?;
+        let (record, comments, _, crinex_stats) =
+            Record::parse_lenient_with_stats(&mut header, &mut reader, 0, options)?;
 
         Ok(Self {
             header,
             comments,
             record,
             production: Default::default(),
+            crinex_stats,
+        })
+    }
+
+    /// Parses [Rinex] from [Read]able I/O, like [Self::parse], but Navigation frames
+    /// are decoded in parallel, which speeds up the parsing of large (daily multi GNSS)
+    /// Navigation files. The result is identical to [Self::parse].
+    /// Other types of RINEX are parsed serially.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::{fs::File, io::BufReader};
+    /// use flate2::read::GzDecoder;
+    ///
+    /// let fd = File::open("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// let mut reader = BufReader::new(GzDecoder::new(fd));
+    ///
+    /// let rinex = Rinex::parse_parallel(&mut reader)
+    ///     .unwrap();
+    ///
+    /// assert!(rinex.is_navigation_rinex());
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn parse_parallel<R: Read>(reader: &mut BufReader<R>) -> Result<Self, ParsingError> {
+        let mut reader = BufReader::new(LineEndingNormalizer::new(reader));
+
+        let mut header = Header::parse(&mut reader)?;
+
+        let (record, comments, crinex_stats) = if header.rinex_type == RinexType::NavigationData {
+            let (record, comments) = Record::parse_nav_parallel(&header, &mut reader)?;
+            (record, comments, None)
+        } else {
+            let (record, comments, _, crinex_stats) = Record::parse_lenient_with_stats(
+                &mut header,
+                &mut reader,
+                0,
+                &ParsingOptions::default(),
+            )?;
+            (record, comments, crinex_stats)
+        };
+
+        Ok(Self {
+            header,
+            comments,
+            record,
+            production: Default::default(),
+            crinex_stats,
         })
     }
 
@@ -813,8 +1244,137 @@ impl Rinex {
     /// and following standard specifications. The revision to be followed is defined
     /// in [Header] section. This is the mirror operation of [Self::parse].
     pub fn format<W: Write>(&self, writer: &mut BufWriter<W>) -> Result<(), FormattingError> {
-        self.header.format(writer)?;
-        self.record.format(writer, &self.header)?;
+        self.format_with_options(writer, &FormattingOptions::default())
+    }
+
+    /// Format [RINEX] into writable I/O, like [Self::format],
+    /// but with custom [FormattingOptions].
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::io::BufWriter;
+    ///
+    /// let rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// let options = FormattingOptions::default()
+    ///     .with_line_ending(LineEnding::CRLF);
+    ///
+    /// let mut writer = BufWriter::new(Vec::new());
+    /// rinex.format_with_options(&mut writer, &options)
+    ///     .unwrap();
+    /// ```
+    pub fn format_with_options<W: Write>(
+        &self,
+        writer: &mut BufWriter<W>,
+        options: &FormattingOptions,
+    ) -> Result<(), FormattingError> {
+        match options.line_ending {
+            LineEnding::LF => {
+                self.format_content(writer, options)?;
+            },
+            LineEnding::CRLF => {
+                let crlf = formatting::LineEndingWriter::new(&mut *writer, LineEnding::CRLF);
+                let mut crlf_writer = BufWriter::new(crlf);
+                self.format_content(&mut crlf_writer, options)?;
+            },
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Formats [Header] and [Record] sections.
+    fn format_content<W: Write>(
+        &self,
+        writer: &mut BufWriter<W>,
+        options: &FormattingOptions,
+    ) -> Result<(), FormattingError> {
+        self.header.format_with_options(writer, options)?;
+        self.record
+            .format_with_options(writer, &self.header, options)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Converts and serializes this [Rinex] into the desired [OutputFormat].
+    /// This is a single entry point to all our formatters and binary streamers.
+    /// ## Inputs
+    /// - format: [OutputFormat]
+    /// - writer: I/O interface
+    /// ## Returns
+    /// - [FormattingError::UnsupportedOutputFormat] if this [Rinex] type
+    ///   cannot be converted to this [OutputFormat]. [OutputFormat::Crinex] only applies
+    ///   to Observation RINEX, binary streams currently only apply to Navigation RINEX.
+    /// ```
+    /// use rinex::prelude::*;
+    ///
+    /// let rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// let mut crinex = Vec::new();
+    /// rinex.convert(OutputFormat::Crinex, &mut crinex)
+    ///     .unwrap();
+    /// ```
+    pub fn convert<W: Write>(
+        &self,
+        format: OutputFormat,
+        writer: &mut W,
+    ) -> Result<(), FormattingError> {
+        match format {
+            OutputFormat::Rinex => {
+                let mut writer = BufWriter::new(&mut *writer);
+                self.crnx2rnx().format(&mut writer)?;
+            },
+            OutputFormat::Crinex => {
+                if !self.is_observation_rinex() {
+                    return Err(FormattingError::UnsupportedOutputFormat);
+                }
+                let mut writer = BufWriter::new(&mut *writer);
+                self.rnx2crnx().format(&mut writer)?;
+            },
+            #[cfg(feature = "binex")]
+            OutputFormat::Binex(meta) => {
+                if !self.is_navigation_rinex() {
+                    return Err(FormattingError::UnsupportedOutputFormat);
+                }
+                let streamer = self
+                    .rnx2bin(meta)
+                    .ok_or(FormattingError::UnsupportedOutputFormat)?;
+
+                let mut buf = Vec::new();
+                for msg in streamer {
+                    let size = msg.encoding_size();
+                    buf.resize(size, 0);
+                    msg.encode(&mut buf, size)
+                        .map_err(|_| FormattingError::BinaryEncoding)?;
+                    writer.write_all(&buf[..size])?;
+                }
+            },
+            #[cfg(feature = "rtcm")]
+            OutputFormat::Rtcm => {
+                if !self.is_navigation_rinex() {
+                    return Err(FormattingError::UnsupportedOutputFormat);
+                }
+                let streamer = self
+                    .rnx2rtcm()
+                    .ok_or(FormattingError::UnsupportedOutputFormat)?;
+
+                let mut builder = rtcm_rs::MessageBuilder::new();
+                for msg in streamer {
+                    let bytes = builder
+                        .build_message(&msg)
+                        .map_err(|_| FormattingError::BinaryEncoding)?;
+                    writer.write_all(bytes)?;
+                }
+            },
+            #[cfg(feature = "ublox")]
+            OutputFormat::Ubx => {
+                if !self.is_navigation_rinex() {
+                    return Err(FormattingError::UnsupportedOutputFormat);
+                }
+                let mut streamer = self.rnx2ubx();
+                std::io::copy(&mut streamer, writer)?;
+            },
+        }
         writer.flush()?;
         Ok(())
     }
@@ -858,6 +1418,78 @@ impl Rinex {
         Ok(rinex)
     }
 
+    /// Parses [Rinex] from local readable file, like [Self::from_file],
+    /// but in a lenient manner: record entries (or lines) that cannot be parsed
+    /// are skipped and reported as [ParseWarning]s, with their line number,
+    /// while the rest of the record is preserved. This is convenient to
+    /// recover real-world archives that contain a few corrupt epochs.
+    /// The [Header] section still needs to be valid.
+    /// Observation files that end mid-epoch (typically, files logged in real-time)
+    /// see their incomplete final epoch dropped and reported as
+    /// [ParsingError::TruncatedEpoch].
+    /// Returns [ParsingError::InputError] if provided file does not exist or is not readable.
+    /// ```
+    /// use rinex::prelude::*;
+    ///
+    /// let (rinex, warnings) = Rinex::from_file_lenient("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// assert!(warnings.is_empty());
+    ///
+    /// for warning in warnings.iter() {
+    ///     println!("{}", warning);
+    /// }
+    /// ```
+    pub fn from_file_lenient<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<(Rinex, Vec<ParseWarning>), ParsingError> {
+        let path = path.as_ref();
+
+        // deduce all we can from file name
+        let file_attributes = match path.file_name() {
+            Some(filename) => {
+                let filename = filename.to_string_lossy().to_string();
+                if let Ok(prod) = ProductionAttributes::from_str(&filename) {
+                    prod
+                } else {
+                    ProductionAttributes::default()
+                }
+            },
+            _ => ProductionAttributes::default(),
+        };
+
+        let bytes = std::fs::read(path)?;
+        let content = String::from_utf8_lossy(&bytes);
+
+        // so reported line numbers are file line numbers
+        let num_header_lines = content
+            .lines()
+            .position(|line| line.contains("END OF HEADER"))
+            .map(|pos| pos + 1)
+            .unwrap_or(0);
+
+        let mut reader = BufReader::new(LineEndingNormalizer::new(content.as_bytes()));
+
+        let mut header = Header::parse(&mut reader)?;
+
+        let (record, comments, warnings, crinex_stats) = Record::parse_lenient_with_stats(
+            &mut header,
+            &mut reader,
+            num_header_lines,
+            &ParsingOptions::default(),
+        )?;
+
+        let rinex = Self {
+            header,
+            comments,
+            record,
+            production: file_attributes,
+            crinex_stats,
+        };
+
+        Ok((rinex, warnings))
+    }
+
     /// Dumps [RINEX] into writable local file (as readable ASCII UTF-8)
     /// using efficient buffered formatting.
     /// This is the mirror operation of [Self::from_file].
@@ -881,6 +1513,95 @@ impl Rinex {
         Ok(())
     }
 
+    /// Compresses this Observation [Rinex] to the desired [CrinexVersion] and dumps it
+    /// into writable local file. This [Rinex] is not modified, refer to
+    /// [Self::rnx2crnx_version_mut] for the applied conversions and possible errors.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("data/OBS/V2/aopr0010.17o")
+    ///     .unwrap();
+    ///
+    /// // RINEX V2 may be compressed to either revision
+    /// assert!(rinex.to_crinex_file("crinex1.17d", CrinexVersion::V1).is_ok());
+    /// assert!(rinex.to_crinex_file("crinex3.crx", CrinexVersion::V3).is_ok());
+    ///
+    /// let parsed = Rinex::from_file("crinex3.crx")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(parsed.header.crinex_revision(), Some(CrinexVersion::V3));
+    ///
+    /// let _ = std::fs::remove_file("crinex1.17d");
+    /// let _ = std::fs::remove_file("crinex3.crx");
+    /// ```
+    pub fn to_crinex_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        revision: CrinexVersion,
+    ) -> Result<(), FormattingError> {
+        if !self.is_observation_rinex() {
+            return Err(FormattingError::UnsupportedOutputFormat);
+        }
+
+        let mut s = self.clone();
+        s.rnx2crnx_version_mut(revision)?;
+        s.to_file(path)
+    }
+
+    /// Appends the record of this [Rinex] to an existing local file, without
+    /// rewriting its [Header]. This is typically used to extend a file that is
+    /// logged in real-time, one batch of epochs at a time, without loading it.
+    /// The existing [Header] is parsed first and must be compatible with ours,
+    /// see [Header::is_append_compatible]. The record is then formatted
+    /// according to the existing [Header] (for example, its observable order).
+    /// You are responsible for appending epochs in chronological order,
+    /// the existing record is not parsed.
+    /// If the file does not exist, this is equivalent to [Self::to_file].
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rnx = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///   .unwrap();
+    /// let _ = std::fs::remove_file("appended.rnx");
+    /// // creates the file
+    /// assert!(rnx.append_to_file("appended.rnx").is_ok());
+    /// // record is duplicated
+    /// assert!(rnx.append_to_file("appended.rnx").is_ok());
+    /// let _ = std::fs::remove_file("appended.rnx");
+    /// ```
+    pub fn append_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), FormattingError> {
+        let path = path.as_ref();
+
+        if !path.exists() {
+            return self.to_file(path);
+        }
+
+        let existing = {
+            let fd = File::open(path)?;
+            let mut reader = BufReader::new(LineEndingNormalizer::new(fd));
+            Header::parse(&mut reader).map_err(|_| FormattingError::ExistingHeaderParsing)?
+        };
+
+        if !self.header.is_append_compatible(&existing) {
+            return Err(FormattingError::IncompatibleHeader);
+        }
+
+        let mut fd = OpenOptions::new().read(true).append(true).open(path)?;
+
+        // make sure we start on a new line
+        let mut last_byte = [0u8; 1];
+        fd.seek(SeekFrom::End(-1))?;
+        fd.read_exact(&mut last_byte)?;
+
+        let mut writer = BufWriter::new(fd);
+
+        if last_byte[0] != b'\n' {
+            writeln!(writer)?;
+        }
+
+        self.record.format(&mut writer, &existing)?;
+        writer.flush()?;
+        Ok(())
+    }
+
     /// Parses [Rinex] from local gzip compressed file.
     /// Will panic if provided file does not exist or is not readable.
     /// Refer to [Self::from_file] for more information.
@@ -912,6 +1633,7 @@ impl Rinex {
         let mut reader = BufReader::new(reader);
         let mut rinex = Self::parse(&mut reader)?;
         rinex.production = file_attributes;
+        rinex.production.gzip_compressed = true;
         Ok(rinex)
     }
 
@@ -941,6 +1663,72 @@ impl Rinex {
         Ok(())
     }
 
+    /// Returns true if this [Rinex] is Hatanaka compressed (CRINEX).
+    /// This is the case when parsed from a CRINEX file, or after [Self::rnx2crnx].
+    /// The original CRINEX [Version] is then retained, refer to [Header::crinex_version].
+    pub fn is_hatanaka(&self) -> bool {
+        self.header.is_crinex()
+    }
+
+    /// Returns [CrinexStats] gathered while decompressing this [Rinex],
+    /// when it was parsed from a CRINEX file. Returns None for all other files,
+    /// and after [Self::rnx2crnx] (nothing was decompressed).
+    /// ```
+    /// use rinex::prelude::*;
+    ///
+    /// let rinex = Rinex::from_file("data/CRNX/V3/KMS300DNK_R_20221591000_01H_30S_MO.crx")
+    ///     .unwrap();
+    ///
+    /// let stats = rinex.crinex_decode_stats()
+    ///     .unwrap();
+    ///
+    /// // an epoch count mismatch is the sign of a corrupt or truncated file
+    /// assert_eq!(stats.epochs, rinex.epoch_iter().count());
+    ///
+    /// println!("compression ratio: {:.2}", stats.compression_ratio().unwrap());
+    /// ```
+    pub fn crinex_decode_stats(&self) -> Option<CrinexStats> {
+        self.crinex_stats
+    }
+
+    /// Returns true if this [Rinex] was parsed from a Gzip compressed file.
+    pub fn is_gzip(&self) -> bool {
+        self.production.gzip_compressed
+    }
+
+    /// Returns the number of leap seconds that apply at this [Epoch].
+    /// The [Header] declaration is used by default, unless the Observation record
+    /// contains a special event, prior this [Epoch], that updates the leap second count.
+    /// ```
+    /// use rinex::prelude::*;
+    ///
+    /// let rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// let t0 = rinex.first_epoch().unwrap();
+    /// assert_eq!(rinex.leap_seconds_at(t0), rinex.header.leap_seconds());
+    /// ```
+    pub fn leap_seconds_at(&self, t: Epoch) -> Option<u32> {
+        let mut leap_seconds = self.header.leap_seconds();
+
+        if let Some(rec) = self.record.as_obs() {
+            for (k, v) in rec.iter() {
+                if k.epoch > t {
+                    break;
+                }
+
+                if k.flag.is_event() {
+                    let event = observation::RecordEvent::parse(k.flag, &v.events);
+                    if let Some(leap) = event.leap {
+                        leap_seconds = Some(leap.leap);
+                    }
+                }
+            }
+        }
+
+        leap_seconds
+    }
+
     /// Returns true if this is an ATX RINEX
     pub fn is_antex(&self) -> bool {
         self.header.rinex_type == types::Type::AntennaData
@@ -997,6 +1785,45 @@ impl Rinex {
  * These methods are used to browse data easily and efficiently.
  */
 impl Rinex {
+    /// Returns the number of entries in this [Rinex] record, whatever its type:
+    /// - Observation RINEX: number of epochs (including events)
+    /// - Navigation RINEX: number of frames (ephemeris, ionosphere models..)
+    /// - Meteo RINEX: number of sensor measurements
+    /// - Clock RINEX: number of epochs
+    /// - ANTEX: number of antennas
+    pub fn record_len(&self) -> usize {
+        match &self.record {
+            Record::AntexRecord(r) => r.len(),
+            Record::ClockRecord(r) => r.len(),
+            Record::MeteoRecord(r) => r.len(),
+            Record::NavRecord(r) => r.len(),
+            Record::ObsRecord(r) => r.len(),
+        }
+    }
+
+    /// Returns true if this [Rinex] record does not contain any data,
+    /// which is typically the case of files that only contain a header.
+    /// You should test this prior unwrapping [Self::first_epoch] for example.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// let rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// assert!(!rinex.is_empty());
+    /// assert_eq!(rinex.epoch_count(), rinex.record_len());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.record_len() == 0
+    }
+
+    /// Returns the number of unique [Epoch]s in this [Rinex] record.
+    /// Unlike [Self::record_len], each [Epoch] is only counted once,
+    /// even though it is shared by several entries (for example, several
+    /// satellites broadcasting at the same time). ANTEX records are not
+    /// indexed by [Epoch] and always return 0.
+    pub fn epoch_count(&self) -> usize {
+        self.epoch_iter().unique().count()
+    }
+
     /// Returns [Epoch] Iterator. This applies to all but ANTEX special format,
     /// for which we return null.
     pub fn epoch_iter(&self) -> Box<dyn Iterator<Item = Epoch> + '_> {
@@ -1138,6 +1965,73 @@ impl Rinex {
         Box::new(self.sv_iter().map(|sv| sv.constellation).unique().sorted())
     }
 
+    /// Compares the [Constellation]s declared in the [Header] to the
+    /// [Constellation]s actually found in the record, to identify mislabeled files.
+    /// Returns (declared, actual, extra, missing), where
+    /// - declared is obtained from the "SYS / # / OBS TYPES" fields of modern Observation RINEX,
+    ///   or from the [Header] constellation in other cases.
+    ///   A [Constellation::Mixed] declaration accepts all [Constellation]s.
+    /// - extra: [Constellation]s found in the record that were not declared
+    /// - missing: declared [Constellation]s that are not found in the record
+    ///
+    /// All SBAS augmentation systems are considered [Constellation::SBAS] here.
+    /// ```
+    /// use rinex::prelude::*;
+    ///
+    /// let rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// let (declared, actual, extra, _) = rinex.constellation_consistency();
+    ///
+    /// assert!(extra.is_empty());
+    /// assert!(actual.is_subset(&declared));
+    /// ```
+    pub fn constellation_consistency(
+        &self,
+    ) -> (
+        BTreeSet<Constellation>,
+        BTreeSet<Constellation>,
+        BTreeSet<Constellation>,
+        BTreeSet<Constellation>,
+    ) {
+        let normalize = |c: Constellation| {
+            if c.is_sbas() {
+                Constellation::SBAS
+            } else {
+                c
+            }
+        };
+
+        let mut declared = BTreeSet::new();
+
+        match &self.header.obs {
+            Some(obs) if self.header.version.major > 2 && !obs.codes.is_empty() => {
+                declared.extend(obs.codes.keys().copied().map(normalize));
+            },
+            _ => {
+                if let Some(constellation) = self.header.constellation {
+                    declared.insert(normalize(constellation));
+                }
+            },
+        }
+
+        let actual = self
+            .constellations_iter()
+            .map(normalize)
+            .collect::<BTreeSet<_>>();
+
+        let (extra, missing) = if declared.contains(&Constellation::Mixed) {
+            (BTreeSet::new(), BTreeSet::new())
+        } else {
+            (
+                actual.difference(&declared).copied().collect(),
+                declared.difference(&actual).copied().collect(),
+            )
+        };
+
+        (declared, actual, extra, missing)
+    }
+
     // /// Returns an Iterator over Unique Constellations, per Epoch
     // pub fn constellation_epoch(
     //     &self,
@@ -1244,7 +2138,7 @@ impl Rinex {
                         min_dt < Duration::MAX
                     });
 
-                    !v.signals.is_empty()
+                    !v.signals.is_empty() || !v.events.is_empty()
                 });
             }
         }
@@ -1272,6 +2166,46 @@ impl Masking for Rinex {
         } else if let Some(rec) = self.record.as_mut_meteo() {
             meteo_mask_mut(rec, f);
         }
+        self.recompute_time_bounds_mut();
+    }
+}
+
+#[cfg(feature = "processing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "processing")))]
+impl Rinex {
+    /// Applies [ObservableSNRMask] to this Observation [Rinex], which
+    /// masks a single [Observable] according to its [SNR], and preserves all other signals.
+    /// This has no effect on other RINEX formats.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use rinex::prelude::processing::{MaskOperand, ObservableSNRMask};
+    /// use rinex::observation::SNR;
+    /// use std::str::FromStr;
+    ///
+    /// let rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// // drop C1C below 30 dB/Hz, preserve phase observations
+    /// let mask = ObservableSNRMask::new(
+    ///     Observable::from_str("C1C").unwrap(),
+    ///     SNR::DbHz30_35,
+    ///     MaskOperand::GreaterEquals,
+    /// );
+    ///
+    /// let masked = rinex.observable_snr_mask(&mask);
+    /// ```
+    pub fn observable_snr_mask(&self, mask: &ObservableSNRMask) -> Self {
+        let mut s = self.clone();
+        s.observable_snr_mask_mut(mask);
+        s
+    }
+
+    /// Mutable [Self::observable_snr_mask] implementation.
+    pub fn observable_snr_mask_mut(&mut self, mask: &ObservableSNRMask) {
+        if let Some(rec) = self.record.as_mut_obs() {
+            observable_snr_mask_mut(rec, mask);
+            self.recompute_time_bounds_mut();
+        }
     }
 }
 
@@ -1307,6 +2241,41 @@ impl Rinex {
             })
         }))
     }
+
+    /// Interpolates the precise clock bias (in seconds) of this [SV] at desired [Epoch],
+    /// from the two closest samples of this Clock [Rinex]. Samples are linearly interpolated.
+    /// Returns None if [Epoch] is not surrounded by two samples (no extrapolation),
+    /// unless it matches one sample exactly.
+    pub fn precise_sv_clock_bias_interpolated(&self, t: Epoch, sv: SV) -> Option<f64> {
+        let mut before = Option::<(Epoch, f64)>::None;
+        let mut after = Option::<(Epoch, f64)>::None;
+
+        for (epoch, clk_sv, _, profile) in self.precise_sv_clock() {
+            if clk_sv != sv {
+                continue;
+            }
+
+            if epoch == t {
+                return Some(profile.bias);
+            }
+
+            if epoch < t {
+                before = Some((epoch, profile.bias));
+            } else {
+                after = Some((epoch, profile.bias));
+                break;
+            }
+        }
+
+        let (t0, y0) = before?;
+        let (t1, y1) = after?;
+
+        let dt = (t1 - t0).to_seconds();
+        let dx = (t - t0).to_seconds();
+
+        Some(y0 + (y1 - y0) * dx / dt)
+    }
+
     /// Returns Iterator over Clock RINEX content for Ground Station clocks only (not onboard clocks)
     pub fn precise_station_clock(
         &self,
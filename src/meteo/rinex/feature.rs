@@ -163,6 +163,53 @@ impl Rinex {
         }))
     }
 
+    /// Linear interpolation of desired meteo [Observable] at any [Epoch],
+    /// typically to evaluate tropospheric corrections at observation [Epoch]s,
+    /// which rarely match the meteo sensor sampling. Applies to Meteo RINEX.
+    /// ## Inputs
+    /// - observable: desired meteo [Observable]
+    /// - t: target [Epoch]
+    /// ## Returns
+    /// - interpolated value, None if `t` is not surrounded by two
+    /// samples of this [Observable] (we do not extrapolate).
+    /// ```
+    /// use std::str::FromStr;
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("data/MET/V2/abvi0010.15m")
+    ///     .unwrap();
+    /// let t = Epoch::from_str("2015-01-01T00:00:30 UTC").unwrap();
+    /// let temperature = rinex.meteo_interpolated(&Observable::Temperature, t);
+    /// assert!(temperature.is_some());
+    /// ```
+    pub fn meteo_interpolated(&self, observable: &Observable, t: Epoch) -> Option<f64> {
+        let record = self.record.as_meteo()?;
+
+        let mut before = Option::<(Epoch, f64)>::None;
+        let mut after = Option::<(Epoch, f64)>::None;
+
+        for (k, v) in record.iter() {
+            if k.observable != *observable {
+                continue;
+            }
+
+            if k.epoch == t {
+                return Some(*v);
+            } else if k.epoch < t {
+                before = Some((k.epoch, *v));
+            } else {
+                after = Some((k.epoch, *v));
+                break;
+            }
+        }
+
+        let ((t_0, y_0), (t_1, y_1)) = (before?, after?);
+
+        let dt = (t_1 - t_0).to_seconds();
+        let ratio = (t - t_0).to_seconds() / dt;
+
+        Some(y_0 + (y_1 - y_0) * ratio)
+    }
+
     //   /// Returns true if hail was detected during this time frame
     //   /// ```
     //   /// use std::str::FromStr;
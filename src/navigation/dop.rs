@@ -0,0 +1,62 @@
+//! Dilution of Precision
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nalgebra::{Matrix4, Vector4};
+
+use crate::prelude::nav::AzElRange;
+
+/// [Dop] (Dilution of Precision) describes how the satellites geometry
+/// degrades the precision of the navigation solution.
+/// The lower, the better.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Dop {
+    /// Geometric Dilution of Precision
+    pub gdop: f64,
+
+    /// Position (3D) Dilution of Precision
+    pub pdop: f64,
+
+    /// Horizontal Dilution of Precision
+    pub hdop: f64,
+
+    /// Vertical Dilution of Precision
+    pub vdop: f64,
+
+    /// Time Dilution of Precision
+    pub tdop: f64,
+}
+
+impl Dop {
+    /// Calculates [Dop] from the satellites geometry,
+    /// described by [AzElRange] from the receiver position.
+    /// Requires at least 4 satellites.
+    pub fn from_azimuth_elevation_range(geometry: &[AzElRange]) -> Option<Self> {
+        if geometry.len() < 4 {
+            return None;
+        }
+
+        // G^T * G, where each row of G is the
+        // (-east, -north, -up, 1) line of sight vector
+        let mut gtg = Matrix4::<f64>::zeros();
+
+        for azelrange in geometry.iter() {
+            let (sin_el, cos_el) = azelrange.elevation_deg.to_radians().sin_cos();
+            let (sin_az, cos_az) = azelrange.azimuth_deg.to_radians().sin_cos();
+
+            let g = Vector4::new(-cos_el * sin_az, -cos_el * cos_az, -sin_el, 1.0);
+            gtg += g * g.transpose();
+        }
+
+        let q = gtg.try_inverse()?;
+
+        Some(Self {
+            gdop: q.trace().sqrt(),
+            pdop: (q[(0, 0)] + q[(1, 1)] + q[(2, 2)]).sqrt(),
+            hdop: (q[(0, 0)] + q[(1, 1)]).sqrt(),
+            vdop: q[(2, 2)].sqrt(),
+            tdop: q[(3, 3)].sqrt(),
+        })
+    }
+}
@@ -2,14 +2,13 @@
 
 use crate::{
     epoch::parse_in_timescale as parse_epoch_in_timescale,
+    parse_f64,
     prelude::{Epoch, ParsingError, TimeScale},
 };
 
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
-use std::str::FromStr;
-
 /// Earth Orientation Message
 #[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
@@ -46,23 +45,23 @@ impl EarthOrientation {
         let epoch = parse_epoch_in_timescale(epoch.trim(), ts)?;
 
         let x = (
-            f64::from_str(xp.trim()).unwrap_or(0.0_f64),
-            f64::from_str(dxp.trim()).unwrap_or(0.0_f64),
-            f64::from_str(ddxp.trim()).unwrap_or(0.0_f64),
+            parse_f64(xp.trim()).unwrap_or(0.0_f64),
+            parse_f64(dxp.trim()).unwrap_or(0.0_f64),
+            parse_f64(ddxp.trim()).unwrap_or(0.0_f64),
         );
 
         let y = (
-            f64::from_str(yp.trim()).unwrap_or(0.0_f64),
-            f64::from_str(dyp.trim()).unwrap_or(0.0_f64),
-            f64::from_str(ddyp.trim()).unwrap_or(0.0_f64),
+            parse_f64(yp.trim()).unwrap_or(0.0_f64),
+            parse_f64(dyp.trim()).unwrap_or(0.0_f64),
+            parse_f64(ddyp.trim()).unwrap_or(0.0_f64),
         );
 
-        let t_tm = f64::from_str(t_tm.trim()).unwrap_or(0.0_f64);
+        let t_tm = parse_f64(t_tm.trim()).unwrap_or(0.0_f64);
 
         let delta_ut1 = (
-            f64::from_str(dut.trim()).unwrap_or(0.0_f64),
-            f64::from_str(ddut.trim()).unwrap_or(0.0_f64),
-            f64::from_str(dddut.trim()).unwrap_or(0.0_f64),
+            parse_f64(dut.trim()).unwrap_or(0.0_f64),
+            parse_f64(ddut.trim()).unwrap_or(0.0_f64),
+            parse_f64(dddut.trim()).unwrap_or(0.0_f64),
         );
 
         Ok((
@@ -3,7 +3,7 @@ use crate::{
     prelude::{Constellation, Epoch, SV},
 };
 
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
 use binex::prelude::{EphemerisFrame, GALEphemeris, GLOEphemeris, GPSEphemeris, SBASEphemeris};
 
@@ -23,15 +23,20 @@ impl Ephemeris {
                     orbits: HashMap::from_iter([("week".to_string(), OrbitItem::from(0.0f64))]),
                 },
             )),
-            EphemerisFrame::SBAS(serialized) => Some((
-                SV::new(Constellation::SBAS, serialized.sbas_prn - 100),
-                Self {
-                    clock_bias: serialized.clock_offset as f64,
-                    clock_drift: serialized.clock_drift as f64,
-                    clock_drift_rate: 0.0,
-                    orbits: HashMap::from_iter([("week".to_string(), OrbitItem::from(0.0f64))]),
-                },
-            )),
+            EphemerisFrame::SBAS(serialized) => {
+                // resolve the SBAS augmentation system, like RINEX parsing does
+                let sv = SV::from_str(&format!("S{:02}", serialized.sbas_prn - 100)).ok()?;
+
+                Some((
+                    sv,
+                    Self {
+                        clock_bias: serialized.clock_offset as f64,
+                        clock_drift: serialized.clock_drift as f64,
+                        clock_drift_rate: 0.0,
+                        orbits: HashMap::from_iter([("week".to_string(), OrbitItem::from(0.0f64))]),
+                    },
+                ))
+            },
             EphemerisFrame::GLO(serialized) => Some((
                 SV::new(Constellation::Glonass, serialized.slot),
                 Self {
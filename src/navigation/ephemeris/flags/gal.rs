@@ -50,3 +50,33 @@ bitflags! {
         const TEMPORAL_SISA_E1_E5B= 0x00000200;
     }
 }
+
+/// [GalNavType] describes which of the two Galileo navigation messages
+/// (I/NAV or F/NAV) an ephemeris frame was decoded from.
+/// Both describe the same orbit and clock, but are not computed
+/// nor referenced identically, so they should not be mixed together.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum GalNavType {
+    /// I/NAV message, streamed on E1-B and E5b-I.
+    /// The clock is referenced to the E1/E5b pair.
+    INav,
+    /// F/NAV message, streamed on E5a-I.
+    /// The clock is referenced to the E1/E5a pair.
+    FNav,
+}
+
+impl GalDataSource {
+    /// Returns the [GalNavType] this data source indication describes,
+    /// if it could be determined.
+    pub fn nav_type(&self) -> Option<GalNavType> {
+        if self.intersects(Self::FNAV_E5A_I) {
+            Some(GalNavType::FNav)
+        } else if self.intersects(Self::INAV_E1B | Self::FNAV_E5B_I) {
+            // E5b-I is an I/NAV stream
+            Some(GalNavType::INav)
+        } else {
+            None
+        }
+    }
+}
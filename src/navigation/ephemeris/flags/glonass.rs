@@ -27,6 +27,57 @@ bitflags! {
     }
 }
 
+/// [GlonassHealthDetail] is the structured interpretation of the Glonass health words:
+/// the Bn flag ([GlonassHealth]) and, in modern FDMA frames, the Cn and ln bits
+/// of the subsidary [GlonassHealth2] flags.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct GlonassHealthDetail {
+    /// Bn: true if the ephemeris declares this satellite healthy
+    pub bn_healthy: bool,
+
+    /// Cn: true if the almanac declares this satellite healthy.
+    /// None when the almanac health is not reported in this frame.
+    pub cn_healthy: Option<bool>,
+
+    /// ln: true if the navigation data (string 3) is declared valid.
+    /// Only defined for GLO-M/K satellites, as indicated by [GlonassStatus].
+    pub ln_healthy: Option<bool>,
+}
+
+impl GlonassHealthDetail {
+    /// Returns true if this satellite is suitable for navigation:
+    /// all health indications that were reported are positive.
+    pub fn healthy(&self) -> bool {
+        self.bn_healthy && self.cn_healthy.unwrap_or(true) && self.ln_healthy.unwrap_or(true)
+    }
+
+    /// Builds [GlonassHealthDetail] from the Bn flag, and possible
+    /// subsidary [GlonassHealth2] flags and [GlonassStatus] mask.
+    pub(crate) fn new(
+        health: &GlonassHealth,
+        health2: Option<&GlonassHealth2>,
+        status: Option<&GlonassStatus>,
+    ) -> Self {
+        let mut detail = Self {
+            bn_healthy: !health.intersects(GlonassHealth::UNHEALTHY),
+            ..Default::default()
+        };
+
+        if let Some(health2) = health2 {
+            if health2.intersects(GlonassHealth2::ALAMANAC_IS_REPORTED) {
+                detail.cn_healthy = Some(health2.intersects(GlonassHealth2::HEALTHY_ALMANAC));
+            }
+
+            if status.map(|status| status.glonass_mk_type_flag()) == Some(true) {
+                detail.ln_healthy = Some(!health2.intersects(GlonassHealth2::M_K_ONLY_L3_BIT));
+            }
+        }
+
+        detail
+    }
+}
+
 /// [GlonassStatus] 9-bit binary status mask
 #[derive(Default, Debug, Clone, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
@@ -43,11 +43,45 @@ impl GpsQzssl1l2l5Health {
 }
 
 bitflags! {
-    /// [GpsQzssl1cHealth] L1 C/A sanity flag
+    /// [GpsQzssl1cHealth] health word, as streamed by the CNAV-2 (L1C) frame.
+    /// IS-GPS-800 only defines the L1C health bit, other bits are spare
+    /// and retained as is (refer to [GpsQzssl1cHealthDetail]).
     #[derive(Default, Debug, Clone)]
     #[derive(PartialEq, PartialOrd)]
     #[cfg_attr(feature = "serde", derive(Serialize))]
     pub struct GpsQzssl1cHealth : u32 {
+        /// L1C signal is unhealthy
         const UNHEALTHY = 0x00000001;
     }
 }
+
+/// [GpsQzssl1cHealthDetail] is the complete interpretation of the
+/// [GpsQzssl1cHealth] word, as per IS-GPS-800 (and IS-QZSS-PNT for QZSS).
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct GpsQzssl1cHealthDetail {
+    /// True if the L1C signal is declared healthy
+    pub l1c_healthy: bool,
+
+    /// Spare bits of the health word that were asserted.
+    /// These are not defined by the ICD and should always be null:
+    /// a non null value indicates a non standard (or corrupt) frame.
+    pub spare_bits: u32,
+}
+
+impl GpsQzssl1cHealthDetail {
+    /// Returns true if this satellite is suitable for L1C navigation:
+    /// the L1C signal is healthy and the health word is standard.
+    pub fn healthy(&self) -> bool {
+        self.l1c_healthy && self.spare_bits == 0
+    }
+}
+
+impl From<&GpsQzssl1cHealth> for GpsQzssl1cHealthDetail {
+    fn from(health: &GpsQzssl1cHealth) -> Self {
+        Self {
+            l1c_healthy: !health.intersects(GpsQzssl1cHealth::UNHEALTHY),
+            spare_bits: health.bits() & !GpsQzssl1cHealth::UNHEALTHY.bits(),
+        }
+    }
+}
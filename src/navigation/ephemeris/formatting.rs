@@ -5,7 +5,7 @@ use crate::{
         NavMessageType,
     },
     prelude::{Constellation, SV},
-    FormattingError, Version,
+    FormattingError, FormattingOptions, Version,
 };
 
 use std::io::{BufWriter, Write};
@@ -19,6 +19,20 @@ impl Ephemeris {
         version: Version,
         msgtype: NavMessageType,
     ) -> Result<(), FormattingError> {
+        self.format_with_options(w, sv, version, msgtype, &FormattingOptions::default())
+    }
+
+    /// Formats [Ephemeris] according to RINEX standards, with custom [FormattingOptions]
+    pub fn format_with_options<W: Write>(
+        &self,
+        w: &mut BufWriter<W>,
+        sv: SV,
+        version: Version,
+        msgtype: NavMessageType,
+        options: &FormattingOptions,
+    ) -> Result<(), FormattingError> {
+        let d_exponent = options.nav_d_exponent;
+
         let sv_constellation = if sv.constellation.is_sbas() {
             Constellation::SBAS
         } else {
@@ -38,26 +52,21 @@ impl Ephemeris {
         write!(
             w,
             "{}{}{}",
-            NavFormatter::new(self.clock_bias),
-            NavFormatter::new(self.clock_drift),
-            NavFormatter::new(self.clock_drift_rate),
+            NavFormatter::new(self.clock_bias).with_d_exponent(d_exponent),
+            NavFormatter::new(self.clock_drift).with_d_exponent(d_exponent),
+            NavFormatter::new(self.clock_drift_rate).with_d_exponent(d_exponent),
         )?;
 
         // following standard specs
         let data_fields = &standard_specs.items;
         for i in 0..data_fields.len() {
-            if let Some(value) = self.get_orbit_f64(data_fields[i].0) {
-                if i % 4 == 0 {
-                    write!(w, "\n   {}", NavFormatter::new(value))?;
-                } else {
-                    write!(w, "{}", NavFormatter::new(value))?;
-                }
+            let value = self.get_orbit_f64(data_fields[i].0).unwrap_or(0.0);
+            let formatted = NavFormatter::new(value).with_d_exponent(d_exponent);
+
+            if i % 4 == 0 {
+                write!(w, "\n   {}", formatted)?;
             } else {
-                if i % 4 == 0 {
-                    write!(w, "\n   {}", NavFormatter::new(0.0))?;
-                } else {
-                    write!(w, "{}", NavFormatter::new(0.0))?;
-                }
+                write!(w, "{}", formatted)?;
             }
         }
 
@@ -114,14 +123,14 @@ mod test {
 
         assert_eq!(
             utf8,
-            "-1.000000000000E-04-2.000000000000E-11 0.000000000000E+00
-    1.000000000000E+00 2.000000000000E+00 3.000000000000E+00 0.000000000000E+00
-    5.000000000000E+00 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
-    0.000000000000E+00 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
-    0.000000000000E+00 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
-    0.000000000000E+00 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
-    0.000000000000E+00 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
-    0.000000000000E+00 0.000000000000E+00\n"
+            "-1.000000000000D-04-2.000000000000D-11 0.000000000000D+00
+    1.000000000000D+00 2.000000000000D+00 3.000000000000D+00 0.000000000000D+00
+    5.000000000000D+00 0.000000000000D+00 0.000000000000D+00 0.000000000000D+00
+    0.000000000000D+00 0.000000000000D+00 0.000000000000D+00 0.000000000000D+00
+    0.000000000000D+00 0.000000000000D+00 0.000000000000D+00 0.000000000000D+00
+    0.000000000000D+00 0.000000000000D+00 0.000000000000D+00 0.000000000000D+00
+    0.000000000000D+00 0.000000000000D+00 0.000000000000D+00 0.000000000000D+00
+    0.000000000000D+00 0.000000000000D+00\n"
         );
     }
 }
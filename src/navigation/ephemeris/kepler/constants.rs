@@ -0,0 +1,106 @@
+use crate::{
+    constants::{DtrF, Omega, GM},
+    prelude::Constellation,
+};
+
+/// [OrbitalConstants] are the physical constants the Keplerian solver
+/// uses for one specific [Constellation].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OrbitalConstants {
+    /// Earth gravitational constant (in m³.s⁻²)
+    pub gm_m3_s2: f64,
+
+    /// Earth rotation rate (in radians.s⁻¹)
+    pub omega_rad_s: f64,
+
+    /// Relativistic clock correction factor -2√GM/c² (in s.m^-1/2)
+    pub dtr_f: f64,
+}
+
+/// [SolverConstants] gathers the [OrbitalConstants] of each [Constellation].
+/// The [Default] values are the ones defined by each ICD,
+/// you should only modify them when working in a specific reference frame,
+/// or with a different ICD revision. QZSS follows GPS.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SolverConstants {
+    /// [Constellation::GPS] and [Constellation::QZSS] [OrbitalConstants]
+    pub gps: OrbitalConstants,
+
+    /// [Constellation::Galileo] [OrbitalConstants]
+    pub galileo: OrbitalConstants,
+
+    /// [Constellation::BeiDou] [OrbitalConstants]
+    pub beidou: OrbitalConstants,
+
+    /// [Constellation::Glonass] [OrbitalConstants]
+    pub glonass: OrbitalConstants,
+
+    /// [Constellation::IRNSS] [OrbitalConstants]
+    pub irnss: OrbitalConstants,
+}
+
+impl Default for SolverConstants {
+    fn default() -> Self {
+        Self {
+            gps: OrbitalConstants {
+                gm_m3_s2: GM::GPS,
+                omega_rad_s: Omega::GPS,
+                dtr_f: DtrF::GPS,
+            },
+            galileo: OrbitalConstants {
+                gm_m3_s2: GM::GAL,
+                omega_rad_s: Omega::GAL,
+                dtr_f: DtrF::GAL,
+            },
+            beidou: OrbitalConstants {
+                gm_m3_s2: GM::BDS,
+                omega_rad_s: Omega::BDS,
+                dtr_f: DtrF::BDS,
+            },
+            glonass: OrbitalConstants {
+                gm_m3_s2: GM::GLO,
+                omega_rad_s: Omega::GLO,
+                dtr_f: DtrF::GPS,
+            },
+            irnss: OrbitalConstants {
+                gm_m3_s2: GM::IRN,
+                omega_rad_s: Omega::IRN,
+                dtr_f: DtrF::GPS,
+            },
+        }
+    }
+}
+
+impl SolverConstants {
+    /// Returns [OrbitalConstants] to be used for this [Constellation].
+    pub fn constants(&self, constellation: Constellation) -> OrbitalConstants {
+        match constellation {
+            Constellation::Galileo => self.galileo,
+            Constellation::BeiDou => self.beidou,
+            Constellation::Glonass => self.glonass,
+            Constellation::IRNSS => self.irnss,
+            _ => self.gps,
+        }
+    }
+
+    /// Copies and returns [SolverConstants] with updated [OrbitalConstants]
+    /// for this [Constellation]. Modifying [Constellation::GPS] also applies to
+    /// [Constellation::QZSS] and other constellations that follow GPS.
+    pub fn with_constants(
+        &self,
+        constellation: Constellation,
+        constants: OrbitalConstants,
+    ) -> Self {
+        let mut s = *self;
+        match constellation {
+            Constellation::Galileo => s.galileo = constants,
+            Constellation::BeiDou => s.beidou = constants,
+            Constellation::Glonass => s.glonass = constants,
+            Constellation::IRNSS => s.irnss = constants,
+            _ => s.gps = constants,
+        }
+        s
+    }
+}
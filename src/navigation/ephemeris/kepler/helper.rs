@@ -2,8 +2,7 @@
 use log::{error, warn};
 
 use crate::{
-    constants::{Constants, Omega},
-    navigation::Ephemeris,
+    navigation::{Ephemeris, SolverConfig},
     prelude::{Constellation, Epoch, SV},
 };
 
@@ -20,6 +19,18 @@ pub struct Helper {
     /// The difference between the calculated time and the ephemeris reference time
     pub t_k: f64,
 
+    /// Corrected mean motion (in radians.s⁻¹)
+    pub n: f64,
+
+    /// Mean anomaly (in radians)
+    pub m_k: f64,
+
+    /// Eccentric anomaly (in radians)
+    pub e_k: f64,
+
+    /// True anomaly (in radians)
+    pub v_k: f64,
+
     /// Ascending angle (corrected) in radians
     pub u_k: f64,
 
@@ -52,6 +63,15 @@ pub struct Helper {
 
     /// r_sv in meters ECEF
     pub r_sv: (f64, f64, f64),
+
+    /// Earth rotation rate (in radians.s⁻¹) used by the solver
+    pub omega_e: f64,
+
+    /// Number of iterations the eccentric anomaly solver required
+    pub iterations: usize,
+
+    /// True when the eccentric anomaly solver converged
+    pub converged: bool,
 }
 
 impl Helper {
@@ -66,7 +86,7 @@ impl Helper {
     /// Returns GEO to ECEF [Rotation3] matrix
     fn geo_orbit_to_ecef_rotation_matrix(&self) -> Rotation<f64, 3> {
         let rotation_x = Rotation::from_axis_angle(&Vector3::x_axis(), 5.0f64.to_radians());
-        let rotation_z = Rotation::from_axis_angle(&Vector3::z_axis(), -Omega::BDS * self.t_k);
+        let rotation_z = Rotation::from_axis_angle(&Vector3::z_axis(), -self.omega_e * self.t_k);
         rotation_z * rotation_x
     }
 
@@ -124,29 +144,49 @@ impl Helper {
         (self.ecef_position(), self.ecef_velocity())
     }
 
-    /// Returns ECEF position [Vector3] in km, for BeiDou GEO specifically
-    pub fn beidou_geo_ecef_position(&self) -> Vector3 {
+    /// Returns position [Vector3] in meters, in the BeiDou GEO custom inertial frame (GK)
+    fn beidou_geo_gk_position(&self) -> Vector3 {
         let orbit_xyz = Vector3::new(self.r_sv.0, self.r_sv.1, 0.0);
-        let rotation1 = self.meo_orbit_to_ecef_rotation_matrix();
-        let rotation2 = self.geo_orbit_to_ecef_rotation_matrix();
-        let ecef_xyz = rotation2 * rotation1 * orbit_xyz;
-        ecef_xyz / 1000.0
+        self.meo_orbit_to_ecef_rotation_matrix() * orbit_xyz
     }
 
-    /// Returns ECEF velocity [Vector3] in km/s, for BeiDou GEO specifically
-    pub fn beidou_geo_ecef_velocity(&self) -> Vector3 {
+    /// Returns velocity [Vector3] in m/s, in the BeiDou GEO custom inertial frame (GK)
+    fn beidou_geo_gk_velocity(&self) -> Vector3 {
         let (x, y, _) = self.r_sv;
         let (sin_omega_k, cos_omega_k) = self.omega_k.sin_cos();
         let (sin_i_k, cos_i_k) = self.i_k.sin_cos();
         let (fd_x, fd_y) = self.orbit_velocity();
-        let fd_xgk = -y * self.fd_omega_k - fd_y * cos_i_k * sin_omega_k + fd_x * cos_omega_k;
-        let fd_ygk = x * self.fd_omega_k + fd_y * cos_i_k * cos_omega_k + fd_x * sin_omega_k;
-        let fd_zgk = fd_y * sin_i_k + y * self.fd_i_k * cos_i_k;
-
-        let rx = Rotation3::from_axis_angle(&Vector3::x_axis(), 5.0);
-        let rz = Rotation3::from_axis_angle(&Vector3::z_axis(), -Omega::BDS * self.t_k);
-        let (sin_omega_tk, cos_omega_tk) = (Omega::BDS * self.t_k).sin_cos();
-        let fd_rz = self.fd_omega_k
+
+        let fd_xgk = fd_x * cos_omega_k
+            - fd_y * cos_i_k * sin_omega_k
+            - (x * sin_omega_k + y * cos_omega_k * cos_i_k) * self.fd_omega_k
+            + y * sin_omega_k * sin_i_k * self.fd_i_k;
+
+        let fd_ygk = fd_x * sin_omega_k
+            + fd_y * cos_i_k * cos_omega_k
+            + (x * cos_omega_k - y * sin_omega_k * cos_i_k) * self.fd_omega_k
+            - y * cos_omega_k * sin_i_k * self.fd_i_k;
+
+        let fd_zgk = fd_y * sin_i_k + y * cos_i_k * self.fd_i_k;
+
+        Vector3::new(fd_xgk, fd_ygk, fd_zgk)
+    }
+
+    /// Returns ECEF position [Vector3] in km, for BeiDou GEO specifically
+    pub fn beidou_geo_ecef_position(&self) -> Vector3 {
+        let rotation = self.geo_orbit_to_ecef_rotation_matrix();
+        let ecef_xyz = rotation * self.beidou_geo_gk_position();
+        ecef_xyz / 1000.0
+    }
+
+    /// Returns ECEF velocity [Vector3] in km/s, for BeiDou GEO specifically
+    pub fn beidou_geo_ecef_velocity(&self) -> Vector3 {
+        let rx = Rotation3::from_axis_angle(&Vector3::x_axis(), 5.0f64.to_radians());
+        let rz = Rotation3::from_axis_angle(&Vector3::z_axis(), -self.omega_e * self.t_k);
+        let (sin_omega_tk, cos_omega_tk) = (self.omega_e * self.t_k).sin_cos();
+
+        // First derivative of the Earth rotation matrix
+        let fd_rz = self.omega_e
             * Matrix3::new(
                 -sin_omega_tk,
                 cos_omega_tk,
@@ -158,47 +198,27 @@ impl Helper {
                 0.0,
                 0.0,
             );
-        let pos = self.beidou_geo_ecef_position();
-        let fd_pos = Vector3::new(fd_xgk, fd_ygk, fd_zgk);
-        let vel = fd_rz * rx * pos + rz * rx * fd_pos;
-        vel
+
+        let pos = self.beidou_geo_gk_position();
+        let fd_pos = self.beidou_geo_gk_velocity();
+        let vel = fd_rz * (rx * pos) + rz * rx * fd_pos;
+        vel / 1000.0
     }
 
     /// Returns ECEF (position, velocity) [Vector3]s in (km, km/s), for BeiDou GEO specifically.
     pub fn beidou_geo_ecef_pv(&self) -> (Vector3, Vector3) {
-        let (x, y, _) = self.r_sv;
-        let (sin_omega_k, cos_omega_k) = self.omega_k.sin_cos();
-        let (sin_i_k, cos_i_k) = self.i_k.sin_cos();
-        let (fd_x, fd_y) = self.orbit_velocity();
-        let fd_xgk = -y * self.fd_omega_k - fd_y * cos_i_k * sin_omega_k + fd_x * cos_omega_k;
-        let fd_ygk = x * self.fd_omega_k + fd_y * cos_i_k * cos_omega_k + fd_x * sin_omega_k;
-        let fd_zgk = fd_y * sin_i_k + y * self.fd_i_k * cos_i_k;
-
-        let rx = Rotation3::from_axis_angle(&Vector3::x_axis(), 5.0);
-        let rz = Rotation3::from_axis_angle(&Vector3::z_axis(), -Omega::BDS * self.t_k);
-        let (sin_omega_tk, cos_omega_tk) = (Omega::BDS * self.t_k).sin_cos();
-        let fd_rz = self.fd_omega_k
-            * Matrix3::new(
-                -sin_omega_tk,
-                cos_omega_tk,
-                0.0,
-                -cos_omega_tk,
-                -sin_omega_tk,
-                0.0,
-                0.0,
-                0.0,
-                0.0,
-            );
-        let pos = self.beidou_geo_ecef_position();
-        let fd_pos = Vector3::new(fd_xgk, fd_ygk, fd_zgk);
-        let vel = fd_rz * rx * pos + rz * rx * fd_pos;
-        (pos, vel)
+        (
+            self.beidou_geo_ecef_position(),
+            self.beidou_geo_ecef_velocity(),
+        )
     }
 
     /// Returns ECEF position [Vector3] in km.
     pub fn position(&self) -> Option<Vector3> {
         match self.sv.constellation {
-            Constellation::GPS | Constellation::Galileo => Some(self.ecef_position()),
+            Constellation::GPS | Constellation::Galileo | Constellation::IRNSS => {
+                Some(self.ecef_position())
+            },
             Constellation::BeiDou => {
                 if self.sv.is_beidou_geo() {
                     Some(self.beidou_geo_ecef_position())
@@ -220,9 +240,10 @@ impl Helper {
             Some(self.beidou_geo_ecef_pv())
         } else {
             match self.sv.constellation {
-                Constellation::GPS | Constellation::Galileo | Constellation::BeiDou => {
-                    Some(self.ecef_pv())
-                },
+                Constellation::GPS
+                | Constellation::Galileo
+                | Constellation::BeiDou
+                | Constellation::IRNSS => Some(self.ecef_pv()),
                 _ => {
                     #[cfg(feature = "log")]
                     warn!("{} is not supported", self.sv.constellation);
@@ -235,42 +256,76 @@ impl Helper {
 
 impl Ephemeris {
     /// Try to form obtain a [Helper] for Keplerian equations solving.
-    /// This will fail on Glonass and SBAS constellations.
+    /// This will fail on Glonass and SBAS constellations, and on non physical
+    /// orbits: use [Ephemeris::checked_kepler] to obtain the reason.
     pub fn helper(&self, sv: SV, t: Epoch) -> Option<Helper> {
+        self.helper_with_config(sv, t, SolverConfig::default())
+    }
+
+    /// [Self::helper] using custom [SolverConfig].
+    pub fn helper_with_config(&self, sv: SV, t: Epoch, cfg: SolverConfig) -> Option<Helper> {
         // const
-        let gm_m3_s2 = Constants::gm(sv);
-        let omega = Constants::omega(sv);
-        let dtr_f = Constants::dtr_f(sv);
+        let constants = cfg.constants.constants(sv.constellation);
+        let gm_m3_s2 = constants.gm_m3_s2;
+        let omega = constants.omega_rad_s;
+        let dtr_f = constants.dtr_f;
+
+        // reject invalid configurations, rather than diverging
+        if let Err(_e) = cfg.validate() {
+            #[cfg(feature = "log")]
+            error!("{} solver: {}", sv, _e);
+            return None;
+        }
 
         let t_k = self.t_k(sv, t)?;
 
         let mut kepler = self.kepler()?;
         let perturbations = self.perturbations()?;
 
+        // CNAV and B-CNAV: the mean motion is that of the reference semi major axis,
+        // Adot only applies to the orbit radius (IS-GPS-200 30.3.3.1.3)
+        let n0 = (gm_m3_s2 / kepler.a.powi(3)).sqrt(); // average angular velocity
+
         // considering the filed a_dot
         if let Some(a_dot) = self.a_dot() {
             kepler.a += a_dot * t_k;
         }
 
-        let n0 = (gm_m3_s2 / kepler.a.powi(3)).sqrt(); // average angular velocity
-        let n = n0 + perturbations.dn; // corrected mean angular velocity
+        // reject non physical orbits, rather than producing NaN
+        if let Err(_e) = kepler.validate() {
+            #[cfg(feature = "log")]
+            error!("{} kepler: {}", sv, _e);
+            return None;
+        }
+
+        let mut dn = perturbations.dn;
+
+        // CNAV and B-CNAV: the mean motion difference drifts linearly
+        // (IS-GPS-200 30.3.3.1.3, BDS-SIS-ICD B1C 7.2.4)
+        if let Some(dn_dot) = self.delta_n0_dot() {
+            dn += 0.5 * dn_dot * t_k;
+        }
+
+        let n = n0 + dn; // corrected mean angular velocity
         let m_k = kepler.m_0 + n * t_k; // average anomaly
 
         // Iterative calculation of e_k
         let mut e_k_lst: f64 = 0.0;
-        let mut e_k;
-        let mut i = 0;
+        let mut e_k = m_k;
+        let mut iterations = 0;
+        let mut converged = false;
 
-        loop {
+        while iterations < cfg.max_iteration {
             e_k = m_k + kepler.e * e_k_lst.sin();
-            if (e_k - e_k_lst).abs() < 1e-10 {
+            iterations += 1;
+            if (e_k - e_k_lst).abs() < cfg.tolerance {
+                converged = true;
                 break;
             }
-            i += 1;
             e_k_lst = e_k;
         }
 
-        if i >= Constants::MAX_KEPLER_ITER {
+        if !converged {
             #[cfg(feature = "log")]
             error!("{} kepler iteration overflow", sv);
         }
@@ -294,7 +349,12 @@ impl Ephemeris {
         let di_k = perturbations.cis * x2_sin_phi_k + perturbations.cic * x2_cos_phi_k;
 
         // first derivatives
-        let fd_omega_k = perturbations.omega_dot - omega;
+        let fd_omega_k = if sv.is_beidou_geo() {
+            // BeiDou [GEO]: Earth rotation is applied in a dedicated step
+            perturbations.omega_dot
+        } else {
+            perturbations.omega_dot - omega
+        };
 
         let fd_e_k = n / (1.0 - kepler.e * e_k.cos());
         let fd_phi_k = ((1.0 + kepler.e) / (1.0 - kepler.e)).sqrt()
@@ -339,6 +399,10 @@ impl Ephemeris {
         Some(Helper {
             sv,
             t_k,
+            n,
+            m_k,
+            e_k,
+            v_k,
             omega_k,
             dtr,
             fd_dtr,
@@ -350,6 +414,9 @@ impl Ephemeris {
             fd_i_k,
             fd_omega_k,
             r_sv,
+            omega_e: omega,
+            iterations,
+            converged,
         })
     }
 }
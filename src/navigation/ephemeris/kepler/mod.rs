@@ -1,13 +1,22 @@
-use crate::prelude::{nav::Orbit, Constellation, Epoch, SV};
+use crate::prelude::{
+    nav::{Almanac, Frame, Orbit},
+    Constellation, Duration, EphemerisError, Epoch, SV,
+};
 
-use crate::navigation::Ephemeris;
+use crate::{
+    constants::Constants,
+    navigation::{sv_timescale, Ephemeris},
+};
 
 use anise::{
     constants::frames::IAU_EARTH_FRAME,
     math::{Vector3, Vector6},
 };
 
+mod constants;
 mod helper;
+
+pub use constants::{OrbitalConstants, SolverConstants};
 pub use helper::Helper;
 
 #[cfg(doc)]
@@ -41,6 +50,167 @@ pub struct Kepler {
     pub toe: f64,
 }
 
+impl Kepler {
+    /// Verifies that these [Kepler]ian parameters describe a physical (elliptical) orbit:
+    /// 0 <= e < 1 and a > 0. Corrupt or misparsed frames fail this test,
+    /// and would otherwise have the solver produce NaN coordinates.
+    pub fn validate(&self) -> Result<(), EphemerisError> {
+        if !(0.0..1.0).contains(&self.e) {
+            return Err(EphemerisError::NonPhysicalEccentricity(self.e));
+        }
+
+        if self.a.is_nan() || self.a <= 0.0 {
+            return Err(EphemerisError::NonPhysicalSemiMajorAxis(self.a));
+        }
+
+        Ok(())
+    }
+}
+
+/// [VelocityMethod] describes how the satellite velocity is obtained
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VelocityMethod {
+    /// Analytical first derivative of the Keplerian equations (default).
+    #[default]
+    Analytical,
+
+    /// Central finite difference of two position evaluations,
+    /// at t ± Δt, where Δt is expressed in seconds and must be strictly positive.
+    /// This is slower (three solver runs) but robust, and offers a
+    /// good cross-check of the analytical form.
+    FiniteDifference(f64),
+}
+
+/// [SolverConfig] describes how the iterative Kepler solver should behave.
+/// The eccentric anomaly is solved iteratively, until two consecutive
+/// estimates differ by less than the tolerance, or the maximal number of
+/// iterations has been reached. A looser tolerance converges faster
+/// at the expense of precision, a tighter tolerance gains precision
+/// but requires more iterations (you may then need to increase the iteration limit).
+/// The [Default] configuration suits all navigation applications.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SolverConfig {
+    /// Convergence tolerance on the eccentric anomaly (in radians).
+    /// Default value is 1E-10.
+    pub tolerance: f64,
+
+    /// Maximal number of iterations
+    pub max_iteration: usize,
+
+    /// [VelocityMethod] to be used. Default is [VelocityMethod::Analytical].
+    pub velocity: VelocityMethod,
+
+    /// [SolverConstants] to be used. Default values follow each ICD.
+    pub constants: SolverConstants,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self {
+            tolerance: 1.0E-10,
+            max_iteration: Constants::MAX_KEPLER_ITER as usize,
+            velocity: VelocityMethod::default(),
+            constants: SolverConstants::default(),
+        }
+    }
+}
+
+impl SolverConfig {
+    /// Copies and returns [SolverConfig] with updated convergence tolerance (in radians).
+    pub fn with_tolerance(&self, tolerance: f64) -> Self {
+        let mut s = *self;
+        s.tolerance = tolerance;
+        s
+    }
+
+    /// Copies and returns [SolverConfig] with updated maximal number of iterations.
+    pub fn with_max_iteration(&self, max_iteration: usize) -> Self {
+        let mut s = *self;
+        s.max_iteration = max_iteration;
+        s
+    }
+
+    /// Copies and returns [SolverConfig] with updated [VelocityMethod].
+    pub fn with_velocity_method(&self, velocity: VelocityMethod) -> Self {
+        let mut s = *self;
+        s.velocity = velocity;
+        s
+    }
+
+    /// Copies and returns [SolverConfig] with updated [SolverConstants].
+    pub fn with_constants(&self, constants: SolverConstants) -> Self {
+        let mut s = *self;
+        s.constants = constants;
+        s
+    }
+
+    /// Verifies that this [SolverConfig] is usable: the tolerance must be strictly
+    /// positive, otherwise the solver never converges, and so must the
+    /// [VelocityMethod::FiniteDifference] step, otherwise the velocity is a division by zero.
+    /// The solver rejects invalid configurations.
+    pub fn validate(&self) -> Result<(), EphemerisError> {
+        if self.tolerance.is_nan() || self.tolerance <= 0.0 {
+            return Err(EphemerisError::InvalidSolverTolerance(self.tolerance));
+        }
+
+        if let VelocityMethod::FiniteDifference(dt_s) = self.velocity {
+            if dt_s.is_nan() || dt_s <= 0.0 {
+                return Err(EphemerisError::InvalidFiniteDifferenceStep(dt_s));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// [SolverState] exposes the corrected orbital parameters that the Keplerian
+/// solver evaluated at a given [Epoch]. It is intended to diagnose anomalies,
+/// or to cross-check the solver against hand calculations: see [Ephemeris::solve_debug].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SolverState {
+    /// Time elapsed since ToE (in seconds)
+    pub t_k: f64,
+
+    /// Corrected mean motion (in radians.s⁻¹)
+    pub n: f64,
+
+    /// Mean anomaly (in radians)
+    pub m_k: f64,
+
+    /// Eccentric anomaly (in radians)
+    pub e_k: f64,
+
+    /// True anomaly (in radians)
+    pub v_k: f64,
+
+    /// Corrected argument of latitude (in radians)
+    pub u_k: f64,
+
+    /// Corrected orbit radius (in meters)
+    pub r_k: f64,
+
+    /// Corrected inclination (in radians)
+    pub i_k: f64,
+
+    /// Corrected longitude of the ascending node (in radians)
+    pub omega_k: f64,
+
+    /// Relativistic clock correction (in seconds)
+    pub dtr: f64,
+
+    /// Number of iterations of the eccentric anomaly solver
+    pub iterations: usize,
+
+    /// True if the eccentric anomaly solver converged within the iteration limit
+    pub converged: bool,
+
+    /// ECEF position (x, y, z) in kilometers
+    pub position_km: (f64, f64, f64),
+}
+
 /// Orbit [Perturbations]
 #[derive(Default, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -90,6 +260,18 @@ impl Ephemeris {
         })
     }
 
+    /// Retrieves Orbit Keplerian parameters, like [Self::kepler],
+    /// but also verifies they describe a physical orbit (see [Kepler::validate]).
+    /// Use this to understand why the solver rejects this [Ephemeris].
+    pub fn checked_kepler(&self) -> Result<Kepler, EphemerisError> {
+        let kepler = self
+            .kepler()
+            .ok_or(EphemerisError::MissingKeplerParameters)?;
+
+        kepler.validate()?;
+        Ok(kepler)
+    }
+
     /// Creates new [Ephemeris] frame from [Kepler]ian parameters
     pub fn with_kepler(&self, kepler: Kepler) -> Self {
         let mut s = self.clone();
@@ -103,7 +285,9 @@ impl Ephemeris {
         s
     }
 
-    /// Retrieves Orbit [Perturbations] from [Ephemeris]
+    /// Retrieves Orbit [Perturbations] from [Ephemeris].
+    /// CNAV frames describe the mean motion difference at reference time (deltaN0),
+    /// which is returned as [Perturbations::dn].
     pub fn perturbations(&self) -> Option<Perturbations> {
         Some(Perturbations {
             cuc: self.get_orbit_f64("cuc")?,
@@ -112,7 +296,9 @@ impl Ephemeris {
             cis: self.get_orbit_f64("cis")?,
             crc: self.get_orbit_f64("crc")?,
             crs: self.get_orbit_f64("crs")?,
-            dn: self.get_orbit_f64("deltaN")?,
+            dn: self
+                .get_orbit_f64("deltaN")
+                .or(self.get_orbit_f64("deltaN0"))?,
             i_dot: self.get_orbit_f64("idot")?,
             omega_dot: self.get_orbit_f64("omegaDot")?,
         })
@@ -133,6 +319,55 @@ impl Ephemeris {
         s
     }
 
+    /// Solves the Keplerian equations for this [SV] at desired [Epoch] and returns
+    /// all intermediate quantities as [SolverState], rather than the final [Orbit].
+    /// This is intended to diagnose per-satellite anomalies, or compare to hand calculations.
+    /// Like [Self::kepler2position], this does not apply to Glonass and SBAS.
+    /// ## Input
+    /// - sv: [SV] satellite identity
+    /// - epoch: desired [Epoch]
+    /// - max_iter: maximal number of iterations of the eccentric anomaly solver.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    ///
+    /// let rinex = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// let g10 = SV::from_str("G10").unwrap();
+    /// let t = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+    ///
+    /// let (_, _, eph) = rinex.nav_ephemeris_selection(g10, t)
+    ///     .unwrap();
+    ///
+    /// let state = eph.solve_debug(g10, t, 30)
+    ///     .unwrap();
+    ///
+    /// assert!(state.converged);
+    /// println!("{:#?}", state);
+    /// ```
+    pub fn solve_debug(&self, sv: SV, epoch: Epoch, max_iter: usize) -> Option<SolverState> {
+        let cfg = SolverConfig::default().with_max_iteration(max_iter);
+        let helper = self.helper_with_config(sv, epoch, cfg)?;
+        let position = helper.position()?;
+
+        Some(SolverState {
+            t_k: helper.t_k,
+            n: helper.n,
+            m_k: helper.m_k,
+            e_k: helper.e_k,
+            v_k: helper.v_k,
+            u_k: helper.u_k,
+            r_k: helper.r_k,
+            i_k: helper.i_k,
+            omega_k: helper.omega_k,
+            dtr: helper.dtr,
+            iterations: helper.iterations,
+            converged: helper.converged,
+            position_km: (position[0], position[1], position[2]),
+        })
+    }
+
     /// Returns total seconds elapsed in the timescale, between [Epoch] and ToE [Epoch].
     /// NB: this does not apply toe GEO [Ephemeris]
     fn t_k(&self, sv: SV, t: Epoch) -> Option<f64> {
@@ -141,12 +376,32 @@ impl Ephemeris {
             return None;
         }
 
-        let sv_ts = sv.timescale()?;
+        let sv_ts = sv_timescale(sv)?;
         let toe = self.toe(sv)?;
         let dt = t.to_time_scale(sv_ts) - toe;
         Some(dt.to_seconds())
     }
 
+    /// Returns ECEF velocity [Vector3] in km/s, using the [VelocityMethod]
+    /// defined in [SolverConfig], which was validated when forming the [Helper].
+    fn kepler_velocity(
+        &self,
+        sv: SV,
+        epoch: Epoch,
+        cfg: SolverConfig,
+        helper: &Helper,
+    ) -> Option<Vector3> {
+        match cfg.velocity {
+            VelocityMethod::Analytical => Some(helper.ecef_velocity()),
+            VelocityMethod::FiniteDifference(dt_s) => {
+                let dt = Duration::from_seconds(dt_s);
+                let before = self.helper_with_config(sv, epoch - dt, cfg)?;
+                let after = self.helper_with_config(sv, epoch + dt, cfg)?;
+                Some((after.ecef_position() - before.ecef_position()) / (2.0 * dt_s))
+            },
+        }
+    }
+
     /// Returns [SV] [Orbit]al state at t [Epoch].
     /// Self must be correctly selected from navigation record.
     /// See [Bibliography::AsceAppendix3], [Bibliography::JLe19] and [Bibliography::BeiDouICD]
@@ -154,6 +409,16 @@ impl Ephemeris {
     /// - sv: [SV] satellite identity
     /// - epoch: desired [Epoch]
     pub fn kepler2position(&self, sv: SV, epoch: Epoch) -> Option<Orbit> {
+        self.kepler2position_with_config(sv, epoch, SolverConfig::default())
+    }
+
+    /// [Self::kepler2position] using custom [SolverConfig].
+    pub fn kepler2position_with_config(
+        &self,
+        sv: SV,
+        epoch: Epoch,
+        cfg: SolverConfig,
+    ) -> Option<Orbit> {
         if sv.constellation.is_sbas() || sv.constellation == Constellation::Glonass {
             let (x_km, y_km, z_km) = (
                 self.get_orbit_f64("satPosX")?,
@@ -169,9 +434,9 @@ impl Ephemeris {
                 IAU_EARTH_FRAME,
             ))
         } else {
-            let helper = self.helper(sv, epoch)?;
+            let helper = self.helper_with_config(sv, epoch, cfg)?;
             let pos = helper.ecef_position();
-            let vel = helper.ecef_velocity();
+            let vel = self.kepler_velocity(sv, epoch, cfg, &helper)?;
             Some(Orbit::from_cartesian_pos_vel(
                 Vector6::new(pos[0], pos[1], pos[2], vel[0], vel[1], vel[2]),
                 epoch,
@@ -180,6 +445,131 @@ impl Ephemeris {
         }
     }
 
+    /// Resolves the [Orbit]al state of this [SV] at desired [Epoch] (ECEF),
+    /// using custom [SolverConstants] rather than the ICD constants.
+    /// ## Input
+    /// - sv: [SV] satellite identity
+    /// - epoch: desired [Epoch]
+    /// - max_iter: maximal number of iterations of the eccentric anomaly solver.
+    /// - constants: [SolverConstants] to use
+    /// ```
+    /// use rinex::prelude::*;
+    /// use rinex::navigation::{OrbitalConstants, SolverConstants};
+    /// use std::str::FromStr;
+    ///
+    /// let rinex = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// let g10 = SV::from_str("G10").unwrap();
+    /// let t = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+    ///
+    /// let (_, _, eph) = rinex.nav_ephemeris_selection(g10, t)
+    ///     .unwrap();
+    ///
+    /// // use the WGS84 gravitational constant for GPS
+    /// let constants = SolverConstants::default()
+    ///     .with_constants(Constellation::GPS, OrbitalConstants {
+    ///         gm_m3_s2: 3.986004418E14,
+    ///         ..SolverConstants::default().gps
+    ///     });
+    ///
+    /// let orbit = eph.resolve_orbital_state_with_constants(g10, t, 30, constants)
+    ///     .unwrap();
+    /// ```
+    pub fn resolve_orbital_state_with_constants(
+        &self,
+        sv: SV,
+        epoch: Epoch,
+        max_iter: usize,
+        constants: SolverConstants,
+    ) -> Option<Orbit> {
+        let cfg = SolverConfig::default()
+            .with_max_iteration(max_iter)
+            .with_constants(constants);
+
+        self.kepler2position_with_config(sv, epoch, cfg)
+    }
+
+    /// Resolves the [Orbit]al state of this [SV] at desired [Epoch], expressed in
+    /// any [Frame]. [Self::kepler2position] resolves the ECEF state
+    /// (IAU_EARTH_FRAME), which we then transform with this [Almanac].
+    /// The [Almanac] should therefore describe the Earth orientation, and
+    /// the origin of this [Frame] when it is not centered on Earth.
+    /// ## Input
+    /// - sv: [SV] satellite identity
+    /// - epoch: desired [Epoch]
+    /// - frame: desired [Frame], for example EARTH_J2000 (ECI)
+    /// - max_iter: maximal number of iterations of the eccentric anomaly solver.
+    /// - almanac: [Almanac] used for the transformation
+    /// ## Returns
+    /// - [Orbit] expressed in this [Frame]
+    /// - None if the state could not be resolved, or the [Almanac] lacks
+    ///   the data this transformation requires.
+    pub fn resolve_orbital_state_in_frame(
+        &self,
+        sv: SV,
+        epoch: Epoch,
+        frame: Frame,
+        max_iter: usize,
+        almanac: &Almanac,
+    ) -> Option<Orbit> {
+        let cfg = SolverConfig::default().with_max_iteration(max_iter);
+        let ecef = self.kepler2position_with_config(sv, epoch, cfg)?;
+        almanac.transform_to(ecef, frame, None).ok()
+    }
+
+    /// Resolves the [Orbit]al state at the reference time of this [Ephemeris]
+    /// and at desired [Epoch], in one call and with the same solver setup.
+    /// This is convenient to validate an [Ephemeris], since the state at the
+    /// reference time does not suffer from propagation errors.
+    /// ## Input
+    /// - sv: [SV] satellite identity
+    /// - toc: Time of Clock, as stored in the [NavKey](crate::navigation::NavKey).
+    ///   It is the reference time when ToE does not apply (Glonass and SBAS).
+    /// - epoch: desired [Epoch]
+    /// - max_iter: maximal number of iterations of the eccentric anomaly solver.
+    /// ## Returns
+    /// - (reference, requested) [Orbit]al states. The reference state is expressed
+    ///   at ToE, in the [SV] timescale.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    ///
+    /// let rinex = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// let g10 = SV::from_str("G10").unwrap();
+    /// let t = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+    ///
+    /// let (toc, _, eph) = rinex.nav_ephemeris_selection(g10, t)
+    ///     .unwrap();
+    ///
+    /// let (at_toe, at_t) = eph.resolve_reference_and_epoch(g10, toc, t, 30)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(at_t.epoch, t);
+    /// ```
+    pub fn resolve_reference_and_epoch(
+        &self,
+        sv: SV,
+        toc: Epoch,
+        epoch: Epoch,
+        max_iter: usize,
+    ) -> Option<(Orbit, Orbit)> {
+        let cfg = SolverConfig::default().with_max_iteration(max_iter);
+
+        let reference = if sv.constellation.is_sbas() || sv.constellation == Constellation::Glonass
+        {
+            toc
+        } else {
+            self.toe(sv)?
+        };
+
+        let reference = self.kepler2position_with_config(sv, reference, cfg)?;
+        let requested = self.kepler2position_with_config(sv, epoch, cfg)?;
+        Some((reference, requested))
+    }
+
     /// Calculates ECEF (position, velocity) [Vector3] duplet
     /// ## Input
     /// - sv: desired [SV]
@@ -188,6 +578,16 @@ impl Ephemeris {
     /// - (position, velocity): [Vector3] duplet, in (km, km/s)
     /// See [Bibliography::AsceAppendix3], [Bibliography::JLe19] and [Bibliography::BeiDouICD]
     pub fn kepler2position_velocity(&self, sv: SV, epoch: Epoch) -> Option<(Vector3, Vector3)> {
+        self.kepler2position_velocity_with_config(sv, epoch, SolverConfig::default())
+    }
+
+    /// [Self::kepler2position_velocity] using custom [SolverConfig].
+    pub fn kepler2position_velocity_with_config(
+        &self,
+        sv: SV,
+        epoch: Epoch,
+        cfg: SolverConfig,
+    ) -> Option<(Vector3, Vector3)> {
         // In gloass and SBAS scenarios,
         // we only need to pick up the values from the record.
         // NB: this is incorrect, it requires an integration process
@@ -210,8 +610,14 @@ impl Ephemeris {
             Some((position, velocity))
         } else {
             // form keplerian helper
-            let helper = self.helper(sv, epoch)?;
-            helper.position_velocity()
+            let helper = self.helper_with_config(sv, epoch, cfg)?;
+            let (pos, vel) = helper.position_velocity()?;
+            match cfg.velocity {
+                VelocityMethod::Analytical => Some((pos, vel)),
+                VelocityMethod::FiniteDifference(_) => {
+                    Some((pos, self.kepler_velocity(sv, epoch, cfg, &helper)?))
+                },
+            }
         }
     }
 }
@@ -9,8 +9,9 @@ use orbits::OrbitItem;
 
 use flags::{
     bds::{BdsHealth, BdsSatH1},
-    glonass::{GlonassHealth, GlonassHealth2},
-    gps::GpsQzssl1cHealth,
+    gal::GalNavType,
+    glonass::GlonassHealthDetail,
+    gps::GpsQzssl1cHealthDetail,
 };
 
 #[cfg(feature = "log")]
@@ -44,7 +45,11 @@ use anise::{
 
 use std::collections::HashMap;
 
-use crate::prelude::{Constellation, Duration, Epoch, TimeScale, SV};
+use crate::{
+    epoch::resolve_time_of_week,
+    navigation::{sv_timescale, NavKey, NavMessageType},
+    prelude::{Constellation, Duration, Epoch, TimeScale, SV},
+};
 
 /// Ephemeris Navigation message. May be found in all RINEX revisions.
 /// Describes the content of the radio message at publication time.
@@ -214,6 +219,7 @@ impl Ephemeris {
     }
 
     /// Returns true if this [Ephemeris] declares attached SV as suitable for navigation.
+    /// For Glonass, this is [GlonassHealthDetail::healthy].
     pub fn sv_healthy(&self) -> bool {
         let health = self.orbits.get("health");
 
@@ -225,20 +231,10 @@ impl Ephemeris {
 
         if let Some(flag) = health.as_gps_qzss_l1l2l5_health_flag() {
             flag.healthy()
-        } else if let Some(flag) = health.as_gps_qzss_l1c_health_flag() {
-            !flag.intersects(GpsQzssl1cHealth::UNHEALTHY)
-        } else if let Some(flag) = health.as_glonass_health_flag() {
-            // TODO: Status mask .. ?
-            if let Some(flag2) = self
-                .orbits
-                .get("health2")
-                .and_then(|item| Some(item.as_glonass_health2_flag().unwrap()))
-            {
-                !flag.intersects(GlonassHealth::UNHEALTHY)
-                    && flag2.intersects(GlonassHealth2::HEALTHY_ALMANAC)
-            } else {
-                !flag.intersects(GlonassHealth::UNHEALTHY)
-            }
+        } else if let Some(detail) = self.l1c_health_detail() {
+            detail.healthy()
+        } else if let Some(detail) = self.glonass_health_detail() {
+            detail.healthy()
         } else if let Some(flag) = health.as_geo_health_flag() {
             // TODO !
             false
@@ -251,6 +247,36 @@ impl Ephemeris {
         }
     }
 
+    /// Returns the complete interpretation of the L1C health word,
+    /// in case this is a GPS or QZSS CNAV-2 [Ephemeris] frame.
+    pub fn l1c_health_detail(&self) -> Option<GpsQzssl1cHealthDetail> {
+        let flag = self.orbits.get("health")?.as_gps_qzss_l1c_health_flag()?;
+        Some(GpsQzssl1cHealthDetail::from(&flag))
+    }
+
+    /// Returns the structured interpretation of the health words (Bn, Cn, ln),
+    /// in case this is a Glonass [Ephemeris] frame. Cn and ln are only
+    /// reported by modern (RINEX4) frames.
+    pub fn glonass_health_detail(&self) -> Option<GlonassHealthDetail> {
+        let health = self.orbits.get("health")?.as_glonass_health_flag()?;
+
+        let health2 = self
+            .orbits
+            .get("health2")
+            .and_then(|item| item.as_glonass_health2_flag());
+
+        let status = self
+            .orbits
+            .get("status")
+            .and_then(|item| item.as_glonass_status_mask());
+
+        Some(GlonassHealthDetail::new(
+            &health,
+            health2.as_ref(),
+            status.as_ref(),
+        ))
+    }
+
     /// Returns true if this [Ephemeris] message declares this satellite in testing mode.
     pub fn sv_in_testing(&self) -> bool {
         let health = self.orbits.get("health");
@@ -279,9 +305,60 @@ impl Ephemeris {
         }
     }
 
-    /// Return Time of [Ephemeris] (ToE) expressed as [Epoch]
+    /// Returns the Issue of Data of this [Ephemeris], whatever the [Constellation]:
+    /// IODE (GPS, QZSS, BeiDou CNAV), IODnav (Galileo), AODE (BeiDou D1/D2) or IODN (SBAS).
+    /// Glonass does not define such a field.
+    pub fn issue_of_data(&self) -> Option<u32> {
+        ["iode", "iodnav", "aode", "iodn"]
+            .iter()
+            .find_map(|field| self.orbits.get(*field))
+            .map(|value| value.as_f64().round() as u32)
+    }
+
+    /// Returns true if the Issue of Data differs between two [Ephemeris] frames,
+    /// meaning the satellite broadcast a new ephemeris set.
+    /// See [Self::issue_of_data] for the supported fields. We return false
+    /// when either frame does not define the Issue of Data (Glonass for example):
+    /// prefer [Self::differs_from] in that case.
+    pub fn iode_changed(&self, other: &Self) -> bool {
+        match (self.issue_of_data(), other.issue_of_data()) {
+            (Some(lhs), Some(rhs)) => lhs != rhs,
+            _ => false,
+        }
+    }
+
+    /// Returns true if two [Ephemeris] frames describe different ephemeris sets.
+    /// That is either the Issue of Data changed (see [Self::iode_changed]),
+    /// or the clock correction or one of the key orbital elements differ.
+    /// This is the basis of ephemeris update detection.
+    pub fn differs_from(&self, other: &Self) -> bool {
+        if self.iode_changed(other) || self.sv_clock() != other.sv_clock() {
+            return true;
+        }
+
+        // Keplerian elements, or state vector (Glonass, SBAS)
+        [
+            "toe", "sqrta", "e", "i0", "omega0", "omega", "m0", "deltaN", "satPosX", "satPosY",
+            "satPosZ", "velX", "velY", "velZ",
+        ]
+        .iter()
+        .any(|field| self.get_orbit_f64(field) != other.get_orbit_f64(field))
+    }
+
+    /// Return Time of [Ephemeris] (ToE) expressed as [Epoch].
+    /// ToE is defined by the week counter and the "toe" seconds of week,
+    /// expressed in the [SV] timescale. IRNSS does not have a dedicated timescale
+    /// in our time library: its week counter being aligned to the GPS week, we express it in GPST.
+    ///
+    /// This does not apply to the following constellations, for which None is returned:
+    /// - Glonass does not broadcast a week counter nor a ToE, but a minute of day (tb).
+    ///   Use [Self::glonass_toe] instead.
+    /// - SBAS (GEO) frames are state vectors, published with their time of
+    ///   applicability (ToC) only. The ToC should be used directly.
+    ///
+    /// Modern (RINEX V4) messages do not stream a week counter, prefer
+    /// [Self::toe_with_msgtype] whenever the [NavMessageType] is known.
     pub fn toe(&self, sv: SV) -> Option<Epoch> {
-        // TODO: in CNAV V4 TOC is said to be TOE... ...
         let (week, seconds) = (self.get_week()?, self.get_orbit_f64("toe")?);
         let nanos = (seconds * 1.0E9).round() as u64;
 
@@ -290,6 +367,7 @@ impl Ephemeris {
                 Some(Epoch::from_time_of_week(week, nanos, TimeScale::GPST))
             },
             Constellation::BeiDou => Some(Epoch::from_time_of_week(week, nanos, TimeScale::BDT)),
+            Constellation::IRNSS => Some(Epoch::from_time_of_week(week, nanos, sv_timescale(sv)?)),
             _ => {
                 #[cfg(feature = "log")]
                 error!("{} is not supported", sv.constellation);
@@ -298,9 +376,109 @@ impl Ephemeris {
         }
     }
 
+    /// Return Time of [Ephemeris] (ToE) expressed as [Epoch], taking the [NavMessageType]
+    /// (from the [NavKey](crate::navigation::NavKey)) into account:
+    /// - GPS and QZSS CNAV / CNAV-2 frames do not stream a ToE, which is the ToC.
+    /// - BeiDou CNAV-1/2/3 frames stream the ToE seconds of week, but no week counter:
+    ///   we resolve the week with respect to the ToC.
+    /// - other messages are handled by [Self::toe].
+    /// ## Input
+    /// - sv: [SV] broadcasting this frame
+    /// - msgtype: [NavMessageType] of this frame
+    /// - toc: Time of Clock, as stored in the [NavKey](crate::navigation::NavKey)
+    pub fn toe_with_msgtype(&self, sv: SV, msgtype: NavMessageType, toc: Epoch) -> Option<Epoch> {
+        match (sv.constellation, msgtype) {
+            (
+                Constellation::GPS | Constellation::QZSS,
+                NavMessageType::CNAV | NavMessageType::CNV2,
+            ) => Some(toc.to_time_scale(TimeScale::GPST)),
+            (
+                Constellation::BeiDou,
+                NavMessageType::CNV1 | NavMessageType::CNV2 | NavMessageType::CNV3,
+            ) => {
+                let nanos = (self.get_orbit_f64("toe")? * 1.0E9).round() as u64;
+                Some(resolve_time_of_week(toc, nanos, TimeScale::BDT))
+            },
+            _ => self.toe(sv),
+        }
+    }
+
+    /// Returns the Glonass reference time (tb), expressed in minutes of the Moscow day
+    /// (UTC(SU) + 3h), if it was provided or could be derived when decoding this frame.
+    pub fn glonass_tb_minutes(&self) -> Option<u16> {
+        let tb = self.get_orbit_f64("tb")?;
+        Some(tb.round() as u16)
+    }
+
+    /// Reconstructs the Glonass ToE [Epoch] from the tb field, which only
+    /// describes a minute of day. Glonass does not define a week counter nor a ToE
+    /// like other constellations, which is why [Self::toe] does not apply here.
+    /// ## Inputs
+    /// - sv: Glonass [SV]
+    /// - reference: any [Epoch] close to the publication of this frame,
+    /// usually the ToC or the file's date. The tb occurrence closest to this reference
+    /// is returned, so day boundaries are correctly handled.
+    /// ## Returns
+    /// - ToE as [Epoch] expressed in the [TimeScale] of the reference
+    /// - None if [SV] is not a Glonass vehicle or tb is not defined.
+    pub fn glonass_toe(&self, sv: SV, reference: Epoch) -> Option<Epoch> {
+        if sv.constellation != Constellation::Glonass {
+            return None;
+        }
+
+        let tb = self.glonass_tb_minutes()?;
+        let moscow_offset = Duration::from_hours(3.0);
+
+        let t_msk = reference.to_time_scale(TimeScale::UTC) + moscow_offset;
+        let (y, m, d, _, _, _, _) = t_msk.to_gregorian_utc();
+
+        let mut toe = Epoch::from_gregorian_utc_at_midnight(y, m, d)
+            + Duration::from_seconds(tb as f64 * 60.0)
+            - moscow_offset;
+
+        let dt = toe - reference.to_time_scale(TimeScale::UTC);
+
+        if dt > Duration::from_hours(12.0) {
+            toe -= Duration::from_days(1.0);
+        } else if dt < Duration::from_hours(-12.0) {
+            toe += Duration::from_days(1.0);
+        }
+
+        Some(toe.to_time_scale(reference.time_scale))
+    }
+
+    /// Derives the Glonass tb (minutes of the Moscow day) from the ToC
+    /// of a RINEX frame, which is the tb expressed in UTC.
+    pub(crate) fn glonass_tb_from_toc(toc: Epoch) -> f64 {
+        let t_msk = toc.to_time_scale(TimeScale::UTC) + Duration::from_hours(3.0);
+        let (_, _, _, hh, mm, _, _) = t_msk.to_gregorian_utc();
+        (hh as f64) * 60.0 + mm as f64
+    }
+
+    /// Returns the Galileo [GalNavType] (I/NAV or F/NAV) this [Ephemeris] was decoded from.
+    /// Modern RINEX identify it with the [NavMessageType] itself, while
+    /// older revisions only describe it in the data source ("source") field.
+    pub fn galileo_nav_type(&self, msgtype: NavMessageType) -> Option<GalNavType> {
+        match msgtype {
+            NavMessageType::INAV => Some(GalNavType::INav),
+            NavMessageType::FNAV => Some(GalNavType::FNav),
+            _ => self
+                .orbits
+                .get("source")?
+                .as_galileo_data_source()?
+                .nav_type(),
+        }
+    }
+
     /// Returns Adot parameter from a CNAV ephemeris
     pub(crate) fn a_dot(&self) -> Option<f64> {
-        self.get_orbit_f64("a_dot")
+        self.get_orbit_f64("adot")
+    }
+
+    /// Returns the rate of change of the mean motion difference (in radians.s⁻²)
+    /// from a CNAV ephemeris.
+    pub(crate) fn delta_n0_dot(&self) -> Option<f64> {
+        self.get_orbit_f64("deltaN0Dot")
     }
 }
 
@@ -317,6 +495,16 @@ impl Ephemeris {
         self.with_orbit("week", OrbitItem::from(week))
     }
 
+    /// Creates new [Ephemeris] with desired ToE (week counter and seconds of week),
+    /// expressed in the [SV] timescale. Modern (CNAV) frames do not define
+    /// both: resolve it with [Self::toe_with_msgtype] then define it here,
+    /// prior to using the Keplerian solver.
+    pub fn with_toe(&self, toe: Epoch) -> Self {
+        let (week, nanos) = toe.to_time_of_week();
+        self.with_week(week)
+            .with_orbit("toe", OrbitItem::F64(nanos as f64 * 1.0E-9))
+    }
+
     /// Calculates Clock correction for [SV] at [Epoch] based on [Self]
     /// and ToC [Epoch] of publication of [Self] from the free running clock.
     pub fn clock_correction(
@@ -326,7 +514,7 @@ impl Ephemeris {
         sv: SV,
         max_iter: usize,
     ) -> Option<Duration> {
-        let sv_ts = sv.constellation.timescale()?;
+        let sv_ts = sv_timescale(sv)?;
 
         let t_sv = t.to_time_scale(sv_ts);
         let toc_sv = toc.to_time_scale(sv_ts);
@@ -336,15 +524,23 @@ impl Ephemeris {
             error!("t < t_oc: bad op!");
             None
         } else {
-            let (a0, a1, a2) = (self.clock_bias, self.clock_drift, self.clock_drift_rate);
             let mut dt = (t_sv - toc_sv).to_seconds();
             for _ in 0..max_iter {
-                dt -= a0 + a1 * dt + a2 * dt.powi(2);
+                dt -= self.clock_polynomial(dt);
             }
-            Some(Duration::from_seconds(a0 + a1 * dt + a2 * dt.powi(2)))
+            Some(Duration::from_seconds(self.clock_polynomial(dt)))
         }
     }
 
+    /// Evaluates the [SV] clock polynomial a0 + a1 * dt + a2 * dt²,
+    /// where dt is the elapsed time since ToC (in seconds).
+    /// Returns the clock offset (in seconds). Unlike [Self::clock_correction],
+    /// dt is used as is and not iterated on.
+    pub fn clock_polynomial(&self, dt_seconds: f64) -> f64 {
+        let (a0, a1, a2) = (self.clock_bias, self.clock_drift, self.clock_drift_rate);
+        a0 + a1 * dt_seconds + a2 * dt_seconds.powi(2)
+    }
+
     /// (elevation, azimuth, range) determination helper,
     /// returned in the form of [AzElRange], for desired [SV] observed at RX coordinates,
     /// expressed in km in fixed body [Frame] centered on Earth.
@@ -369,11 +565,18 @@ impl Ephemeris {
     /// Returns True if this [Ephemeris] frame is valid for specified epoch.
     /// NB: this only applies to MEO Ephemerides, not GEO Ephemerides,
     /// which should always be considered "valid".
+    /// This relies on [Self::toe], which cannot resolve GPS/QZSS CNAV
+    /// and BeiDou CNAV-1/2/3 frames: prefer [Self::is_valid_with_msgtype].
     /// ## Input
     /// - sv: [SV] identity
     /// - epoch: test [Epoch]
     pub fn is_valid(&self, sv: SV, t: Epoch) -> bool {
-        if let Some(toe) = self.toe(sv) {
+        let toe = match sv.constellation {
+            Constellation::Glonass => self.glonass_toe(sv, t),
+            _ => self.toe(sv),
+        };
+
+        if let Some(toe) = toe {
             if let Some(max_dtoe) = Self::validity_duration(sv.constellation) {
                 (t - toe).abs() < max_dtoe
             } else {
@@ -388,6 +591,56 @@ impl Ephemeris {
         }
     }
 
+    /// Returns True if this [Ephemeris] frame is valid at specified [Epoch],
+    /// taking the [NavMessageType] into account, like [Self::toe_with_msgtype].
+    /// The frame is valid within [Self::validity_duration] of its reference time:
+    /// - ToE, as resolved by [Self::toe_with_msgtype]
+    /// - Glonass frames use their tb, see [Self::glonass_toe]
+    /// - SBAS (GEO) frames do not describe a ToE and use the ToC.
+    /// ## Input
+    /// - sv: [SV] identity
+    /// - msgtype: [NavMessageType] of this frame
+    /// - toc: Time of Clock, as stored in the [NavKey](crate::navigation::NavKey)
+    /// - t: test [Epoch]
+    pub fn is_valid_with_msgtype(
+        &self,
+        sv: SV,
+        msgtype: NavMessageType,
+        toc: Epoch,
+        t: Epoch,
+    ) -> bool {
+        let reference = if sv.constellation.is_sbas() {
+            Some(toc)
+        } else if sv.constellation == Constellation::Glonass {
+            self.glonass_toe(sv, toc)
+        } else {
+            self.toe_with_msgtype(sv, msgtype, toc)
+        };
+
+        match (reference, Self::validity_duration(sv.constellation)) {
+            (Some(reference), Some(max_dtoe)) => (t - reference).abs() < max_dtoe,
+            _ => {
+                #[cfg(feature = "log")]
+                error!("{}({}) - ToE calculation", toc, sv);
+                false
+            },
+        }
+    }
+
+    /// Returns True if this [Ephemeris] frame is valid at specified [Epoch],
+    /// using the [NavKey] it is indexed by, so the caller does not have
+    /// to resolve the reference time. This is [Self::is_valid_with_msgtype]:
+    /// - Glonass frames use their tb (ToE), see [Self::glonass_toe].
+    /// - SBAS (GEO) frames do not describe a ToE: they are valid within
+    ///   [Self::validity_duration] of their ToC.
+    /// - other constellations use their ToE, see [Self::toe_with_msgtype].
+    /// ## Input
+    /// - key: [NavKey] of this frame
+    /// - epoch: test [Epoch]
+    pub fn is_valid_at(&self, key: &NavKey, epoch: Epoch) -> bool {
+        self.is_valid_with_msgtype(key.sv, key.msgtype, key.epoch, epoch)
+    }
+
     /// Ephemeris validity period for this [Constellation]
     pub fn validity_duration(c: Constellation) -> Option<Duration> {
         match c {
@@ -461,3 +714,37 @@ impl std::fmt::Display for Ephemeris {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::Ephemeris;
+
+    #[test]
+    fn clock_polynomial() {
+        let eph = Ephemeris {
+            clock_bias: 1.0E-4,
+            clock_drift: -2.0E-11,
+            clock_drift_rate: 3.0E-18,
+            ..Default::default()
+        };
+
+        for (dt_seconds, expected) in [
+            (0.0, 1.0E-4),
+            // 1E-4 - 2E-11 * 100 + 3E-18 * 1E4
+            (100.0, 9.999_800_003E-5),
+            // 1E-4 + 2E-11 * 3600 + 3E-18 * 1.296E7
+            (-3600.0, 1.000_720_388_8E-4),
+            // 1E-4 - 2E-11 * 7200 + 3E-18 * 5.184E7
+            (7200.0, 9.985_615_552E-5),
+        ] {
+            let dut = eph.clock_polynomial(dt_seconds);
+            assert!(
+                (dut - expected).abs() < 1.0E-16,
+                "dt={}s: got {:e} expecting {:e}",
+                dt_seconds,
+                dut,
+                expected
+            );
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //! NAV Orbits description, spanning all revisions and constellations
 use std::str::FromStr;
 
+use bitflags::Flags;
+
 use crate::{
     navigation::ephemeris::flags::{
         bds::{
@@ -22,6 +24,19 @@ use serde::Serialize;
 
 include!(concat!(env!("OUT_DIR"), "/nav_orbits.rs"));
 
+/// Converts an angle (or angular rate) expressed in radians, which is
+/// how RINEX stores them, to semicircles, which is how most ICDs (and
+/// therefore UBX and RTCM) stream them.
+pub fn rad_to_semicircles(rad: f64) -> f64 {
+    rad / std::f64::consts::PI
+}
+
+/// Converts an angle (or angular rate) expressed in semicircles
+/// to radians. This is the reciprocal of [rad_to_semicircles].
+pub fn semicircles_to_rad(semicircles: f64) -> f64 {
+    semicircles * std::f64::consts::PI
+}
+
 /// [OrbitItem] item is Navigation ephemeris entry.
 /// It is a complex data wrapper, for high level
 /// record description, across all revisions and constellations
@@ -91,6 +106,36 @@ pub enum OrbitItem {
     BdsB2bIntegrity(BdsB2bIntegrity),
 }
 
+/// Writes the name of each asserted flag, or `none` when no flag is asserted.
+/// Bits that do not have a name are written in hexadecimal.
+fn fmt_flags<F: Flags<Bits = u32>>(
+    f: &mut std::fmt::Formatter,
+    flags: &F,
+    none: &str,
+) -> std::fmt::Result {
+    if flags.is_empty() {
+        return f.write_str(none);
+    }
+
+    let mut named = 0;
+    let mut names = Vec::new();
+
+    for (name, flag) in flags.iter_names() {
+        names.push(name.to_string());
+        named |= flag.bits();
+    }
+
+    let unknown = flags.bits() & !named;
+
+    if unknown != 0 {
+        names.push(format!("0x{:x}", unknown));
+    }
+
+    write!(f, "{}", names.join(" | "))
+}
+
+/// [OrbitItem]s are displayed with their interpreted meaning: flags
+/// are decoded and named, numerical values are displayed as is.
 impl std::fmt::Display for OrbitItem {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -98,22 +143,52 @@ impl std::fmt::Display for OrbitItem {
             Self::I8(val) => write!(f, "{:02x}", val),
             Self::U32(val) => write!(f, "{:08X}", val),
             Self::F64(val) => write!(f, "{}", val),
-            Self::Gpsl2pFlag(val) => write!(f, "l2p={:?}", val),
-            Self::GeoHealth(val) => write!(f, "{:?}", val),
-            Self::GalHealth(val) => write!(f, "{:?}", val),
-            Self::GalDataSource(val) => write!(f, "{:?}", val),
-            Self::IrnssHealth(val) => write!(f, "{:?}", val),
-            Self::GlonassHealth(val) => write!(f, "{:?}", val),
-            Self::GlonassStatus(val) => write!(f, "{:?}", val),
-            Self::BdsSatH1(val) => write!(f, "{:?}", val),
+            Self::Gpsl2pFlag(val) => {
+                if *val {
+                    write!(f, "L2P(Y) data ON")
+                } else {
+                    write!(f, "L2P(Y) data OFF")
+                }
+            },
+            Self::GpsQzssl1l2l5Health(val) => {
+                if val.healthy() {
+                    return write!(f, "HEALTHY");
+                }
+                let mut unhealthy = Vec::with_capacity(3);
+                if val.l1_unhealthy() {
+                    unhealthy.push("L1");
+                }
+                if val.l2_unhealthy() {
+                    unhealthy.push("L2");
+                }
+                if val.l5_unhealthy() {
+                    unhealthy.push("L5");
+                }
+                if unhealthy.is_empty() {
+                    write!(f, "UNHEALTHY (0x{:x})", val.0)
+                } else {
+                    write!(f, "UNHEALTHY ({})", unhealthy.join(" | "))
+                }
+            },
+            Self::GpsQzssl1cHealth(val) => fmt_flags(f, val, "HEALTHY"),
+            Self::GeoHealth(val) => fmt_flags(f, val, "HEALTHY"),
+            Self::GalHealth(val) => fmt_flags(f, val, "HEALTHY"),
+            Self::GalDataSource(val) => fmt_flags(f, val, "UNKNOWN"),
+            Self::IrnssHealth(val) => fmt_flags(f, val, "HEALTHY"),
+            Self::GlonassHealth(val) => fmt_flags(f, val, "HEALTHY"),
+            Self::GlonassHealth2(val) => fmt_flags(f, val, "NONE"),
+            Self::GlonassStatus(val) => write!(
+                f,
+                "{:?} | {:?}",
+                val.glonass_p2_update_validity_interval(),
+                val.glonass_mk_time_offset_source()
+            ),
+            Self::BdsSatH1(val) => fmt_flags(f, val, "HEALTHY"),
             Self::BdsHealth(val) => write!(f, "{:?}", val),
             Self::BdsSatelliteType(val) => write!(f, "{:?}", val),
-            Self::GlonassHealth2(val) => write!(f, "{:?}", val),
-            Self::GpsQzssl1cHealth(val) => write!(f, "{:?}", val),
-            Self::GpsQzssl1l2l5Health(val) => write!(f, "{:?}", val),
-            Self::BdsB1cIntegrity(val) => write!(f, "{:?}", val),
-            Self::BdsB2aB1cIntegrity(val) => write!(f, "{:?}", val),
-            Self::BdsB2bIntegrity(val) => write!(f, "{:?}", val),
+            Self::BdsB1cIntegrity(val) => fmt_flags(f, val, "NONE"),
+            Self::BdsB2aB1cIntegrity(val) => fmt_flags(f, val, "NONE"),
+            Self::BdsB2bIntegrity(val) => fmt_flags(f, val, "NONE"),
         }
     }
 }
@@ -209,8 +284,8 @@ impl OrbitItem {
                                 Ok(OrbitItem::GpsQzssl1l2l5Health(flags))
                             },
                             (NavMessageType::CNV2, Constellation::GPS | Constellation::QZSS) => {
-                                let flags = GpsQzssl1cHealth::from_bits(unsigned)
-                                    .ok_or(ParsingError::NavFlagsMapping)?;
+                                // spare bits are retained, see GpsQzssl1cHealthDetail
+                                let flags = GpsQzssl1cHealth::from_bits_retain(unsigned);
 
                                 Ok(OrbitItem::GpsQzssl1cHealth(flags))
                             },
@@ -335,6 +410,13 @@ impl OrbitItem {
         }
     }
 
+    /// Unwraps this angular [OrbitItem], stored in radians, as semicircles.
+    /// Only applies to angles and angular rates (m0, omega, i0, idot..),
+    /// any other field is meaningless once converted.
+    pub fn as_semicircles(&self) -> f64 {
+        rad_to_semicircles(self.as_f64())
+    }
+
     /// Unwraps [OrbitItem] as [f64] (always feasible)
     pub fn as_f64(&self) -> f64 {
         match self {
@@ -503,6 +585,14 @@ impl OrbitItem {
         }
     }
 
+    /// Unwraps Self as [GalDataSource] indication (if feasible)
+    pub fn as_galileo_data_source(&self) -> Option<GalDataSource> {
+        match self {
+            OrbitItem::GalDataSource(source) => Some(*source),
+            _ => None,
+        }
+    }
+
     /// Unwraps Self as historical (and D1/D2) [BdsSatH1] flag (if feasible)
     pub fn as_bds_sat_h1_flag(&self) -> Option<BdsSatH1> {
         match self {
@@ -867,6 +957,18 @@ mod test {
         assert_eq!(e.as_f64(), 1.0);
     }
 
+    #[test]
+    fn test_semicircles() {
+        use std::f64::consts::PI;
+
+        assert_eq!(rad_to_semicircles(PI), 1.0);
+        assert_eq!(rad_to_semicircles(-PI / 2.0), -0.5);
+        assert_eq!(semicircles_to_rad(0.25), PI / 4.0);
+
+        let m0 = OrbitItem::F64(1.234);
+        assert!((semicircles_to_rad(m0.as_semicircles()) - 1.234).abs() < 1e-15);
+    }
+
     #[test]
     fn test_orbit_channel_5() {
         let lnav = NavMessageType::LNAV;
@@ -880,4 +982,35 @@ mod test {
         )
         .unwrap();
     }
+
+    #[test]
+    fn test_orbit_display() {
+        let health = OrbitItem::GalHealth(GalHealth::empty());
+        assert_eq!(health.to_string(), "HEALTHY");
+
+        let health = OrbitItem::GalHealth(GalHealth::E1B_DVS | GalHealth::E5A_HS_BIT0);
+        assert_eq!(health.to_string(), "E1B_DVS | E5A_HS_BIT0");
+
+        let health = OrbitItem::GlonassHealth(GlonassHealth::UNHEALTHY);
+        assert_eq!(health.to_string(), "UNHEALTHY");
+
+        let health = OrbitItem::GpsQzssl1l2l5Health(GpsQzssl1l2l5Health::from(0));
+        assert_eq!(health.to_string(), "HEALTHY");
+
+        let health = OrbitItem::GpsQzssl1l2l5Health(GpsQzssl1l2l5Health::from(0x5));
+        assert_eq!(health.to_string(), "UNHEALTHY (L1 | L5)");
+
+        // unnamed bits
+        let health = OrbitItem::BdsSatH1(BdsSatH1::from_bits_retain(0x3));
+        assert_eq!(health.to_string(), "UNHEALTHY | 0x2");
+
+        let source = OrbitItem::GalDataSource(GalDataSource::INAV_E1B);
+        assert_eq!(source.to_string(), "INAV_E1B");
+
+        let health = OrbitItem::BdsHealth(BdsHealth::Healthy);
+        assert_eq!(health.to_string(), "Healthy");
+
+        assert_eq!(OrbitItem::Gpsl2pFlag(true).to_string(), "L2P(Y) data ON");
+        assert_eq!(OrbitItem::F64(1.5).to_string(), "1.5");
+    }
 }
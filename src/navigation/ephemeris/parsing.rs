@@ -2,7 +2,7 @@ use crate::{
     epoch::parse_in_timescale as parse_epoch_in_timescale,
     navigation::{
         ephemeris::orbits::{closest_nav_standards, OrbitItem},
-        Ephemeris, NavMessageType,
+        normalize_qzss_sv, sv_timescale, Ephemeris, NavMessageType,
     },
     parse_f64,
     prelude::{Constellation, Epoch, ParsingError, TimeScale, Version, SV},
@@ -130,10 +130,9 @@ impl Ephemeris {
             },
         };
 
-        let ts = sv
-            .constellation
-            .timescale()
-            .ok_or(ParsingError::NoTimescaleDefinition)?;
+        let sv = normalize_qzss_sv(sv);
+
+        let ts = sv_timescale(sv).ok_or(ParsingError::NoTimescaleDefinition)?;
 
         let epoch = parse_epoch_in_timescale(date.trim(), ts)?;
 
@@ -159,6 +158,14 @@ impl Ephemeris {
             clock_drift_rate = 0.0_f64; // drift rate null: non existing
         }
 
+        if sv.constellation == Constellation::Glonass {
+            // tb is not explicitly described: ToC is tb expressed in UTC
+            orbits.insert(
+                "tb".to_string(),
+                OrbitItem::F64(Self::glonass_tb_from_toc(epoch)),
+            );
+        }
+
         Ok((
             epoch,
             sv,
@@ -210,6 +217,14 @@ impl Ephemeris {
             clock_drift_rate = 0.0_f64; // drift rate null: non existing
         }
 
+        if sv.constellation == Constellation::Glonass {
+            // tb is not explicitly described: ToC is tb expressed in UTC
+            orbits.insert(
+                "tb".to_string(),
+                OrbitItem::F64(Self::glonass_tb_from_toc(epoch)),
+            );
+        }
+
         Ok((
             epoch,
             sv,
@@ -226,7 +241,7 @@ impl Ephemeris {
 #[cfg(test)]
 mod test {
     use crate::{
-        navigation::{Ephemeris, NavMessageType},
+        navigation::{gal::GalDataSource, Ephemeris, NavMessageType},
         prelude::{Constellation, Version},
     };
 
@@ -302,6 +317,13 @@ mod test {
 
         assert_eq!(ephemeris.get_orbit_f64("idot"), Some(1.839362331110e-10));
         assert_eq!(ephemeris.get_orbit_f64("source"), Some(2.580000000000e+02));
+
+        // typed flag, not a mangled float
+        let source = ephemeris.orbits.get("source").unwrap();
+        assert_eq!(
+            source.as_galileo_data_source(),
+            Some(GalDataSource::INAV_E1B | GalDataSource::TEMPORAL_SISA_E1_E5A)
+        );
         assert_eq!(ephemeris.get_week(), Some(2111));
 
         assert_eq!(ephemeris.get_orbit_f64("sisa"), Some(3.120000000000e+00));
@@ -1,7 +1,9 @@
 use hifitime::prelude::{Duration, Unit};
 
+use std::collections::HashMap;
+
 use crate::{
-    navigation::Ephemeris,
+    navigation::{gps::GpsQzssl1l2l5Health, semicircles_to_rad, Ephemeris, OrbitItem},
     prelude::{Constellation, Epoch, SV},
 };
 
@@ -17,7 +19,121 @@ use rtcm_rs::msg::{
 #[cfg(doc)]
 use crate::prelude::Rinex;
 
+/// GPS URA index to nominal accuracy (in meters), as per IS-GPS-200
+const GPS_URA_METERS: [f64; 15] = [
+    2.4, 3.4, 4.85, 6.85, 9.65, 13.65, 24.0, 48.0, 96.0, 192.0, 384.0, 768.0, 1536.0, 3072.0,
+    6144.0,
+];
+
+/// Converts the GPS fit interval flag to a fit interval (in hours),
+/// as per IS-GPS-200 Table 20-XII.
+fn gps_fit_interval_hours(flag: u8, iodc: u16) -> f64 {
+    if flag == 0 {
+        return 4.0;
+    }
+
+    match iodc {
+        240..=247 => 8.0,
+        248..=255 | 496 => 14.0,
+        497..=503 | 1021..=1023 => 26.0,
+        504..=510 => 50.0,
+        511 | 752..=756 => 74.0,
+        757 => 98.0,
+        _ => 6.0,
+    }
+}
+
 impl Ephemeris {
+    /// Decodes this RTCM [Msg1019T] [Constellation::GPS] ephemeris message
+    /// as [Ephemeris] structure, ready to format.
+    /// Angular terms are converted from semi-circles to radians.
+    ///
+    /// ## Input
+    /// - msg: [Msg1019T] GPS ephemeris message
+    /// - week: complete GPS week counter. The message only streams
+    ///   this counter modulo 1024, it is up to you to resolve the rollover.
+    ///
+    /// ## Returns
+    /// - Identified [Constellation::GPS] message emitter
+    /// - [Ephemeris] structure ready to format.
+    pub fn from_rtcm_gps1019(msg: &Msg1019T, week: u32) -> (SV, Self) {
+        let accuracy = GPS_URA_METERS
+            .get(msg.ura_index as usize)
+            .copied()
+            .unwrap_or(6144.0);
+
+        let fit_interval = gps_fit_interval_hours(msg.fit_interval_ind, msg.iodc);
+
+        (
+            SV::new(Constellation::GPS, msg.gps_satellite_id),
+            Self {
+                clock_bias: msg.af0_s,
+                clock_drift: msg.af1_s_s as f64,
+                clock_drift_rate: msg.af2_s_s2 as f64,
+                orbits: HashMap::from_iter([
+                    ("iode".to_string(), OrbitItem::F64(msg.iode as f64)),
+                    ("crs".to_string(), OrbitItem::F64(msg.crs_m as f64)),
+                    (
+                        "deltaN".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(msg.delta_n_sc_s as f64)),
+                    ),
+                    (
+                        "m0".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(msg.m0_sc)),
+                    ),
+                    ("cuc".to_string(), OrbitItem::F64(msg.cuc_rad as f64)),
+                    ("e".to_string(), OrbitItem::F64(msg.eccentricity)),
+                    ("cus".to_string(), OrbitItem::F64(msg.cus_rad as f64)),
+                    ("sqrta".to_string(), OrbitItem::F64(msg.sqrt_a_sqrt_m)),
+                    ("toe".to_string(), OrbitItem::F64(msg.toe_s as f64)),
+                    ("cic".to_string(), OrbitItem::F64(msg.cic_rad as f64)),
+                    (
+                        "omega0".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(msg.omega0_sc)),
+                    ),
+                    ("cis".to_string(), OrbitItem::F64(msg.cis_rad as f64)),
+                    (
+                        "i0".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(msg.i0_sc)),
+                    ),
+                    ("crc".to_string(), OrbitItem::F64(msg.crc_m as f64)),
+                    (
+                        "omega".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(msg.omega_sc)),
+                    ),
+                    (
+                        "omegaDot".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(msg.omegadot_sc_s)),
+                    ),
+                    (
+                        "idot".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(msg.idot_sc_s)),
+                    ),
+                    (
+                        "l2Codes".to_string(),
+                        OrbitItem::F64(msg.code_on_l2_ind as f64),
+                    ),
+                    ("week".to_string(), OrbitItem::U32(week)),
+                    (
+                        "l2p".to_string(),
+                        OrbitItem::Gpsl2pFlag(msg.l2_p_data_flag > 0),
+                    ),
+                    ("accuracy".to_string(), OrbitItem::F64(accuracy)),
+                    (
+                        "health".to_string(),
+                        OrbitItem::GpsQzssl1l2l5Health(GpsQzssl1l2l5Health::from(
+                            msg.sv_health_ind as u32,
+                        )),
+                    ),
+                    ("tgd".to_string(), OrbitItem::F64(msg.tgd_s as f64)),
+                    ("iodc".to_string(), OrbitItem::F64(msg.iodc as f64)),
+                    ("t_tm".to_string(), OrbitItem::F64(0.0)),
+                    ("fitInt".to_string(), OrbitItem::F64(fit_interval)),
+                ]),
+            },
+        )
+    }
+
     /// Converts this [Ephemeris] to [Msg1019T] [Constellation::GPS] ephemeris message.
     /// ## Input
     /// - toc: Time of Clock as [Epoch]
@@ -33,38 +149,45 @@ impl Ephemeris {
         let (toc_week, toc_week_nanos) = toc.to_time_of_week();
 
         let toc_s = (toc_week_nanos as f32) * 1.0E-9;
-        let toe_s = self.toe(sv)?.duration.to_unit(Unit::Second) as f32;
+        let toe_s = (self.toe(sv)?.to_time_of_week().1 as f64 * 1.0E-9) as f32;
 
         let gps_satellite_id = sv.prn;
 
-        let ura_index = self.get_orbit_f64("accuracy").unwrap_or_default() as u8;
-        let idot_sc_s = self.get_orbit_f64("idot")?;
+        let accuracy = self.get_orbit_f64("accuracy").unwrap_or_default();
+
+        let ura_index = GPS_URA_METERS
+            .iter()
+            .position(|meters| accuracy <= *meters)
+            .unwrap_or(GPS_URA_METERS.len()) as u8;
+
+        let idot_sc_s = self.orbits.get("idot")?.as_semicircles();
         let iodc = self.get_orbit_f64("iodc")? as u16;
         let crs_m = self.get_orbit_f64("crs")? as f32;
-        let delta_n_sc_s = self.get_orbit_f64("deltaN")? as f32;
-        let m0_sc = self.get_orbit_f64("m0")?;
+        let delta_n_sc_s = self.orbits.get("deltaN")?.as_semicircles() as f32;
+        let m0_sc = self.orbits.get("m0")?.as_semicircles();
         let cic_rad = self.get_orbit_f64("cic")? as f32;
         let cis_rad = self.get_orbit_f64("cis")? as f32;
         let cuc_rad = self.get_orbit_f64("cuc")? as f32;
         let cus_rad = self.get_orbit_f64("cus")? as f32;
         let eccentricity = self.get_orbit_f64("e")?;
         let sqrt_a_sqrt_m = self.get_orbit_f64("sqrta")?;
-        let i0_sc = self.get_orbit_f64("i0")?;
+        let i0_sc = self.orbits.get("i0")?.as_semicircles();
         let iode = self.get_orbit_f64("iode")? as u8;
         let crc_m = self.get_orbit_f64("crc")? as f32;
-        let omega_sc = self.get_orbit_f64("omega")?;
-        let omegadot_sc_s = self.get_orbit_f64("omegaDot")?;
-        let omega0_sc = self.get_orbit_f64("omega0")?;
+        let omega_sc = self.orbits.get("omega")?.as_semicircles();
+        let omegadot_sc_s = self.orbits.get("omegaDot")?.as_semicircles();
+        let omega0_sc = self.orbits.get("omega0")?.as_semicircles();
         let sv_health_ind = self.get_orbit_f64("health")? as u8;
         let l2_p_data_flag = self.get_orbit_f64("l2p")? as u8;
-        let fit_interval_ind = self.get_orbit_f64("fitInt").unwrap_or_default() as u8; // TODO fit int issue
+        // fit interval flag: 0 means 4 hours, 1 means more than 4 hours
+        let fit_interval_ind = (self.get_orbit_f64("fitInt").unwrap_or_default() > 4.0) as u8;
         let tgd_s = self.tgd().unwrap_or(Duration::ZERO).to_unit(Unit::Second) as f32;
 
-        let code_on_l2_ind = 0; // TODO
+        let code_on_l2_ind = self.get_orbit_f64("l2Codes").unwrap_or_default() as u8;
 
         Some(Msg1019T {
             gps_satellite_id,
-            gps_week_number: toc_week as u16,
+            gps_week_number: (toc_week % 1024) as u16,
             ura_index,
             code_on_l2_ind,
             idot_sc_s,
@@ -219,7 +342,7 @@ impl Ephemeris {
         let (toc_week, toc_nanos) = toc.to_time_of_week();
 
         let toc_s = (toc_nanos as f32) * 1.0E-9;
-        let toe_s = self.toe(sv)?.duration.to_unit(Unit::Second) as f32;
+        let toe_s = (self.toe(sv)?.to_time_of_week().1 as f64 * 1.0E-9) as f32;
 
         let crc_m = self.get_orbit_f64("crc")? as f32;
         let crs_m = self.get_orbit_f64("crs")? as f32;
@@ -227,14 +350,14 @@ impl Ephemeris {
         let cis_rad = self.get_orbit_f64("cis")? as f32;
         let cuc_rad = self.get_orbit_f64("cuc")? as f32;
         let cus_rad = self.get_orbit_f64("cus")? as f32;
-        let delta_n_sc_s = self.get_orbit_f64("deltaN")? as f32;
+        let delta_n_sc_s = self.orbits.get("deltaN")?.as_semicircles() as f32;
         let eccentricity = self.get_orbit_f64("e")?;
-        let i0_sc = self.get_orbit_f64("i0")?;
-        let m0_sc = self.get_orbit_f64("m0")?;
-        let idot_sc_s = self.get_orbit_f64("idot")? as f32;
-        let omega0_sc = self.get_orbit_f64("omega0")?;
-        let omega_sc = self.get_orbit_f64("omega")?;
-        let omegadot_sc_s = self.get_orbit_f64("omegaDot")?;
+        let i0_sc = self.orbits.get("i0")?.as_semicircles();
+        let m0_sc = self.orbits.get("m0")?.as_semicircles();
+        let idot_sc_s = self.orbits.get("idot")?.as_semicircles() as f32;
+        let omega0_sc = self.orbits.get("omega0")?.as_semicircles();
+        let omega_sc = self.orbits.get("omega")?.as_semicircles();
+        let omegadot_sc_s = self.orbits.get("omegaDot")?.as_semicircles();
         let sqrt_a_sqrt_m = self.get_orbit_f64("sqrta")?;
         let iodnav = self.get_orbit_f64("iodnav").unwrap_or_default() as u16; // TODO IODNAV issue?
         let bgd_e1_e5a_s = self.get_orbit_f64("bgdE5aE1").unwrap_or_default() as f32; // TODO BGD_E1/E5A
@@ -291,7 +414,7 @@ impl Ephemeris {
         let (toc_week, toc_nanos) = toc.to_time_of_week();
 
         let toc_s = (toc_nanos as f32) * 1.0e-9;
-        let toe_s = self.toe(sv)?.duration.to_unit(Unit::Second) as f32;
+        let toe_s = (self.toe(sv)?.to_time_of_week().1 as f64 * 1.0E-9) as f32;
 
         let crc_m = self.get_orbit_f64("crc")? as f32;
         let crs_m = self.get_orbit_f64("crs")? as f32;
@@ -299,14 +422,14 @@ impl Ephemeris {
         let cis_rad = self.get_orbit_f64("cis")? as f32;
         let cuc_rad = self.get_orbit_f64("cuc")? as f32;
         let cus_rad = self.get_orbit_f64("cus")? as f32;
-        let i0_sc = self.get_orbit_f64("i0")?;
-        let m0_sc = self.get_orbit_f64("m0")?;
-        let idot_sc_s = self.get_orbit_f64("idot")? as f32;
+        let i0_sc = self.orbits.get("i0")?.as_semicircles();
+        let m0_sc = self.orbits.get("m0")?.as_semicircles();
+        let idot_sc_s = self.orbits.get("idot")?.as_semicircles() as f32;
         let eccentricity = self.get_orbit_f64("e")?;
-        let delta_n_sc_s = self.get_orbit_f64("deltaN")? as f32;
-        let omega_sc = self.get_orbit_f64("omega")?;
-        let omegadot_sc_s = self.get_orbit_f64("omegaDot")?;
-        let omega0_sc = self.get_orbit_f64("omega0")?;
+        let delta_n_sc_s = self.orbits.get("deltaN")?.as_semicircles() as f32;
+        let omega_sc = self.orbits.get("omega")?.as_semicircles();
+        let omegadot_sc_s = self.orbits.get("omegaDot")?.as_semicircles();
+        let omega0_sc = self.orbits.get("omega0")?.as_semicircles();
         let sqrt_a_sqrt_m = self.get_orbit_f64("sqrta")?;
 
         let bgd_e1_e5a_s = 0.0; // TODO
@@ -370,7 +493,7 @@ impl Ephemeris {
         let (toc_week, toc_nanos) = toc.to_time_of_week();
 
         let toc_s = (toc_nanos as f32) * 1.0e-9;
-        let toe_s = self.toe(sv)?.duration.to_unit(Unit::Second) as f32;
+        let toe_s = (self.toe(sv)?.to_time_of_week().1 as f64 * 1.0E-9) as f32;
 
         let aodc = 0; // TODO
         let aode = 0; // TODO
@@ -381,14 +504,14 @@ impl Ephemeris {
         let cis_rad = self.get_orbit_f64("cis")? as f32;
         let cuc_rad = self.get_orbit_f64("cuc")? as f32;
         let cus_rad = self.get_orbit_f64("cus")? as f32;
-        let delta_n_sc_s = self.get_orbit_f64("deltaN")? as f32;
-        let i0_sc = self.get_orbit_f64("i0")?;
-        let m0_sc = self.get_orbit_f64("m0")?;
-        let idot_sc_s = self.get_orbit_f64("idot")?;
+        let delta_n_sc_s = self.orbits.get("deltaN")?.as_semicircles() as f32;
+        let i0_sc = self.orbits.get("i0")?.as_semicircles();
+        let m0_sc = self.orbits.get("m0")?.as_semicircles();
+        let idot_sc_s = self.orbits.get("idot")?.as_semicircles();
         let eccentricity = self.get_orbit_f64("e")?;
-        let omega_sc = self.get_orbit_f64("omega")?;
-        let omegadot_sc_s = self.get_orbit_f64("omegaDot")?;
-        let omega0_sc = self.get_orbit_f64("omega0")?;
+        let omega_sc = self.orbits.get("omega")?.as_semicircles();
+        let omegadot_sc_s = self.orbits.get("omegaDot")?.as_semicircles();
+        let omega0_sc = self.orbits.get("omega0")?.as_semicircles();
         let sqrt_a_sqrt_m = self.get_orbit_f64("sqrta")?;
 
         let sv_health_flag = 0; // TODO
@@ -444,36 +567,37 @@ impl Ephemeris {
         let (toc_week, toc_week_nanos) = epoch.to_time_of_week();
 
         let toc_s = (toc_week_nanos as f32) * 1.0E-9;
-        let toe_s = self.toe(sv)?.duration.to_unit(Unit::Second) as f32;
+        let toe_s = (self.toe(sv)?.to_time_of_week().1 as f64 * 1.0E-9) as f32;
 
-        let idot_sc_s = self.get_orbit_f64("idot")?;
+        let idot_sc_s = self.orbits.get("idot")?.as_semicircles();
         let iodc = self.get_orbit_f64("iodc")? as u16;
         let crs_m = self.get_orbit_f64("crs")? as f32;
-        let delta_n_sc_s = self.get_orbit_f64("deltaN")? as f32;
-        let m0_sc = self.get_orbit_f64("m0")?;
+        let delta_n_sc_s = self.orbits.get("deltaN")?.as_semicircles() as f32;
+        let m0_sc = self.orbits.get("m0")?.as_semicircles();
         let cic_rad = self.get_orbit_f64("cic")? as f32;
         let cis_rad = self.get_orbit_f64("cis")? as f32;
         let cuc_rad = self.get_orbit_f64("cuc")? as f32;
         let cus_rad = self.get_orbit_f64("cus")? as f32;
         let eccentricity = self.get_orbit_f64("e")?;
         let sqrt_a_sqrt_m = self.get_orbit_f64("sqrta")?;
-        let i0_sc = self.get_orbit_f64("i0")?;
+        let i0_sc = self.orbits.get("i0")?.as_semicircles();
         let iode = self.get_orbit_f64("iode")? as u8;
         let crc_m = self.get_orbit_f64("crc")? as f32;
-        let omega_sc = self.get_orbit_f64("omega")?;
-        let omegadot_sc_s = self.get_orbit_f64("omegaDot")?;
-        let omega0_sc = self.get_orbit_f64("omega0")?;
+        let omega_sc = self.orbits.get("omega")?.as_semicircles();
+        let omegadot_sc_s = self.orbits.get("omegaDot")?.as_semicircles();
+        let omega0_sc = self.orbits.get("omega0")?.as_semicircles();
         let tgd_s = self.tgd()?.to_unit(Unit::Second) as f32;
         let sv_health_ind = self.get_orbit_f64("health")? as u8;
         // let l2_p_data_flag = self.get_orbit_f64("l2p")? as u8;
-        let fit_interval_ind = self.get_orbit_f64("fitInt").unwrap_or_default() as u8; // TODO fitInt issue
+        // fit interval flag: 0 means 4 hours, 1 means more than 4 hours
+        let fit_interval_ind = (self.get_orbit_f64("fitInt").unwrap_or_default() > 4.0) as u8;
 
         let code_on_l2_ind = 0; // TODO
         let ura_index = 0; // TODO
 
         Some(Msg1044T {
             qzss_satellite_id: sv.prn,
-            qzss_week_number: toc_week as u16,
+            qzss_week_number: (toc_week % 1024) as u16,
             ura_index,
             code_on_l2_ind,
             idot_sc_s,
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
 use crate::{
-    navigation::{Ephemeris, OrbitItem},
+    navigation::{
+        gal::{GalDataSource, GalHealth},
+        normalize_qzss_sv, semicircles_to_rad, Ephemeris, OrbitItem,
+    },
     prelude::{Constellation, Epoch, SV},
 };
 
@@ -44,14 +47,33 @@ impl Ephemeris {
                     ("tgd".to_string(), OrbitItem::F64(ubx.tgd_s())),
                     ("sqrta".to_string(), OrbitItem::F64(ubx.sqrt_a())),
                     ("iodc".to_string(), OrbitItem::F64(ubx.iodc() as f64)),
-                    ("m0".to_string(), OrbitItem::F64(ubx.m0_semicircles())),
-                    ("deltaN".to_string(), OrbitItem::F64(ubx.dn_semicircles())),
-                    ("idot".to_string(), OrbitItem::F64(ubx.idot_semicircles())),
-                    ("omega".to_string(), OrbitItem::F64(ubx.omega_semicircles())),
-                    ("omegaDot".to_string(), OrbitItem::F64(ubx.omega_dot())),
+                    (
+                        "m0".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.m0_semicircles())),
+                    ),
+                    (
+                        "deltaN".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.dn_semicircles())),
+                    ),
+                    (
+                        "idot".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.idot_semicircles())),
+                    ),
+                    (
+                        "omega".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.omega_semicircles())),
+                    ),
+                    (
+                        "omegaDot".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.omega_dot())),
+                    ),
+                    (
+                        "i0".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.i0_semicircles())),
+                    ),
                     (
                         "omega0".to_string(),
-                        OrbitItem::F64(ubx.omega0_semicircles()),
+                        OrbitItem::F64(semicircles_to_rad(ubx.omega0_semicircles())),
                     ),
                 ]),
             },
@@ -65,10 +87,10 @@ impl Ephemeris {
     /// - [Ephemeris] structure ready to format.
     pub fn from_ubx_mga_qzss(ubx: MgaGpsEphRef) -> (SV, Self) {
         (
-            SV {
+            normalize_qzss_sv(SV {
                 prn: ubx.sv_id(),
                 constellation: Constellation::QZSS,
-            },
+            }),
             Self {
                 clock_bias: ubx.af0(),
                 clock_drift: ubx.af1(),
@@ -85,14 +107,33 @@ impl Ephemeris {
                     ("crs".to_string(), OrbitItem::F64(ubx.crs_rad())),
                     ("sqrta".to_string(), OrbitItem::F64(ubx.sqrt_a())),
                     ("iodc".to_string(), OrbitItem::F64(ubx.iodc() as f64)),
-                    ("m0".to_string(), OrbitItem::F64(ubx.m0_semicircles())),
-                    ("deltaN".to_string(), OrbitItem::F64(ubx.dn_semicircles())),
-                    ("idot".to_string(), OrbitItem::F64(ubx.idot_semicircles())),
-                    ("omega".to_string(), OrbitItem::F64(ubx.omega_semicircles())),
-                    ("omegaDot".to_string(), OrbitItem::F64(ubx.omega_dot())),
+                    (
+                        "m0".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.m0_semicircles())),
+                    ),
+                    (
+                        "deltaN".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.dn_semicircles())),
+                    ),
+                    (
+                        "idot".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.idot_semicircles())),
+                    ),
+                    (
+                        "omega".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.omega_semicircles())),
+                    ),
+                    (
+                        "omegaDot".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.omega_dot())),
+                    ),
+                    (
+                        "i0".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.i0_semicircles())),
+                    ),
                     (
                         "omega0".to_string(),
-                        OrbitItem::F64(ubx.omega0_semicircles()),
+                        OrbitItem::F64(semicircles_to_rad(ubx.omega0_semicircles())),
                     ),
                 ]),
             },
@@ -100,6 +141,7 @@ impl Ephemeris {
     }
 
     /// Encodes this [Ephemeris] as UBX [MgaGpsEphRef] frame.
+    /// Angles and angular rates, stored in radians, are streamed in semicircles.
     ///
     /// ## Input
     /// - toc: time of clock as [Epoch]
@@ -129,13 +171,13 @@ impl Ephemeris {
 
         let e = self.get_orbit_f64("e")?;
         let sqrt_a = self.get_orbit_f64("sqrta")?;
-        let omega0_semicircles = self.get_orbit_f64("omega0")?;
-        let omega_semicircles = self.get_orbit_f64("omega")?;
-        let omega_dot = self.get_orbit_f64("omegaDot")?;
-        let dn_semicircles = self.get_orbit_f64("deltaN")?;
-        let m0_semicircles = self.get_orbit_f64("m0")?;
-        let i0_semicircles = self.get_orbit_f64("i0")?;
-        let idot_semicircles = self.get_orbit_f64("idot")?;
+        let omega0_semicircles = self.orbits.get("omega0")?.as_semicircles();
+        let omega_semicircles = self.orbits.get("omega")?.as_semicircles();
+        let omega_dot = self.orbits.get("omegaDot")?.as_semicircles();
+        let dn_semicircles = self.orbits.get("deltaN")?.as_semicircles();
+        let m0_semicircles = self.orbits.get("m0")?.as_semicircles();
+        let i0_semicircles = self.orbits.get("i0")?.as_semicircles();
+        let idot_semicircles = self.orbits.get("idot")?.as_semicircles();
 
         // TODO check whether these exist in V2
         let ura_index = self.get_orbit_f64("accuracy").unwrap_or_default() as u8;
@@ -205,9 +247,18 @@ impl Ephemeris {
                     ("crs".to_string(), OrbitItem::F64(ubx.crs_rad())),
                     ("sqrta".to_string(), OrbitItem::F64(ubx.sqrt_a())),
                     ("iodc".to_string(), OrbitItem::F64(ubx.iodc() as f64)),
-                    ("m0".to_string(), OrbitItem::F64(ubx.m0_semicircles())),
-                    ("deltaN".to_string(), OrbitItem::F64(ubx.dn_semicircles())),
-                    ("idot".to_string(), OrbitItem::F64(ubx.i_dot_semicircles())),
+                    (
+                        "m0".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.m0_semicircles())),
+                    ),
+                    (
+                        "deltaN".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.dn_semicircles())),
+                    ),
+                    (
+                        "idot".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.i_dot_semicircles())),
+                    ),
                     (
                         "tgd1b1b2".to_string(),
                         OrbitItem::F64(ubx.tgd_ns() * 1.0E-9),
@@ -216,14 +267,17 @@ impl Ephemeris {
                         "tgd1b2b3".to_string(),
                         OrbitItem::F64(ubx.tgd_ns() * 1.0E-9),
                     ),
-                    ("omega".to_string(), OrbitItem::F64(ubx.omega_semicircles())),
+                    (
+                        "omega".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.omega_semicircles())),
+                    ),
                     (
                         "omega0".to_string(),
-                        OrbitItem::F64(ubx.omega0_semicircles()),
+                        OrbitItem::F64(semicircles_to_rad(ubx.omega0_semicircles())),
                     ),
                     (
                         "omegaDot".to_string(),
-                        OrbitItem::F64(ubx.omega_dot_semicircles()),
+                        OrbitItem::F64(semicircles_to_rad(ubx.omega_dot_semicircles())),
                     ),
                 ]),
             },
@@ -231,6 +285,7 @@ impl Ephemeris {
     }
 
     /// Encodes this [Ephemeris] as UBX [MgaBdsEphRef] frame.
+    /// Angles and angular rates, stored in radians, are streamed in semicircles.
     ///
     /// ## Input
     /// - toc: time of clock as [Epoch]
@@ -267,13 +322,13 @@ impl Ephemeris {
 
         let e = self.get_orbit_f64("e")?;
         let sqrt_a = self.get_orbit_f64("sqrta")?;
-        let omega0_semicircles = self.get_orbit_f64("omega0")?;
-        let omega_semicircles = self.get_orbit_f64("omega")?;
-        let omega_dot_semicircles = self.get_orbit_f64("omegaDot")?;
-        let dn_semicircles = self.get_orbit_f64("deltaN")?;
-        let m0_semicircles = self.get_orbit_f64("m0")?;
-        let i0_semicircles = self.get_orbit_f64("i0")?;
-        let i_dot_semicircles = self.get_orbit_f64("idot")?;
+        let omega0_semicircles = self.orbits.get("omega0")?.as_semicircles();
+        let omega_semicircles = self.orbits.get("omega")?.as_semicircles();
+        let omega_dot_semicircles = self.orbits.get("omegaDot")?.as_semicircles();
+        let dn_semicircles = self.orbits.get("deltaN")?.as_semicircles();
+        let m0_semicircles = self.orbits.get("m0")?.as_semicircles();
+        let i0_semicircles = self.orbits.get("i0")?.as_semicircles();
+        let i_dot_semicircles = self.orbits.get("idot")?.as_semicircles();
 
         let toe = self.get_orbit_f64("toe")?;
 
@@ -456,29 +511,51 @@ impl Ephemeris {
                     ("crc".to_string(), OrbitItem::F64(ubx.crc_rad())),
                     ("crs".to_string(), OrbitItem::F64(ubx.crs_rad())),
                     ("sqrta".to_string(), OrbitItem::F64(ubx.sqrt_a())),
-                    ("m0".to_string(), OrbitItem::F64(ubx.m0_semicircles())),
-                    ("deltaN".to_string(), OrbitItem::F64(ubx.dn_semicircles())),
-                    ("idot".to_string(), OrbitItem::F64(ubx.i_dot_semicircles())),
-                    ("omega".to_string(), OrbitItem::F64(ubx.omega_semicircles())),
+                    (
+                        "m0".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.m0_semicircles())),
+                    ),
+                    (
+                        "deltaN".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.dn_semicircles())),
+                    ),
+                    (
+                        "idot".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.i_dot_semicircles())),
+                    ),
+                    (
+                        "omega".to_string(),
+                        OrbitItem::F64(semicircles_to_rad(ubx.omega_semicircles())),
+                    ),
                     ("iodnav".to_string(), OrbitItem::F64(ubx.iodnav() as f64)),
                     (
                         "health".to_string(),
-                        OrbitItem::F64(ubx.e1b_health() as f64),
+                        OrbitItem::GalHealth(GalHealth::from_bits_truncate(
+                            (ubx.e1b_validity() as u32 & 0x1)
+                                | ((ubx.e1b_health() as u32 & 0x3) << 1)
+                                | ((ubx.e5b_validity() as u32 & 0x1) << 6)
+                                | ((ubx.e5b_health() as u32 & 0x3) << 7),
+                        )),
                     ),
                     ("bdgE5aE1".to_string(), OrbitItem::F64(ubx.bgd_e1_e5b_s())),
                     ("bdgE5bE1".to_string(), OrbitItem::F64(ubx.bgd_e1_e5b_s())),
                     ("sisa".to_string(), OrbitItem::F64(ubx.sisa_e1_e5b() as f64)),
                     (
                         "source".to_string(),
-                        OrbitItem::F64(ubx.omega_semicircles()),
+                        // I/NAV message, SISA and clock corrections are E1/E5b
+                        OrbitItem::GalDataSource(
+                            GalDataSource::INAV_E1B
+                                | GalDataSource::FNAV_E5B_I
+                                | GalDataSource::TEMPORAL_SISA_E1_E5B,
+                        ),
                     ),
                     (
                         "omega0".to_string(),
-                        OrbitItem::F64(ubx.omega0_semicircles()),
+                        OrbitItem::F64(semicircles_to_rad(ubx.omega0_semicircles())),
                     ),
                     (
                         "omegaDot".to_string(),
-                        OrbitItem::F64(ubx.omega_dot_semicircles()),
+                        OrbitItem::F64(semicircles_to_rad(ubx.omega_dot_semicircles())),
                     ),
                 ]),
             },
@@ -486,6 +563,7 @@ impl Ephemeris {
     }
 
     /// Encodes this [Ephemeris] as UBX [MgaGalEphRef] frame.
+    /// Angles and angular rates, stored in radians, are streamed in semicircles.
     ///
     /// ## Input
     /// - toc: time of clock as [Epoch]
@@ -510,19 +588,26 @@ impl Ephemeris {
 
         let e = self.get_orbit_f64("e")?;
         let sqrt_a = self.get_orbit_f64("sqrta")?;
-        let omega0_semicircles = self.get_orbit_f64("omega0")?;
-        let omega_semicircles = self.get_orbit_f64("omega")?;
-        let omega_dot_semicircles = self.get_orbit_f64("omegaDot")?;
-        let dn_semicircles = self.get_orbit_f64("deltaN")?;
-        let m0_semicircles = self.get_orbit_f64("m0")?;
-        let i0_semicircles = self.get_orbit_f64("i0")?;
-        let i_dot_semicircles = self.get_orbit_f64("idot")?;
+        let omega0_semicircles = self.orbits.get("omega0")?.as_semicircles();
+        let omega_semicircles = self.orbits.get("omega")?.as_semicircles();
+        let omega_dot_semicircles = self.orbits.get("omegaDot")?.as_semicircles();
+        let dn_semicircles = self.orbits.get("deltaN")?.as_semicircles();
+        let m0_semicircles = self.orbits.get("m0")?.as_semicircles();
+        let i0_semicircles = self.orbits.get("i0")?.as_semicircles();
+        let i_dot_semicircles = self.orbits.get("idot")?.as_semicircles();
 
         let toe = self.get_orbit_f64("toe")?;
 
         // TODO exists in V4, check V2 and V3
         // let ura = self.get_orbit_f64("accuracy").unwrap_or_default() as u8;
 
+        let health = self
+            .orbits
+            .get("health")
+            .and_then(|item| item.as_galileo_health_flag())
+            .unwrap_or_default()
+            .bits();
+
         let builder = MgaGalEphBuilder {
             msg_type: 0, // TODO
             version: 0,  // TODO
@@ -548,13 +633,13 @@ impl Ephemeris {
             crs_rad,
             cic_rad,
             cis_rad,
-            iodnav: 0,         // TODO
-            e1b_health: 0,     // TODO
-            e5b_health: 0,     // TODO
-            e5b_validity: 0,   // TODO
+            iodnav: 0, // TODO
+            e1b_health: ((health >> 1) & 0x3) as u8,
+            e5b_health: ((health >> 7) & 0x3) as u8,
+            e5b_validity: ((health >> 6) & 0x1) as u8,
             bgd_e1_e5b_s: 0.0, // TODO
             sisa_e1_e5b: 0,    // TODO
-            e1b_validity: 0,   // TODO
+            e1b_validity: (health & 0x1) as u8,
             reserved2: [0, 0],
             reserved3: [0, 0, 0, 0],
         };
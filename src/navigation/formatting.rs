@@ -5,6 +5,7 @@ use std::io::{BufWriter, Write};
 use crate::{
     epoch::epoch_decompose as epoch_decomposition,
     error::FormattingError,
+    formatting::FormattingOptions,
     navigation::{NavFrame, NavFrameType, NavKey, Record},
     prelude::{Constellation, Header},
 };
@@ -13,6 +14,7 @@ pub(crate) struct NavFormatter {
     value: f64,
     width: usize,
     precision: usize,
+    d_exponent: bool,
 }
 
 impl NavFormatter {
@@ -21,6 +23,7 @@ impl NavFormatter {
             value,
             width: 15,
             precision: 12,
+            d_exponent: false,
         }
     }
 
@@ -29,6 +32,7 @@ impl NavFormatter {
             value,
             width: 3,
             precision: 4,
+            d_exponent: false,
         }
     }
 
@@ -37,6 +41,7 @@ impl NavFormatter {
             value,
             width: 17,
             precision: 12,
+            d_exponent: false,
         }
     }
 
@@ -45,6 +50,7 @@ impl NavFormatter {
             value,
             width: 14,
             precision: 10,
+            d_exponent: false,
         }
     }
 
@@ -53,8 +59,15 @@ impl NavFormatter {
             value,
             width: 13,
             precision: 9,
+            d_exponent: false,
         }
     }
+
+    /// Use the fortran 'D' exponent (true), instead of 'E' (false).
+    pub fn with_d_exponent(mut self, d_exponent: bool) -> Self {
+        self.d_exponent = d_exponent;
+        self
+    }
 }
 
 impl std::fmt::Display for NavFormatter {
@@ -79,7 +92,8 @@ impl std::fmt::Display for NavFormatter {
                 .parse::<i32>()
                 .unwrap();
             let formatted_exponent = format!("{}{:02}", exp_sign, exp_value);
-            write!(f, "{}{}E{}", sign_str, base, formatted_exponent)
+            let exp_char = if self.d_exponent { 'D' } else { 'E' };
+            write!(f, "{}{}{}{}", sign_str, base, exp_char, formatted_exponent)
         } else {
             write!(f, "{}", formatted)
         }
@@ -136,11 +150,8 @@ fn format_epoch_v4<W: Write>(w: &mut BufWriter<W>, k: &NavKey) -> std::io::Resul
             )
         },
         NavFrameType::SystemTimeOffset => {
-            write!(
-                w,
-                "> STO {:x} {}\n        {:04} {:02} {:02} {:02} {:02} {:02}",
-                k.sv, k.msgtype, yyyy, m, d, hh, mm, ss
-            )
+            // epoch is described by the frame itself
+            write!(w, "> STO {:x} {}", k.sv, k.msgtype)
         },
         NavFrameType::EarthOrientation => {
             write!(
@@ -156,6 +167,7 @@ pub fn format<W: Write>(
     writer: &mut BufWriter<W>,
     rec: &Record,
     header: &Header,
+    options: &FormattingOptions,
 ) -> Result<(), FormattingError> {
     let version = header.version;
 
@@ -213,6 +225,11 @@ pub fn format<W: Write>(
                         .filter(|(k, _v)| k.epoch == epoch && k.sv == sv && k.frmtype == frmtype)
                         .reduce(|k, _| k)
                     {
+                        // system time offsets are described in header, prior V4
+                        if !v4 && v.as_system_time().is_some() {
+                            continue;
+                        }
+
                         // format epoch
                         if v4 {
                             format_epoch_v4(writer, k)?;
@@ -222,7 +239,13 @@ pub fn format<W: Write>(
 
                         // format entry
                         match v {
-                            NavFrame::EPH(eph) => eph.format(writer, k.sv, version, k.msgtype)?,
+                            NavFrame::EPH(eph) => {
+                                eph.format_with_options(writer, k.sv, version, k.msgtype, options)?
+                            },
+                            NavFrame::STO(sto) => {
+                                writeln!(writer)?;
+                                sto.format_v4(writer, options.nav_d_exponent)?;
+                            },
                             _ => {},
                         };
                     }
@@ -259,6 +282,18 @@ mod test {
         }
     }
 
+    #[test]
+    fn nav_formatter_d_exponent() {
+        for (value, expected) in [
+            (0.0, " 0.000000000000D+00"),
+            (-1.0, "-1.000000000000D+00"),
+            (0.123, " 1.230000000000D-01"),
+        ] {
+            let formatted = NavFormatter::new(value).with_d_exponent(true);
+            assert_eq!(formatted.to_string(), expected);
+        }
+    }
+
     #[test]
     fn system_time_corr_v2_formatter() {
         for (value, expected) in [(-1.862645149231E-09, "-1.862645149231E-09")] {
@@ -376,11 +411,7 @@ G01 2023 03 12 00 00 00"
 
         let utf8_ascii = inner.to_ascii_utf8();
 
-        assert_eq!(
-            &utf8_ascii,
-            "> STO C21 CNVX
-        2023 03 12 00 20 00"
-        );
+        assert_eq!(&utf8_ascii, "> STO C21 CNVX");
     }
 
     #[test]
@@ -25,11 +25,13 @@ impl HeaderFields {
         self.time_offsets.push(offset);
     }
 
-    /// Format according to RINEX standards
+    /// Format according to RINEX standards,
+    /// using either 'D' (true) or 'E' (false) exponent.
     pub(crate) fn format<W: Write>(
         &self,
         w: &mut BufWriter<W>,
         major: u8,
+        d_exponent: bool,
     ) -> Result<(), FormattingError> {
         // time offsets
         match major {
@@ -39,7 +41,7 @@ impl HeaderFields {
                     .iter()
                     .find(|k| k.lhs == TimeScale::GPST && k.rhs == TimeScale::UTC)
                 {
-                    t_gpst_utc.format_v2_delta_utc(w)?;
+                    t_gpst_utc.format_v2_delta_utc(w, d_exponent)?;
                 }
 
                 // TODO glonassT
@@ -48,12 +50,12 @@ impl HeaderFields {
                     .iter()
                     .find(|k| k.lhs == TimeScale::GPST && k.rhs == TimeScale::UTC)
                 {
-                    t_glonasst_utc.format_v2_corr_to_system_time(w)?;
+                    t_glonasst_utc.format_v2_corr_to_system_time(w, d_exponent)?;
                 }
             },
             3 => {
                 for time_offset in self.time_offsets.iter() {
-                    time_offset.format_v3(w)?;
+                    time_offset.format_v3(w, d_exponent)?;
                 }
             },
             _ => {}, // N/A
@@ -3,13 +3,11 @@ use crate::{
     error::FormattingError,
     fmt_rinex,
     navigation::formatting::NavFormatter,
+    parse_f64,
     prelude::{Constellation, Epoch, ParsingError, TimeScale},
 };
 
-use std::{
-    io::{BufWriter, Write},
-    str::FromStr,
-};
+use std::io::{BufWriter, Write};
 
 /// Klobuchar Parameters region
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
@@ -77,7 +75,7 @@ impl KbModel {
         let region: KbRegionCode = match region.trim().len() {
             0 => KbRegionCode::Worldwide,
             _ => {
-                if let Ok(f) = f64::from_str(region.trim()) {
+                if let Ok(f) = parse_f64(region.trim()) {
                     let code = f as u8;
                     if code == 1 {
                         KbRegionCode::Japan
@@ -92,16 +90,16 @@ impl KbModel {
 
         let epoch = parse_epoch_in_timescale(epoch.trim(), ts)?;
         let alpha = (
-            f64::from_str(a0.trim()).map_err(|_| ParsingError::KlobucharData)?,
-            f64::from_str(a1.trim()).map_err(|_| ParsingError::KlobucharData)?,
-            f64::from_str(a2.trim()).map_err(|_| ParsingError::KlobucharData)?,
-            f64::from_str(a3.trim()).map_err(|_| ParsingError::KlobucharData)?,
+            parse_f64(a0.trim()).map_err(|_| ParsingError::KlobucharData)?,
+            parse_f64(a1.trim()).map_err(|_| ParsingError::KlobucharData)?,
+            parse_f64(a2.trim()).map_err(|_| ParsingError::KlobucharData)?,
+            parse_f64(a3.trim()).map_err(|_| ParsingError::KlobucharData)?,
         );
         let beta = (
-            f64::from_str(b0.trim()).map_err(|_| ParsingError::KlobucharData)?,
-            f64::from_str(b1.trim()).map_err(|_| ParsingError::KlobucharData)?,
-            f64::from_str(b2.trim()).map_err(|_| ParsingError::KlobucharData)?,
-            f64::from_str(b3.trim()).map_err(|_| ParsingError::KlobucharData)?,
+            parse_f64(b0.trim()).map_err(|_| ParsingError::KlobucharData)?,
+            parse_f64(b1.trim()).map_err(|_| ParsingError::KlobucharData)?,
+            parse_f64(b2.trim()).map_err(|_| ParsingError::KlobucharData)?,
+            parse_f64(b3.trim()).map_err(|_| ParsingError::KlobucharData)?,
         );
 
         Ok((
@@ -178,61 +176,68 @@ impl KbModel {
     //     }
     // }
 
-    /// Format this [KbModel] for a V2 header
-    pub fn format_v2_header<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
+    /// Format this [KbModel] for a V2 header,
+    /// using either 'D' (true) or 'E' (false) exponent.
+    pub fn format_v2_header<W: Write>(
+        &self,
+        w: &mut BufWriter<W>,
+        d_exponent: bool,
+    ) -> Result<(), FormattingError> {
         let formatted = format!(
             "   {} {} {} {}",
-            NavFormatter::new_iono_alpha_beta(self.alpha.0),
-            NavFormatter::new_iono_alpha_beta(self.alpha.1),
-            NavFormatter::new_iono_alpha_beta(self.alpha.2),
-            NavFormatter::new_iono_alpha_beta(self.alpha.3),
+            NavFormatter::new_iono_alpha_beta(self.alpha.0).with_d_exponent(d_exponent),
+            NavFormatter::new_iono_alpha_beta(self.alpha.1).with_d_exponent(d_exponent),
+            NavFormatter::new_iono_alpha_beta(self.alpha.2).with_d_exponent(d_exponent),
+            NavFormatter::new_iono_alpha_beta(self.alpha.3).with_d_exponent(d_exponent),
         );
 
         write!(w, "{}\n", fmt_rinex(&formatted, "ION ALPHA"))?;
 
         let formatted = format!(
             "   {} {} {} {}",
-            NavFormatter::new_iono_alpha_beta(self.beta.0),
-            NavFormatter::new_iono_alpha_beta(self.beta.1),
-            NavFormatter::new_iono_alpha_beta(self.beta.2),
-            NavFormatter::new_iono_alpha_beta(self.beta.3),
+            NavFormatter::new_iono_alpha_beta(self.beta.0).with_d_exponent(d_exponent),
+            NavFormatter::new_iono_alpha_beta(self.beta.1).with_d_exponent(d_exponent),
+            NavFormatter::new_iono_alpha_beta(self.beta.2).with_d_exponent(d_exponent),
+            NavFormatter::new_iono_alpha_beta(self.beta.3).with_d_exponent(d_exponent),
         );
 
         write!(w, "{}\n", fmt_rinex(&formatted, "ION BETA"))?;
         Ok(())
     }
 
-    /// Format this [KbModel] for a V3 [Constellation] header.
+    /// Format this [KbModel] for a V3 [Constellation] header,
+    /// using either 'D' (true) or 'E' (false) exponent.
     pub fn format_v3_header<W: Write>(
         &self,
         w: &mut BufWriter<W>,
         constellation: Constellation,
+        d_exponent: bool,
     ) -> Result<(), FormattingError> {
         // QZSS + IRNSS : truncated cases
         let formatted = if constellation == Constellation::QZSS {
             format!(
                 "QZSA  {} {} {} {}",
-                NavFormatter::new_iono_alpha_beta(self.alpha.0),
-                NavFormatter::new_iono_alpha_beta(self.alpha.1),
-                NavFormatter::new_iono_alpha_beta(self.alpha.2),
-                NavFormatter::new_iono_alpha_beta(self.alpha.3),
+                NavFormatter::new_iono_alpha_beta(self.alpha.0).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.alpha.1).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.alpha.2).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.alpha.3).with_d_exponent(d_exponent),
             )
         } else if constellation == Constellation::IRNSS {
             format!(
                 "IRNA  {} {} {} {}",
-                NavFormatter::new_iono_alpha_beta(self.alpha.0),
-                NavFormatter::new_iono_alpha_beta(self.alpha.1),
-                NavFormatter::new_iono_alpha_beta(self.alpha.2),
-                NavFormatter::new_iono_alpha_beta(self.alpha.3),
+                NavFormatter::new_iono_alpha_beta(self.alpha.0).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.alpha.1).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.alpha.2).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.alpha.3).with_d_exponent(d_exponent),
             )
         } else {
             format!(
                 "{:E}A  {} {} {} {}",
                 constellation,
-                NavFormatter::new_iono_alpha_beta(self.alpha.0),
-                NavFormatter::new_iono_alpha_beta(self.alpha.1),
-                NavFormatter::new_iono_alpha_beta(self.alpha.2),
-                NavFormatter::new_iono_alpha_beta(self.alpha.3),
+                NavFormatter::new_iono_alpha_beta(self.alpha.0).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.alpha.1).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.alpha.2).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.alpha.3).with_d_exponent(d_exponent),
             )
         };
 
@@ -242,27 +247,27 @@ impl KbModel {
         let formatted = if constellation == Constellation::QZSS {
             format!(
                 "QZSB  {} {} {} {}",
-                NavFormatter::new_iono_alpha_beta(self.beta.0),
-                NavFormatter::new_iono_alpha_beta(self.beta.1),
-                NavFormatter::new_iono_alpha_beta(self.beta.2),
-                NavFormatter::new_iono_alpha_beta(self.beta.3),
+                NavFormatter::new_iono_alpha_beta(self.beta.0).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.beta.1).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.beta.2).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.beta.3).with_d_exponent(d_exponent),
             )
         } else if constellation == Constellation::IRNSS {
             format!(
                 "IRNB  {} {} {} {}",
-                NavFormatter::new_iono_alpha_beta(self.beta.0),
-                NavFormatter::new_iono_alpha_beta(self.beta.1),
-                NavFormatter::new_iono_alpha_beta(self.beta.2),
-                NavFormatter::new_iono_alpha_beta(self.beta.3),
+                NavFormatter::new_iono_alpha_beta(self.beta.0).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.beta.1).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.beta.2).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.beta.3).with_d_exponent(d_exponent),
             )
         } else {
             format!(
                 "{:E}B  {} {} {} {}",
                 constellation,
-                NavFormatter::new_iono_alpha_beta(self.beta.0),
-                NavFormatter::new_iono_alpha_beta(self.beta.1),
-                NavFormatter::new_iono_alpha_beta(self.beta.2),
-                NavFormatter::new_iono_alpha_beta(self.beta.3),
+                NavFormatter::new_iono_alpha_beta(self.beta.0).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.beta.1).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.beta.2).with_d_exponent(d_exponent),
+                NavFormatter::new_iono_alpha_beta(self.beta.3).with_d_exponent(d_exponent),
             )
         };
 
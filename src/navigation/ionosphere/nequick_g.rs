@@ -75,19 +75,22 @@ impl NgModel {
     //     0.0_f64
     // }
 
-    /// Format this [NgModel] for a V3 [Constellation] header.
+    /// Format this [NgModel] for a V3 [Constellation] header,
+    /// using either 'D' (true) or 'E' (false) exponent.
     pub fn format_header<W: Write>(
         &self,
         w: &mut BufWriter<W>,
         constellation: &Constellation,
+        d_exponent: bool,
     ) -> Result<(), FormattingError> {
         let formatted = format!(
             "{:E}   {} {} {} {}",
             constellation,
-            NavFormatter::new_iono_alpha_beta(self.a.0),
-            NavFormatter::new_iono_alpha_beta(self.a.1),
-            NavFormatter::new_iono_alpha_beta(self.a.2),
-            NavFormatter::new_iono_alpha_beta(self.region.bits() as f64),
+            NavFormatter::new_iono_alpha_beta(self.a.0).with_d_exponent(d_exponent),
+            NavFormatter::new_iono_alpha_beta(self.a.1).with_d_exponent(d_exponent),
+            NavFormatter::new_iono_alpha_beta(self.a.2).with_d_exponent(d_exponent),
+            NavFormatter::new_iono_alpha_beta(self.region.bits() as f64)
+                .with_d_exponent(d_exponent),
         );
 
         write!(w, "{}\n", fmt_rinex(&formatted, "IONOSPHERIC CORR"))?;
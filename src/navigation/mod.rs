@@ -17,7 +17,11 @@ pub(crate) use parsing::{is_new_epoch, parse_epoch};
 
 pub use crate::navigation::{
     earth_orientation::EarthOrientation,
-    ephemeris::{flags::*, orbits::OrbitItem, Ephemeris},
+    ephemeris::{
+        flags::*,
+        orbits::{rad_to_semicircles, semicircles_to_rad, OrbitItem},
+        Ephemeris,
+    },
     frame::{NavFrame, NavFrameType},
     header::HeaderFields,
     ionosphere::{BdModel, IonosphereModel, KbModel, KbRegionCode, NgModel, NgRegionFlags},
@@ -25,8 +29,35 @@ pub use crate::navigation::{
     time::TimeOffset,
 };
 
+#[cfg(any(feature = "binex", feature = "rtcm", feature = "ublox"))]
+mod skipped;
+
+#[cfg(any(feature = "binex", feature = "rtcm", feature = "ublox"))]
+pub use crate::navigation::skipped::SkipReason;
+
+#[cfg(feature = "nav")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nav")))]
+mod dop;
+
+#[cfg(feature = "nav")]
+pub use crate::navigation::dop::Dop;
+
+#[cfg(feature = "nav")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nav")))]
+mod pass;
+
 #[cfg(feature = "nav")]
-pub use crate::navigation::ephemeris::kepler::{Helper, Kepler, Perturbations};
+pub use crate::navigation::pass::Pass;
+
+mod topocentric;
+
+pub use crate::navigation::topocentric::topocentric_az_el_range;
+
+#[cfg(feature = "nav")]
+pub use crate::navigation::ephemeris::kepler::{
+    Helper, Kepler, OrbitalConstants, Perturbations, SolverConfig, SolverConstants, SolverState,
+    VelocityMethod,
+};
 
 #[cfg(feature = "processing")]
 pub(crate) mod mask; // mask Trait implementation
@@ -42,7 +73,7 @@ use serde::{Deserialize, Serialize};
 
 use std::collections::BTreeMap;
 
-use crate::prelude::{Epoch, SV};
+use crate::prelude::{Constellation, Epoch, TimeScale, SV};
 
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -64,3 +95,57 @@ pub struct NavKey {
 /// [NavKey] contains everything that is required to store & index a [NavFrame].
 /// Several types of frames may exist (in modern RINEX). Refer to following types.
 pub type Record = BTreeMap<NavKey, NavFrame>;
+
+/// QZSS satellites are either identified by their PRN number (193..=202),
+/// or by the RINEX "Jnn" offset notation (PRN - 192). We always index
+/// QZSS [SV]s with the latter, whatever the source, so a satellite
+/// may be searched for using either notations.
+pub(crate) fn normalize_qzss_sv(sv: SV) -> SV {
+    if sv.constellation == Constellation::QZSS && sv.prn >= 193 {
+        SV {
+            prn: sv.prn - 192,
+            constellation: sv.constellation,
+        }
+    } else {
+        sv
+    }
+}
+
+/// Returns the [TimeScale] in which this [SV] expresses its navigation messages.
+/// IRNSST is not defined by hifitime, but its week counter is aligned to the GPS week
+/// (RINEX 3.03 and later), with identical origin, so we express IRNSS in GPST.
+pub(crate) fn sv_timescale(sv: SV) -> Option<TimeScale> {
+    match sv.constellation {
+        Constellation::IRNSS => Some(TimeScale::GPST),
+        constellation => constellation.timescale(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::normalize_qzss_sv;
+    use crate::prelude::{Constellation, SV};
+    use std::str::FromStr;
+
+    #[test]
+    fn qzss_sv_normalization() {
+        let j01 = SV::from_str("J01").unwrap();
+        assert_eq!(normalize_qzss_sv(j01), j01);
+
+        let j193 = SV {
+            prn: 193,
+            constellation: Constellation::QZSS,
+        };
+        assert_eq!(normalize_qzss_sv(j193), j01);
+
+        let j202 = SV {
+            prn: 202,
+            constellation: Constellation::QZSS,
+        };
+        assert_eq!(normalize_qzss_sv(j202).prn, 10);
+
+        // other constellations are left untouched
+        let s23 = SV::from_str("S23").unwrap();
+        assert_eq!(normalize_qzss_sv(s23), s23);
+    }
+}
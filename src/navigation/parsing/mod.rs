@@ -82,7 +82,7 @@ mod test {
     use super::{is_new_epoch, parse_epoch};
 
     use crate::{
-        navigation::{NavFrameType, NavMessageType},
+        navigation::{gal::GalDataSource, NavFrameType, NavMessageType},
         prelude::{Constellation, Epoch, Header, Version},
     };
 
@@ -182,7 +182,7 @@ mod test {
         assert_eq!(ephemeris.clock_drift_rate, 7.38E4);
 
         let orbits = &ephemeris.orbits;
-        assert_eq!(orbits.len(), 10);
+        assert_eq!(orbits.len(), 11);
 
         for (k, v) in orbits.iter() {
             if k.eq("satPosX") {
@@ -209,6 +209,8 @@ mod test {
                 assert_eq!(v.as_f64(), -9.313225746150E-10);
             } else if k.eq("ageOp") {
                 assert_eq!(v.as_f64(), 0.0);
+            } else if k.eq("tb") {
+                assert_eq!(v.as_f64(), 165.0);
             } else {
                 panic!("Got unexpected key \"{}\" for GLOV2 record", k);
             }
@@ -376,7 +378,11 @@ mod test {
             } else if k.eq("idot") {
                 assert_eq!(v.as_f64(), -0.595381942905e-09);
             } else if k.eq("source") {
-                assert_eq!(v.as_f64(), 0.258000000000e+03);
+                let source = v.as_galileo_data_source().unwrap();
+                assert_eq!(
+                    source,
+                    GalDataSource::INAV_E1B | GalDataSource::TEMPORAL_SISA_E1_E5A
+                );
             } else if k.eq("week") {
                 assert_eq!(v.as_u32(), 2138);
             //SPARE
@@ -425,7 +431,7 @@ mod test {
         assert_eq!(ephemeris.clock_drift_rate, 0.342000000000e+05);
 
         let orbits = &ephemeris.orbits;
-        assert_eq!(orbits.len(), 9);
+        assert_eq!(orbits.len(), 10);
 
         for (k, v) in orbits.iter() {
             if k.eq("satPosX") {
@@ -452,6 +458,8 @@ mod test {
                 assert_eq!(v.as_f64(), -0.279396772385e-08);
             } else if k.eq("ageOp") {
                 assert_eq!(v.as_f64(), 0.000000000000e+00);
+            } else if k.eq("tb") {
+                assert_eq!(v.as_f64(), 765.0);
             } else {
                 panic!("Got unexpected key \"{}\" for GLOV3 record", k);
             }
@@ -1,7 +1,7 @@
 use crate::{
     navigation::{
-        BdModel, EarthOrientation, Ephemeris, IonosphereModel, KbModel, NavFrame, NavFrameType,
-        NavKey, NavMessageType, NgModel, TimeOffset,
+        sv_timescale, BdModel, EarthOrientation, Ephemeris, IonosphereModel, KbModel, NavFrame,
+        NavFrameType, NavKey, NavMessageType, NgModel, TimeOffset,
     },
     prelude::{Constellation, Epoch, ParsingError, SV},
 };
@@ -25,10 +25,7 @@ pub fn parse(content: &str) -> Result<(NavKey, NavFrame), ParsingError> {
     let sv = svnn.trim().parse::<SV>()?;
     let msgtype = rem.trim().parse::<NavMessageType>()?;
 
-    let ts = sv
-        .constellation
-        .timescale()
-        .ok_or(ParsingError::NoTimescaleDefinition)?;
+    let ts = sv_timescale(sv).ok_or(ParsingError::NoTimescaleDefinition)?;
 
     // Parses navframe type dependent and epoch of publication
     let (epoch, fr) = match frmtype {
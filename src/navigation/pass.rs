@@ -0,0 +1,27 @@
+//! Satellite visibility pass
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::{Duration, Epoch};
+
+/// [Pass] describes one contiguous period of time during which
+/// a satellite remains above the elevation mask, as seen from the receiver.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pass {
+    /// First [Epoch] above the elevation mask
+    pub rise: Epoch,
+
+    /// Last [Epoch] above the elevation mask
+    pub set: Epoch,
+
+    /// Maximal elevation reached during this pass (in degrees)
+    pub max_elevation_deg: f64,
+}
+
+impl Pass {
+    /// Returns the [Duration] of this [Pass].
+    pub fn duration(&self) -> Duration {
+        self.set - self.rise
+    }
+}
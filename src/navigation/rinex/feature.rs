@@ -1,11 +1,20 @@
 use crate::{
-    navigation::{BdModel, Ephemeris, IonosphereModel, KbModel, NavKey, NgModel},
+    constants::{Wgs84, SPEED_OF_LIGHT_M_S},
+    navigation::{
+        gal::GalNavType, normalize_qzss_sv, BdModel, Dop, Ephemeris, IonosphereModel, KbModel,
+        NavKey, NgModel, Pass, SolverConfig,
+    },
     prelude::{
-        nav::{Almanac, AzElRange, Orbit},
-        Epoch, Rinex, SV,
+        nav::{Almanac, AzElRange, Frame, Orbit},
+        Constellation, Duration, Epoch, Rinex, SV,
     },
 };
 
+use anise::{constants::frames::IAU_EARTH_FRAME, structure::planetocentric::ellipsoid::Ellipsoid};
+
+#[cfg(feature = "clock")]
+use anise::math::Vector3;
+
 impl Rinex {
     /// [SV] orbital state vector determination attempt, that only applies
     /// to Navigation [Rinex].
@@ -42,6 +51,289 @@ impl Rinex {
         Some(azelrange)
     }
 
+    /// Earth body fixed [Frame], described by the WGS84 ellipsoid.
+    /// This is all we need to resolve topocentric coordinates,
+    /// without loading any planetary kernel.
+    fn wgs84_earth_frame() -> Frame {
+        IAU_EARTH_FRAME.with_ellipsoid(Ellipsoid::from_spheroid(
+            Wgs84::SEMI_MAJOR_AXIS_KM,
+            Wgs84::semi_minor_axis_km(),
+        ))
+    }
+
+    /// [SV] (azimuth, elevation, slant range) triplet determination,
+    /// from receiver geodetic coordinates. This is a simpler version of
+    /// [Self::nav_azimuth_elevation_range], which does not require any [Almanac]
+    /// nor [Orbit] definition: we use a default Earth model (WGS84).
+    /// It only applies to Navigation [Rinex].
+    /// ## Inputs
+    /// - sv: target [SV]
+    /// - t: target [Epoch]
+    /// - rx_lat_deg: receiver latitude (in degrees)
+    /// - rx_lon_deg: receiver longitude (in degrees)
+    /// - rx_alt_m: receiver altitude above the ellipsoid (in meters)
+    /// - max_iter: maximal number of iterations of the Kepler solver
+    /// ## Returns
+    /// - (azimuth (degrees), elevation (degrees), range (meters)) on calculations success
+    pub fn sky_position(
+        &self,
+        sv: SV,
+        t: Epoch,
+        rx_lat_deg: f64,
+        rx_lon_deg: f64,
+        rx_alt_m: f64,
+        max_iter: usize,
+    ) -> Option<(f64, f64, f64)> {
+        let frame = Self::wgs84_earth_frame();
+        let cfg = SolverConfig::default().with_max_iteration(max_iter);
+
+        let (rx_x_km, rx_y_km, rx_z_km) = Wgs84::geodetic2ecef_km(rx_lat_deg, rx_lon_deg, rx_alt_m);
+        let rx_orbit = Orbit::from_position(rx_x_km, rx_y_km, rx_z_km, t, frame);
+
+        let azelrange = self.wgs84_azimuth_elevation_range(sv, t, rx_orbit, cfg)?;
+
+        Some((
+            azelrange.azimuth_deg,
+            azelrange.elevation_deg,
+            azelrange.range_km * 1.0E3,
+        ))
+    }
+
+    /// [AzElRange] determination, for receiver [Orbit] expressed
+    /// in [Self::wgs84_earth_frame].
+    fn wgs84_azimuth_elevation_range(
+        &self,
+        sv: SV,
+        t: Epoch,
+        rx_orbit: Orbit,
+        cfg: SolverConfig,
+    ) -> Option<AzElRange> {
+        let (_, _, eph) = self.nav_ephemeris_selection(sv, t)?;
+        let sv_state = eph
+            .kepler2position_with_config(sv, t, cfg)?
+            .to_cartesian_pos_vel();
+
+        let sv_orbit = Orbit::from_cartesian_pos_vel(sv_state, t, rx_orbit.frame);
+
+        let almanac = Almanac::default();
+
+        almanac
+            .azimuth_elevation_range_sez(sv_orbit, rx_orbit, None, None)
+            .ok()
+    }
+
+    /// [AzElRange] determination, from receiver ECEF coordinates (in meters),
+    /// as described in the [Header](crate::prelude::Header) of Observation [Rinex].
+    pub(crate) fn ecef_azimuth_elevation_range(
+        &self,
+        sv: SV,
+        t: Epoch,
+        rx_ecef_m: (f64, f64, f64),
+    ) -> Option<AzElRange> {
+        let frame = Self::wgs84_earth_frame();
+
+        let (rx_x_m, rx_y_m, rx_z_m) = rx_ecef_m;
+        let rx_orbit = Orbit::from_position(rx_x_m / 1e3, rx_y_m / 1e3, rx_z_m / 1e3, t, frame);
+
+        self.wgs84_azimuth_elevation_range(sv, t, rx_orbit, SolverConfig::default())
+    }
+
+    /// Returns all satellites in sight at desired [Epoch], from receiver geodetic coordinates.
+    /// This is the core of any sky plot or constellation availability tool.
+    /// It only applies to Navigation [Rinex]. [SV]s without valid [Ephemeris] are ignored.
+    /// ## Inputs
+    /// - t: target [Epoch]
+    /// - rx_position: receiver (latitude (degrees), longitude (degrees), altitude (meters))
+    /// - min_elevation_deg: elevation mask (in degrees)
+    /// - max_iter: maximal number of iterations of the Kepler solver
+    /// ## Returns
+    /// - list of ([SV], [AzElRange]) for each satellite above the elevation mask.
+    pub fn visible_satellites(
+        &self,
+        t: Epoch,
+        rx_position: (f64, f64, f64),
+        min_elevation_deg: f64,
+        max_iter: usize,
+    ) -> Vec<(SV, AzElRange)> {
+        let frame = Self::wgs84_earth_frame();
+        let cfg = SolverConfig::default().with_max_iteration(max_iter);
+
+        let (rx_lat_deg, rx_lon_deg, rx_alt_m) = rx_position;
+        let (rx_x_km, rx_y_km, rx_z_km) = Wgs84::geodetic2ecef_km(rx_lat_deg, rx_lon_deg, rx_alt_m);
+        let rx_orbit = Orbit::from_position(rx_x_km, rx_y_km, rx_z_km, t, frame);
+
+        self.sv_iter()
+            .filter_map(|sv| {
+                let azelrange = self.wgs84_azimuth_elevation_range(sv, t, rx_orbit, cfg)?;
+                if azelrange.elevation_deg >= min_elevation_deg {
+                    Some((sv, azelrange))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Dilution of Precision ([Dop]) determination, at desired [Epoch]
+    /// and from receiver geodetic coordinates. This is a standard planning and QC metric.
+    /// It only applies to Navigation [Rinex]. Refer to [Self::visible_satellites]
+    /// for more information.
+    /// ## Inputs
+    /// - t: target [Epoch]
+    /// - rx_position: receiver (latitude (degrees), longitude (degrees), altitude (meters))
+    /// - min_elevation_deg: elevation mask (in degrees)
+    /// - max_iter: maximal number of iterations of the Kepler solver
+    /// ## Returns
+    /// - [Dop] when at least 4 satellites are in sight
+    pub fn compute_dop(
+        &self,
+        t: Epoch,
+        rx_position: (f64, f64, f64),
+        min_elevation_deg: f64,
+        max_iter: usize,
+    ) -> Option<Dop> {
+        let geometry = self
+            .visible_satellites(t, rx_position, min_elevation_deg, max_iter)
+            .into_iter()
+            .map(|(_, azelrange)| azelrange)
+            .collect::<Vec<_>>();
+
+        Dop::from_azimuth_elevation_range(&geometry)
+    }
+
+    /// Visibility [Pass]es of desired [SV], from receiver geodetic coordinates.
+    /// The time frame of this Navigation [Rinex] is walked at the requested `step`,
+    /// so rise and set [Epoch]s are only as accurate as the sampling period.
+    /// Epochs without valid [Ephemeris] interrupt the ongoing [Pass].
+    /// ## Inputs
+    /// - sv: target [SV]
+    /// - rx_position: receiver (latitude (degrees), longitude (degrees), altitude (meters))
+    /// - min_elevation_deg: elevation mask (in degrees)
+    /// - step: sampling period, as [Duration]
+    /// - max_iter: maximal number of iterations of the Kepler solver
+    /// ## Returns
+    /// - list of [Pass]es, in chronological order
+    pub fn visibility_passes(
+        &self,
+        sv: SV,
+        rx_position: (f64, f64, f64),
+        min_elevation_deg: f64,
+        step: Duration,
+        max_iter: usize,
+    ) -> Vec<Pass> {
+        let mut passes = Vec::new();
+
+        let (first, last) = match (self.first_epoch(), self.last_epoch()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return passes,
+        };
+
+        if step <= Duration::ZERO {
+            return passes;
+        }
+
+        let frame = Self::wgs84_earth_frame();
+        let cfg = SolverConfig::default().with_max_iteration(max_iter);
+
+        let (rx_lat_deg, rx_lon_deg, rx_alt_m) = rx_position;
+        let (rx_x_km, rx_y_km, rx_z_km) = Wgs84::geodetic2ecef_km(rx_lat_deg, rx_lon_deg, rx_alt_m);
+
+        let mut ongoing = Option::<Pass>::None;
+        let mut t = first;
+
+        while t <= last {
+            let rx_orbit = Orbit::from_position(rx_x_km, rx_y_km, rx_z_km, t, frame);
+
+            let elevation_deg = self
+                .wgs84_azimuth_elevation_range(sv, t, rx_orbit, cfg)
+                .map(|azelrange| azelrange.elevation_deg)
+                .filter(|elevation_deg| *elevation_deg >= min_elevation_deg);
+
+            match (elevation_deg, ongoing.as_mut()) {
+                (Some(elevation_deg), Some(pass)) => {
+                    pass.set = t;
+                    pass.max_elevation_deg = pass.max_elevation_deg.max(elevation_deg);
+                },
+                (Some(elevation_deg), None) => {
+                    ongoing = Some(Pass {
+                        rise: t,
+                        set: t,
+                        max_elevation_deg: elevation_deg,
+                    });
+                },
+                (None, _) => {
+                    if let Some(pass) = ongoing.take() {
+                        passes.push(pass);
+                    }
+                },
+            }
+
+            t += step;
+        }
+
+        if let Some(pass) = ongoing {
+            passes.push(pass);
+        }
+
+        passes
+    }
+
+    /// Ground track (sub-satellite point) of desired [SV], from `start` to `end`
+    /// (both included), sampled every `step`. Epochs without valid [Ephemeris] are
+    /// simply omitted. This only applies to Navigation [Rinex].
+    /// ## Inputs
+    /// - sv: target [SV]
+    /// - start: first [Epoch]
+    /// - end: last [Epoch]
+    /// - step: sampling period, as [Duration]
+    /// - max_iter: maximal number of iterations of the Kepler solver
+    /// ## Returns
+    /// - list of ([Epoch], latitude (degrees), longitude (degrees)), in chronological order.
+    /// Longitudes are wrapped to ]-180°, 180°], so the track is discontinuous
+    /// where it crosses the antimeridian.
+    pub fn ground_track(
+        &self,
+        sv: SV,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+        max_iter: usize,
+    ) -> Vec<(Epoch, f64, f64)> {
+        let mut track = Vec::new();
+
+        if step <= Duration::ZERO {
+            return track;
+        }
+
+        let cfg = SolverConfig::default().with_max_iteration(max_iter);
+
+        let mut t = start;
+
+        while t <= end {
+            let position_km = self
+                .nav_ephemeris_selection(sv, t)
+                .and_then(|(_, _, eph)| eph.kepler2position_with_config(sv, t, cfg))
+                .map(|orbit| orbit.radius_km);
+
+            if let Some(position_km) = position_km {
+                let (lat_deg, mut lon_deg, _) =
+                    Wgs84::ecef2geodetic_km(position_km[0], position_km[1], position_km[2]);
+
+                if lon_deg > 180.0 {
+                    lon_deg -= 360.0;
+                } else if lon_deg <= -180.0 {
+                    lon_deg += 360.0;
+                }
+
+                track.push((t, lat_deg, lon_deg));
+            }
+
+            t += step;
+        }
+
+        track
+    }
+
     /// Ephemeris selection, that only applies to Navigation [Rinex].
     /// ## Inputs
     /// - sv: desired [SV]
@@ -51,23 +343,48 @@ impl Rinex {
     /// was decoded in the correct time frame.
     /// Note that `ToE` does not exist for GEO/SBAS [SV], so `ToC` is simply
     /// copied in this case, to maintain the API.
+    /// This is the first candidate of [Self::nav_ephemeris_selection_all].
     pub fn nav_ephemeris_selection(&self, sv: SV, t: Epoch) -> Option<(Epoch, Epoch, &Ephemeris)> {
+        self.nav_ephemeris_selection_all(sv, t).into_iter().next()
+    }
+
+    /// Returns all the [Ephemeris] frames that may apply to this [SV] at this [Epoch],
+    /// sorted by order of preference: closest ToE first (closest ToC for GEO/SBAS [SV]).
+    /// Candidates that are equally distant keep the record order (oldest ToC first):
+    /// inspect them to identify ambiguous selections, or to apply your own tie-break.
+    /// ## Inputs
+    /// - sv: desired [SV]
+    /// - t: desired [Epoch]
+    /// ## Returns
+    /// - list of (toc, toe, [Ephemeris]) triplets, empty when no frame applies.
+    pub fn nav_ephemeris_selection_all(&self, sv: SV, t: Epoch) -> Vec<(Epoch, Epoch, &Ephemeris)> {
+        let sv = normalize_qzss_sv(sv);
+
         if sv.constellation.is_sbas() {
-            self.nav_ephemeris_frames_iter()
+            let mut candidates = self
+                .nav_ephemeris_frames_iter()
                 .filter_map(|(k, eph)| {
-                    if k.sv == sv {
+                    if k.sv == sv && eph.is_valid_with_msgtype(k.sv, k.msgtype, k.epoch, t) {
                         Some((k.epoch, k.epoch, eph))
                     } else {
                         None
                     }
                 })
-                .min_by_key(|(toc, _, _)| t - *toc)
+                .collect::<Vec<_>>();
+
+            candidates.sort_by_key(|(toc, _, _)| (t - *toc).abs());
+            candidates
         } else {
-            self.nav_ephemeris_frames_iter()
+            let mut candidates = self
+                .nav_ephemeris_frames_iter()
                 .filter_map(|(k, eph)| {
                     if k.sv == sv {
-                        if eph.is_valid(sv, t) {
-                            if let Some(toe) = eph.toe(k.sv) {
+                        if eph.is_valid_with_msgtype(k.sv, k.msgtype, k.epoch, t) {
+                            let toe = match k.sv.constellation {
+                                Constellation::Glonass => eph.glonass_toe(k.sv, k.epoch),
+                                _ => eph.toe_with_msgtype(k.sv, k.msgtype, k.epoch),
+                            };
+                            if let Some(toe) = toe {
                                 Some((k.epoch, toe, eph))
                             } else {
                                 None
@@ -79,8 +396,197 @@ impl Rinex {
                         None
                     }
                 })
-                .min_by_key(|(_, toe, _)| (t - *toe).abs())
+                .collect::<Vec<_>>();
+
+            candidates.sort_by_key(|(_, toe, _)| (t - *toe).abs());
+            candidates
+        }
+    }
+
+    /// Time intervals during which this [SV] is covered by at least one valid
+    /// [Ephemeris], according to [Ephemeris::validity_duration]. Overlapping
+    /// (or adjacent) validity windows are merged, so any discontinuity in the returned
+    /// list is a gap in the navigation message, which is not to be confused with
+    /// an observation gap. This only applies to Navigation [Rinex].
+    /// ## Input
+    /// - sv: desired [SV]
+    /// ## Returns
+    /// - list of (start, end) [Epoch]s, in chronological order
+    pub fn nav_sv_validity_intervals(&self, sv: SV) -> Vec<(Epoch, Epoch)> {
+        let sv = normalize_qzss_sv(sv);
+
+        let validity = match Ephemeris::validity_duration(sv.constellation) {
+            Some(validity) => validity,
+            None => return Vec::new(),
+        };
+
+        let mut windows = self
+            .nav_ephemeris_frames_iter()
+            .filter(|(k, _)| k.sv == sv)
+            .filter_map(|(k, eph)| {
+                let reference = if sv.constellation.is_sbas() {
+                    Some(k.epoch)
+                } else if sv.constellation == Constellation::Glonass {
+                    eph.glonass_toe(k.sv, k.epoch)
+                } else {
+                    eph.toe_with_msgtype(k.sv, k.msgtype, k.epoch)
+                }?;
+
+                Some((reference - validity, reference + validity))
+            })
+            .collect::<Vec<_>>();
+
+        windows.sort_by_key(|(start, _)| *start);
+
+        let mut intervals = Vec::<(Epoch, Epoch)>::with_capacity(windows.len());
+
+        for (start, end) in windows {
+            match intervals.last_mut() {
+                Some((_, last_end)) if start <= *last_end => {
+                    if end > *last_end {
+                        *last_end = end;
+                    }
+                },
+                _ => intervals.push((start, end)),
+            }
         }
+
+        intervals
+    }
+
+    /// Galileo [Ephemeris] selection, like [Self::nav_ephemeris_selection],
+    /// but frames decoded from the preferred [GalNavType] (I/NAV or F/NAV) always win.
+    /// Frames from the other message type are only returned when no preferred
+    /// frame is valid at this [Epoch]: prefer a single message type
+    /// throughout your processing, to avoid mixing their clock references.
+    /// Falls back to [Self::nav_ephemeris_selection] for other constellations.
+    /// ## Inputs
+    /// - sv: desired [SV]
+    /// - t: desired [Epoch]
+    /// - prefer: preferred [GalNavType]
+    /// ## Returns
+    /// - (toc, toe, [Ephemeris]) triplet
+    pub fn nav_ephemeris_selection_galileo(
+        &self,
+        sv: SV,
+        t: Epoch,
+        prefer: GalNavType,
+    ) -> Option<(Epoch, Epoch, &Ephemeris)> {
+        if sv.constellation != Constellation::Galileo {
+            return self.nav_ephemeris_selection(sv, t);
+        }
+
+        self.nav_ephemeris_frames_iter()
+            .filter_map(|(k, eph)| {
+                if k.sv != sv || !eph.is_valid(sv, t) {
+                    return None;
+                }
+                let toe = eph.toe(sv)?;
+                let preferred = eph.galileo_nav_type(k.msgtype) == Some(prefer);
+                Some((!preferred, k.epoch, toe, eph))
+            })
+            .min_by_key(|(not_preferred, _, toe, _)| (*not_preferred, (t - *toe).abs()))
+            .map(|(_, toc, toe, eph)| (toc, toe, eph))
+    }
+
+    /// Broadcast clock residual, which is the clock component of the
+    /// Signal In Space Range Error (SISRE): the broadcast clock correction
+    /// of this [SV] at desired [Epoch], minus the clock bias provided by a precise
+    /// Clock RINEX product.
+    /// ## Inputs
+    /// - sv: desired [SV]
+    /// - t: [Epoch] of evaluation
+    /// - reference_clock: precise Clock [Rinex], interpolated at [Epoch]
+    /// - max_iter: maximal number of iterations, refer to [Ephemeris::clock_correction]
+    /// ## Returns
+    /// - residual as [Duration]
+    /// - None if no [Ephemeris] may apply or if the precise product does not
+    /// describe this [SV] at this [Epoch].
+    #[cfg(feature = "clock")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clock")))]
+    pub fn nav_clock_residual(
+        &self,
+        sv: SV,
+        t: Epoch,
+        reference_clock: &Rinex,
+        max_iter: usize,
+    ) -> Option<Duration> {
+        let (toc, _, eph) = self.nav_ephemeris_selection(sv, t)?;
+        let broadcast = eph.clock_correction(toc, t, sv, max_iter)?;
+        let precise = reference_clock.precise_sv_clock_bias_interpolated(t, sv)?;
+        Some(broadcast - Duration::from_seconds(precise))
+    }
+
+    /// Signal In Space Range Error (SISRE) of this [SV] at desired [Epoch], which is the
+    /// headline metric to monitor the quality of the broadcast orbits and clocks.
+    /// The broadcast state is compared to a precise product: the orbit error is projected
+    /// onto the radial, along-track and cross-track directions, and combined to the
+    /// clock residual (see [Self::nav_clock_residual]) using the standard weights of each
+    /// [Constellation] (Montenbruck et al. 2015):
+    /// SISRE = √((w_r·ΔR - c·ΔT)² + w_ac²·(ΔA² + ΔC²)).
+    /// This crate does not parse SP3 files: precise coordinates are provided by the
+    /// user, typically interpolated from a SP3 product. Note that precise orbits refer to
+    /// the center of mass, while GPS broadcast orbits refer to the antenna phase center:
+    /// you may want to compensate this offset in the provided coordinates.
+    /// ## Inputs
+    /// - sv: desired [SV]
+    /// - t: [Epoch] of evaluation
+    /// - precise_orbit: returns the precise ECEF coordinates (in km) of this [SV]
+    /// at this [Epoch], when they are known.
+    /// - precise_clock: precise Clock [Rinex], interpolated at [Epoch]
+    /// - max_iter: maximal number of iterations of the Kepler solver and clock correction
+    /// ## Returns
+    /// - SISRE in meters
+    /// - None if no [Ephemeris] may apply, the precise products do not describe
+    /// this [SV] at this [Epoch], or this [Constellation] is not supported (SBAS).
+    #[cfg(feature = "clock")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clock")))]
+    pub fn sisre<F: Fn(SV, Epoch) -> Option<(f64, f64, f64)>>(
+        &self,
+        sv: SV,
+        t: Epoch,
+        precise_orbit: F,
+        precise_clock: &Rinex,
+        max_iter: usize,
+    ) -> Option<f64> {
+        // (radial, along/cross track squared) weights
+        let (w_r, w_ac2) = match sv.constellation {
+            Constellation::GPS => (0.98, 1.0 / 49.0),
+            Constellation::Glonass => (0.98, 1.0 / 45.0),
+            Constellation::Galileo => (0.98, 1.0 / 61.0),
+            Constellation::BeiDou if sv.is_beidou_geo() => (0.99, 1.0 / 126.0),
+            Constellation::BeiDou => (0.98, 1.0 / 54.0),
+            Constellation::QZSS | Constellation::IRNSS => (0.99, 1.0 / 126.0),
+            _ => return None,
+        };
+
+        let (_, _, eph) = self.nav_ephemeris_selection(sv, t)?;
+
+        let cfg = SolverConfig::default().with_max_iteration(max_iter);
+        let broadcast = eph.kepler2position_with_config(sv, t, cfg)?;
+
+        let pos_vel = broadcast.to_cartesian_pos_vel();
+        let position = Vector3::new(pos_vel[0], pos_vel[1], pos_vel[2]);
+        let velocity = Vector3::new(pos_vel[3], pos_vel[4], pos_vel[5]);
+
+        let (x_km, y_km, z_km) = precise_orbit(sv, t)?;
+        let error_m = (position - Vector3::new(x_km, y_km, z_km)) * 1.0E3;
+
+        // local orbital frame
+        let radial = position.normalize();
+        let cross = position.cross(&velocity).normalize();
+        let along = cross.cross(&radial);
+
+        let (d_r, d_a, d_c) = (
+            error_m.dot(&radial),
+            error_m.dot(&along),
+            error_m.dot(&cross),
+        );
+
+        let clock_residual = self.nav_clock_residual(sv, t, precise_clock, max_iter)?;
+        let clock_m = clock_residual.to_seconds() * SPEED_OF_LIGHT_M_S;
+
+        Some(((w_r * d_r - clock_m).powi(2) + w_ac2 * (d_a.powi(2) + d_c.powi(2))).sqrt())
     }
 
     /// Klobuchar [KbModel] Ionosphere model [Iterator].
@@ -12,21 +12,179 @@ pub mod ut1; // feature dependent high level methods
 
 use crate::{
     navigation::{
-        EarthOrientation, Ephemeris, NavFrame, NavFrameType, NavKey, NavMessageType, TimeOffset,
+        normalize_qzss_sv, EarthOrientation, Ephemeris, NavFrame, NavFrameType, NavKey,
+        NavMessageType, TimeOffset,
     },
-    prelude::{Epoch, Rinex, RinexType, SV},
+    prelude::{Constellation, Duration, Epoch, Header, Rinex, RinexType, TimeScale, Version, SV},
 };
 
-use std::collections::btree_map::Keys;
+use std::collections::{btree_map::Keys, BTreeSet};
 
 use super::IonosphereModel;
 
 impl Rinex {
+    /// Builds an empty Navigation [Rinex] of given [Version], dedicated to
+    /// this [Constellation] (use [Constellation::Mixed] for multi GNSS files).
+    /// Populate it with [Self::add_ephemeris], then format it to obtain a valid file.
+    /// This is typically used to log [Ephemeris] decoded from real-time streams.
+    /// ```
+    /// use rinex::prelude::{Constellation, Rinex, Version};
+    ///
+    /// let rinex = Rinex::new_navigation(Version::new(3, 4), Constellation::GPS);
+    /// assert!(rinex.is_navigation_rinex());
+    /// assert_eq!(rinex.header.constellation, Some(Constellation::GPS));
+    /// ```
+    pub fn new_navigation(version: Version, constellation: Constellation) -> Self {
+        let header = Header::basic_nav()
+            .with_version(version)
+            .with_constellation(constellation);
+
+        Self::basic_nav().with_header(header)
+    }
+
+    /// Stores this [Ephemeris] frame, indexed by [NavKey], in this Navigation [Rinex].
+    /// The [NavFrameType] of the key is always set to [NavFrameType::Ephemeris],
+    /// and a pre-existing frame for this key is replaced.
+    /// This has no effect if this is not a Navigation [Rinex].
+    pub fn add_ephemeris(&mut self, key: NavKey, ephemeris: Ephemeris) {
+        let key = NavKey {
+            sv: normalize_qzss_sv(key.sv),
+            frmtype: NavFrameType::Ephemeris,
+            ..key
+        };
+
+        if let Some(rec) = self.record.as_mut_nav() {
+            rec.insert(key, NavFrame::EPH(ephemeris));
+        }
+    }
+
     /// Returns true if this [Rinex] is [RinexType::NavigationData].
     pub fn is_navigation_rinex(&self) -> bool {
         self.header.rinex_type == RinexType::NavigationData
     }
 
+    /// Returns the set of [Constellation]s actually found in this Navigation [Rinex] record.
+    /// Returns an empty set if this is not a Navigation [Rinex].
+    pub fn nav_constellations(&self) -> BTreeSet<Constellation> {
+        match self.record.as_nav() {
+            Some(rec) => rec.keys().map(|k| k.sv.constellation).collect(),
+            None => BTreeSet::new(),
+        }
+    }
+
+    /// Returns true if this is a multi GNSS Navigation [Rinex]: either the [Header]
+    /// declares a [Constellation::Mixed] file (type "M" and typically, `MN` file names),
+    /// or the record contains frames from more than one [Constellation].
+    /// Returns false for single system files and other types of RINEX.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    ///
+    /// let rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// assert!(rinex.nav_is_mixed());
+    /// ```
+    pub fn nav_is_mixed(&self) -> bool {
+        if !self.is_navigation_rinex() {
+            return false;
+        }
+
+        self.header.constellation == Some(Constellation::Mixed)
+            || self.nav_constellations().len() > 1
+    }
+
+    /// Copies and returns a new Navigation [Rinex] from which this [Constellation]
+    /// was removed. See [Self::remove_constellation_mut].
+    pub fn remove_constellation(&self, constellation: Constellation) -> Self {
+        let mut s = self.clone();
+        s.remove_constellation_mut(constellation);
+        s
+    }
+
+    /// Removes all data published by this [Constellation] from this Navigation [Rinex]:
+    /// ephemeris frames, ionosphere models and system time offsets (both in the record
+    /// and the [Header]). When a single [Constellation] remains, the [Header] now
+    /// describes a single system file. This is typically used to obtain a mono GNSS
+    /// file from a mixed file, for legacy tools.
+    /// This has no effect if this is not a Navigation [Rinex].
+    /// ```
+    /// use rinex::prelude::{Constellation, Rinex};
+    ///
+    /// let mut rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// rinex.remove_constellation_mut(Constellation::Galileo);
+    ///
+    /// assert!(!rinex.nav_constellations().contains(&Constellation::Galileo));
+    /// ```
+    pub fn remove_constellation_mut(&mut self, constellation: Constellation) {
+        let rec = match self.record.as_mut_nav() {
+            Some(rec) => rec,
+            None => return,
+        };
+
+        rec.retain(|k, _| k.sv.constellation != constellation);
+
+        let timescale = constellation.timescale();
+
+        if let Some(nav) = &mut self.header.nav {
+            nav.time_offsets
+                .retain(|offset| timescale.is_none() || Some(offset.lhs) != timescale);
+        }
+
+        self.header.ionod_corrections.remove(&constellation);
+
+        self.header
+            .glo_channels
+            .retain(|sv, _| sv.constellation != constellation);
+
+        let remaining = self.nav_constellations();
+
+        if remaining.len() == 1 {
+            self.header.constellation = remaining.into_iter().next();
+        }
+    }
+
+    /// Copies and returns a new Navigation [Rinex] that only contains the
+    /// [Ephemeris] frames valid at this [Epoch]. See [Self::nav_retain_valid_at_mut].
+    pub fn nav_retain_valid_at(&self, epoch: Epoch) -> Self {
+        let mut s = self.clone();
+        s.nav_retain_valid_at_mut(epoch);
+        s
+    }
+
+    /// Drops all [Ephemeris] frames that are not valid at this [Epoch], according
+    /// to [Ephemeris::is_valid_at], whatever their [NavMessageType] (CNAV included).
+    /// SBAS frames are retained within [Ephemeris::validity_duration] of their ToC.
+    /// Every satellite that could be resolved at that instant remains resolvable,
+    /// which is typically what real-time assistance services distribute.
+    /// Several frames may remain per satellite, when their validity periods overlap
+    /// or when it broadcasts several message types.
+    /// Other frames (ionosphere models, system time offsets..) are preserved.
+    /// This has no effect if this is not a Navigation [Rinex].
+    /// ```
+    /// use std::str::FromStr;
+    /// use rinex::prelude::{Epoch, Rinex};
+    ///
+    /// let mut rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// let t = Epoch::from_str("2020-06-25T12:00:00 GPST").unwrap();
+    /// rinex.nav_retain_valid_at_mut(t);
+    ///
+    /// for (k, eph) in rinex.nav_ephemeris_frames_iter() {
+    ///     assert!(eph.is_valid_at(k, t));
+    /// }
+    /// ```
+    pub fn nav_retain_valid_at_mut(&mut self, epoch: Epoch) {
+        if let Some(rec) = self.record.as_mut_nav() {
+            rec.retain(|k, frame| match frame.as_ephemeris() {
+                Some(eph) => eph.is_valid_at(k, epoch),
+                None => true,
+            });
+        }
+    }
+
     /// [NavKey]s [Iterator]
     pub fn navigation_keys(&self) -> Keys<'_, NavKey, NavFrame> {
         if let Some(rec) = self.record.as_nav() {
@@ -47,6 +205,42 @@ impl Rinex {
         }
     }
 
+    /// [NavFrame]s [Iterator], whatever their [NavFrameType].
+    /// This is convenient to process heterogeneous (typically V4) records in a single loop,
+    /// matching on [NavKey::frmtype]. Refer to [Self::nav_ephemeris_frames_iter]
+    /// (and similar methods) to iterate over one kind of frame specifically.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use rinex::navigation::NavFrameType;
+    ///
+    /// let rinex = Rinex::from_gzip_file("data/NAV/V4/KMS300DNK_R_20221591000_01H_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// for (key, frame) in rinex.nav_frames_iter() {
+    ///     match key.frmtype {
+    ///         NavFrameType::Ephemeris => {
+    ///             let _ephemeris = frame.as_ephemeris().unwrap();
+    ///         },
+    ///         NavFrameType::IonosphereModel => {
+    ///             let _model = frame.as_ionosphere_model().unwrap();
+    ///         },
+    ///         NavFrameType::SystemTimeOffset => {
+    ///             let _time_offset = frame.as_system_time().unwrap();
+    ///         },
+    ///         NavFrameType::EarthOrientation => {
+    ///             let _eop = frame.as_earth_orientation().unwrap();
+    ///         },
+    ///     }
+    /// }
+    /// ```
+    pub fn nav_frames_iter(&self) -> Box<dyn Iterator<Item = (&NavKey, &NavFrame)> + '_> {
+        if let Some(rec) = self.record.as_nav() {
+            Box::new(rec.iter())
+        } else {
+            Box::new([].into_iter())
+        }
+    }
+
     /// [Ephemeris] frames [Iterator]
     pub fn nav_ephemeris_frames_iter(
         &self,
@@ -84,8 +278,27 @@ impl Rinex {
         }
     }
 
-    /// [IonosphereModel] frames [Iterator].
+    /// [IonosphereModel] frames [Iterator], whatever the kind of model.
     /// This type of frames exists in NAV V4 only.
+    /// This is convenient when a file mixes several kinds of models
+    /// (typically, one per constellation), that you can handle in a single loop.
+    /// Refer to [Self::nav_klobuchar_models_iter] (and similar methods)
+    /// to iterate over one kind of model specifically.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use rinex::navigation::IonosphereModel;
+    ///
+    /// let rinex = Rinex::from_gzip_file("data/NAV/V4/KMS300DNK_R_20221591000_01H_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// for (key, model) in rinex.nav_ionosphere_models_iter() {
+    ///     match model {
+    ///         IonosphereModel::Klobuchar(kb) => {},
+    ///         IonosphereModel::NequickG(ng) => {},
+    ///         IonosphereModel::Bdgim(bd) => {},
+    ///     }
+    /// }
+    /// ```
     pub fn nav_ionosphere_models_iter(
         &self,
     ) -> Box<dyn Iterator<Item = (&NavKey, &IonosphereModel)> + '_> {
@@ -103,6 +316,53 @@ impl Rinex {
         }
     }
 
+    /// [IonosphereModel]s [Iterator], per [Constellation], as described in the
+    /// [Header](crate::prelude::Header) section of V2 ("ION ALPHA" / "ION BETA")
+    /// and V3 ("IONOSPHERIC CORR") files.
+    /// This is the V2/V3 counterpart of [Self::nav_ionosphere_models_iter],
+    /// models are published once for the entire file.
+    /// ```
+    /// use rinex::prelude::{Rinex, Constellation};
+    ///
+    /// let rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// for (constellation, model) in rinex.header_ionosphere_models() {
+    ///     if *constellation == Constellation::GPS {
+    ///         let kb = model.as_klobuchar()
+    ///             .unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub fn header_ionosphere_models(
+        &self,
+    ) -> Box<dyn Iterator<Item = (&Constellation, &IonosphereModel)> + '_> {
+        Box::new(self.header.ionod_corrections.iter())
+    }
+
+    /// [TimeOffset]s [Iterator], as described in the [Header](crate::prelude::Header)
+    /// section ("TIME SYSTEM CORR" in V3, "DELTA-UTC" and "CORR TO SYSTEM TIME" in V2).
+    /// This is the V2/V3 counterpart of [Self::nav_system_time_frames_iter].
+    /// GLONASS corrections (GLUT, GLGP) are not exposed, because
+    /// the GLONASS timescale is not supported yet.
+    /// ```
+    /// use rinex::prelude::{Rinex, TimeScale};
+    ///
+    /// let rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// let gput = rinex.header_time_offsets()
+    ///     .find(|offset| offset.lhs == TimeScale::GPST && offset.rhs == TimeScale::UTC)
+    ///     .unwrap();
+    /// ```
+    pub fn header_time_offsets(&self) -> Box<dyn Iterator<Item = &TimeOffset> + '_> {
+        if let Some(nav) = &self.header.nav {
+            Box::new(nav.time_offsets.iter())
+        } else {
+            Box::new([].into_iter())
+        }
+    }
+
     /// [SystemTime] frames [Iterator].
     /// This type of frames exists in NAV V4 only.
     pub fn nav_system_time_frames_iter(
@@ -122,6 +382,55 @@ impl Rinex {
         }
     }
 
+    /// Returns the time difference between two system times (lhs - rhs) at this [Epoch],
+    /// for example GPST - GST, using the system time corrections this Navigation [Rinex]
+    /// describes (either in the [Header] or as V4 frames). When several corrections apply,
+    /// we use the one with the closest reference time. Reciprocal corrections are
+    /// supported: GPST - GST is obtained from a GAGP correction as well.
+    /// Returns None when no correction exists for this pair of [TimeScale]s.
+    /// ```
+    /// use std::str::FromStr;
+    /// use rinex::prelude::{Epoch, Rinex, TimeScale};
+    ///
+    /// let rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// let t = Epoch::from_str("2020-06-25T12:00:00 GPST").unwrap();
+    ///
+    /// let gpst_gst = rinex.inter_system_bias(TimeScale::GPST, TimeScale::GST, t)
+    ///     .unwrap();
+    ///
+    /// // GPST - GST is a few nanoseconds
+    /// assert!(gpst_gst.abs().to_seconds() < 1.0E-6);
+    /// ```
+    pub fn inter_system_bias(
+        &self,
+        lhs: TimeScale,
+        rhs: TimeScale,
+        epoch: Epoch,
+    ) -> Option<Duration> {
+        self.header_time_offsets()
+            .chain(self.nav_system_time_frames_iter().map(|(_, offset)| offset))
+            .filter_map(|offset| {
+                if offset.lhs == lhs && offset.rhs == rhs {
+                    Some((offset, false))
+                } else if offset.lhs == rhs && offset.rhs == lhs {
+                    Some((offset, true))
+                } else {
+                    None
+                }
+            })
+            .min_by_key(|(offset, _)| (epoch - offset.t_ref_epoch()).abs())
+            .map(|(offset, reciprocal)| {
+                let bias = offset.offset_at(epoch);
+                if reciprocal {
+                    -bias
+                } else {
+                    bias
+                }
+            })
+    }
+
     /// [SV] clock state [Iterator].
     /// ## Inputs
     /// - self: Navigation [Rinex]
@@ -0,0 +1,23 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [SkipReason] explains why a NAV frame did not make it into
+/// a binary stream (BINEX, RTCM, UBX..).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum SkipReason {
+    /// This constellation is not supported by the output format,
+    /// or by our encoder (yet).
+    NonSupportedConstellation,
+    /// Some fields, that the output format requires, are missing.
+    MissingFields,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NonSupportedConstellation => write!(f, "non supported constellation"),
+            Self::MissingFields => write!(f, "missing fields"),
+        }
+    }
+}
@@ -16,6 +16,7 @@ impl TimeOffset {
             (TimeScale::GPST, TimeScale::GST) => "GPGA",
             (TimeScale::GPST, TimeScale::BDT) => "GPBD",
             (TimeScale::QZSST, TimeScale::UTC) => "QZUT",
+            (TimeScale::QZSST, TimeScale::GPST) => "QZGP",
             (TimeScale::QZSST, TimeScale::GST) => "QZGA",
             (TimeScale::QZSST, TimeScale::BDT) => "QZBD",
             (TimeScale::GST, TimeScale::UTC) => "GAUT",
@@ -32,6 +33,7 @@ impl TimeOffset {
     pub(crate) fn format_v2_delta_utc<W: Write>(
         &self,
         w: &mut BufWriter<W>,
+        d_exponent: bool,
     ) -> Result<(), FormattingError> {
         writeln!(
             w,
@@ -39,8 +41,10 @@ impl TimeOffset {
             fmt_rinex(
                 &format!(
                     "   {}{} {:8} {:8}",
-                    NavFormatter::new_time_system_correction_v2(self.polynomial.0),
-                    NavFormatter::new_time_system_correction_v2(self.polynomial.1),
+                    NavFormatter::new_time_system_correction_v2(self.polynomial.0)
+                        .with_d_exponent(d_exponent),
+                    NavFormatter::new_time_system_correction_v2(self.polynomial.1)
+                        .with_d_exponent(d_exponent),
                     self.t_ref.1 / 1_000_000_000,
                     self.t_ref.0,
                 ),
@@ -55,6 +59,7 @@ impl TimeOffset {
     pub(crate) fn format_v2_corr_to_system_time<W: Write>(
         &self,
         w: &mut BufWriter<W>,
+        d_exponent: bool,
     ) -> Result<(), FormattingError> {
         let t = Epoch::from_time_of_week(self.t_ref.0, self.t_ref.1, self.lhs);
 
@@ -70,6 +75,7 @@ impl TimeOffset {
                     m,
                     d,
                     NavFormatter::new_time_system_correction_v2(self.polynomial.0)
+                        .with_d_exponent(d_exponent)
                 ),
                 "CORR TO SYSTEM TIME",
             ),
@@ -79,7 +85,11 @@ impl TimeOffset {
     }
 
     /// Format [TimeOffset] according to RINEXv3 standard
-    pub(crate) fn format_v3<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
+    pub(crate) fn format_v3<W: Write>(
+        &self,
+        w: &mut BufWriter<W>,
+        d_exponent: bool,
+    ) -> Result<(), FormattingError> {
         writeln!(
             w,
             "{}",
@@ -87,8 +97,10 @@ impl TimeOffset {
                 &format!(
                     "{} {}{} {:6}{:5}",
                     self.to_lhs_rhs_timescales(),
-                    NavFormatter::new_time_system_correction_v3_offset(self.polynomial.0),
-                    NavFormatter::new_time_system_correction_v3_drift(self.polynomial.1),
+                    NavFormatter::new_time_system_correction_v3_offset(self.polynomial.0)
+                        .with_d_exponent(d_exponent),
+                    NavFormatter::new_time_system_correction_v3_drift(self.polynomial.1)
+                        .with_d_exponent(d_exponent),
                     self.t_ref.1 / 1_000_000_000,
                     self.t_ref.0
                 ),
@@ -99,12 +111,17 @@ impl TimeOffset {
         Ok(())
     }
 
-    pub(crate) fn format_v4<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
+    /// Format [TimeOffset] according to RINEXv4 standard (STO frame body).
+    /// Transmission time is not stored: we use the reference time of week.
+    pub(crate) fn format_v4<W: Write>(
+        &self,
+        w: &mut BufWriter<W>,
+        d_exponent: bool,
+    ) -> Result<(), FormattingError> {
         let t = Epoch::from_time_of_week(self.t_ref.0, self.t_ref.1, self.lhs);
         let (y, m, d, hh, mm, ss, _) = epoch_decompose(t);
 
-        writeln!(
-            w,
+        let epoch = format!(
             "    {:04} {:02} {:02} {:02} {:02} {:02} {}",
             y,
             m,
@@ -113,15 +130,20 @@ impl TimeOffset {
             mm,
             ss,
             self.to_lhs_rhs_timescales(),
-        )?;
+        );
+
+        match &self.utc {
+            Some(utc) => writeln!(w, "{:<62}{}", epoch, utc)?,
+            None => writeln!(w, "{}", epoch)?,
+        }
 
         writeln!(
             w,
             "    {}{}{}{}",
-            NavFormatter::new((self.t_ref.1 / 1_000_000_000) as f64),
-            NavFormatter::new(self.polynomial.0),
-            NavFormatter::new(self.polynomial.1),
-            NavFormatter::new(self.polynomial.2),
+            NavFormatter::new((self.t_ref.1 / 1_000_000_000) as f64).with_d_exponent(d_exponent),
+            NavFormatter::new(self.polynomial.0).with_d_exponent(d_exponent),
+            NavFormatter::new(self.polynomial.1).with_d_exponent(d_exponent),
+            NavFormatter::new(self.polynomial.2).with_d_exponent(d_exponent),
         )?;
 
         Ok(())
@@ -68,6 +68,20 @@ impl TimeOffset {
         }
     }
 
+    /// Returns the reference [Epoch] of this [TimeOffset], expressed in [Self::lhs].
+    pub fn t_ref_epoch(&self) -> Epoch {
+        Epoch::from_time_of_week(self.t_ref.0, self.t_ref.1, self.lhs)
+    }
+
+    /// Evaluates this [TimeOffset] at desired [Epoch], which returns
+    /// the time difference [Self::lhs] - [Self::rhs] at that instant:
+    /// a0 + a1 * dt + a2 * dt², where dt is the elapsed time since [Self::t_ref].
+    pub fn offset_at(&self, t: Epoch) -> Duration {
+        let dt = (t.to_time_scale(self.lhs) - self.t_ref_epoch()).to_seconds();
+        let (a0, a1, a2) = self.polynomial;
+        Duration::from_seconds(a0 + a1 * dt + a2 * dt.powi(2))
+    }
+
     /// Converts this [TimeOffset] to Hifitime [Polynomial].
     pub(crate) fn to_hifitime_polynomial(&self) -> Polynomial {
         Polynomial {
@@ -0,0 +1,86 @@
+//! Topocentric sky geometry
+use crate::constants::Wgs84;
+
+/// Resolves the (azimuth, elevation, slant range) of a satellite as seen from
+/// a receiver, using pure geometry. Unlike [Ephemeris::elevation_azimuth_range]
+/// or [Rinex::nav_azimuth_elevation_range], this does not involve any [Almanac]
+/// or [Frame]: the local (East, North, Up) frame is derived from the receiver
+/// geodetic coordinates on the WGS84 ellipsoid. It therefore does not
+/// require the `nav` feature.
+/// ## Inputs
+/// - sv_ecef_km: satellite ECEF coordinates (in km)
+/// - rx_ecef_km: receiver ECEF coordinates (in km)
+/// ## Returns
+/// - (azimuth (degrees, 0..360), elevation (degrees), slant range (meters))
+///
+/// [Ephemeris::elevation_azimuth_range]: crate::navigation::Ephemeris::elevation_azimuth_range
+/// [Rinex::nav_azimuth_elevation_range]: crate::prelude::Rinex::nav_azimuth_elevation_range
+/// [Almanac]: crate::prelude::nav::Almanac
+/// [Frame]: crate::prelude::nav::Frame
+pub fn topocentric_az_el_range(
+    sv_ecef_km: (f64, f64, f64),
+    rx_ecef_km: (f64, f64, f64),
+) -> (f64, f64, f64) {
+    let (rx_x_km, rx_y_km, rx_z_km) = rx_ecef_km;
+    let (lat_deg, lon_deg, _) = Wgs84::ecef2geodetic_km(rx_x_km, rx_y_km, rx_z_km);
+
+    let (sin_lat, cos_lat) = lat_deg.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = lon_deg.to_radians().sin_cos();
+
+    let (dx_km, dy_km, dz_km) = (
+        sv_ecef_km.0 - rx_x_km,
+        sv_ecef_km.1 - rx_y_km,
+        sv_ecef_km.2 - rx_z_km,
+    );
+
+    let east = -sin_lon * dx_km + cos_lon * dy_km;
+    let north = -sin_lat * cos_lon * dx_km - sin_lat * sin_lon * dy_km + cos_lat * dz_km;
+    let up = cos_lat * cos_lon * dx_km + cos_lat * sin_lon * dy_km + sin_lat * dz_km;
+
+    let range_km = (dx_km.powi(2) + dy_km.powi(2) + dz_km.powi(2)).sqrt();
+
+    let elevation_deg = up.atan2((east.powi(2) + north.powi(2)).sqrt()).to_degrees();
+    let azimuth_deg = east.atan2(north).to_degrees().rem_euclid(360.0);
+
+    (azimuth_deg, elevation_deg, range_km * 1.0E3)
+}
+
+#[cfg(test)]
+mod test {
+    use super::topocentric_az_el_range;
+    use crate::constants::Wgs84;
+
+    #[test]
+    fn zenith_and_horizon() {
+        let rx_ecef_km = Wgs84::geodetic2ecef_km(45.0, 10.0, 0.0);
+        let up_ecef_km = Wgs84::geodetic2ecef_km(45.0, 10.0, 20_000.0E3);
+
+        let (_, elevation_deg, range_m) = topocentric_az_el_range(up_ecef_km, rx_ecef_km);
+        assert!((elevation_deg - 90.0).abs() < 1.0E-6);
+        assert!((range_m - 20_000.0E3).abs() < 1.0E-3);
+
+        // due north, on the local horizon
+        let north_ecef_km = Wgs84::geodetic2ecef_km(45.001, 10.0, 0.0);
+        let (azimuth_deg, elevation_deg, _) = topocentric_az_el_range(north_ecef_km, rx_ecef_km);
+        assert!(azimuth_deg.abs() < 1.0E-3 || (azimuth_deg - 360.0).abs() < 1.0E-3);
+        assert!(elevation_deg.abs() < 0.01);
+
+        // due east
+        let east_ecef_km = Wgs84::geodetic2ecef_km(45.0, 10.001, 0.0);
+        let (azimuth_deg, _, _) = topocentric_az_el_range(east_ecef_km, rx_ecef_km);
+        assert!((azimuth_deg - 90.0).abs() < 1.0E-3);
+    }
+
+    #[test]
+    fn ecef2geodetic() {
+        for (lat_deg, lon_deg, alt_m) in
+            [(55.0, 10.0, 0.0), (-33.5, -70.6, 520.0), (0.0, 180.0, 10.0)]
+        {
+            let (x_km, y_km, z_km) = Wgs84::geodetic2ecef_km(lat_deg, lon_deg, alt_m);
+            let (lat, lon, alt) = Wgs84::ecef2geodetic_km(x_km, y_km, z_km);
+            assert!((lat - lat_deg).abs() < 1.0E-9);
+            assert!((lon - lon_deg).abs() < 1.0E-9 || (lon.abs() - 180.0).abs() < 1.0E-9);
+            assert!((alt - alt_m).abs() < 1.0E-3);
+        }
+    }
+}
@@ -5,7 +5,7 @@ use crate::prelude::{Carrier, Constellation, Error, ParsingError};
 #[derive(Debug, Clone, PartialEq, PartialOrd, Hash, Ord, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Observable {
-    /// Carrier phase range converted to [m] (not cycles!)
+    /// Carrier phase range, in cycles of the carrier
     PhaseRange(String),
     /// Doppler shift observation
     Doppler(String),
@@ -13,7 +13,7 @@ pub enum Observable {
     SSI(String),
     /// Received Power [dBm]
     Power(String),
-    /// Decoded Pseudo range converted to [m]
+    /// Decoded Pseudo range, in [m]
     PseudoRange(String),
     /// Channel number Pseudo Observable.
     /// Attached to Phase or PseudoRange observable to accurately
@@ -118,11 +118,131 @@ impl Observable {
         }
     }
 
+    /// Converts a RINEX V2 [Observable], which does not describe the tracking
+    /// attribute, to its RINEX V3 equivalent, for given [Constellation].
+    /// The default mapping follows IGS conventions:
+    ///
+    /// | Constellation | V2                 | V3                                  |
+    /// |---------------|--------------------|-------------------------------------|
+    /// | GPS           | C1, L1, D1, S1     | C1C, L1C, D1C, S1C                  |
+    /// | GPS           | P1                 | C1W                                 |
+    /// | GPS           | P2, L2, D2, S2     | C2W, L2W, D2W, S2W                  |
+    /// | GPS           | C2                 | C2X                                 |
+    /// | GPS           | C5, L5, D5, S5     | C5X, L5X, D5X, S5X                  |
+    /// | Glonass       | C1, L1, D1, S1     | C1C, L1C, D1C, S1C                  |
+    /// | Glonass       | P1                 | C1P                                 |
+    /// | Glonass       | C2                 | C2C                                 |
+    /// | Glonass       | P2, L2, D2, S2     | C2P, L2P, D2P, S2P                  |
+    /// | BeiDou        | x2, x7, x6         | x2I, x7I, x6I                       |
+    /// | SBAS, QZSS    | x1                 | x1C                                 |
+    /// | Others        | xN                 | xNX                                 |
+    ///
+    /// V3 observables and Meteo observables are returned as is.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    ///
+    /// let p2 = Observable::from_str("P2").unwrap();
+    /// assert_eq!(
+    ///     p2.v2_to_v3(Constellation::GPS),
+    ///     Observable::from_str("C2W").unwrap(),
+    /// );
+    ///
+    /// let l1 = Observable::from_str("L1").unwrap();
+    /// assert_eq!(
+    ///     l1.v2_to_v3(Constellation::Glonass),
+    ///     Observable::from_str("L1C").unwrap(),
+    /// );
+    /// ```
+    pub fn v2_to_v3(&self, constellation: Constellation) -> Self {
+        let code = match self {
+            Self::PhaseRange(c) | Self::Doppler(c) | Self::SSI(c) | Self::PseudoRange(c) => c,
+            _ => return self.clone(),
+        };
+
+        if code.len() != 2 {
+            return self.clone();
+        }
+
+        let mut chars = code.chars();
+        let (physics, band) = match (chars.next(), chars.next()) {
+            (Some(physics), Some(band)) => (physics, band),
+            _ => return self.clone(),
+        };
+
+        // V2 'P' code is a pseudo range on precise code
+        let precise = physics == 'P';
+        let physics = if precise { 'C' } else { physics };
+
+        let constellation = if constellation.is_sbas() {
+            Constellation::SBAS
+        } else {
+            constellation
+        };
+
+        let attribute = match (constellation, band) {
+            (Constellation::GPS, '1') => {
+                if precise {
+                    'W'
+                } else {
+                    'C'
+                }
+            },
+            (Constellation::GPS, '2') => {
+                if physics == 'C' && !precise {
+                    'X'
+                } else {
+                    'W'
+                }
+            },
+            (Constellation::Glonass, '1') | (Constellation::Glonass, '2') => {
+                if precise || (band == '2' && physics != 'C') {
+                    'P'
+                } else {
+                    'C'
+                }
+            },
+            (Constellation::BeiDou, '2') | (Constellation::BeiDou, '7') => 'I',
+            (Constellation::BeiDou, '6') => 'I',
+            (Constellation::SBAS, '1') | (Constellation::QZSS, '1') => 'C',
+            _ => 'X',
+        };
+
+        let code = format!("{}{}{}", physics, band, attribute);
+
+        match self {
+            Self::PhaseRange(_) => Self::PhaseRange(code),
+            Self::Doppler(_) => Self::Doppler(code),
+            Self::SSI(_) => Self::SSI(code),
+            _ => Self::PseudoRange(code),
+        }
+    }
+
     /// Tries to convert into [Carrier] frequency.
     pub fn to_carrier(&self, c: Constellation) -> Result<Carrier, Error> {
         Carrier::from_observable(c, self)
     }
 
+    /// Returns the wavelength (in meters) of the [Carrier] this [Observable]
+    /// is modulated onto. Glonass being FDMA, you should provide the
+    /// frequency channel of the satellite (-7..=6) to obtain the exact
+    /// wavelength: otherwise, the central (channel 0) frequency is used.
+    /// The channel number is ignored for all other [Constellation]s.
+    pub fn wavelength_m(
+        &self,
+        constellation: Constellation,
+        glonass_channel: Option<i8>,
+    ) -> Result<f64, Error> {
+        let carrier = self.to_carrier(constellation)?;
+        let carrier = match glonass_channel {
+            Some(channel) if constellation == Constellation::Glonass => {
+                carrier.with_glonass_offset(channel)
+            },
+            _ => carrier,
+        };
+        Ok(carrier.wavelength())
+    }
+
     /// Tries to create a Pseudo Range [Observable] from
     /// provided signal frequency in MHz and provided [Constellation].
     /// This requires a 1kHz accuracy on given frequency.
@@ -519,6 +639,8 @@ impl std::str::FromStr for Observable {
                         Ok(Self::Power(content.to_string()))
                     } else if content.starts_with('D') {
                         Ok(Self::Doppler(content.to_string()))
+                    } else if content.starts_with('X') {
+                        Ok(Self::ChannelNumber(content.to_string()))
                     } else {
                         Err(ParsingError::UnknownObservable)
                     }
@@ -568,6 +690,7 @@ mod test {
         assert!(Observable::from_str("S2").unwrap().is_ssi_observable());
         assert!(Observable::from_str("S1P").unwrap().is_ssi_observable());
         assert!(Observable::from_str("S1W").unwrap().is_ssi_observable());
+        assert!(Observable::from_str("X1").unwrap().is_channel_number());
     }
     #[test]
     fn test_observable() {
@@ -645,6 +768,76 @@ mod test {
         assert_eq!(Observable::Doppler("C7X".to_string()).to_string(), "C7X",);
     }
 
+    #[test]
+    fn wavelength_m() {
+        let l1c = Observable::from_str("L1C").unwrap();
+        let lambda = l1c.wavelength_m(Constellation::GPS, None).unwrap();
+        assert!((lambda - 0.190_293_672).abs() < 1.0E-9);
+
+        // channel is only meaningful to Glonass
+        let lambda = l1c.wavelength_m(Constellation::GPS, Some(-3)).unwrap();
+        assert!((lambda - 0.190_293_672).abs() < 1.0E-9);
+
+        let lambda = l1c.wavelength_m(Constellation::Glonass, None).unwrap();
+        assert!((lambda - 299_792_458.0 / 1602.0E6).abs() < 1.0E-9);
+
+        let lambda = l1c.wavelength_m(Constellation::Glonass, Some(-3)).unwrap();
+        assert!((lambda - 299_792_458.0 / 1600.3125E6).abs() < 1.0E-9);
+
+        let l2p = Observable::from_str("L2P").unwrap();
+        let lambda = l2p.wavelength_m(Constellation::Glonass, Some(5)).unwrap();
+        assert!((lambda - 299_792_458.0 / 1248.1875E6).abs() < 1.0E-9);
+    }
+
+    #[test]
+    fn v2_to_v3_gps() {
+        for (v2, v3) in [
+            ("C1", "C1C"),
+            ("P1", "C1W"),
+            ("L1", "L1C"),
+            ("D1", "D1C"),
+            ("S1", "S1C"),
+            ("C2", "C2X"),
+            ("P2", "C2W"),
+            ("L2", "L2W"),
+            ("D2", "D2W"),
+            ("S2", "S2W"),
+            ("C5", "C5X"),
+            ("L5", "L5X"),
+            ("C1C", "C1C"),
+            ("L2L", "L2L"),
+        ] {
+            let v2 = Observable::from_str(v2).unwrap();
+            let v3 = Observable::from_str(v3).unwrap();
+            assert_eq!(v2.v2_to_v3(Constellation::GPS), v3, "GPS {}", v2);
+        }
+    }
+
+    #[test]
+    fn v2_to_v3_glonass() {
+        for (v2, v3) in [
+            ("C1", "C1C"),
+            ("P1", "C1P"),
+            ("L1", "L1C"),
+            ("D1", "D1C"),
+            ("S1", "S1C"),
+            ("C2", "C2C"),
+            ("P2", "C2P"),
+            ("L2", "L2P"),
+            ("D2", "D2P"),
+            ("S2", "S2P"),
+        ] {
+            let v2 = Observable::from_str(v2).unwrap();
+            let v3 = Observable::from_str(v3).unwrap();
+            assert_eq!(v2.v2_to_v3(Constellation::Glonass), v3, "Glonass {}", v2);
+        }
+
+        assert_eq!(
+            Observable::Temperature.v2_to_v3(Constellation::Glonass),
+            Observable::Temperature
+        );
+    }
+
     #[test]
     fn test_same_physics() {
         assert!(Observable::Temperature.same_physics(&Observable::Temperature));
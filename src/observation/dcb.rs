@@ -0,0 +1,46 @@
+//! Differential Code Biases (DCB)
+use crate::prelude::{Observable, SV};
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// [DcbSource] gathers satellite Differential Code Biases, typically
+/// extracted from an external product (DCB or Bias-SINEX file).
+/// Each bias is defined for one [SV] and a pair of code [Observable]s (OBS1, OBS2),
+/// as the difference of their respective hardware delays B(OBS1) - B(OBS2), in nanoseconds.
+/// Refer to [Rinex::apply_dcb_mut](crate::Rinex::apply_dcb_mut) to correct
+/// Observation RINEX with it.
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DcbSource {
+    /// Biases (in nanoseconds), indexed by ([SV], OBS1, OBS2)
+    pub biases: BTreeMap<(SV, Observable, Observable), f64>,
+}
+
+impl DcbSource {
+    /// Builds a new empty [DcbSource]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copies and returns [DcbSource] with one more bias B(OBS1) - B(OBS2),
+    /// expressed in nanoseconds, for this [SV].
+    pub fn with_bias(&self, sv: SV, obs1: Observable, obs2: Observable, bias_ns: f64) -> Self {
+        let mut s = self.clone();
+        s.biases.insert((sv, obs1, obs2), bias_ns);
+        s
+    }
+
+    /// Returns the bias (in nanoseconds) that applies to this [SV] and
+    /// code [Observable], with the reference [Observable] it is defined against.
+    /// You should only define one reference per [SV] and code, otherwise
+    /// the first one (in [Observable] order) is picked.
+    pub fn bias_ns(&self, sv: SV, observable: &Observable) -> Option<(&Observable, f64)> {
+        self.biases
+            .iter()
+            .find(|((bias_sv, obs1, _), _)| *bias_sv == sv && obs1 == observable)
+            .map(|((_, _, obs2), bias_ns)| (obs2, *bias_ns))
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{leap::Leap, observation::EpochFlag, parse_f64, prelude::GeodeticMarker};
+
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// [RecordEvent] describes the special records (header like lines)
+/// that follow an event epoch (flags 2 to 5) in the Observation record.
+/// They usually describe a modification of the setup, that applies
+/// to all following observations.
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecordEvent {
+    /// [EpochFlag] describing this event
+    pub flag: EpochFlag,
+    /// Possible new [GeodeticMarker] ("MARKER NAME" / "MARKER NUMBER")
+    pub geodetic_marker: Option<GeodeticMarker>,
+    /// Possible new approximate ECEF position, in meters ("APPROX POSITION XYZ")
+    pub rx_position: Option<(f64, f64, f64)>,
+    /// Possible new antenna eccentricities (height, eastern, northern)
+    /// in meters ("ANTENNA: DELTA H/E/N")
+    pub antenna_delta_hen: Option<(f64, f64, f64)>,
+    /// Possible new [Leap] second count ("LEAP SECONDS")
+    pub leap: Option<Leap>,
+    /// All special records, stored as is.
+    pub records: Vec<String>,
+}
+
+impl RecordEvent {
+    /// Interprets special records that followed an event
+    pub(crate) fn parse(flag: EpochFlag, records: &[String]) -> Self {
+        let mut event = Self {
+            flag,
+            records: records.to_vec(),
+            ..Default::default()
+        };
+
+        for record in records.iter() {
+            if record.len() < 60 {
+                continue;
+            }
+
+            let (content, marker) = record.split_at(60);
+            let marker = marker.trim();
+
+            if marker.eq("MARKER NAME") {
+                let name = content.split_at(20).0.trim();
+                event.geodetic_marker = Some(GeodeticMarker::default().with_name(name));
+            } else if marker.eq("MARKER NUMBER") {
+                let number = content.split_at(20).0.trim();
+                if let Some(ref mut geodetic_marker) = event.geodetic_marker {
+                    *geodetic_marker = geodetic_marker.with_number(number);
+                }
+            } else if marker.eq("APPROX POSITION XYZ") {
+                event.rx_position = Self::parse_triplet(content);
+            } else if marker.eq("ANTENNA: DELTA H/E/N") {
+                event.antenna_delta_hen = Self::parse_triplet(content);
+            } else if marker.eq("LEAP SECONDS") {
+                let leap = content.split_at(40).0.trim();
+                event.leap = Leap::from_str(leap).ok();
+            }
+        }
+
+        event
+    }
+
+    fn parse_triplet(content: &str) -> Option<(f64, f64, f64)> {
+        let mut items = content
+            .split_ascii_whitespace()
+            .filter_map(|item| parse_f64(item).ok());
+
+        let (x, y, z) = (items.next()?, items.next()?, items.next()?);
+        Some((x, y, z))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn antenna_delta_hen() {
+        let records = vec![
+            "        1.2500        0.0100       -0.0200                  ANTENNA: DELTA H/E/N"
+                .to_string(),
+        ];
+
+        let event = RecordEvent::parse(EpochFlag::HeaderInformationFollows, &records);
+        assert_eq!(event.flag, EpochFlag::HeaderInformationFollows);
+        assert_eq!(event.antenna_delta_hen, Some((1.25, 0.01, -0.02)));
+        assert!(event.geodetic_marker.is_none());
+        assert!(event.rx_position.is_none());
+        assert!(event.leap.is_none());
+        assert_eq!(event.records, records);
+    }
+
+    #[test]
+    fn leap_seconds() {
+        let records = vec![
+            "    18                                                      LEAP SECONDS".to_string(),
+        ];
+
+        let event = RecordEvent::parse(EpochFlag::HeaderInformationFollows, &records);
+        assert_eq!(event.leap.map(|leap| leap.leap), Some(18));
+    }
+}
@@ -36,6 +36,19 @@ impl EpochFlag {
     pub fn is_ok(self) -> bool {
         self == Self::Ok
     }
+
+    /// Returns true if this flag describes a special event
+    /// (flags 2 to 5). Such epochs do not contain signal observations
+    /// but are followed by special records (header like lines).
+    pub fn is_event(self) -> bool {
+        matches!(
+            self,
+            Self::AntennaBeingMoved
+                | Self::NewSiteOccupation
+                | Self::HeaderInformationFollows
+                | Self::ExternalEvent
+        )
+    }
 }
 
 impl FromStr for EpochFlag {
@@ -96,6 +109,16 @@ mod test {
         assert!(EpochFlag::from_str("7").is_err());
     }
     #[test]
+    fn events() {
+        assert!(!EpochFlag::Ok.is_event());
+        assert!(!EpochFlag::PowerFailure.is_event());
+        assert!(EpochFlag::AntennaBeingMoved.is_event());
+        assert!(EpochFlag::NewSiteOccupation.is_event());
+        assert!(EpochFlag::HeaderInformationFollows.is_event());
+        assert!(EpochFlag::ExternalEvent.is_event());
+        assert!(!EpochFlag::CycleSlip.is_event());
+    }
+    #[test]
     fn to_str() {
         assert_eq!(format!("{}", EpochFlag::Ok), "0");
         assert_eq!(format!("{}", EpochFlag::PowerFailure), "1");
@@ -3,7 +3,7 @@ use crate::{
     epoch::format as epoch_format,
     error::FormattingError,
     observation::{HeaderFields, ObsKey, Observations},
-    prelude::{Constellation, RinexType, SV},
+    prelude::{Constellation, FormattingOptions, RinexType, SV},
 };
 
 use itertools::Itertools;
@@ -19,6 +19,24 @@ impl Observations {
         header: &HeaderFields,
         w: &mut BufWriter<W>,
     ) -> Result<(), FormattingError> {
+        self.format_with_options(v2, key, header, &FormattingOptions::default(), w)
+    }
+
+    /// Format [Observations] with custom [FormattingOptions].
+    pub fn format_with_options<W: Write>(
+        &self,
+        v2: bool,
+        key: &ObsKey,
+        header: &HeaderFields,
+        options: &FormattingOptions,
+        w: &mut BufWriter<W>,
+    ) -> Result<(), FormattingError> {
+        let decimals = options.observation_decimals;
+
+        if key.flag.is_event() {
+            return self.format_event(w, v2, key);
+        }
+
         let sv_list = self
             .signals
             .iter()
@@ -30,10 +48,45 @@ impl Observations {
         let numsat = sv_list.len();
 
         if v2 {
-            self.format_v2(w, key, &header, &sv_list, numsat)
+            self.format_v2(w, key, &header, &sv_list, numsat, decimals)
+        } else {
+            self.format_v3(w, key, &header, &sv_list, numsat, decimals)
+        }
+    }
+
+    /// Formats special event, which is the epoch description
+    /// followed by the special records.
+    fn format_event<W: Write>(
+        &self,
+        w: &mut BufWriter<W>,
+        v2: bool,
+        key: &ObsKey,
+    ) -> Result<(), FormattingError> {
+        let numrec = self.events.len();
+
+        if v2 {
+            writeln!(
+                w,
+                " {}  {} {:2}",
+                epoch_format(key.epoch, RinexType::ObservationData, 2),
+                key.flag,
+                numrec,
+            )?;
         } else {
-            self.format_v3(w, key, &header, &sv_list, numsat)
+            writeln!(
+                w,
+                "> {}  {} {:2}",
+                epoch_format(key.epoch, RinexType::ObservationData, 3),
+                key.flag,
+                numrec,
+            )?;
         }
+
+        for event in self.events.iter() {
+            writeln!(w, "{}", event)?;
+        }
+
+        Ok(())
     }
 
     /// Formats [Observations] according to RINEXv2 standards.
@@ -44,6 +97,7 @@ impl Observations {
         header: &HeaderFields,
         sv_list: &[SV],
         numsat: usize,
+        decimals: usize,
     ) -> Result<(), FormattingError> {
         let observables = &header.codes;
 
@@ -83,7 +137,7 @@ impl Observations {
                     .filter(|sig| &sig.sv == sv && &sig.observable == observable)
                     .reduce(|k, _| k)
                 {
-                    write!(w, "{:14.3}", observation.value)?;
+                    write!(w, "{:14.decimals$}", observation.value)?;
 
                     if let Some(lli) = observation.lli {
                         write!(w, "{:x}", lli)?;
@@ -169,6 +223,7 @@ impl Observations {
         header: &HeaderFields,
         sv_list: &[SV],
         numsat: usize,
+        decimals: usize,
     ) -> Result<(), FormattingError> {
         const BLANKING: &str = "                ";
 
@@ -235,7 +290,7 @@ impl Observations {
                         .filter(|sig| sig.sv == *sv && sig.observable == *observable)
                         .reduce(|k, _| k)
                     {
-                        write!(w, "{:14.3}", observation.value)?;
+                        write!(w, "{:14.decimals$}", observation.value)?;
 
                         if let Some(lli) = &observation.lli {
                             write!(w, "{}", lli.bits())?;
@@ -315,6 +370,7 @@ mod test {
 
         let obs = Observations {
             clock: None,
+            events: Vec::new(),
             signals: vec![
                 SignalObservation {
                     value: 1.0,
@@ -408,6 +464,7 @@ mod test {
 
         let obs = Observations {
             clock: None,
+            events: Vec::new(),
             signals: vec![
                 SignalObservation {
                     value: 1.0,
@@ -573,6 +630,7 @@ mod test {
 
         let obs = Observations {
             clock: None,
+            events: Vec::new(),
             signals: vec![
                 SignalObservation {
                     value: 1.0,
@@ -689,6 +747,7 @@ mod test {
 
         let mut obs = Observations {
             clock: None,
+            events: Vec::new(),
             signals: Vec::new(),
         };
 
@@ -773,6 +832,7 @@ mod test {
 
         let mut obs = Observations {
             clock: None,
+            events: Vec::new(),
             signals: Vec::new(),
         };
 
@@ -836,6 +896,7 @@ mod test {
 
         let mut obs = Observations {
             clock: None,
+            events: Vec::new(),
             signals: Vec::new(),
         };
 
@@ -2,6 +2,7 @@
 
 use crate::{
     epoch::epoch_decompose as epoch_decomposition,
+    fmt_rinex,
     hatanaka::CRINEX,
     prelude::{Constellation, Epoch, FormattingError, Observable, TimeScale},
 };
@@ -88,6 +89,14 @@ impl HeaderFields {
             _ => self.format_v3_observables(w)?,
         }
 
+        if self.clock_offset_applied {
+            writeln!(
+                w,
+                "{}",
+                fmt_rinex(&format!("{:6}", 1), "RCV CLOCK OFFS APPL")
+            )?;
+        }
+
         //TODO scaling
         //TODO DCBs
         Ok(())
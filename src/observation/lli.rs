@@ -1,6 +1,10 @@
 //! Lost of Lock Indication (LLI) for phase tracking
 use bitflags::bitflags;
 
+use std::collections::BTreeMap;
+
+use crate::prelude::Constellation;
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -21,3 +25,65 @@ bitflags! {
         const UNDER_ANTI_SPOOFING = 0x04;
     }
 }
+
+/// [LliCounts] counts the occurrences of each [LliFlags] bit.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LliCounts {
+    /// Number of [LliFlags::LOCK_LOSS] occurrences
+    pub lock_loss: usize,
+    /// Number of [LliFlags::HALF_CYCLE_SLIP] occurrences
+    pub half_cycle_slip: usize,
+    /// Number of [LliFlags::UNDER_ANTI_SPOOFING] occurrences
+    pub under_anti_spoofing: usize,
+}
+
+impl LliCounts {
+    /// Increments the counters that this [LliFlags] describes.
+    pub(crate) fn add(&mut self, lli: LliFlags) {
+        if lli.intersects(LliFlags::LOCK_LOSS) {
+            self.lock_loss += 1;
+        }
+        if lli.intersects(LliFlags::HALF_CYCLE_SLIP) {
+            self.half_cycle_slip += 1;
+        }
+        if lli.intersects(LliFlags::UNDER_ANTI_SPOOFING) {
+            self.under_anti_spoofing += 1;
+        }
+    }
+}
+
+impl std::ops::Add for LliCounts {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            lock_loss: self.lock_loss + rhs.lock_loss,
+            half_cycle_slip: self.half_cycle_slip + rhs.half_cycle_slip,
+            under_anti_spoofing: self.under_anti_spoofing + rhs.under_anti_spoofing,
+        }
+    }
+}
+
+/// [LliStats] summarizes the [LliFlags] found in an Observation RINEX,
+/// per [Constellation]. This is a quick data quality indicator.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LliStats {
+    /// [LliCounts] per [Constellation]
+    pub constellations: BTreeMap<Constellation, LliCounts>,
+}
+
+impl LliStats {
+    /// Returns [LliCounts] for this [Constellation], if at least one
+    /// observation was sampled for it.
+    pub fn constellation(&self, constellation: Constellation) -> Option<LliCounts> {
+        self.constellations.get(&constellation).copied()
+    }
+
+    /// Returns total [LliCounts], all constellations combined.
+    pub fn total(&self) -> LliCounts {
+        self.constellations
+            .values()
+            .fold(LliCounts::default(), |acc, counts| acc + *counts)
+    }
+}
@@ -10,7 +10,65 @@ use qc_traits::{FilterItem, MaskFilter, MaskOperand};
 
 use std::str::FromStr;
 
-/// Applies [MaskFilter] to [Record]
+/// [ObservableSNRMask] applies an [SNR] condition to a single [Observable],
+/// while other signals are preserved regardless of their [SNR].
+/// For example, drop C1C below 30 dB/Hz but keep all L1C observations.
+/// Signals of this [Observable] that do not come with an [SNR] are dropped.
+/// Event epochs (flags 2 to 5) are preserved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObservableSNRMask {
+    /// [Observable] this mask applies to
+    pub observable: Observable,
+    /// [SNR] reference
+    pub snr: SNR,
+    /// [MaskOperand] to compare signals [SNR] to the reference
+    pub operand: MaskOperand,
+}
+
+impl ObservableSNRMask {
+    /// Builds a new [ObservableSNRMask].
+    pub fn new(observable: Observable, snr: SNR, operand: MaskOperand) -> Self {
+        Self {
+            observable,
+            snr,
+            operand,
+        }
+    }
+
+    /// Returns true if this [SNR] passes this mask.
+    fn retains(&self, snr: SNR) -> bool {
+        match self.operand {
+            MaskOperand::Equals => snr == self.snr,
+            MaskOperand::NotEquals => snr != self.snr,
+            MaskOperand::GreaterEquals => snr >= self.snr,
+            MaskOperand::GreaterThan => snr > self.snr,
+            MaskOperand::LowerEquals => snr <= self.snr,
+            MaskOperand::LowerThan => snr < self.snr,
+        }
+    }
+}
+
+/// Applies [ObservableSNRMask] to [Record]
+pub fn observable_snr_mask_mut(rec: &mut Record, mask: &ObservableSNRMask) {
+    rec.retain(|_, obs| {
+        obs.signals.retain(|sig| {
+            if sig.observable == mask.observable {
+                if let Some(snr) = sig.snr {
+                    mask.retains(snr)
+                } else {
+                    false // no SNR: drop out
+                }
+            } else {
+                true
+            }
+        });
+        !obs.signals.is_empty() || !obs.events.is_empty()
+    });
+}
+
+/// Applies [MaskFilter] to [Record].
+/// Event epochs (flags 2 to 5) do not contain signals: they are preserved
+/// by signal masks, and only [FilterItem::EpochItem] masks may drop them.
 pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
     match mask.operand {
         MaskOperand::Equals => match &mask.item {
@@ -30,13 +88,13 @@ pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
                             constells.contains(&sig.sv.constellation)
                         }
                     });
-                    !obs.signals.is_empty()
+                    !obs.signals.is_empty() || !obs.events.is_empty()
                 });
             },
             FilterItem::SvItem(items) => {
                 rec.retain(|_, obs| {
                     obs.signals.retain(|sig| items.contains(&sig.sv));
-                    !obs.signals.is_empty()
+                    !obs.signals.is_empty() || !obs.events.is_empty()
                 });
             },
             FilterItem::SNRItem(filter) => {
@@ -49,7 +107,7 @@ pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
                             false // no SNR: drop out
                         }
                     });
-                    !obs.signals.is_empty()
+                    !obs.signals.is_empty() || !obs.events.is_empty()
                 });
             },
             FilterItem::ComplexItem(filter) => {
@@ -69,7 +127,7 @@ pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
                     rec.retain(|_, obs| {
                         obs.signals
                             .retain(|sig| observables.contains(&sig.observable));
-                        !obs.signals.is_empty()
+                        !obs.signals.is_empty() || !obs.events.is_empty()
                     });
                 }
             },
@@ -83,13 +141,13 @@ pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
                 rec.retain(|_, obs| {
                     obs.signals
                         .retain(|sig| !constells.contains(&sig.sv.constellation));
-                    !obs.signals.is_empty()
+                    !obs.signals.is_empty() || !obs.events.is_empty()
                 });
             },
             FilterItem::SvItem(items) => {
                 rec.retain(|_, obs| {
                     obs.signals.retain(|sig| !items.contains(&sig.sv));
-                    !obs.signals.is_empty()
+                    !obs.signals.is_empty() || !obs.events.is_empty()
                 });
             },
             FilterItem::ComplexItem(filter) => {
@@ -109,7 +167,7 @@ pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
                     rec.retain(|_, obs| {
                         obs.signals
                             .retain(|sig| !observables.contains(&sig.observable));
-                        !obs.signals.is_empty()
+                        !obs.signals.is_empty() || !obs.events.is_empty()
                     });
                 }
             },
@@ -128,7 +186,7 @@ pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
                         }
                         retained
                     });
-                    !obs.signals.is_empty()
+                    !obs.signals.is_empty() || !obs.events.is_empty()
                 });
             },
             FilterItem::SNRItem(filter) => {
@@ -141,7 +199,7 @@ pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
                             false // no SNR: drop out
                         }
                     });
-                    !obs.signals.is_empty()
+                    !obs.signals.is_empty() || !obs.events.is_empty()
                 });
             },
             _ => {},
@@ -159,7 +217,7 @@ pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
                         }
                         retained
                     });
-                    !obs.signals.is_empty()
+                    !obs.signals.is_empty() || !obs.events.is_empty()
                 });
             },
             FilterItem::SNRItem(filter) => {
@@ -172,7 +230,7 @@ pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
                             false // no SNR: drop out
                         }
                     });
-                    !obs.signals.is_empty()
+                    !obs.signals.is_empty() || !obs.events.is_empty()
                 });
             },
             _ => {},
@@ -190,7 +248,7 @@ pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
                         }
                         retained
                     });
-                    !obs.signals.is_empty()
+                    !obs.signals.is_empty() || !obs.events.is_empty()
                 });
             },
             FilterItem::SNRItem(filter) => {
@@ -203,7 +261,7 @@ pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
                             false // no SNR: drop out
                         }
                     });
-                    !obs.signals.is_empty()
+                    !obs.signals.is_empty() || !obs.events.is_empty()
                 });
             },
             _ => {},
@@ -221,7 +279,7 @@ pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
                         }
                         retained
                     });
-                    !obs.signals.is_empty()
+                    !obs.signals.is_empty() || !obs.events.is_empty()
                 });
             },
             FilterItem::SNRItem(filter) => {
@@ -234,7 +292,7 @@ pub fn mask_mut(rec: &mut Record, mask: &MaskFilter) {
                             false // no SNR: drop out
                         }
                     });
-                    !obs.signals.is_empty()
+                    !obs.signals.is_empty() || !obs.events.is_empty()
                 });
             },
             _ => {},
@@ -1,5 +1,7 @@
 //! Observation RINEX module
 mod clock;
+mod dcb;
+mod event;
 mod flag;
 mod formatting; // formatter
 mod header;
@@ -8,6 +10,7 @@ mod parsing; // parser
 mod rinex; // high level methods
 mod signal;
 mod snr;
+mod weight;
 
 #[cfg(feature = "obs")]
 pub use rinex::feature::{Combination, CombinationKey};
@@ -15,6 +18,9 @@ pub use rinex::feature::{Combination, CombinationKey};
 #[cfg(feature = "processing")]
 pub(crate) mod mask; // mask Trait implementation
 
+#[cfg(feature = "processing")]
+pub use mask::ObservableSNRMask;
+
 #[cfg(feature = "processing")]
 pub(crate) mod decim; // decim Trait implementation
 
@@ -25,13 +31,16 @@ pub(crate) mod repair; // repair Trait implementation
 use serde::{Deserialize, Serialize};
 
 pub use clock::ClockObservation;
+pub use dcb::DcbSource;
+pub use event::RecordEvent;
 pub use flag::EpochFlag;
 pub use header::HeaderFields;
-pub use lli::LliFlags;
-pub use signal::SignalObservation;
+pub use lli::{LliCounts, LliFlags, LliStats};
+pub use signal::{ObservableUnit, SignalObservation};
 pub use snr::SNR;
+pub use weight::WeightModel;
 
-pub(crate) use parsing::{is_new_epoch, parse_epoch};
+pub(crate) use parsing::{is_new_epoch, is_truncated_epoch, parse_epoch};
 
 #[cfg(docsrs)]
 use crate::Bibliography;
@@ -40,6 +49,9 @@ use std::collections::BTreeMap;
 
 use crate::prelude::Epoch;
 
+#[cfg(doc)]
+use crate::prelude::{Observable, SV};
+
 /// [Observations] describes all the content an Observation Epoch
 /// indexed by [ObsKey] may contain.
 #[derive(Debug, Clone, PartialEq)]
@@ -53,6 +65,11 @@ pub struct Observations {
 
     /// List of [SignalObservation]s.
     pub signals: Vec<SignalObservation>,
+
+    /// Special records (header like lines) that follow an event
+    /// (flags 2 to 5) in the record, stored as is.
+    /// Always empty for standard sampling epochs.
+    pub events: Vec<String>,
 }
 
 impl Default for Observations {
@@ -60,6 +77,7 @@ impl Default for Observations {
         Self {
             clock: None,
             signals: Vec::with_capacity(16),
+            events: Vec::new(),
         }
     }
 }
@@ -81,6 +99,13 @@ impl Observations {
         s.clock = Some(clock);
         s
     }
+
+    /// Sorts [SignalObservation]s by [SV] then [Observable].
+    /// The sort is stable: duplicated signals keep their relative order.
+    pub(crate) fn normalize_signal_order_mut(&mut self) {
+        self.signals
+            .sort_by(|a, b| (a.sv, &a.observable).cmp(&(b.sv, &b.observable)));
+    }
 }
 
 /// [ObsKey] is used to Index [Observations] in the [Record] dataset.
@@ -5,7 +5,7 @@ use crate::{
         ClockObservation, EpochFlag, LliFlags, ObsKey, Observations, SignalObservation, SNR,
     },
     parse_f64,
-    prelude::{Constellation, Header, Observable, ParsingError, TimeScale, Version, SV},
+    prelude::{Constellation, Epoch, Header, Observable, ParsingError, TimeScale, Version, SV},
 };
 
 use std::{
@@ -55,12 +55,187 @@ pub fn is_new_epoch(line: &str, v: Version) -> bool {
     }
 }
 
+/// Returns true if this epoch content is incomplete: its descriptor was cut short,
+/// fewer lines follow than the descriptor announces, or its last signal line
+/// was cut in the middle of an observation, according to the observables
+/// that the [Header] declares for that constellation. This is typical of
+/// the final epoch of files that were logged in real-time.
+/// Epochs we cannot fully interpret are not considered truncated:
+/// [parse_epoch] is in charge of reporting them.
+pub fn is_truncated_epoch(header: &Header, content: &str) -> bool {
+    const MAX_V2_OBSERVABLES_LINE: usize = 5;
+
+    let mut lines = content.lines();
+
+    let mut line = match lines.next() {
+        Some(l) => l,
+        _ => return false,
+    };
+
+    if line.starts_with('>') {
+        line = line.split_at(1).1;
+    }
+
+    let mut offset: usize = 26; // Y d m h m secs
+    if header.version.major > 2 {
+        offset += 2;
+    }
+
+    let (flag, num_sat) = match (
+        line.get(offset..offset + 3),
+        line.get(offset + 3..offset + 6),
+    ) {
+        (Some(flag), Some(num_sat)) => (flag, num_sat),
+        _ => return true, // descriptor was cut
+    };
+
+    let flag = match EpochFlag::from_str(flag.trim()) {
+        Ok(flag) => flag,
+        Err(_) => return false,
+    };
+
+    let num_sat = match num_sat.trim().parse::<usize>() {
+        Ok(num_sat) => num_sat,
+        Err(_) => return false,
+    };
+
+    if flag.is_event() {
+        return lines.count() < num_sat;
+    }
+
+    let constellation_observables = |constellation: Constellation| {
+        let observables = &header.obs.as_ref()?.codes;
+        if constellation.is_sbas() {
+            observables.get(&Constellation::SBAS)
+        } else {
+            observables.get(&constellation)
+        }
+    };
+
+    if header.version.major > 2 {
+        let lines = lines
+            .filter(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>();
+
+        if lines.len() < num_sat {
+            return true;
+        }
+
+        if lines.len() > num_sat {
+            return false;
+        }
+
+        // last signal line may still be cut
+        let last = match lines.last() {
+            Some(last) => last,
+            None => return false,
+        };
+
+        let constellation = match last.get(0..3).map(SV::from_str) {
+            Some(Ok(sv)) => sv.constellation,
+            _ => return last.trim_end().len() < 3,
+        };
+
+        return match constellation_observables(constellation) {
+            Some(observables) => is_truncated_signal_line(last, 3, observables.len()),
+            None => false,
+        };
+    }
+
+    // V2: satellites description may wrap over several lines,
+    // then each satellite spans as many lines as its observables require.
+    if header.obs.is_none() {
+        return false;
+    }
+
+    let rem = line.get(offset + 6..).unwrap_or_default();
+
+    let systems_str = match v2_satellites_description(rem, &mut lines, num_sat) {
+        Some(systems_str) => systems_str,
+        None => return true,
+    };
+
+    let mut expected_lines = 0;
+    let mut last_line_observables = 0;
+
+    for nth in 0..num_sat {
+        let system = match systems_str.get(nth * 3..nth * 3 + 3) {
+            Some(system) => system.trim(),
+            None => return false,
+        };
+
+        let constellation = match SV::from_str(system) {
+            Ok(sv) => sv.constellation,
+            Err(_) => match header.constellation {
+                Some(Constellation::Mixed) | None => return false,
+                Some(constellation) => constellation,
+            },
+        };
+
+        match constellation_observables(constellation) {
+            Some(observables) => {
+                let num_lines = div_ceil(observables.len(), MAX_V2_OBSERVABLES_LINE);
+                expected_lines += num_lines;
+                last_line_observables =
+                    observables.len() - num_lines.saturating_sub(1) * MAX_V2_OBSERVABLES_LINE;
+            },
+            None => return false,
+        }
+    }
+
+    let lines = lines.collect::<Vec<_>>();
+
+    if lines.len() != expected_lines {
+        return lines.len() < expected_lines;
+    }
+
+    // last signal line may still be cut
+    match lines.last() {
+        Some(last) => is_truncated_signal_line(last, 0, last_line_observables),
+        None => false,
+    }
+}
+
+/// Returns true if this signal line was cut in the middle of an observation.
+/// F14.3 values are right aligned, so a complete observation always
+/// ends past its 14th column (LLI and SNR flags being optional):
+/// a line that was cut right after one of its observations cannot be told apart
+/// from a line that omits its trailing (blank) observations.
+/// Lines that describe more observations than `num_observables` are not truncated,
+/// [parse_epoch] is in charge of reporting them.
+/// ## Input
+/// - line: signal line
+/// - prefix: number of leading characters that do not describe observations
+/// (SV identifier in V3)
+/// - num_observables: number of observables this line may describe
+fn is_truncated_signal_line(line: &str, prefix: usize, num_observables: usize) -> bool {
+    const OBSERVABLE_F14_WIDTH: usize = 14;
+    const OBSERVABLE_WIDTH: usize = 16; // F14 +2 flags
+
+    let width = line.trim_end().len().saturating_sub(prefix);
+
+    if width == 0 {
+        return false;
+    }
+
+    let nth_observable = (width - 1) / OBSERVABLE_WIDTH;
+
+    if nth_observable >= num_observables {
+        return false;
+    }
+
+    (width - 1) % OBSERVABLE_WIDTH + 1 < OBSERVABLE_F14_WIDTH
+}
+
 /// Parses record entries from readable content
 /// ## Input
 ///   - header: reference to previously parsed [Header]
 ///   - content: readable content
 ///   - ts: [TimeScale] defined by [Header]
 ///   - observations: preallocated [Observations] for performance issue.
+///   - prev_epoch: previously parsed [Epoch], used by events that omit their timestamp.
+///   - skipped_lines: index (within this content) of signal lines that were skipped
+///   because they could not be interpreted.
 /// ## Output
 ///   - [ObsKey] record indexer
 pub fn parse_epoch(
@@ -68,6 +243,8 @@ pub fn parse_epoch(
     content: &str,
     ts: TimeScale,
     observations: &mut Observations,
+    prev_epoch: Option<Epoch>,
+    skipped_lines: &mut Vec<usize>,
 ) -> Result<ObsKey, ParsingError> {
     let mut lines = content.lines();
 
@@ -94,12 +271,22 @@ pub fn parse_epoch(
         line = line.split_at(1).1;
     }
 
+    if line.len() < offset + 6 {
+        return Err(ParsingError::TruncatedEpoch);
+    }
+
     let (date, rem) = line.split_at(offset);
-    let epoch = parse_epoch_in_timescale(date, ts)?;
 
     let (flag, rem) = rem.split_at(3);
     let flag = EpochFlag::from_str(flag.trim())?;
 
+    let epoch = if flag.is_event() && date.trim().is_empty() {
+        // events are allowed to omit the timestamp
+        prev_epoch.ok_or(ParsingError::ObsHardwareEvent)?
+    } else {
+        parse_epoch_in_timescale(date, ts)?
+    };
+
     let key = ObsKey { epoch, flag };
 
     let (num_sat, rem) = rem.split_at(3);
@@ -145,14 +332,21 @@ pub fn parse_epoch(
         }
     }
 
-    match flag {
-        EpochFlag::Ok | EpochFlag::PowerFailure | EpochFlag::CycleSlip => {
-            parse_observations(header, num_sat, rem, lines, &mut observations.signals)?;
-        },
-        _ => {
-            // Hardware events are not supported yet (coming soon)
-            return Err(ParsingError::ObsHardwareEvent);
-        },
+    if flag.is_event() {
+        // special records: number of lines to follow
+        // is described by the "numsat" field
+        for line in lines.take(num_sat as usize) {
+            observations.events.push(line.trim_end().to_string());
+        }
+    } else {
+        parse_observations(
+            header,
+            num_sat,
+            rem,
+            lines,
+            &mut observations.signals,
+            skipped_lines,
+        )?;
     }
 
     Ok(key)
@@ -164,6 +358,7 @@ fn parse_observations(
     rem: &str,
     mut lines: Lines<'_>,
     signals: &mut Vec<SignalObservation>,
+    skipped_lines: &mut Vec<usize>,
 ) -> Result<(), ParsingError> {
     // retrieve header specs
     let constellation = header.constellation;
@@ -172,46 +367,73 @@ fn parse_observations(
     let observables = &obs.codes;
 
     // V1 / V2 tedious case
-    let rem = rem.trim();
-    let remainder_len = rem.len();
-
     if header.version.major < 3 {
         // Sets the satellite systems description, which consits in
         //  - end of current line
         //  - possible following lines
+        let num_lines = lines.clone().count();
 
-        // starts with first line content
-        let end = (12 * 3).min(remainder_len);
-        let mut systems_str = rem[..end].to_string();
-        let mut systems_str_len = systems_str.len();
-
-        // grab following lines (if we need to)
-        while systems_str_len / 3 < num_sat.into() {
-            if let Some(line) = lines.next() {
-                let trimmed = line.trim();
-                systems_str_len += trimmed.len();
-                systems_str.push_str(trimmed);
-            } else {
-                return Err(ParsingError::BadV2SatellitesDescription);
-            }
-        }
+        let systems_str = v2_satellites_description(rem, &mut lines, num_sat as usize)
+            .ok_or(ParsingError::BadV2SatellitesDescription)?;
+
+        // epoch descriptor and its continuation lines
+        let first_line = 1 + num_lines - lines.clone().count();
 
-        let systems_str_len = systems_str.len();
         parse_signals_v2(
             &systems_str,
-            systems_str_len,
             constellation,
             observables,
             &mut lines,
             signals,
+            first_line,
+            skipped_lines,
         );
     } else {
-        parse_signals_v3(observables, lines, signals);
+        parse_signals_v3(observables, lines, signals, skipped_lines);
     }
 
     Ok(())
 }
 
+/// Gathers the V2 satellites description of an epoch: it starts on the epoch line
+/// (after the number of satellites) and wraps over as many continuation lines as needed,
+/// 12 satellites per line. Columns are preserved, so satellites with a blank system
+/// identifier (for example "  5", allowed in GPS files) remain aligned, and the
+/// clock offset that may follow is not mistaken for satellites.
+/// Returns None when content ends before all satellites were described.
+fn v2_satellites_description(rem: &str, lines: &mut Lines<'_>, num_sat: usize) -> Option<String> {
+    const MAX_SATELLITES_LINE: usize = 12;
+    const CONTINUATION_INDENT: usize = 32;
+
+    let mut description = rem
+        .get(..3 * MAX_SATELLITES_LINE)
+        .unwrap_or(rem)
+        .trim_end()
+        .to_string();
+
+    while description.len() / 3 < num_sat {
+        let line = lines.next()?;
+
+        let continuation = match line.get(..CONTINUATION_INDENT) {
+            Some(indent) if indent.trim().is_empty() => &line[CONTINUATION_INDENT..],
+            _ => line.trim_start(), // non standard indentation
+        };
+
+        let continuation = continuation
+            .get(..3 * MAX_SATELLITES_LINE)
+            .unwrap_or(continuation)
+            .trim_end();
+
+        if continuation.is_empty() {
+            return None;
+        }
+
+        description.push_str(continuation);
+    }
+
+    Some(description)
+}
+
 /// Parses all [SignalObservation]s as described by following V2 content.
 /// Old format is tedious:
 ///   - vehicle description is contained in first line
@@ -221,19 +443,24 @@ fn parse_observations(
 ///   - constellation: [Constellation] specs defined in [Header]
 ///   - observables: reference to [Observable]s specs defined in [Header]
 ///   - lines: remaining [Lines] Iterator via mutable reference
+///   - first_line: index of the first of these [Lines] (0 being the epoch descriptor)
+///   - skipped_lines: index of the lines that could not be interpreted
 fn parse_signals_v2(
     systems_str: &str,
-    systems_str_len: usize,
     head_constellation: Option<Constellation>,
     head_observables: &HashMap<Constellation, Vec<Observable>>,
     lines: &mut Lines<'_>,
     signals: &mut Vec<SignalObservation>,
+    first_line: usize,
+    skipped_lines: &mut Vec<usize>,
 ) {
     const SVNN_SIZE: usize = 3; // SVNN standard
     const MAX_OBSERVABLES_LINE: usize = 5; // max in a single line
     const OBSERVABLE_F14_WIDTH: usize = 14;
     const OBSERVABLE_WIDTH: usize = OBSERVABLE_F14_WIDTH + 2; // data +lli +snr +1separator
 
+    let systems_str_len = systems_str.len();
+
     // basic check that avoid entering the loop for nothing
     if systems_str_len < SVNN_SIZE {
         // does not look good (=rubbish first line)
@@ -243,6 +470,7 @@ fn parse_signals_v2(
     }
 
     let mut sv_ptr = 0;
+    let mut nth_line = first_line;
 
     // Process each SV sequentially
     while sv_ptr < systems_str_len {
@@ -284,6 +512,8 @@ fn parse_signals_v2(
                     None => return, // EOF
                 };
 
+                nth_line += 1;
+
                 let line_width = line.len();
                 let trimmed_len = line.trim().len();
 
@@ -294,6 +524,8 @@ fn parse_signals_v2(
 
                 let num_obs_this_line = div_ceil(line_width, OBSERVABLE_WIDTH);
                 let mut offset = 0;
+                let mut num_parsed = 0;
+                let mut num_corrupt = 0;
 
                 //#[cfg(feature = "log")]
                 //debug!(
@@ -330,7 +562,9 @@ fn parse_signals_v2(
                     }
 
                     let end = slice.len().min(OBSERVABLE_F14_WIDTH);
-                    if let Ok(value) = parse_f64(slice[..end].trim()) {
+                    let value = slice[..end].trim();
+
+                    if let Ok(value) = parse_f64(value) {
                         signals.push(SignalObservation {
                             sv,
                             snr,
@@ -338,12 +572,19 @@ fn parse_signals_v2(
                             value,
                             observable: observables[obs_ptr].clone(),
                         });
+                        num_parsed += 1;
+                    } else if !value.is_empty() {
+                        num_corrupt += 1;
                     }
 
                     obs_ptr += 1;
                     offset += OBSERVABLE_F14_WIDTH + 2;
                 }
 
+                if num_corrupt > 0 && num_parsed == 0 {
+                    skipped_lines.push(nth_line - 1);
+                }
+
                 if num_obs_this_line < MAX_OBSERVABLES_LINE {
                     obs_ptr += MAX_OBSERVABLES_LINE - num_obs_this_line;
                 }
@@ -356,10 +597,13 @@ fn parse_signals_v2(
 }
 
 /// Parses all [SignalObservation]s described by following [Lines].
+/// Lines that cannot be interpreted are skipped, their index (starting
+/// at 1, 0 being the epoch descriptor) is stored in skipped_lines.
 fn parse_signals_v3(
     head_observables: &HashMap<Constellation, Vec<Observable>>,
     lines: Lines<'_>,
     signals: &mut Vec<SignalObservation>,
+    skipped_lines: &mut Vec<usize>,
 ) {
     const SVNN_SIZE: usize = 3;
     const OBSERVABLE_F14_WIDTH: usize = 14;
@@ -368,14 +612,26 @@ fn parse_signals_v3(
     let mut sv; // single alloc
 
     // browse all lines
-    for line in lines {
+    for (nth, line) in lines.enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
         // identify SV
-        let sv_str = &line[0..SVNN_SIZE];
+        let sv_str = match line.get(0..SVNN_SIZE) {
+            Some(sv_str) => sv_str,
+            None => {
+                skipped_lines.push(nth + 1);
+                continue;
+            },
+        };
+
         match SV::from_str(sv_str) {
             Ok(found) => {
                 sv = found;
             },
             Err(_) => {
+                skipped_lines.push(nth + 1);
                 continue;
             },
         }
@@ -395,6 +651,8 @@ fn parse_signals_v3(
 
         let num_obs = line.len() / OBSERVABLE_WIDTH;
         let mut offset = SVNN_SIZE + 1;
+        let mut num_parsed = 0;
+        let mut num_corrupt = 0;
 
         for i in 0..num_obs {
             if i == observables.len() {
@@ -407,7 +665,8 @@ fn parse_signals_v3(
 
             let mut lli = Option::<LliFlags>::None;
 
-            if slice.len() > OBSERVABLE_F14_WIDTH {
+            // LLI and SNR flags may terminate the line
+            if slice.len() >= OBSERVABLE_F14_WIDTH {
                 let start = offset + OBSERVABLE_F14_WIDTH - 1;
                 let lli_slice = &line[start..start + 1];
                 match lli_slice.parse::<u8>() {
@@ -423,7 +682,7 @@ fn parse_signals_v3(
 
             let mut snr = Option::<SNR>::None;
 
-            if slice.len() > OBSERVABLE_F14_WIDTH + 1 {
+            if slice.len() > OBSERVABLE_F14_WIDTH {
                 let start = offset + OBSERVABLE_F14_WIDTH;
                 let snr_slice = &line[start..start + 1];
 
@@ -432,9 +691,12 @@ fn parse_signals_v3(
                 }
             }
 
-            let end = OBSERVABLE_F14_WIDTH.min(slice.len());
+            // F14 value is right before the LLI and SNR flags
+            let start = offset - 1;
+            let end = (start + OBSERVABLE_F14_WIDTH).min(line.len());
+            let value = line[start..end].trim();
 
-            if let Ok(value) = parse_f64(slice[..end].trim()) {
+            if let Ok(value) = parse_f64(value) {
                 signals.push(SignalObservation {
                     sv,
                     value,
@@ -442,10 +704,17 @@ fn parse_signals_v3(
                     snr,
                     observable: observables[i].clone(),
                 });
+                num_parsed += 1;
+            } else if !value.is_empty() {
+                num_corrupt += 1;
             }
 
             offset += OBSERVABLE_F14_WIDTH + 2;
         }
+
+        if num_corrupt > 0 && num_parsed == 0 {
+            skipped_lines.push(nth + 1);
+        }
     } //browse all lines
 }
 
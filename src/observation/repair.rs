@@ -15,7 +15,7 @@ fn repair_zero_mut(rec: &mut Record) {
                 true
             }
         });
-        !obs.signals.is_empty()
+        !obs.signals.is_empty() || !obs.events.is_empty()
     });
 }
 
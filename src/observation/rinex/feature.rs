@@ -1,9 +1,13 @@
 //! Feature dependent high level methods
 use crate::{
-    observation::{EpochFlag, LliFlags, ObsKey, SignalObservation},
-    prelude::{Carrier, Epoch, Observable, Rinex, SV},
+    constants::SPEED_OF_LIGHT_M_S,
+    observation::{DcbSource, EpochFlag, LliFlags, LliStats, ObsKey, SignalObservation, SNR},
+    prelude::{Carrier, Duration, Epoch, Observable, Rinex, SV},
 };
 
+#[cfg(feature = "nav")]
+use crate::observation::WeightModel;
+
 use itertools::Itertools;
 
 use std::collections::{BTreeMap, HashMap};
@@ -76,6 +80,30 @@ pub struct MultipathKey {
     pub rhs: Observable,
 }
 
+/// Returns the C/N0 (in dB-Hz) of this [SignalObservation], sampled
+/// among other `signals` of the same epoch. SSI observables are used as is,
+/// otherwise we prefer the SSI observable of the same carrier and code,
+/// and fall back to the [SNR] indication.
+fn signal_c_n0(sig: &SignalObservation, signals: &[SignalObservation]) -> Option<f64> {
+    let code = match &sig.observable {
+        Observable::SSI(_) => return Some(sig.value),
+        Observable::PhaseRange(code)
+        | Observable::PseudoRange(code)
+        | Observable::Doppler(code) => Some(code),
+        _ => None,
+    };
+
+    let explicit = code.and_then(|code| {
+        let counterpart = Observable::SSI(format!("S{}", &code[1..]));
+        signals
+            .iter()
+            .find(|ssi| ssi.sv == sig.sv && ssi.observable == counterpart)
+            .map(|ssi| ssi.value)
+    });
+
+    explicit.or(sig.snr.map(f64::from))
+}
+
 impl Rinex {
     /// Returns [Carrier] signals Iterator
     pub fn carrier_iter(&self) -> Box<dyn Iterator<Item = Carrier> + '_> {
@@ -180,6 +208,104 @@ impl Rinex {
         }))
     }
 
+    /// Phase range [Iterator], converted from carrier cycles to meters, for desired [SV]
+    /// and [Observable::PhaseRange]. The ambiguity is not resolved: this is simply
+    /// the measurement scaled by the carrier wavelength. For Glonass, the frequency channel
+    /// is picked up from the [Header](crate::prelude::Header), when defined.
+    /// Only epochs sampled in good conditions are proposed.
+    /// ## Inputs
+    /// - sv: target [SV]
+    /// - observable: target [Observable::PhaseRange]
+    /// ## Returns
+    /// - (Epoch, phase range (m)) for each sample. This is empty when the
+    /// [Observable] is not a phase measurement or its [Carrier] is not known.
+    pub fn phase_range_iter(
+        &self,
+        sv: SV,
+        observable: &Observable,
+    ) -> Box<dyn Iterator<Item = (Epoch, f64)> + '_> {
+        if !observable.is_phase_range_observable() {
+            return Box::new([].into_iter());
+        }
+
+        let glonass_channel = self.header.glo_channels.get(&sv).copied();
+
+        let lambda = match observable.wavelength_m(sv.constellation, glonass_channel) {
+            Ok(lambda) => lambda,
+            Err(_) => return Box::new([].into_iter()),
+        };
+
+        let observable = observable.clone();
+
+        Box::new(
+            self.phase_range_sampling_ok_iter()
+                .filter_map(move |(t, sig)| {
+                    if sig.sv == sv && sig.observable == observable {
+                        Some((t, sig.value * lambda))
+                    } else {
+                        None
+                    }
+                }),
+        )
+    }
+
+    /// Carrier phase arcs [Iterator], for desired [SV] and [Observable::PhaseRange].
+    /// The phase time series is segmented into continuous arcs: a new arc starts
+    /// on each cycle slip (lock loss, as indicated by the [LliFlags]).
+    /// Within each arc, the phase range (in meters) is expressed relative to the first
+    /// observation of that arc, which removes the integer ambiguity (constant over the arc).
+    /// This is the typical input of arc based processing.
+    /// Only epochs sampled in good conditions are proposed, like [Self::phase_range_iter].
+    /// ## Inputs
+    /// - sv: target [SV]
+    /// - observable: target [Observable::PhaseRange]
+    /// ## Returns
+    /// - one list of (Epoch, relative phase range (m)) per arc, in chronological order.
+    /// This is empty when the [Observable] is not a phase measurement or its [Carrier] is not known.
+    pub fn carrier_phase_arc_iter(
+        &self,
+        sv: SV,
+        observable: &Observable,
+    ) -> Box<dyn Iterator<Item = Vec<(Epoch, f64)>> + '_> {
+        if !observable.is_phase_range_observable() {
+            return Box::new([].into_iter());
+        }
+
+        let glonass_channel = self.header.glo_channels.get(&sv).copied();
+
+        let lambda = match observable.wavelength_m(sv.constellation, glonass_channel) {
+            Ok(lambda) => lambda,
+            Err(_) => return Box::new([].into_iter()),
+        };
+
+        let mut arcs = Vec::<Vec<(Epoch, f64)>>::new();
+        let mut arc_origin_m = 0.0;
+
+        for (t, sig) in self.phase_range_sampling_ok_iter() {
+            if sig.sv != sv || sig.observable != *observable {
+                continue;
+            }
+
+            let phase_m = sig.value * lambda;
+
+            let slip = match sig.lli {
+                Some(lli) => lli.intersects(LliFlags::LOCK_LOSS),
+                None => false,
+            };
+
+            if slip || arcs.is_empty() {
+                arc_origin_m = phase_m;
+                arcs.push(Vec::new());
+            }
+
+            if let Some(arc) = arcs.last_mut() {
+                arc.push((t, phase_m - arc_origin_m));
+            }
+        }
+
+        Box::new(arcs.into_iter())
+    }
+
     /// Returns Iterator over Phase Cycle slips events.
     pub fn phase_cycle_slip_events(
         &self,
@@ -216,6 +342,31 @@ impl Rinex {
         }))
     }
 
+    /// Summarizes all [LliFlags] found in this Observation [Rinex],
+    /// per constellation. This is a quick data quality indicator:
+    /// lock losses and half cycle slips affect phase tracking.
+    /// Constellations for which no flag was raised are still listed, with null counts.
+    pub fn lli_statistics(&self) -> LliStats {
+        let mut stats = LliStats::default();
+
+        if !self.is_observation_rinex() {
+            return stats;
+        }
+
+        for (_, sig) in self.signal_observations_iter() {
+            let counts = stats
+                .constellations
+                .entry(sig.sv.constellation)
+                .or_default();
+
+            if let Some(lli) = sig.lli {
+                counts.add(lli);
+            }
+        }
+
+        stats
+    }
+
     /// Copies and returns a new [Rinex] where [LliFlags] mask (and mask) was applied.
     /// This only impacts Observation RINEX.
     pub fn observation_phase_tracking_lli_masking(&self, mask: LliFlags) -> Self {
@@ -240,11 +391,202 @@ impl Rinex {
                         true
                     }
                 });
-                !v.signals.is_empty()
+                !v.signals.is_empty() || !v.events.is_empty()
             })
         }
     }
 
+    /// Copies and returns a new [Rinex] where satellite Differential Code Biases
+    /// were compensated. See [Self::apply_dcb_mut].
+    pub fn apply_dcb(&self, dcb_source: &DcbSource) -> Self {
+        let mut s = self.clone();
+        s.apply_dcb_mut(dcb_source);
+        s
+    }
+
+    /// Compensates the satellite Differential Code Biases described by [DcbSource].
+    /// Each pseudo range observation OBS1, for which a bias B(OBS1) - B(OBS2) is defined,
+    /// is corrected by this amount (converted to meters), so it now shares the hardware
+    /// delay of OBS2. This is typically used to align all codes to the reference
+    /// of the precise clock products. All other observations are left untouched.
+    /// This only impacts Observation RINEX.
+    pub fn apply_dcb_mut(&mut self, dcb_source: &DcbSource) {
+        let rec = match self.record.as_mut_obs() {
+            Some(rec) => rec,
+            None => return,
+        };
+
+        for (_, v) in rec.iter_mut() {
+            for sig in v.signals.iter_mut() {
+                if !sig.observable.is_pseudo_range_observable() {
+                    continue;
+                }
+
+                if let Some((_, bias_ns)) = dcb_source.bias_ns(sig.sv, &sig.observable) {
+                    sig.value -= bias_ns * 1.0E-9 * SPEED_OF_LIGHT_M_S;
+                }
+            }
+        }
+    }
+
+    /// Copies and returns a new [Rinex] where missing [SNR] indications
+    /// were derived from the C/N0 (SSI) observations. See [Self::derive_snr_mut].
+    pub fn derive_snr(&self) -> Self {
+        let mut s = self.clone();
+        s.derive_snr_mut();
+        s
+    }
+
+    /// Some receivers provide the C/N0 estimate (S observable) but leave
+    /// the SNR indicator blank. This derives the [SNR] of each signal observation,
+    /// from the S observable sampled on the same carrier and code, using the standard
+    /// RINEX mapping. Existing [SNR] indications are preserved.
+    /// This only impacts Observation RINEX, and allows SNR masking on such files.
+    pub fn derive_snr_mut(&mut self) {
+        let rec = match self.record.as_mut_obs() {
+            Some(rec) => rec,
+            None => return,
+        };
+
+        for (_, v) in rec.iter_mut() {
+            let ssi = v
+                .signals
+                .iter()
+                .filter(|sig| sig.observable.is_ssi_observable())
+                .map(|sig| (sig.sv, sig.observable.clone(), sig.value))
+                .collect::<Vec<_>>();
+
+            if ssi.is_empty() {
+                continue;
+            }
+
+            for sig in v.signals.iter_mut() {
+                if sig.snr.is_some() {
+                    continue;
+                }
+
+                let counterpart = match &sig.observable {
+                    Observable::PhaseRange(code)
+                    | Observable::PseudoRange(code)
+                    | Observable::Doppler(code) => Observable::SSI(format!("S{}", &code[1..])),
+                    _ => continue,
+                };
+
+                if let Some((_, _, c_n0)) = ssi
+                    .iter()
+                    .find(|(sv, observable, _)| *sv == sig.sv && *observable == counterpart)
+                {
+                    sig.snr = Some(SNR::from(*c_n0));
+                }
+            }
+        }
+    }
+
+    /// Exports this [Observable] as a dense (epoch × satellite) matrix, for numerical
+    /// pipelines (data science, machine learning..). Returns
+    /// - the epoch axis: each [Epoch] where at least one signal was observed, in chronological order
+    /// - the satellite axis: each [SV] that observed this [Observable], in ascending order
+    /// - the value grid, indexed as `grid[epoch][sv]`, where gaps are set to None.
+    pub fn to_observation_matrix(
+        &self,
+        observable: &Observable,
+    ) -> (Vec<Epoch>, Vec<SV>, Vec<Vec<Option<f64>>>) {
+        let rec = match self.record.as_obs() {
+            Some(rec) => rec,
+            None => return (Vec::new(), Vec::new(), Vec::new()),
+        };
+
+        let epochs = rec
+            .iter()
+            .filter_map(|(k, v)| {
+                if v.signals.is_empty() {
+                    None
+                } else {
+                    Some(k.epoch)
+                }
+            })
+            .unique()
+            .sorted()
+            .collect::<Vec<_>>();
+
+        let satellites = rec
+            .iter()
+            .flat_map(|(_, v)| v.signals.iter())
+            .filter_map(|sig| {
+                if sig.observable == *observable {
+                    Some(sig.sv)
+                } else {
+                    None
+                }
+            })
+            .unique()
+            .sorted()
+            .collect::<Vec<_>>();
+
+        let mut grid = vec![vec![None; satellites.len()]; epochs.len()];
+
+        for (k, v) in rec.iter() {
+            let row = match epochs.binary_search(&k.epoch) {
+                Ok(row) => row,
+                Err(_) => continue,
+            };
+
+            for sig in v.signals.iter() {
+                if sig.observable != *observable {
+                    continue;
+                }
+
+                if let Ok(col) = satellites.binary_search(&sig.sv) {
+                    grid[row][col] = Some(sig.value);
+                }
+            }
+        }
+
+        (epochs, satellites, grid)
+    }
+
+    /// Returns the mean and standard deviation of the C/N0 (in dB-Hz) of each [SV],
+    /// for this [Observable], over the entire record. This is a common metric
+    /// to qualify a site or an antenna installation.
+    /// - when [Observable] is a SSI observable (for example "S1C"),
+    ///   its values are used as is.
+    /// - otherwise, we use the explicit C/N0 measured on the same carrier and code
+    ///   (for example "S1C" for "L1C") when it exists, or the [SNR] indication
+    ///   of the signal observation. The latter is only a coarse (upper bound) estimate.
+    ///
+    /// Satellites for which no C/N0 could be determined are not listed.
+    /// This only applies to Observation RINEX.
+    pub fn snr_statistics_per_sv(&self, observable: &Observable) -> HashMap<SV, (f64, f64)> {
+        let mut samples = HashMap::<SV, Vec<f64>>::new();
+
+        let rec = match self.record.as_obs() {
+            Some(rec) => rec,
+            None => return HashMap::new(),
+        };
+
+        for (_, v) in rec.iter() {
+            for sig in v.signals.iter() {
+                if sig.observable != *observable {
+                    continue;
+                }
+
+                if let Some(c_n0) = signal_c_n0(sig, &v.signals) {
+                    samples.entry(sig.sv).or_default().push(c_n0);
+                }
+            }
+        }
+
+        samples
+            .into_iter()
+            .map(|(sv, values)| {
+                let n = values.len() as f64;
+                let mean = values.iter().sum::<f64>() / n;
+                let var = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+                (sv, (mean, var.sqrt()))
+            })
+            .collect()
+    }
+
     /// Returns an Iterator over [Epoch]s where abnormal sampling conditions were detected.
     /// Anomalies are described by the attached [EpochFlag] in each [ObsKey].
     pub fn epoch_anomalies(&self) -> Box<dyn Iterator<Item = &ObsKey> + '_> {
@@ -511,13 +853,241 @@ impl Rinex {
         }
         ret
     }
+
+    /// Between receivers Single Difference (SD) of the desired [Observable],
+    /// formed as `self - rhs`, for each common [SV] and [Epoch].
+    /// Usually `self` is the rover and `rhs` the base station.
+    /// The SD cancels out the satellite clock offset, and most of the propagation
+    /// biases on short baselines.
+    /// ## Inputs
+    /// - rhs: remote Observation [Rinex]
+    /// - observable: [Observable] to differentiate
+    /// - tolerance: maximal [Duration] between two epochs to consider them synchronous,
+    /// for receivers with slightly offset sampling.
+    /// ## Returns
+    /// - SD indexed by `self` [Epoch] and [SV]. Only sane epochs are considered.
+    pub fn single_difference(
+        &self,
+        rhs: &Self,
+        observable: &Observable,
+        tolerance: Duration,
+    ) -> BTreeMap<(Epoch, SV), f64> {
+        let mut ret = BTreeMap::new();
+
+        let mut remote = BTreeMap::<Epoch, HashMap<SV, f64>>::new();
+
+        for (k, v) in rhs.signal_observations_iter() {
+            if k.flag.is_ok() && v.observable == *observable {
+                remote.entry(k.epoch).or_default().insert(v.sv, v.value);
+            }
+        }
+
+        for (k, v) in self.signal_observations_iter() {
+            if !k.flag.is_ok() || v.observable != *observable {
+                continue;
+            }
+
+            // closest remote epoch, within tolerance
+            let closest = remote
+                .range(k.epoch - tolerance..=k.epoch + tolerance)
+                .min_by_key(|(t, _)| (**t - k.epoch).abs());
+
+            if let Some((_, values)) = closest {
+                if let Some(reference) = values.get(&v.sv) {
+                    ret.insert((k.epoch, v.sv), v.value - reference);
+                }
+            }
+        }
+
+        ret
+    }
+
+    /// Between receivers and between satellites Double Difference (DD)
+    /// of the desired [Observable]. DDs are formed from the [Self::single_difference],
+    /// with respect to a reference [SV] of the same [Constellation](crate::prelude::Constellation).
+    /// The DD cancels out both receiver and satellite clock offsets,
+    /// and is the basis of carrier phase (RTK) positioning.
+    /// ## Inputs
+    /// - rhs: remote Observation [Rinex]
+    /// - reference_sv: reference [SV]. When not defined, we select the
+    /// highest satellite in the sky of each constellation, at each epoch.
+    /// This requires the `nav` feature, a Navigation [Rinex] and the receiver
+    /// position to be defined in this [Rinex] header.
+    /// - observable: [Observable] to differentiate
+    /// - tolerance: maximal [Duration] between two epochs to consider them synchronous.
+    /// - nav: Navigation [Rinex], only required by automatic reference selection.
+    /// ## Returns
+    /// - DD indexed by `self` [Epoch] and [SV]. The reference [SV] is not part of the results.
+    pub fn double_difference(
+        &self,
+        rhs: &Self,
+        reference_sv: Option<SV>,
+        observable: &Observable,
+        tolerance: Duration,
+        nav: Option<&Self>,
+    ) -> BTreeMap<(Epoch, SV), f64> {
+        let mut ret = BTreeMap::new();
+
+        let mut single_differences = BTreeMap::<Epoch, Vec<(SV, f64)>>::new();
+
+        for ((t, sv), sd) in self.single_difference(rhs, observable, tolerance) {
+            single_differences.entry(t).or_default().push((sv, sd));
+        }
+
+        for (t, sds) in single_differences.iter() {
+            let constellations = sds.iter().map(|(sv, _)| sv.constellation).unique();
+
+            for constellation in constellations {
+                let reference = match reference_sv {
+                    Some(sv) if sv.constellation == constellation => Some(sv),
+                    Some(_) => None,
+                    None => {
+                        let candidates = sds
+                            .iter()
+                            .filter_map(|(sv, _)| {
+                                if sv.constellation == constellation {
+                                    Some(*sv)
+                                } else {
+                                    None
+                                }
+                            })
+                            .collect::<Vec<_>>();
+
+                        self.double_difference_reference(*t, &candidates, nav)
+                    },
+                };
+
+                let reference = match reference {
+                    Some(reference) => reference,
+                    None => continue,
+                };
+
+                let reference_sd = sds
+                    .iter()
+                    .find(|(sv, _)| *sv == reference)
+                    .map(|(_, sd)| *sd);
+
+                if let Some(reference_sd) = reference_sd {
+                    for (sv, sd) in sds.iter() {
+                        if sv.constellation == constellation && *sv != reference {
+                            ret.insert((*t, *sv), sd - reference_sd);
+                        }
+                    }
+                }
+            }
+        }
+
+        ret
+    }
+
+    /// Returns the weight of each signal observation, according to selected [WeightModel],
+    /// typically to build the weight matrix of a least squares resolution.
+    /// Higher weights describe more trustworthy observations.
+    /// ## Inputs
+    /// - nav: Navigation [Rinex], used to determine the elevation of each [SV]
+    /// - rx_position: receiver (latitude (degrees), longitude (degrees), altitude (meters))
+    /// - model: [WeightModel]
+    /// - max_iter: maximal number of iterations of the Kepler solver
+    ///
+    /// Only valid epochs are considered, and satellites below the horizon are discarded,
+    /// whatever the [WeightModel]. SSI observables are not weighted:
+    /// when using [WeightModel::Snr], they serve as the C/N0 estimate of the other observables.
+    /// Observations for which no C/N0 could be determined are not listed.
+    /// This only applies to Observation RINEX.
+    #[cfg(feature = "nav")]
+    pub fn observation_weights(
+        &self,
+        nav: &Self,
+        rx_position: (f64, f64, f64),
+        model: WeightModel,
+        max_iter: usize,
+    ) -> BTreeMap<(Epoch, SV, Observable), f64> {
+        let mut ret = BTreeMap::new();
+        let (rx_lat_deg, rx_lon_deg, rx_alt_m) = rx_position;
+
+        let rec = match self.record.as_obs() {
+            Some(rec) => rec,
+            None => return ret,
+        };
+
+        for (k, v) in rec.iter() {
+            if !k.flag.is_ok() {
+                continue;
+            }
+
+            let mut elevations = HashMap::<SV, Option<f64>>::new();
+
+            for sig in v.signals.iter() {
+                if sig.observable.is_ssi_observable() {
+                    continue;
+                }
+
+                let elevation_deg = *elevations.entry(sig.sv).or_insert_with(|| {
+                    let (_, elev_deg, _) = nav.sky_position(
+                        sig.sv, k.epoch, rx_lat_deg, rx_lon_deg, rx_alt_m, max_iter,
+                    )?;
+                    Some(elev_deg)
+                });
+
+                let elevation_deg = match elevation_deg {
+                    Some(elevation_deg) if elevation_deg > 0.0 => elevation_deg,
+                    _ => continue,
+                };
+
+                let weight = match model {
+                    WeightModel::Elevation => WeightModel::elevation_weight(elevation_deg),
+                    WeightModel::Snr => match signal_c_n0(sig, &v.signals) {
+                        Some(c_n0) => WeightModel::snr_weight(c_n0),
+                        None => continue,
+                    },
+                };
+
+                ret.insert((k.epoch, sig.sv, sig.observable.clone()), weight);
+            }
+        }
+
+        ret
+    }
+
+    /// Selects the highest [SV] in the sky, among provided candidates.
+    #[cfg(feature = "nav")]
+    fn double_difference_reference(
+        &self,
+        t: Epoch,
+        candidates: &[SV],
+        nav: Option<&Self>,
+    ) -> Option<SV> {
+        let nav = nav?;
+        let rx_ecef_m = self.header.rx_position?;
+
+        candidates
+            .iter()
+            .filter_map(|sv| {
+                let azelrange = nav.ecef_azimuth_elevation_range(*sv, t, rx_ecef_m)?;
+                Some((*sv, azelrange.elevation_deg))
+            })
+            .max_by(|(_, el_a), (_, el_b)| el_a.total_cmp(el_b))
+            .map(|(sv, _)| sv)
+    }
+
+    /// Automatic reference selection requires the `nav` feature.
+    #[cfg(not(feature = "nav"))]
+    fn double_difference_reference(
+        &self,
+        _t: Epoch,
+        _candidates: &[SV],
+        _nav: Option<&Self>,
+    ) -> Option<SV> {
+        None
+    }
 }
 
 #[cfg(test)]
 mod test {
 
     use super::Combination;
-    use crate::prelude::{Carrier, Rinex};
+    use crate::prelude::{Carrier, Duration, Observable, Rinex, SV};
+    use std::str::FromStr;
 
     #[test]
     fn gf_signal_combination() {
@@ -1184,4 +1754,92 @@ mod test {
         }
         assert_eq!(tests_passed, 1);
     }
+
+    #[test]
+    fn single_difference() {
+        let fullpath = format!("{}/data/OBS/V3/DUTH0630.22O", env!("CARGO_MANIFEST_DIR"));
+        let rinex = Rinex::from_file(&fullpath).unwrap();
+
+        let c1c = Observable::from_str("C1C").unwrap();
+        let num_c1c = rinex
+            .pseudo_range_observations_iter()
+            .filter(|(_, sig)| sig.observable == c1c)
+            .count();
+
+        // co-located receivers
+        let sd = rinex.single_difference(&rinex, &c1c, Duration::ZERO);
+        assert_eq!(sd.len(), num_c1c);
+
+        for (_, value) in sd.iter() {
+            assert_eq!(*value, 0.0);
+        }
+
+        // slightly offset receiver
+        let dt = Duration::from_milliseconds(100.0);
+        let mut remote = rinex.clone();
+
+        let offset_rec = remote
+            .record
+            .as_obs()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| {
+                let mut k = *k;
+                k.epoch += dt;
+                (k, v.clone())
+            })
+            .collect();
+
+        *remote.record.as_mut_obs().unwrap() = offset_rec;
+
+        let sd = rinex.single_difference(&remote, &c1c, Duration::from_seconds(1.0));
+        assert_eq!(sd.len(), num_c1c);
+
+        for ((t, _), value) in sd.iter() {
+            assert!(rinex.epoch_iter().any(|e| e == *t));
+            assert_eq!(*value, 0.0);
+        }
+
+        // tolerance too tight
+        let sd = rinex.single_difference(&remote, &c1c, Duration::from_milliseconds(10.0));
+        assert!(sd.is_empty());
+    }
+
+    #[test]
+    fn double_difference() {
+        let fullpath = format!("{}/data/OBS/V3/DUTH0630.22O", env!("CARGO_MANIFEST_DIR"));
+        let rinex = Rinex::from_file(&fullpath).unwrap();
+
+        let c1c = Observable::from_str("C1C").unwrap();
+        let g01 = SV::from_str("G01").unwrap();
+
+        // remote receiver, affected by a constant clock offset (in meters)
+        let clock_offset = 1234.5;
+        let mut remote = rinex.clone();
+
+        for (_, sig) in remote.signal_observations_iter_mut() {
+            sig.value += clock_offset;
+        }
+
+        let sd = rinex.single_difference(&remote, &c1c, Duration::ZERO);
+        assert!(!sd.is_empty());
+
+        for (_, value) in sd.iter() {
+            assert!((value + clock_offset).abs() < 1e-6);
+        }
+
+        // clock offset cancels out
+        let dd = rinex.double_difference(&remote, Some(g01), &c1c, Duration::ZERO, None);
+        assert!(!dd.is_empty());
+
+        for ((_, sv), value) in dd.iter() {
+            assert_ne!(*sv, g01, "reference should not be part of the results");
+            assert_eq!(sv.constellation, g01.constellation);
+            assert!(value.abs() < 1e-6, "{} residual clock offset {}", sv, value);
+        }
+
+        // no reference: no navigation context
+        let dd = rinex.double_difference(&remote, None, &c1c, Duration::ZERO, None);
+        assert!(dd.is_empty());
+    }
 }
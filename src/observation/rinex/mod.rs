@@ -1,7 +1,10 @@
 //! Observation specific high level methods
 use crate::{
-    observation::{ClockObservation, ObsKey, Observations, SignalObservation},
-    prelude::{Rinex, RinexType},
+    observation::{
+        ClockObservation, EpochFlag, HeaderFields, ObsKey, Observations, RecordEvent,
+        SignalObservation,
+    },
+    prelude::{Constellation, Duration, Epoch, Header, Rinex, RinexType, Version},
 };
 
 #[cfg(feature = "obs")]
@@ -11,6 +14,87 @@ pub(crate) mod feature; // feature dependent, high level methods
 use std::collections::btree_map::{Iter, IterMut, Keys};
 
 impl Rinex {
+    /// Builds an empty Observation [Rinex] of given [Version], using this
+    /// [Header] as template (receiver, antenna, marker, etc..).
+    /// Populate it with [Self::add_signal_observations]: the observable codes
+    /// and the time of first and last observation are then managed for you,
+    /// so the result can be formatted to a valid file.
+    /// This is typically used to log observations decoded from real-time streams.
+    pub fn new_observation(version: Version, header: &Header) -> Self {
+        let mut header = header
+            .with_type(RinexType::ObservationData)
+            .with_version(version);
+
+        if header.obs.is_none() {
+            header.obs = Some(HeaderFields::default());
+        }
+
+        Self::basic_obs().with_header(header)
+    }
+
+    /// Stores these [SignalObservation]s, sampled at [Epoch] in "OK" conditions,
+    /// in this Observation [Rinex]. Signals are appended to the existing content, when
+    /// this [Epoch] already exists. The [Header] is updated so that it describes
+    /// all the observables and the time frame that this [Rinex] contains.
+    /// This has no effect if this is not an Observation [Rinex].
+    pub fn add_signal_observations(&mut self, epoch: Epoch, signals: Vec<SignalObservation>) {
+        let rec = match self.record.as_mut_obs() {
+            Some(rec) => rec,
+            None => return,
+        };
+
+        let obs_header = self.header.obs.get_or_insert_with(HeaderFields::default);
+
+        for signal in signals.iter() {
+            let constellation = if signal.sv.constellation.is_sbas() {
+                Constellation::SBAS
+            } else {
+                signal.sv.constellation
+            };
+
+            *obs_header = obs_header.with_observable_code(constellation, signal.observable.clone());
+
+            self.header.constellation = match self.header.constellation {
+                None => Some(constellation),
+                Some(c) if c == constellation => Some(c),
+                Some(_) => Some(Constellation::Mixed),
+            };
+        }
+
+        if obs_header.timeof_first_obs.map_or(true, |t| epoch < t) {
+            obs_header.timeof_first_obs = Some(epoch);
+        }
+
+        if obs_header.timeof_last_obs.map_or(true, |t| epoch > t) {
+            obs_header.timeof_last_obs = Some(epoch);
+        }
+
+        rec.entry(ObsKey::new_ok(epoch))
+            .or_default()
+            .signals
+            .extend(signals);
+    }
+
+    /// Copies and returns a new [Rinex] where signals are sorted within each epoch.
+    /// See [Self::normalize_signal_order_mut].
+    pub fn normalize_signal_order(&self) -> Self {
+        let mut s = self.clone();
+        s.normalize_signal_order_mut();
+        s
+    }
+
+    /// Sorts the [SignalObservation]s of each epoch by satellite, then by observable,
+    /// so that the record (and its formatted output) does not depend on the order
+    /// in which the signals were collected. Merging Observation RINEX does this for you.
+    /// This has no effect if this is not an Observation [Rinex].
+    pub fn normalize_signal_order_mut(&mut self) {
+        if let Some(rec) = self.record.as_mut_obs() {
+            for v in rec.values_mut() {
+                v.normalize_signal_order_mut();
+            }
+        }
+    }
+
     /// Returns true if [Rinex] format is [RinexType::ObservationData].
     /// ```
     /// use rinex::prelude::Rinex;
@@ -51,6 +135,91 @@ impl Rinex {
         }
     }
 
+    /// Returns the [SignalObservation]s sampled at this exact [Epoch], if it exists.
+    /// Special events are not sampling epochs and are ignored.
+    /// Refer to [Self::nearest_epoch] to locate the closest sampling [Epoch] first.
+    /// ```
+    /// use rinex::prelude::{Duration, Rinex};
+    /// let rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// let t0 = rinex.first_epoch().unwrap();
+    /// let signals = rinex.observations_at(t0).unwrap();
+    /// assert!(!signals.is_empty());
+    ///
+    /// // correlate an external event to the observations
+    /// let event = t0 + Duration::from_seconds(1.0);
+    /// let t = rinex.nearest_epoch(event).unwrap();
+    /// assert_eq!(t, t0);
+    /// ```
+    pub fn observations_at(&self, epoch: Epoch) -> Option<&[SignalObservation]> {
+        let rec = self.record.as_obs()?;
+        rec.range(ObsKey::new_ok(epoch)..)
+            .take_while(|(k, _)| k.epoch == epoch)
+            .find(|(k, _)| !k.flag.is_event())
+            .map(|(_, v)| v.signals.as_slice())
+    }
+
+    /// Returns the sampling [Epoch] that is the closest to this [Epoch].
+    /// When exactly in between two sampling epochs, the earliest is returned.
+    /// Special events are not sampling epochs and are ignored.
+    /// Returns None on empty or non Observation [Rinex].
+    pub fn nearest_epoch(&self, epoch: Epoch) -> Option<Epoch> {
+        let rec = self.record.as_obs()?;
+
+        let before = rec
+            .range(
+                ..=ObsKey {
+                    epoch,
+                    flag: EpochFlag::CycleSlip,
+                },
+            )
+            .rev()
+            .find(|(k, _)| !k.flag.is_event())
+            .map(|(k, _)| k.epoch);
+
+        let after = rec
+            .range(ObsKey::new_ok(epoch)..)
+            .find(|(k, _)| !k.flag.is_event())
+            .map(|(k, _)| k.epoch);
+
+        match (before, after) {
+            (Some(before), Some(after)) => {
+                if after - epoch < epoch - before {
+                    Some(after)
+                } else {
+                    Some(before)
+                }
+            },
+            (before, after) => before.or(after),
+        }
+    }
+
+    /// Returns ([Epoch], [EpochFlag]) Iterator, describing the sampling conditions
+    /// of each epoch, including special events. Empty for non Observation [Rinex].
+    pub fn epoch_flags_iter(&self) -> Box<dyn Iterator<Item = (Epoch, EpochFlag)> + '_> {
+        Box::new(
+            self.record
+                .as_obs()
+                .into_iter()
+                .flat_map(|rec| rec.keys().map(|k| (k.epoch, k.flag))),
+        )
+    }
+
+    /// Returns special events Iterator, described by [RecordEvent]s,
+    /// sorted by [ObsKey]. Special events (flags 2 to 5) are usually followed
+    /// by header like lines, that update the setup (antenna, marker..)
+    /// for all following observations.
+    pub fn record_events_iter(&self) -> Box<dyn Iterator<Item = (ObsKey, RecordEvent)> + '_> {
+        Box::new(
+            self.record
+                .as_obs()
+                .into_iter()
+                .flat_map(|rec| rec.iter())
+                .filter(|(k, _)| k.flag.is_event())
+                .map(|(k, v)| (*k, RecordEvent::parse(k.flag, &v.events))),
+        )
+    }
+
     /// Returns [SignalObservation]s Iterator.
     pub fn signal_observations_iter(
         &self,
@@ -81,6 +250,58 @@ impl Rinex {
         }))
     }
 
+    /// Returns true if the receiver clock offset was already compensated for
+    /// ("RCV CLOCK OFFS APPL" header flag): epochs, pseudo ranges and phases
+    /// of this record are then expressed with respect to a corrected clock.
+    /// In that case, [Self::receiver_clock_iter] reports the offset that the receiver applied,
+    /// which must not be applied a second time. Otherwise, the offset is only reported
+    /// and you are responsible for applying it. Returns false for other RINEX formats.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// let rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// if !rinex.receiver_clock_is_applied() {
+    ///     for (epoch, offset) in rinex.receiver_clock_iter() {
+    ///         // correct this epoch
+    ///         let _corrected = epoch - offset;
+    ///     }
+    /// }
+    /// ```
+    pub fn receiver_clock_is_applied(&self) -> bool {
+        self.header
+            .obs
+            .as_ref()
+            .map(|obs| obs.clock_offset_applied)
+            .unwrap_or_default()
+    }
+
+    /// Returns the receiver clock offset [Iterator], as ([Epoch], [Duration]) to the
+    /// [TimeScale](crate::prelude::TimeScale) of this file. Only epochs for which
+    /// the receiver reported its clock state are listed.
+    /// Refer to [Self::receiver_clock_is_applied] to determine whether this
+    /// offset was already applied to the record.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// let rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// for (epoch, offset) in rinex.receiver_clock_iter() {
+    ///     let offset_ns = offset.total_nanoseconds();
+    /// }
+    /// ```
+    pub fn receiver_clock_iter(&self) -> Box<dyn Iterator<Item = (Epoch, Duration)> + '_> {
+        Box::new(
+            self.record
+                .as_obs()
+                .into_iter()
+                .flat_map(|rec| rec.iter())
+                .filter_map(|(k, v)| {
+                    let clock = v.clock?;
+                    Some((k.epoch, Duration::from_seconds(clock.offset_s)))
+                }),
+        )
+    }
+
     /// Mutable [ClockObservation] Iterator
     pub fn clock_observations_iter_mut(
         &mut self,
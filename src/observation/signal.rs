@@ -1,9 +1,41 @@
 use crate::{
     observation::LliFlags,
     observation::SNR,
-    prelude::{Observable, SV},
+    prelude::{Constellation, Observable, SV},
 };
 
+/// [ObservableUnit] is the physical unit of a [SignalObservation] value,
+/// as defined by its [Observable].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ObservableUnit {
+    /// Pseudo range, in meters
+    Meters,
+    /// Carrier phase, in cycles of the carrier
+    Cycles,
+    /// Doppler shift, in Hertz
+    Hertz,
+    /// Signal strength, in dB-Hz
+    DecibelHertz,
+    /// Received power, in dBm
+    DecibelMilliwatt,
+    /// Channel number and other dimensionless values
+    Unitless,
+}
+
+impl std::fmt::Display for ObservableUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Meters => write!(f, "m"),
+            Self::Cycles => write!(f, "cycles"),
+            Self::Hertz => write!(f, "Hz"),
+            Self::DecibelHertz => write!(f, "dB-Hz"),
+            Self::DecibelMilliwatt => write!(f, "dBm"),
+            Self::Unitless => write!(f, ""),
+        }
+    }
+}
+
 /// [SignalObservation] is the result of sampling one signal at
 /// one point in time, by a GNSS receiver.
 #[derive(Default, Clone, Debug, PartialEq, PartialOrd)]
@@ -37,6 +69,50 @@ impl SignalObservation {
         }
     }
 
+    /// Returns the [ObservableUnit] of [Self::value], which depends on [Observable].
+    pub fn unit(&self) -> ObservableUnit {
+        match self.observable {
+            Observable::PseudoRange(_) => ObservableUnit::Meters,
+            Observable::PhaseRange(_) => ObservableUnit::Cycles,
+            Observable::Doppler(_) => ObservableUnit::Hertz,
+            Observable::SSI(_) => ObservableUnit::DecibelHertz,
+            Observable::Power(_) => ObservableUnit::DecibelMilliwatt,
+            _ => ObservableUnit::Unitless,
+        }
+    }
+
+    /// Returns [Self::value] expressed in meters, for range observations only:
+    /// pseudo ranges are returned as is, carrier phases are scaled by the wavelength
+    /// of their carrier, for this [Constellation] (usually [SV] constellation).
+    /// Glonass being FDMA, the frequency channel of the satellite (-7..=6) is required
+    /// to scale carrier phases, it is usually described in the header
+    /// (see [Header::glo_channels](crate::prelude::Header::glo_channels)).
+    /// The channel number is ignored for all other [Constellation]s.
+    /// Returns None for all other [Observable]s, when the carrier cannot be determined,
+    /// or for Glonass carrier phases when the channel is unknown.
+    pub fn value_in_meters(
+        &self,
+        constellation: Constellation,
+        glonass_channel: Option<i8>,
+    ) -> Option<f64> {
+        match self.observable {
+            Observable::PseudoRange(_) => Some(self.value),
+            Observable::PhaseRange(_) => {
+                if constellation == Constellation::Glonass && glonass_channel.is_none() {
+                    return None;
+                }
+
+                let lambda = self
+                    .observable
+                    .wavelength_m(constellation, glonass_channel)
+                    .ok()?;
+
+                Some(self.value * lambda)
+            },
+            _ => None,
+        }
+    }
+
     /// Copy and define [SNR]
     pub fn with_snr(&self, snr: SNR) -> Self {
         let mut s = self.clone();
@@ -74,3 +150,97 @@ impl SignalObservation {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ObservableUnit, SignalObservation};
+    use crate::prelude::{Constellation, Observable, SV};
+    use std::str::FromStr;
+
+    fn signal(observable: &str, value: f64) -> SignalObservation {
+        let sv = SV::from_str("G01").unwrap();
+        let observable = Observable::from_str(observable).unwrap();
+        SignalObservation::new(sv, observable, value)
+    }
+
+    #[test]
+    fn pseudo_range_unit() {
+        let signal = signal("C1C", 20243517.560);
+        assert_eq!(signal.unit(), ObservableUnit::Meters);
+        assert_eq!(
+            signal.value_in_meters(Constellation::GPS, None),
+            Some(20243517.560)
+        );
+    }
+
+    #[test]
+    fn phase_range_unit() {
+        let signal = signal("L1C", 106380022.528);
+        assert_eq!(signal.unit(), ObservableUnit::Cycles);
+
+        let lambda = 299_792_458.0 / 1575.42E6;
+        let meters = signal.value_in_meters(Constellation::GPS, None).unwrap();
+        assert!((meters - 106380022.528 * lambda).abs() < 1.0E-3);
+    }
+
+    #[test]
+    fn glonass_phase_range_unit() {
+        let signal = signal("L1C", 106380022.528);
+
+        // FDMA: channel is required
+        assert!(signal
+            .value_in_meters(Constellation::Glonass, None)
+            .is_none());
+
+        // GLONASS ICD: 1602 MHz + k * 562.5 kHz
+        for (channel, freq_hz) in [(-7, 1598.0625E6), (0, 1602.0E6), (6, 1605.375E6)] {
+            let lambda = 299_792_458.0 / freq_hz;
+
+            let meters = signal
+                .value_in_meters(Constellation::Glonass, Some(channel))
+                .unwrap();
+
+            assert!(
+                (meters - 106380022.528 * lambda).abs() < 1.0E-3,
+                "channel {}: {} m",
+                channel,
+                meters
+            );
+        }
+
+        // pseudo ranges do not depend on the channel
+        let signal = signal("C1C", 20243517.560);
+        assert_eq!(
+            signal.value_in_meters(Constellation::Glonass, None),
+            Some(20243517.560)
+        );
+    }
+
+    #[test]
+    fn doppler_unit() {
+        let signal = signal("D1C", -1234.567);
+        assert_eq!(signal.unit(), ObservableUnit::Hertz);
+        assert!(signal.value_in_meters(Constellation::GPS, None).is_none());
+    }
+
+    #[test]
+    fn ssi_unit() {
+        let signal = signal("S1C", 45.0);
+        assert_eq!(signal.unit(), ObservableUnit::DecibelHertz);
+        assert!(signal.value_in_meters(Constellation::GPS, None).is_none());
+    }
+
+    #[test]
+    fn power_unit() {
+        let signal = signal("W1C", -130.0);
+        assert_eq!(signal.unit(), ObservableUnit::DecibelMilliwatt);
+        assert!(signal.value_in_meters(Constellation::GPS, None).is_none());
+    }
+
+    #[test]
+    fn channel_number_unit() {
+        let signal = signal("X1", 3.0);
+        assert_eq!(signal.unit(), ObservableUnit::Unitless);
+        assert!(signal.value_in_meters(Constellation::GPS, None).is_none());
+    }
+}
@@ -0,0 +1,27 @@
+//! Observation weighting models
+
+/// [WeightModel] describes how we weight each signal observation,
+/// typically prior a least squares resolution.
+/// Refer to [Rinex::observation_weights](crate::Rinex::observation_weights).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum WeightModel {
+    /// w = sin²(elevation): satellites close to the horizon are strongly
+    /// deweighted, because they suffer from multipath and atmospheric delays.
+    #[default]
+    Elevation,
+    /// w = 1/σ², where σ² = 10^(-C/N0/10) is the signal variance
+    /// deduced from the carrier to noise density ratio (in dB.Hz).
+    Snr,
+}
+
+impl WeightModel {
+    /// Returns weight for this elevation (in degrees).
+    pub fn elevation_weight(elevation_deg: f64) -> f64 {
+        elevation_deg.to_radians().sin().powi(2)
+    }
+
+    /// Returns weight for this carrier to noise density ratio (in dB.Hz).
+    pub fn snr_weight(c_n0_dbhz: f64) -> f64 {
+        10.0_f64.powf(c_n0_dbhz / 10.0)
+    }
+}
@@ -0,0 +1,124 @@
+//! Parsing options
+use std::io::Read;
+
+/// [DuplicateEpochPolicy] describes how we resolve Observation RINEX epochs
+/// that appear several times in the record (same timestamp and same flag),
+/// which typically results from receiver glitches.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum DuplicateEpochPolicy {
+    /// Latest occurrence replaces previous content entirely.
+    #[default]
+    Overwrite,
+    /// Occurrences are combined into a single epoch. When the same signal
+    /// is described several times, the latest occurrence wins.
+    Merge,
+    /// Duplicate epochs are considered invalid and the parsing process
+    /// aborts with [ParsingError::DuplicateEpoch](crate::prelude::ParsingError::DuplicateEpoch).
+    Error,
+}
+
+/// [ParsingOptions] allow customizing the behavior of
+/// [Rinex::parse_with_options](crate::Rinex::parse_with_options),
+/// when dealing with real-world data that does not strictly follow the specifications.
+/// The default options preserve the historical behavior.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct ParsingOptions {
+    /// [DuplicateEpochPolicy] to apply to Observation RINEX
+    pub duplicate_epochs: DuplicateEpochPolicy,
+}
+
+impl ParsingOptions {
+    /// Copies and returns [ParsingOptions] with desired [DuplicateEpochPolicy].
+    pub fn with_duplicate_epoch_policy(&self, policy: DuplicateEpochPolicy) -> Self {
+        let mut s = *self;
+        s.duplicate_epochs = policy;
+        s
+    }
+}
+
+/// [LineEndingNormalizer] converts any line termination (`\r\n` Windows style,
+/// `\r` legacy Mac style, or `\n` Unix style) to `\n`, so our fixed-column
+/// parsers never see a CR byte, whatever the origin of the file.
+pub(crate) struct LineEndingNormalizer<R: Read> {
+    reader: R,
+    /// Last byte we streamed was a CR, which we already converted.
+    pending_cr: bool,
+}
+
+impl<R: Read> LineEndingNormalizer<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            pending_cr: false,
+        }
+    }
+}
+
+impl<R: Read> Read for LineEndingNormalizer<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let size = self.reader.read(buf)?;
+
+            if size == 0 {
+                return Ok(0);
+            }
+
+            // output is never longer than input: we can work in place
+            let mut wr_ptr = 0;
+
+            for rd_ptr in 0..size {
+                let byte = buf[rd_ptr];
+
+                if self.pending_cr {
+                    self.pending_cr = false;
+                    if byte == b'\n' {
+                        continue; // CRLF: already converted
+                    }
+                }
+
+                if byte == b'\r' {
+                    self.pending_cr = true;
+                    buf[wr_ptr] = b'\n';
+                } else {
+                    buf[wr_ptr] = byte;
+                }
+
+                wr_ptr += 1;
+            }
+
+            // returning 0 would signal the end of stream
+            if wr_ptr > 0 {
+                return Ok(wr_ptr);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LineEndingNormalizer;
+    use std::io::Read;
+
+    #[test]
+    fn line_ending_normalization() {
+        for (content, expected) in [
+            ("ABC\nDEF\n", "ABC\nDEF\n"),
+            ("ABC\r\nDEF\r\n", "ABC\nDEF\n"),
+            ("ABC\rDEF\r", "ABC\nDEF\n"),
+            ("ABC\r\n\r\nDEF", "ABC\n\nDEF"),
+            ("ABC\r\rDEF\n", "ABC\n\nDEF\n"),
+        ] {
+            let mut normalizer = LineEndingNormalizer::new(content.as_bytes());
+            let mut normalized = String::new();
+            normalizer.read_to_string(&mut normalized).unwrap();
+            assert_eq!(normalized, expected, "failed for {:?}", content);
+        }
+
+        // CRLF split over two reads
+        let content = "ABC\r".as_bytes();
+        let mut normalizer = LineEndingNormalizer::new(content.chain("\nDEF\r".as_bytes()));
+        let mut normalized = String::new();
+        normalizer.read_to_string(&mut normalized).unwrap();
+        assert_eq!(normalized, "ABC\nDEF\n");
+    }
+}
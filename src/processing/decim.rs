@@ -25,5 +25,7 @@ impl Decimate for Rinex {
         } else if let Some(rec) = self.record.as_mut_meteo() {
             meteo_decim_mut(rec, f)
         }
+
+        self.recompute_time_bounds_mut();
     }
 }
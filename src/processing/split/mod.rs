@@ -49,20 +49,26 @@ impl Split for Rinex {
         //  split comments timewise
         //  implement Split for Header
         //  implement Split for production attributes
-        (
-            Rinex {
-                record: r0,
-                header: h0,
-                production: p0,
-                comments: self.comments.clone(),
-            },
-            Rinex {
-                record: r1,
-                header: h1,
-                production: p1,
-                comments: self.comments.clone(),
-            },
-        )
+        let mut r0 = Rinex {
+            record: r0,
+            header: h0,
+            production: p0,
+            crinex_stats: None,
+            comments: self.comments.clone(),
+        };
+
+        let mut r1 = Rinex {
+            record: r1,
+            header: h1,
+            production: p1,
+            crinex_stats: None,
+            comments: self.comments.clone(),
+        };
+
+        r0.recompute_time_bounds_mut();
+        r1.recompute_time_bounds_mut();
+
+        (r0, r1)
     }
 
     fn split_mut(&mut self, t: Epoch) -> Self {
@@ -87,12 +93,18 @@ impl Split for Rinex {
         //  split comments timewise
         //  implement Split for Header ?
         //  implement Split for production attributes ?
-        Self {
+        let mut rhs = Self {
             record,
             header: self.header.clone(),
             comments: self.comments.clone(),
             production: self.production.clone(),
-        }
+            crinex_stats: None,
+        };
+
+        self.recompute_time_bounds_mut();
+        rhs.recompute_time_bounds_mut();
+
+        rhs
     }
 
     fn split_even_dt(&self, dt: Duration) -> Vec<Self> {
@@ -127,11 +139,16 @@ impl Split for Rinex {
 
         records
             .iter()
-            .map(|rec| Rinex {
-                header: self.header.clone(),
-                comments: self.comments.clone(),
-                production: self.production.clone(),
-                record: rec.clone(),
+            .map(|rec| {
+                let mut rinex = Rinex {
+                    header: self.header.clone(),
+                    comments: self.comments.clone(),
+                    production: self.production.clone(),
+                    crinex_stats: None,
+                    record: rec.clone(),
+                };
+                rinex.recompute_time_bounds_mut();
+                rinex
             })
             .collect()
     }
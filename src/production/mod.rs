@@ -64,6 +64,8 @@ pub struct ProductionAttributes {
     /// Optional Regional code present in IONEX file names.
     /// 'G' means Global (World wide) TEC map(s).
     pub region: Option<char>,
+    /// True if the file was (or should be) Gzip compressed.
+    pub gzip_compressed: bool,
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -174,6 +176,7 @@ impl std::str::FromStr for ProductionAttributes {
                     _ => None,
                 },
                 v3_details: None,
+                gzip_compressed: fname.ends_with(".GZ"),
             })
         } else {
             let offset = fname.find('.').unwrap_or(0);
@@ -206,6 +209,7 @@ impl std::str::FromStr for ProductionAttributes {
                         .map_err(|_| Error::NonStandardFileName)?
                 },
                 region: None, // IONEX files only use a short format
+                gzip_compressed: fname.ends_with(".GZ"),
                 v3_details: Some(DetailedProductionAttributes {
                     batch,
                     country: fname[6..9].to_string(),
@@ -17,11 +17,26 @@ use antex::merge_mut as merge_mut_antex;
 use clock::merge_mut as merge_mut_clock;
 use meteo::merge_mut as merge_mut_meteo;
 use nav::merge_mut as merge_mut_nav;
-use obs::merge_mut as merge_mut_obs;
+use obs::{gap_fill_mut as gap_fill_mut_obs, merge_mut as merge_mut_obs};
 
 use std::cmp::PartialEq;
 use std::collections::HashMap;
 
+/// [MergePolicy] describes how [Rinex::merge_all] resolves the epochs
+/// that several Observation files have in common.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Epochs are retained as is, from the file with highest priority
+    /// that contains them. Files with lower priority only fill the gaps (missing epochs).
+    /// Data from different receivers are therefore never mixed within one epoch.
+    #[default]
+    GapFilling,
+    /// Epochs are combined: files with lower priority may complete the
+    /// [SignalObservation](crate::observation::SignalObservation)s of a common epoch.
+    /// When several files provide the same signal, the file with highest priority wins.
+    Combine,
+}
+
 /// Appends given vector into self.
 pub(crate) fn merge_mut_vec<T: Clone>(lhs: &mut Vec<T>, rhs: &Vec<T>) {
     for item in rhs {
@@ -107,6 +122,51 @@ pub(crate) fn merge_time_of_last_obs(lhs: &mut Option<Epoch>, rhs: &Option<Epoch
     }
 }
 
+impl Rinex {
+    /// Merges all these Observation [Rinex] at once, to obtain a single
+    /// and gap-minimized Observation [Rinex]. This generalizes [Merge::merge]
+    /// to many files, where the order of the files is their priority:
+    /// the first file has the highest priority and its data always wins on overlap,
+    /// which allows preferring one receiver over the others.
+    /// ## Inputs
+    /// - files: Observation [Rinex] files, by decreasing priority
+    /// - policy: [MergePolicy] applied on common epochs
+    /// ## Returns
+    /// - merged [Rinex]
+    /// - [MergeError::Other] if no files were provided
+    /// - [MergeError::FileTypeMismatch] if one file is not an Observation [Rinex]
+    pub fn merge_all(files: &[Rinex], policy: MergePolicy) -> Result<Rinex, MergeError> {
+        let (first, others) = files.split_first().ok_or(MergeError::Other)?;
+
+        if !first.is_observation_rinex() {
+            return Err(MergeError::FileTypeMismatch);
+        }
+
+        let mut merged = first.clone();
+
+        for rhs in others.iter() {
+            match policy {
+                MergePolicy::Combine => merged.merge_mut(rhs)?,
+                MergePolicy::GapFilling => {
+                    merged.header.merge_mut(&rhs.header)?;
+                    merged.production.merge_mut(&rhs.production)?;
+
+                    let lhs = merged
+                        .record
+                        .as_mut_obs()
+                        .ok_or(MergeError::FileTypeMismatch)?;
+
+                    let rhs = rhs.record.as_obs().ok_or(MergeError::FileTypeMismatch)?;
+
+                    gap_fill_mut_obs(lhs, rhs)?;
+                },
+            }
+        }
+
+        Ok(merged)
+    }
+}
+
 impl Merge for Rinex {
     fn merge(&self, rhs: &Self) -> Result<Self, MergeError> {
         let mut lhs = self.clone();
@@ -1,5 +1,7 @@
 use crate::{observation::Record, prelude::qc::MergeError};
 
+use std::collections::BTreeSet;
+
 pub fn merge_mut(rec: &mut Record, rhs: &Record) -> Result<(), MergeError> {
     for (k, rhs) in rhs.iter() {
         if let Some(lhs) = rec.get_mut(k) {
@@ -29,5 +31,23 @@ pub fn merge_mut(rec: &mut Record, rhs: &Record) -> Result<(), MergeError> {
             rec.insert(*k, rhs.clone());
         }
     }
+
+    for v in rec.values_mut() {
+        v.normalize_signal_order_mut();
+    }
+
+    Ok(())
+}
+
+/// Inserts the epochs of rhs that lhs does not contain (whatever their flag),
+/// existing epochs are preserved as is.
+pub fn gap_fill_mut(rec: &mut Record, rhs: &Record) -> Result<(), MergeError> {
+    let epochs = rec.keys().map(|k| k.epoch).collect::<BTreeSet<_>>();
+
+    for (k, rhs) in rhs.iter() {
+        if !epochs.contains(&k.epoch) {
+            rec.insert(*k, rhs.clone());
+        }
+    }
     Ok(())
 }
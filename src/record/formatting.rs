@@ -2,17 +2,28 @@ use crate::{
     hatanaka::Compressor,
     meteo::format as format_meteo_observations,
     navigation::format as format_navigation,
-    prelude::{FormattingError, Header},
+    prelude::{FormattingError, FormattingOptions, Header},
     record::Record,
 };
 
 use std::io::{BufWriter, Write};
 
 impl Record {
+    /// Formats [Record] according to standard specifications.
     pub fn format<W: Write>(
         &self,
         w: &mut BufWriter<W>,
         header: &Header,
+    ) -> Result<(), FormattingError> {
+        self.format_with_options(w, header, &FormattingOptions::default())
+    }
+
+    /// Formats [Record] with custom [FormattingOptions].
+    pub fn format_with_options<W: Write>(
+        &self,
+        w: &mut BufWriter<W>,
+        header: &Header,
+        options: &FormattingOptions,
     ) -> Result<(), FormattingError> {
         let version_major = header.version.major;
 
@@ -30,7 +41,7 @@ impl Record {
                 compressor.format(w, &rec, header)?;
             } else {
                 for (k, v) in rec.iter() {
-                    v.format(version_major == 2, k, &header, w)?;
+                    v.format_with_options(version_major == 2, k, &header, options, w)?;
                 }
             }
 
@@ -38,7 +49,7 @@ impl Record {
         } else if let Some(rec) = self.as_meteo() {
             format_meteo_observations(w, rec, header)
         } else if let Some(rec) = self.as_nav() {
-            format_navigation(w, rec, header)
+            format_navigation(w, rec, header, options)
         } else {
             Ok(())
         }
@@ -7,7 +7,7 @@ use crate::{
         record::{is_new_epoch as is_new_clock_epoch, parse_epoch as parse_clock_epoch},
         ClockKey, ClockProfile, Record as ClockRecord,
     },
-    hatanaka::DecompressorExpert,
+    hatanaka::{CrinexStats, DecompressorExpert},
     is_rinex_comment,
     meteo::{
         is_new_epoch as is_new_meteo_epoch, parse_epoch as parse_meteo_epoch, Record as MeteoRecord,
@@ -17,10 +17,13 @@ use crate::{
     },
     observation::Observations,
     observation::{
-        is_new_epoch as is_new_observation_epoch, parse_epoch as parse_observation_epoch,
-        Record as ObservationRecord,
+        is_new_epoch as is_new_observation_epoch,
+        is_truncated_epoch as is_truncated_observation_epoch,
+        parse_epoch as parse_observation_epoch, Record as ObservationRecord,
+    },
+    prelude::{
+        DuplicateEpochPolicy, Epoch, Header, ParseWarning, ParsingError, ParsingOptions, TimeScale,
     },
-    prelude::{Epoch, Header, ParsingError, TimeScale},
     record::{Comments, Record},
     types::Type,
 };
@@ -34,6 +37,30 @@ use std::{
 #[cfg(feature = "log")]
 use log::error;
 
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Merges the [Observations] of a duplicate epoch into the existing ones.
+/// Signals that were already described are replaced by the latest occurrence.
+fn merge_duplicate_observations(existing: &mut Observations, duplicate: &Observations) {
+    if duplicate.clock.is_some() {
+        existing.clock = duplicate.clock;
+    }
+
+    for signal in duplicate.signals.iter() {
+        match existing
+            .signals
+            .iter_mut()
+            .find(|sig| sig.sv == signal.sv && sig.observable == signal.observable)
+        {
+            Some(sig) => *sig = signal.clone(),
+            None => existing.signals.push(signal.clone()),
+        }
+    }
+
+    existing.events.extend(duplicate.events.iter().cloned());
+}
+
 impl Record {
     /// Parses [Record] section by consuming [Reader] entirely.
     /// This requires reference to [Header] that was just parsed by consuming [Reader] until this point.
@@ -41,6 +68,133 @@ impl Record {
         header: &mut Header,
         reader: &mut BufReader<R>,
     ) -> Result<(Self, Comments), ParsingError> {
+        Self::parse_with_options(header, reader, &ParsingOptions::default())
+    }
+
+    /// Parses [Record] section by consuming [Reader] entirely, like [Self::parse],
+    /// but with custom [ParsingOptions].
+    pub fn parse_with_options<R: Read>(
+        header: &mut Header,
+        reader: &mut BufReader<R>,
+        options: &ParsingOptions,
+    ) -> Result<(Self, Comments), ParsingError> {
+        let (record, comments, _) = Self::parse_lenient_with_options(header, reader, 0, options)?;
+        Ok((record, comments))
+    }
+
+    /// Parses Navigation [Record] section by consuming [Reader] entirely, like [Self::parse],
+    /// but frames are decoded in parallel. The record is first split into frames
+    /// (each frame starts with its own epoch line), which are then decoded independently,
+    /// and finally stored in order of appearance: the result is identical to [Self::parse].
+    /// This requires reference to the Navigation [Header] that was just parsed.
+    #[cfg(feature = "rayon")]
+    pub fn parse_nav_parallel<R: Read>(
+        header: &Header,
+        reader: &mut BufReader<R>,
+    ) -> Result<(Self, Comments), ParsingError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        let mut frames = Vec::<String>::new();
+        let mut pending = String::with_capacity(1024);
+
+        // comments, tagged with the number of frames that preceded them
+        let mut pending_comments = Vec::<(usize, String)>::new();
+
+        for line in content.split_inclusive('\n') {
+            if is_rinex_comment(line) {
+                continue;
+            }
+
+            if line.contains("COMMENT") {
+                let comment = line.get(..60).unwrap_or(line).trim();
+                pending_comments.push((frames.len(), comment.to_string()));
+            }
+
+            if !pending.is_empty() && Self::is_new_epoch(line, header) {
+                frames.push(std::mem::take(&mut pending));
+            }
+
+            pending.push_str(line);
+        }
+
+        if !pending.is_empty() {
+            frames.push(pending);
+        }
+
+        let decoded = frames
+            .par_iter()
+            .map(|frame| parse_nav_epoch(header, frame).ok())
+            .collect::<Vec<_>>();
+
+        let mut nav_rec = NavRecord::new();
+        let mut comments = Comments::new();
+        let mut comment_ts = Epoch::default();
+        let mut pending_comments = pending_comments.into_iter().peekable();
+
+        for (index, decoded) in decoded.into_iter().enumerate() {
+            // comments are stored with the latest frame that preceded them
+            while let Some((_, comment)) = pending_comments.next_if(|(n, _)| *n <= index) {
+                comments.entry(comment_ts).or_default().push(comment);
+            }
+
+            if let Some((k, v)) = decoded {
+                comment_ts = k.epoch;
+                nav_rec.insert(k, v);
+            }
+        }
+
+        for (_, comment) in pending_comments {
+            comments.entry(comment_ts).or_default().push(comment);
+        }
+
+        Ok((Record::NavRecord(nav_rec), comments))
+    }
+
+    /// Parses [Record] section by consuming [Reader] entirely, like [Self::parse],
+    /// and also returns a [ParseWarning] for each entry (or line) that was skipped.
+    /// `num_header_lines` is the number of lines that were consumed by the [Header]
+    /// section, so the reported line numbers are file line numbers.
+    pub fn parse_lenient<R: Read>(
+        header: &mut Header,
+        reader: &mut BufReader<R>,
+        num_header_lines: usize,
+    ) -> Result<(Self, Comments, Vec<ParseWarning>), ParsingError> {
+        Self::parse_lenient_with_options(
+            header,
+            reader,
+            num_header_lines,
+            &ParsingOptions::default(),
+        )
+    }
+
+    /// Parses [Record] section in a lenient manner, like [Self::parse_lenient],
+    /// but with custom [ParsingOptions].
+    pub fn parse_lenient_with_options<R: Read>(
+        header: &mut Header,
+        reader: &mut BufReader<R>,
+        num_header_lines: usize,
+        options: &ParsingOptions,
+    ) -> Result<(Self, Comments, Vec<ParseWarning>), ParsingError> {
+        let (record, comments, warnings, _) =
+            Self::parse_lenient_with_stats(header, reader, num_header_lines, options)?;
+        Ok((record, comments, warnings))
+    }
+
+    /// Parses [Record] section like [Self::parse_lenient_with_options],
+    /// and also returns the [CrinexStats] when a CRINEX record was decompressed.
+    pub(crate) fn parse_lenient_with_stats<R: Read>(
+        header: &mut Header,
+        reader: &mut BufReader<R>,
+        num_header_lines: usize,
+        options: &ParsingOptions,
+    ) -> Result<(Self, Comments, Vec<ParseWarning>, Option<CrinexStats>), ParsingError> {
+        let mut warnings = Vec::<ParseWarning>::new();
+
+        // file line number, and first line of pending epoch
+        let mut line_number = num_header_lines;
+        let mut epoch_line_number = line_number + 1;
+
         // eos reached: process pending buffer & exit
         let mut eos = false;
         let mut crinex_error = false;
@@ -65,12 +219,14 @@ impl Record {
         // OBS
         let mut obs_rec = ObservationRecord::new();
         let mut observations = Observations::default();
+        let mut obs_prev_epoch = Option::<Epoch>::None;
 
         // CRINEX case
         const CRINEX_BUF_SIZE: usize = 1024;
         let mut buf = [0; CRINEX_BUF_SIZE];
 
         let mut is_crinex = false;
+        let mut crinex_stats = CrinexStats::default();
         let mut crinex_v3 = false;
         let mut gnss_observables = Default::default();
 
@@ -138,6 +294,8 @@ impl Record {
                 // reached EOS
                 // we might still have something to process prior exiting
                 eos |= true;
+            } else {
+                line_number += 1;
             }
 
             // (special case) COMMENTS: store as is
@@ -165,11 +323,14 @@ impl Record {
             // - decompress new pending line, which may recover several lines (in old V1 format)
             if is_crinex {
                 let line_len = line_buf.len();
+                crinex_stats.input_bytes += line_len;
 
                 // catch errors nicely, simply log them
                 // it is normal to abort on final line for example
                 match decompressor.decompress(&line_buf, line_len, &mut buf, CRINEX_BUF_SIZE) {
                     Ok(size) => {
+                        crinex_stats.output_bytes += size;
+
                         if size > 0 {
                             // clear and overwrite pending content with recovered content
                             // we should have valid ASCII UTF-8 at all times, at this point
@@ -211,65 +372,137 @@ impl Record {
                     //println!("***MATCH***");
 
                     match &header.rinex_type {
-                        Type::NavigationData => {
-                            if let Ok((k, v)) = parse_nav_epoch(&header, &epoch_buf) {
+                        Type::NavigationData => match parse_nav_epoch(&header, &epoch_buf) {
+                            Ok((k, v)) => {
                                 nav_rec.insert(k, v);
                                 // println!("nav_epoch={:?}", k); // DEBUG
                                 comment_ts = k.epoch; // for comments storage
-                            }
+                            },
+                            Err(error) => {
+                                warnings.push(ParseWarning {
+                                    line: epoch_line_number,
+                                    error,
+                                });
+                            },
+                        },
+                        Type::ObservationData
+                            if eos && is_truncated_observation_epoch(header, &epoch_buf) =>
+                        {
+                            // file ended mid-epoch: drop it, rather than
+                            // introducing a partial epoch
+                            warnings.push(ParseWarning {
+                                line: epoch_line_number,
+                                error: ParsingError::TruncatedEpoch,
+                            });
                         },
                         Type::ObservationData => {
+                            if is_crinex {
+                                crinex_stats.epochs += 1;
+                            }
+
+                            let mut skipped_lines = Vec::new();
+
                             match parse_observation_epoch(
                                 header,
                                 &epoch_buf,
                                 obs_ts,
                                 &mut observations,
+                                obs_prev_epoch,
+                                &mut skipped_lines,
                             ) {
                                 Ok(key) => {
                                     //println!("key={:?}", key);
-                                    obs_rec.insert(key, observations.clone());
+                                    match obs_rec.get_mut(&key) {
+                                        Some(existing) => match options.duplicate_epochs {
+                                            DuplicateEpochPolicy::Overwrite => {
+                                                *existing = observations.clone();
+                                            },
+                                            DuplicateEpochPolicy::Merge => {
+                                                merge_duplicate_observations(
+                                                    existing,
+                                                    &observations,
+                                                );
+                                            },
+                                            DuplicateEpochPolicy::Error => {
+                                                return Err(ParsingError::DuplicateEpoch);
+                                            },
+                                        },
+                                        None => {
+                                            obs_rec.insert(key, observations.clone());
+                                        },
+                                    }
+                                    obs_prev_epoch = Some(key.epoch);
                                     comment_ts = key.epoch; // for comments storage
                                 },
-                                #[cfg(feature = "log")]
-                                Err(e) => {
-                                    error!("parsing: {}", e);
+                                Err(error) => {
+                                    #[cfg(feature = "log")]
+                                    error!("parsing: {}", error);
+
+                                    warnings.push(ParseWarning {
+                                        line: epoch_line_number,
+                                        error,
+                                    });
                                 },
-                                #[cfg(not(feature = "log"))]
-                                Err(_) => {},
+                            }
+
+                            for skipped in skipped_lines {
+                                warnings.push(ParseWarning {
+                                    line: epoch_line_number + skipped,
+                                    error: ParsingError::BadSignalObservationLine,
+                                });
                             }
 
                             observations.signals.clear(); // reset for next parsing (single alloc)
+                            observations.events.clear();
                         },
 
-                        Type::MeteoData => {
-                            if let Ok(items) = parse_meteo_epoch(header, &epoch_buf) {
+                        Type::MeteoData => match parse_meteo_epoch(header, &epoch_buf) {
+                            Ok(items) => {
                                 for (k, v) in items.iter() {
                                     met_rec.insert(k.clone(), *v);
                                     comment_ts = k.epoch; // for comments storage
                                 }
-                            }
+                            },
+                            Err(error) => {
+                                warnings.push(ParseWarning {
+                                    line: epoch_line_number,
+                                    error,
+                                });
+                            },
                         },
 
                         Type::ClockData => {
-                            if let Ok((epoch, key, profile)) =
-                                parse_clock_epoch(header.version, &epoch_buf, clk_ts)
-                            {
-                                if let Some(e) = clk_rec.get_mut(&epoch) {
-                                    e.insert(key, profile);
-                                } else {
-                                    let mut inner: BTreeMap<ClockKey, ClockProfile> =
-                                        BTreeMap::new();
-                                    inner.insert(key, profile);
-                                    clk_rec.insert(epoch, inner);
-                                }
-                                comment_ts = epoch; // for comments storage
+                            match parse_clock_epoch(header.version, &epoch_buf, clk_ts) {
+                                Ok((epoch, key, profile)) => {
+                                    if let Some(e) = clk_rec.get_mut(&epoch) {
+                                        e.insert(key, profile);
+                                    } else {
+                                        let mut inner: BTreeMap<ClockKey, ClockProfile> =
+                                            BTreeMap::new();
+                                        inner.insert(key, profile);
+                                        clk_rec.insert(epoch, inner);
+                                    }
+                                    comment_ts = epoch; // for comments storage
+                                },
+                                Err(error) => {
+                                    warnings.push(ParseWarning {
+                                        line: epoch_line_number,
+                                        error,
+                                    });
+                                },
                             }
                         },
 
-                        Type::AntennaData => {
-                            if let Ok((antenna, content)) = parse_antex_antenna(&epoch_buf) {
+                        Type::AntennaData => match parse_antex_antenna(&epoch_buf) {
+                            Ok((antenna, content)) => {
                                 atx_rec.push((antenna, content));
-                            }
+                            },
+                            Err(error) => {
+                                warnings.push(ParseWarning {
+                                    line: epoch_line_number,
+                                    error,
+                                });
+                            },
                         },
                     }
                 }
@@ -280,6 +513,10 @@ impl Record {
                 epoch_buf.clear();
             }
 
+            if epoch_buf.is_empty() {
+                epoch_line_number = line_number;
+            }
+
             // always stack new content
             epoch_buf.push_str(&line_buf);
 
@@ -298,7 +535,8 @@ impl Record {
             Type::NavigationData => Record::NavRecord(nav_rec),
             Type::ObservationData => Record::ObsRecord(obs_rec),
         };
-        Ok((record, comments))
+        let crinex_stats = is_crinex.then_some(crinex_stats);
+        Ok((record, comments, warnings, crinex_stats))
     }
 
     fn is_new_epoch(line: &str, header: &Header) -> bool {
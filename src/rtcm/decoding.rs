@@ -0,0 +1,352 @@
+//! RTCM stream to [Rinex] decoding
+use std::{io::Read, str::FromStr};
+
+use crate::{
+    carrier::Carrier,
+    constants::SPEED_OF_LIGHT_M_S,
+    epoch::resolve_time_of_week,
+    navigation::{Ephemeris, NavFrameType, NavKey, NavMessageType},
+    observation::{SignalObservation, SNR},
+    prelude::{
+        Constellation, Epoch, Header, Observable, ParsingError, Rinex, RinexType, TimeScale,
+        Version, SV,
+    },
+};
+
+use rtcm_rs::{msg::message::Message, next_msg_frame};
+
+/// Satellite data of a MSM7 message, common to all constellations
+struct Msm7Satellite {
+    prn: u8,
+    rough_range_ms: Option<f64>,
+    rough_range_rate_m_s: Option<f64>,
+}
+
+/// Signal data of a MSM7 message, common to all constellations
+struct Msm7Signal {
+    prn: u8,
+    band: u8,
+    attribute: char,
+    fine_range_ms: Option<f64>,
+    fine_phase_range_ms: Option<f64>,
+    fine_range_rate_m_s: Option<f64>,
+    cnr_dbhz: Option<f64>,
+}
+
+/// Converts a MSM7 message to (time of week in ms, satellites, signals).
+/// All MSM7 messages share the same layout, only the type and epoch field name differ.
+macro_rules! msm7 {
+    ($msg:expr, $tow_ms:ident) => {
+        (
+            $msg.$tow_ms,
+            $msg.data_segment
+                .satellite_data
+                .iter()
+                .map(|sat| Msm7Satellite {
+                    prn: sat.satellite_id,
+                    rough_range_ms: sat
+                        .gnss_satellite_rough_range_integer_ms
+                        .map(|int_ms| int_ms as f64 + sat.gnss_satellite_rough_range_mod1ms_ms),
+                    rough_range_rate_m_s: sat
+                        .gnss_satellite_rough_phaserange_rate_m_s
+                        .map(|rate| rate as f64),
+                })
+                .collect::<Vec<_>>(),
+            $msg.data_segment
+                .signal_data
+                .iter()
+                .map(|sig| Msm7Signal {
+                    prn: sig.satellite_id,
+                    band: sig.signal_id.band(),
+                    attribute: sig.signal_id.attribute(),
+                    fine_range_ms: sig.gnss_signal_fine_pseudorange_ext_ms,
+                    fine_phase_range_ms: sig.gnss_signal_fine_phaserange_ext_ms,
+                    fine_range_rate_m_s: sig.gnss_signal_fine_phaserange_rate_m_s.map(|r| r as f64),
+                    cnr_dbhz: sig.gnss_signal_cnr_ext_dbhz.map(|cnr| cnr as f64),
+                })
+                .collect::<Vec<_>>(),
+        )
+    };
+}
+
+/// Converts MSM7 content to [SignalObservation]s.
+fn msm7_signal_observations(
+    constellation: Constellation,
+    satellites: &[Msm7Satellite],
+    signals: &[Msm7Signal],
+) -> Vec<SignalObservation> {
+    let mut observations = Vec::with_capacity(signals.len() * 4);
+
+    for signal in signals.iter() {
+        let satellite = match satellites.iter().find(|sat| sat.prn == signal.prn) {
+            Some(satellite) => satellite,
+            None => continue,
+        };
+
+        let rough_range_ms = match satellite.rough_range_ms {
+            Some(rough_range_ms) => rough_range_ms,
+            None => continue, // invalid satellite
+        };
+
+        let sv = SV::new(constellation, signal.prn);
+        let code = format!("{}{}", signal.band, signal.attribute);
+
+        let pseudo_range = match Observable::from_str(&format!("C{}", code)) {
+            Ok(observable) => observable,
+            Err(_) => continue, // non supported signal
+        };
+
+        let lambda = match Carrier::from_observable(constellation, &pseudo_range) {
+            Ok(carrier) => carrier.wavelength(),
+            Err(_) => continue, // non supported signal
+        };
+
+        let snr = signal.cnr_dbhz.map(SNR::from);
+
+        if let Some(fine_ms) = signal.fine_range_ms {
+            let range_m = (rough_range_ms + fine_ms) * SPEED_OF_LIGHT_M_S * 1.0E-3;
+            observations.push(SignalObservation::new(sv, pseudo_range, range_m));
+        }
+
+        if let Some(fine_ms) = signal.fine_phase_range_ms {
+            if let Ok(observable) = Observable::from_str(&format!("L{}", code)) {
+                let phase_m = (rough_range_ms + fine_ms) * SPEED_OF_LIGHT_M_S * 1.0E-3;
+                let mut observation = SignalObservation::new(sv, observable, phase_m / lambda);
+                observation.snr = snr;
+                observations.push(observation);
+            }
+        }
+
+        if let (Some(rough_rate), Some(fine_rate)) =
+            (satellite.rough_range_rate_m_s, signal.fine_range_rate_m_s)
+        {
+            if let Ok(observable) = Observable::from_str(&format!("D{}", code)) {
+                let doppler_hz = -(rough_rate + fine_rate) / lambda;
+                observations.push(SignalObservation::new(sv, observable, doppler_hz));
+            }
+        }
+
+        if let Some(cnr_dbhz) = signal.cnr_dbhz {
+            if let Ok(observable) = Observable::from_str(&format!("S{}", code)) {
+                observations.push(SignalObservation::new(sv, observable, cnr_dbhz));
+            }
+        }
+    }
+
+    observations
+}
+
+impl Rinex {
+    /// Decodes a RTCM stream (for example, collected from a NTRIP caster)
+    /// into a [Rinex] of the requested [RinexType].
+    /// Messages that do not apply to this [RinexType] are discarded.
+    /// Supported messages:
+    /// - [RinexType::ObservationData]: MSM7 messages (1077 GPS, 1097 Galileo,
+    ///   1117 QZSS and 1127 BeiDou). Pseudo range, phase, doppler and C/N0
+    ///   observations are generated for each signal and all epochs are expressed in GPST.
+    /// - [RinexType::NavigationData]: GPS ephemeris (1019).
+    ///
+    /// RTCM messages only stream a time of week (and a 10 bit GPS week counter).
+    /// They are resolved with respect to the `reference` [Epoch] you provide,
+    /// which should lie within half a week of the recording:
+    /// the recording date, or the current time when decoding a real-time stream.
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use rinex::prelude::{Constellation, Epoch, OutputFormat, Rinex, RinexType};
+    ///
+    /// let rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// // serialize as RTCM stream
+    /// let mut rtcm = Vec::new();
+    /// rinex.convert(OutputFormat::Rtcm, &mut rtcm)
+    ///     .unwrap();
+    ///
+    /// // recording date
+    /// let reference = Epoch::from_str("2020-06-25T00:00:00 GPST")
+    ///     .unwrap();
+    ///
+    /// // decode GPS ephemeris
+    /// let decoded = Rinex::from_rtcm_stream(
+    ///     rtcm.as_slice(),
+    ///     RinexType::NavigationData,
+    ///     reference,
+    /// ).unwrap();
+    ///
+    /// assert!(decoded.is_navigation_rinex());
+    /// assert_eq!(decoded.header.constellation, Some(Constellation::GPS));
+    /// ```
+    pub fn from_rtcm_stream<R: Read>(
+        mut reader: R,
+        rinex_type: RinexType,
+        reference: Epoch,
+    ) -> Result<Self, ParsingError> {
+        let mut rinex = match rinex_type {
+            RinexType::ObservationData => {
+                Self::new_observation(Version::new(3, 5), &Header::basic_obs())
+            },
+            RinexType::NavigationData => {
+                Self::new_navigation(Version::new(3, 5), Constellation::Mixed)
+            },
+            _ => return Err(ParsingError::NonSupportedRtcmType),
+        };
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        let reference = reference.to_time_scale(TimeScale::GPST);
+
+        let mut offset = 0;
+
+        while offset < bytes.len() {
+            let (consumed, frame) = next_msg_frame(&bytes[offset..]);
+
+            if consumed == 0 {
+                break; // incomplete frame
+            }
+
+            offset += consumed;
+
+            let message = match frame {
+                Some(frame) => frame.get_message(),
+                None => continue,
+            };
+
+            match (rinex_type, message) {
+                (RinexType::NavigationData, Message::Msg1019(msg)) => {
+                    // closest week counter, modulo 1024
+                    let (ref_week, _) = reference.to_time_of_week();
+                    let (ref_week, wn) = (ref_week as i64, msg.gps_week_number as i64 % 1024);
+
+                    let mut week = ref_week - (ref_week - wn).rem_euclid(1024);
+
+                    if ref_week - week > 512 {
+                        week += 1024;
+                    }
+
+                    let week = week as u32;
+
+                    let toc_nanos = (msg.toc_s as f64 * 1.0E9).round() as u64;
+                    let toc = Epoch::from_time_of_week(week, toc_nanos, TimeScale::GPST);
+
+                    let (sv, ephemeris) = Ephemeris::from_rtcm_gps1019(&msg, week);
+
+                    let key = NavKey {
+                        sv,
+                        epoch: toc,
+                        msgtype: NavMessageType::LNAV,
+                        frmtype: NavFrameType::Ephemeris,
+                    };
+
+                    rinex.add_ephemeris(key, ephemeris);
+                },
+                (RinexType::ObservationData, message) => {
+                    let (constellation, timescale, (tow_ms, satellites, signals)) = match message {
+                        Message::Msg1077(msg) => (
+                            Constellation::GPS,
+                            TimeScale::GPST,
+                            msm7!(msg, gps_epoch_time_ms),
+                        ),
+                        Message::Msg1097(msg) => (
+                            Constellation::Galileo,
+                            TimeScale::GST,
+                            msm7!(msg, gal_epoch_time_ms),
+                        ),
+                        Message::Msg1117(msg) => (
+                            Constellation::QZSS,
+                            TimeScale::QZSST,
+                            msm7!(msg, qzss_epoch_time_ms),
+                        ),
+                        Message::Msg1127(msg) => (
+                            Constellation::BeiDou,
+                            TimeScale::BDT,
+                            msm7!(msg, bds_epoch_time_ms),
+                        ),
+                        _ => continue,
+                    };
+
+                    let tow_nanos = tow_ms as u64 * 1_000_000;
+
+                    let epoch = resolve_time_of_week(reference, tow_nanos, timescale)
+                        .to_time_scale(TimeScale::GPST);
+
+                    let observations =
+                        msm7_signal_observations(constellation, &satellites, &signals);
+
+                    if !observations.is_empty() {
+                        rinex.add_signal_observations(epoch, observations);
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        rinex.rebuild_header_from_record();
+        Ok(rinex)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn msm7_gps_l1c_observations() {
+        let satellites = [Msm7Satellite {
+            prn: 5,
+            rough_range_ms: Some(70.25),
+            rough_range_rate_m_s: Some(-500.0),
+        }];
+
+        let signals = [Msm7Signal {
+            prn: 5,
+            band: 1,
+            attribute: 'C',
+            fine_range_ms: Some(1.0E-4),
+            fine_phase_range_ms: Some(2.0E-4),
+            fine_range_rate_m_s: Some(0.5),
+            cnr_dbhz: Some(45.0),
+        }];
+
+        let observations = msm7_signal_observations(Constellation::GPS, &satellites, &signals);
+        assert_eq!(observations.len(), 4);
+
+        let g05 = SV::from_str("G05").unwrap();
+
+        // 70.2501 ms, 70.2502 ms, (500.0 - 0.5) m/s on L1 (1575.42 MHz)
+        for (observable, expected, tolerance) in [
+            ("C1C", 21060450.1537458, 1.0E-6),
+            ("L1C", 110673570.084, 1.0E-3),
+            ("D1C", 2624.890216551078, 1.0E-9),
+            ("S1C", 45.0, 0.0),
+        ] {
+            let observable = Observable::from_str(observable).unwrap();
+
+            let observation = observations
+                .iter()
+                .find(|obs| obs.observable == observable)
+                .unwrap_or_else(|| panic!("missing {} observation", observable));
+
+            assert_eq!(observation.sv, g05);
+
+            let err = (observation.value - expected).abs();
+            assert!(
+                err <= tolerance,
+                "{}: decoded {}, expecting {}",
+                observable,
+                observation.value,
+                expected
+            );
+        }
+
+        // invalid satellite data
+        let satellites = [Msm7Satellite {
+            prn: 5,
+            rough_range_ms: None,
+            rough_range_rate_m_s: None,
+        }];
+
+        assert!(msm7_signal_observations(Constellation::GPS, &satellites, &signals).is_empty());
+    }
+}
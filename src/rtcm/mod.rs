@@ -1,5 +1,9 @@
-use crate::prelude::{Rinex, RinexType};
+use crate::{
+    navigation::SkipReason,
+    prelude::{Epoch, Rinex, RinexType, SV},
+};
 
+mod decoding;
 mod nav;
 use nav::Streamer as NavStreamer;
 
@@ -17,6 +21,27 @@ pub struct RNX2RTCM<'a> {
     type_dependent: TypeDependentStreamer<'a>,
 }
 
+impl<'a> RNX2RTCM<'a> {
+    /// Returns the number of RTCM messages this [RNX2RTCM] should produce
+    /// once fully consumed, for buffer sizing or progress reporting.
+    /// This is an upper bound: frames that miss a mandatory field are only
+    /// identified while encoding, and will eventually appear in the [Self::skipped_report].
+    pub fn estimated_message_count(&self) -> usize {
+        match &self.type_dependent {
+            TypeDependentStreamer::NAV(streamer) => streamer.estimated_count(),
+        }
+    }
+
+    /// Returns the frames that could not be converted to RTCM so far,
+    /// as (Epoch, SV, reason). Typically inspected once the stream has been consumed,
+    /// to verify that nothing was silently dropped.
+    pub fn skipped_report(&self) -> &[(Epoch, SV, SkipReason)] {
+        match &self.type_dependent {
+            TypeDependentStreamer::NAV(streamer) => streamer.skipped_report(),
+        }
+    }
+}
+
 impl<'a> Iterator for RNX2RTCM<'a> {
     type Item = Message;
 
@@ -1,22 +1,56 @@
 use crate::{
-    navigation::{Ephemeris, NavKey},
-    prelude::{Constellation, Rinex},
+    navigation::{Ephemeris, NavKey, SkipReason},
+    prelude::{Constellation, Epoch, Rinex, SV},
 };
 
 use rtcm_rs::msg::message::Message;
 
+/// Returns true if we can encode ephemeris of this [Constellation].
+/// Must remain consistent with `Streamer::next`.
+fn supported_constellation(constellation: Constellation) -> bool {
+    matches!(
+        constellation,
+        Constellation::GPS
+            | Constellation::QZSS
+            | Constellation::Galileo
+            | Constellation::Glonass
+            | Constellation::BeiDou
+    )
+}
+
 pub struct Streamer<'a> {
+    /// [Rinex] being streamed
+    rinex: &'a Rinex,
+
     /// Iterator
     ephemeris_iter: Box<dyn Iterator<Item = (&'a NavKey, &'a Ephemeris)> + 'a>,
+
+    /// Frames that could not be streamed
+    skipped: Vec<(Epoch, SV, SkipReason)>,
 }
 
 impl<'a> Streamer<'a> {
     /// Builds a new [Streamer] dedicated to NAV RINEX streaming.
     pub fn new(rinex: &'a Rinex) -> Self {
         Self {
+            rinex,
             ephemeris_iter: rinex.nav_ephemeris_frames_iter(),
+            skipped: Vec::new(),
         }
     }
+
+    /// Number of messages this [Streamer] should produce, when fully consumed.
+    pub fn estimated_count(&self) -> usize {
+        self.rinex
+            .nav_ephemeris_frames_iter()
+            .filter(|(k, _)| supported_constellation(k.sv.constellation))
+            .count()
+    }
+
+    /// Frames that were skipped so far.
+    pub fn skipped_report(&self) -> &[(Epoch, SV, SkipReason)] {
+        &self.skipped
+    }
 }
 
 impl<'a> Iterator for Streamer<'a> {
@@ -27,30 +61,34 @@ impl<'a> Iterator for Streamer<'a> {
         loop {
             let (key, eph) = self.ephemeris_iter.next()?;
 
-            match key.sv.constellation {
-                Constellation::GPS => {
-                    let msg1019 = eph.to_rtcm_gps1019(key.epoch, key.sv)?;
-                    return Some(Message::Msg1019(msg1019));
-                },
-                Constellation::QZSS => {
-                    let msg1044 = eph.to_rtcm_qzss1044(key.epoch, key.sv)?;
-                    return Some(Message::Msg1044(msg1044));
-                },
+            let message = match key.sv.constellation {
+                Constellation::GPS => eph.to_rtcm_gps1019(key.epoch, key.sv).map(Message::Msg1019),
+                Constellation::QZSS => eph
+                    .to_rtcm_qzss1044(key.epoch, key.sv)
+                    .map(Message::Msg1044),
                 Constellation::Galileo => {
                     // TODO may have 2 forms
-                    let msg1045 = eph.to_rtcm_gal1045(key.epoch, key.sv)?;
-                    return Some(Message::Msg1045(msg1045));
+                    eph.to_rtcm_gal1045(key.epoch, key.sv).map(Message::Msg1045)
                 },
                 Constellation::Glonass => {
-                    let msg1020 = eph.to_rtcm_glo1020(key.epoch, key.sv)?;
-                    return Some(Message::Msg1020(msg1020));
+                    eph.to_rtcm_glo1020(key.epoch, key.sv).map(Message::Msg1020)
                 },
                 Constellation::BeiDou => {
-                    let msg1042 = eph.to_rtcm_bds1042(key.epoch, key.sv)?;
-                    return Some(Message::Msg1042(msg1042));
+                    eph.to_rtcm_bds1042(key.epoch, key.sv).map(Message::Msg1042)
                 },
                 _ => {
-                    // Not supported yet
+                    // Not supported yet (SBAS 1043 for example)
+                    self.skipped
+                        .push((key.epoch, key.sv, SkipReason::NonSupportedConstellation));
+                    continue;
+                },
+            };
+
+            match message {
+                Some(message) => return Some(message),
+                None => {
+                    self.skipped
+                        .push((key.epoch, key.sv, SkipReason::MissingFields));
                 },
             }
         }
@@ -11,6 +11,65 @@ impl Rinex {
         self.epoch_iter().last()
     }
 
+    /// Scans the record and updates the "TIME OF FIRST OBS" and "TIME OF LAST OBS"
+    /// [Header] fields, so they match the actual first and last [Epoch].
+    /// This is automatically invoked by all operations that may reduce
+    /// the time frame (decimation, split, masking..), but you can invoke
+    /// it manually, after direct record modifications for example.
+    /// This has no effect if this is not an Observation [Rinex],
+    /// or the record is empty.
+    pub fn recompute_time_bounds_mut(&mut self) {
+        let (first, last) = match (self.first_epoch(), self.last_epoch()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return,
+        };
+
+        if let Some(obs) = &mut self.header.obs {
+            let ts = obs.timescale();
+            obs.timeof_first_obs = Some(first.to_time_scale(ts));
+            obs.timeof_last_obs = Some(last.to_time_scale(ts));
+        }
+    }
+
+    /// Copies and returns a [Rinex] that only contains the data
+    /// within the [start, end] (inclusive) time window.
+    /// This applies to all record types, [Header] time bounds are updated accordingly.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    /// let rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// let start = Epoch::from_str("2022-03-04T00:20:00 GPST").unwrap();
+    /// let end = Epoch::from_str("2022-03-04T01:00:00 GPST").unwrap();
+    ///
+    /// let windowed = rinex.time_window(start, end);
+    /// assert_eq!(windowed.epoch_iter().count(), 2);
+    /// ```
+    pub fn time_window(&self, start: Epoch, end: Epoch) -> Self {
+        let mut s = self.clone();
+        s.time_window_mut(start, end);
+        s
+    }
+
+    /// Mutable [Self::time_window] implementation.
+    pub fn time_window_mut(&mut self, start: Epoch, end: Epoch) {
+        let within = |t: &Epoch| *t >= start && *t <= end;
+
+        if let Some(rec) = self.record.as_mut_obs() {
+            rec.retain(|k, _| within(&k.epoch));
+        } else if let Some(rec) = self.record.as_mut_nav() {
+            rec.retain(|k, _| within(&k.epoch));
+        } else if let Some(rec) = self.record.as_mut_clock() {
+            rec.retain(|t, _| within(t));
+        } else if let Some(rec) = self.record.as_mut_meteo() {
+            rec.retain(|k, _| within(&k.epoch));
+        }
+
+        self.comments.retain(|t, _| within(t));
+        self.recompute_time_bounds_mut();
+    }
+
     /// Returns total [Duration] this [Rinex].
     pub fn duration(&self) -> Option<Duration> {
         let start = self.first_epoch()?;
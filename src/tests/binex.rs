@@ -1,5 +1,6 @@
 use crate::navigation::Ephemeris;
 use crate::prelude::{Constellation, Rinex};
+use crate::tests::toolkit::{assert_ephemeris_roundtrip, RoundtripFormat};
 
 use binex::prelude::Meta;
 
@@ -7,7 +8,7 @@ use binex::prelude::Meta;
 fn esbcdnk_ephv3_binex() {
     let mut gps_passed = 0;
     let mut gal_passed = 0;
-    let mut glo_passed = 0;
+    // TODO let mut glo_passed = 0;
     // TODO let mut bds_passed = 0;
     // TODO let mut qzss_passed = 0;
     let mut sbas_passed = 0;
@@ -17,36 +18,10 @@ fn esbcdnk_ephv3_binex() {
     for (k, ephemeris) in rinex.nav_ephemeris_frames_iter() {
         match k.sv.constellation {
             Constellation::GPS | Constellation::Galileo => {
-                if let Some(serialized) = ephemeris.to_binex(k.epoch, k.sv) {
-                    // mirror
-                    let (decoded_sv, decoded) =
-                        Ephemeris::from_binex(serialized).unwrap_or_else(|| {
-                            panic!("Failed to decoded {}({}) BINEX frame", k.epoch, k.sv);
-                        });
-
-                    // testbench
-                    assert_eq!(k.sv, decoded_sv, "{}({}) invalid SV", k.epoch, k.sv);
-
-                    // TODO achieve full reciprocity
-                    // assert_eq!(
-                    //     *ephemeris, decoded,
-                    //     "{}({}) invalid content decoded",
-                    //     k.epoch, k.sv
-                    // );
-
+                if assert_ephemeris_roundtrip(ephemeris, k.epoch, k.sv, RoundtripFormat::Binex) {
                     match k.sv.constellation {
                         Constellation::GPS => gps_passed += 1,
                         Constellation::Galileo => gal_passed += 1,
-                        Constellation::Glonass => glo_passed += 1,
-                        Constellation::BeiDou => {
-                            // TODO
-                        },
-                        Constellation::Glonass => {
-                            // TODO: issue with sv.PRN
-                        },
-                        Constellation::QZSS => {
-                            // TODO
-                        },
                         _ => {},
                     }
                 }
@@ -61,20 +36,20 @@ fn esbcdnk_ephv3_binex() {
                             });
 
                         // testbench
-                        assert!(decoded_sv.constellation.is_sbas());
-                        // TODO error in the SV::new API unable to identify correctly
-                        // assert_eq!(decoded_sv.prn, k.sv.prn);
-                        // assert_eq!(decoded_sv.constellation, k.sv.constellation);
-
-                        // TODO invalid PRN
-                        // assert_eq!(k.sv, decoded_sv, "{}({}) invalid SV", k.epoch, k.sv);
+                        assert_eq!(k.sv, decoded_sv, "{}({}) invalid SV", k.epoch, k.sv);
+
+                        // only the clock state is decoded from SBAS frames
+                        assert_eq!(
+                            decoded.clock_bias, ephemeris.clock_bias,
+                            "{}({}) invalid clock bias",
+                            k.epoch, k.sv
+                        );
+                        assert_eq!(
+                            decoded.clock_drift, ephemeris.clock_drift,
+                            "{}({}) invalid clock drift",
+                            k.epoch, k.sv
+                        );
 
-                        // TODO achieve full reciprocity
-                        // assert_eq!(
-                        //     *ephemeris, decoded,
-                        //     "{}({}) invalid content decoded",
-                        //     k.epoch, k.sv
-                        // );
                         sbas_passed += 1;
                     }
                 }
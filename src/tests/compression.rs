@@ -109,4 +109,56 @@ mod test {
             let _ = fs_remove_file("v3_compressed.txt");
         }
     }
+
+    #[test]
+    fn crinex_revision_selection() {
+        let rnx = Rinex::from_file("data/OBS/V2/aopr0010.17o").unwrap();
+        assert!(rnx.header.crinex_revision().is_none());
+
+        // V2 compressed to CRINEX1, then decompressed
+        rnx.to_crinex_file("crinex1_revision.17d", CrinexVersion::V1)
+            .unwrap();
+
+        let parsed = Rinex::from_file("crinex1_revision.17d").unwrap();
+        assert_eq!(parsed.header.crinex_revision(), Some(CrinexVersion::V1));
+        assert_eq!(parsed.header.version.major, 2);
+
+        let decompressed = parsed.crnx2rnx();
+        assert!(decompressed.header.crinex_revision().is_none());
+        assert_eq!(decompressed.record, rnx.record);
+
+        // V2 compressed to CRINEX3 (upgraded to V3), then decompressed
+        rnx.to_crinex_file("crinex3_revision.crx", CrinexVersion::V3)
+            .unwrap();
+
+        let parsed = Rinex::from_file("crinex3_revision.crx").unwrap();
+        assert_eq!(parsed.header.crinex_revision(), Some(CrinexVersion::V3));
+        assert_eq!(parsed.header.version.major, 3);
+
+        let decompressed = parsed.crnx2rnx();
+        assert!(decompressed.header.crinex_revision().is_none());
+        assert_eq!(decompressed.record, rnx.rnx2v3().record);
+
+        // V3 cannot be compressed to CRINEX1
+        let rnx = Rinex::from_file("data/OBS/V3/DUTH0630.22O").unwrap();
+
+        assert!(matches!(
+            rnx.to_crinex_file("crinex1_invalid.22d", CrinexVersion::V1),
+            Err(FormattingError::IncompatibleCrinexVersion)
+        ));
+
+        // original specs are retained
+        let compressed = Rinex::from_file("data/CRNX/V3/DUTH0630.22D").unwrap();
+        let crinex = compressed.header.obs.as_ref().unwrap().crinex.clone();
+
+        let mut recompressed = compressed.clone();
+        recompressed
+            .rnx2crnx_version_mut(CrinexVersion::V3)
+            .unwrap();
+
+        assert_eq!(recompressed.header.obs.unwrap().crinex, crinex);
+
+        let _ = fs_remove_file("crinex1_revision.17d");
+        let _ = fs_remove_file("crinex3_revision.crx");
+    }
 }
@@ -0,0 +1,84 @@
+use crate::prelude::{FormattingError, OutputFormat, Rinex};
+
+use std::io::BufReader;
+
+#[cfg(feature = "binex")]
+use binex::prelude::Meta;
+
+#[test]
+fn obs_convert_rinex_crinex() {
+    let path = env!("CARGO_MANIFEST_DIR").to_owned() + "/data/OBS/V3/DUTH0630.22O";
+    let rinex = Rinex::from_file(&path).unwrap();
+
+    let mut buf = Vec::new();
+    rinex.convert(OutputFormat::Rinex, &mut buf).unwrap();
+
+    let content = String::from_utf8(buf).unwrap();
+    assert!(content.contains("END OF HEADER"));
+    assert!(!content.contains("COMPACT RINEX"));
+
+    let parsed = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    assert_eq!(parsed.first_epoch(), rinex.first_epoch());
+    assert_eq!(parsed.last_epoch(), rinex.last_epoch());
+
+    let mut buf = Vec::new();
+    rinex.convert(OutputFormat::Crinex, &mut buf).unwrap();
+
+    let content = String::from_utf8(buf).unwrap();
+    assert!(content.contains("COMPACT RINEX"));
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn nav_convert() {
+    let path = env!("CARGO_MANIFEST_DIR").to_owned()
+        + "/data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz";
+
+    let rinex = Rinex::from_gzip_file(&path).unwrap();
+
+    let mut buf = Vec::new();
+    rinex.convert(OutputFormat::Rinex, &mut buf).unwrap();
+    assert!(!buf.is_empty());
+
+    let mut buf = Vec::new();
+    match rinex.convert(OutputFormat::Crinex, &mut buf) {
+        Err(FormattingError::UnsupportedOutputFormat) => {},
+        _ => panic!("CRINEX should not apply to NAV RINEX"),
+    }
+
+    #[cfg(feature = "binex")]
+    {
+        let mut buf = Vec::new();
+        rinex
+            .convert(OutputFormat::Binex(Meta::default()), &mut buf)
+            .unwrap();
+        assert!(!buf.is_empty());
+    }
+
+    #[cfg(feature = "rtcm")]
+    {
+        let mut buf = Vec::new();
+        rinex.convert(OutputFormat::Rtcm, &mut buf).unwrap();
+        assert!(!buf.is_empty());
+    }
+
+    #[cfg(feature = "ublox")]
+    {
+        let mut buf = Vec::new();
+        rinex.convert(OutputFormat::Ubx, &mut buf).unwrap();
+        assert!(!buf.is_empty());
+    }
+}
+
+#[test]
+#[cfg(any(feature = "rtcm", feature = "ublox"))]
+fn obs_convert_binary_unsupported() {
+    let path = env!("CARGO_MANIFEST_DIR").to_owned() + "/data/OBS/V3/DUTH0630.22O";
+    let rinex = Rinex::from_file(&path).unwrap();
+
+    #[cfg(feature = "rtcm")]
+    assert!(rinex.convert(OutputFormat::Rtcm, &mut Vec::new()).is_err());
+
+    #[cfg(feature = "ublox")]
+    assert!(rinex.convert(OutputFormat::Ubx, &mut Vec::new()).is_err());
+}
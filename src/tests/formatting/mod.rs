@@ -1,5 +1,7 @@
+pub mod convert;
 pub mod header;
 pub mod obs;
+pub mod options;
 
 use std::collections::HashMap;
 use std::{io::Write, str::from_utf8};
@@ -35,6 +37,15 @@ impl Utf8Buffer {
     }
 }
 
+/// Returns true if this content contains a floating point value
+/// formatted with this exponent character ('D' or 'E').
+pub fn contains_exponent(content: &str, exponent: u8) -> bool {
+    content
+        .as_bytes()
+        .windows(3)
+        .any(|w| w[0].is_ascii_digit() && w[1] == exponent && (w[2] == b'+' || w[2] == b'-'))
+}
+
 pub fn generic_formatted_lines_test(utf8_content: &str, test_values: HashMap<usize, &str>) {
     let mut nb_tests = 0usize;
     let total_tests = test_values.len();
@@ -0,0 +1,77 @@
+use crate::{
+    prelude::{FormattingOptions, LineEnding, Rinex},
+    tests::formatting::contains_exponent,
+};
+
+use std::io::BufWriter;
+
+fn format_to_string(rinex: &Rinex, options: &FormattingOptions) -> String {
+    let mut writer = BufWriter::new(Vec::new());
+    rinex.format_with_options(&mut writer, options).unwrap();
+    String::from_utf8(writer.into_inner().unwrap()).unwrap()
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn nav_exponent_options() {
+    let path = env!("CARGO_MANIFEST_DIR").to_owned()
+        + "/data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz";
+
+    let rinex = Rinex::from_gzip_file(&path).unwrap();
+
+    // header (IONOSPHERIC CORR, TIME SYSTEM CORR) and record are consistent
+    let content = format_to_string(&rinex, &FormattingOptions::default());
+    assert!(contains_exponent(&content, b'D'));
+    assert!(!contains_exponent(&content, b'E'));
+
+    let options = FormattingOptions::default().with_nav_d_exponent(false);
+    let content = format_to_string(&rinex, &options);
+    assert!(contains_exponent(&content, b'E'));
+    assert!(!contains_exponent(&content, b'D'));
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn nav_v4_exponent_options() {
+    let path = env!("CARGO_MANIFEST_DIR").to_owned()
+        + "/data/NAV/V4/KMS300DNK_R_20221591000_01H_MN.rnx.gz";
+
+    let rinex = Rinex::from_gzip_file(&path).unwrap();
+
+    // STO frames are consistent with EPH frames
+    let content = format_to_string(&rinex, &FormattingOptions::default());
+    assert!(content.contains("> STO"));
+    assert!(contains_exponent(&content, b'D'));
+    assert!(!contains_exponent(&content, b'E'));
+
+    let options = FormattingOptions::default().with_nav_d_exponent(false);
+    let content = format_to_string(&rinex, &options);
+    assert!(contains_exponent(&content, b'E'));
+    assert!(!contains_exponent(&content, b'D'));
+}
+
+#[test]
+fn obs_decimals_and_line_ending_options() {
+    let path = env!("CARGO_MANIFEST_DIR").to_owned() + "/data/OBS/V3/DUTH0630.22O";
+    let rinex = Rinex::from_file(&path).unwrap();
+
+    let lf = format_to_string(&rinex, &FormattingOptions::default());
+    assert!(!lf.contains('\r'));
+
+    let options = FormattingOptions::default().with_line_ending(LineEnding::CRLF);
+    let crlf = format_to_string(&rinex, &options);
+
+    for line in crlf.split_inclusive('\n') {
+        assert!(line.ends_with("\r\n"), "invalid line ending: \"{}\"", line);
+    }
+
+    assert_eq!(crlf.replace("\r\n", "\n"), lf);
+
+    // G01 C1C on first epoch: 20243517.560
+    assert!(lf.contains("  20243517.560"));
+
+    let options = FormattingOptions::default().with_observation_decimals(2);
+    let content = format_to_string(&rinex, &options);
+    assert!(content.contains("   20243517.56"));
+    assert!(!content.contains("20243517.560"));
+}
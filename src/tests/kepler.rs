@@ -1,7 +1,13 @@
 use crate::{
     // navigation::{NavFrameType, NavMessageType},
+    navigation::{
+        Ephemeris, NavMessageType, OrbitItem, OrbitalConstants, SolverConfig, SolverConstants,
+        VelocityMethod,
+    },
     prelude::{
-        //Constellation,
+        Constellation,
+        Duration,
+        EphemerisError,
         Epoch,
         Rinex,
         //TimeScale,
@@ -14,6 +20,7 @@ use crate::{
 // use hifitime::Unit;
 
 use std::{
+    io::BufReader,
     // path::PathBuf,
     str::FromStr,
 };
@@ -202,3 +209,864 @@ fn v3_kepler() {
         );
     }
 }
+
+#[test]
+fn kepler_solver_tolerance() {
+    init_logger();
+
+    let g10 = SV::from_str("G10").unwrap();
+    let t_gpst = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let (_, _, eph) = dut.nav_ephemeris_selection(g10, t_gpst).unwrap();
+
+    let precise = eph.helper(g10, t_gpst).unwrap();
+
+    let loose_cfg = SolverConfig::default().with_tolerance(1.0E-6);
+    let loose = eph.helper_with_config(g10, t_gpst, loose_cfg).unwrap();
+
+    assert!(
+        loose.iterations < precise.iterations,
+        "looser tolerance should converge faster ({} vs {} iterations)",
+        loose.iterations,
+        precise.iterations,
+    );
+
+    let err_km = (loose.ecef_position() - precise.ecef_position()).norm();
+    assert!(
+        err_km < 1.0E-3,
+        "looser tolerance error too large: {} km",
+        err_km
+    );
+}
+
+#[test]
+fn beidou_geo_velocity() {
+    init_logger();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/BRDC00GOP_R_20210010000_01D_MN.rnx.gz").unwrap();
+
+    let dt = Duration::from_seconds(0.5);
+    let mut num_tests = 0;
+
+    for (key, eph) in dut.nav_ephemeris_frames_iter() {
+        if !key.sv.is_beidou_geo() {
+            continue;
+        }
+
+        let t = key.epoch + Duration::from_seconds(600.0);
+
+        let helper = eph.helper(key.sv, t).unwrap();
+        let before = eph.helper(key.sv, t - dt).unwrap();
+        let after = eph.helper(key.sv, t + dt).unwrap();
+
+        // numerical differentiation of the position
+        let numerical_km_s =
+            (after.ecef_position() - before.ecef_position()) / (2.0 * dt.to_seconds());
+        let analytical_km_s = helper.ecef_velocity();
+
+        let err_m_s = (numerical_km_s - analytical_km_s).norm() * 1.0E3;
+
+        assert!(
+            err_m_s < 1.0E-2,
+            "{}({}) velocity error too large: {} m/s",
+            key.sv,
+            t,
+            err_m_s
+        );
+
+        num_tests += 1;
+    }
+
+    assert!(num_tests > 0, "no BeiDou GEO ephemeris tested");
+}
+
+#[test]
+fn kepler_finite_difference_velocity() {
+    init_logger();
+
+    let g10 = SV::from_str("G10").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let cfg = SolverConfig::default().with_velocity_method(VelocityMethod::FiniteDifference(0.5));
+
+    for t_gpst in [
+        "2020-06-25T02:00:00 GPST",
+        "2020-06-25T02:15:00 GPST",
+        "2020-06-25T02:30:00 GPST",
+    ] {
+        let t_gpst = Epoch::from_str(t_gpst).unwrap();
+
+        let (_, _, eph) = dut.nav_ephemeris_selection(g10, t_gpst).unwrap();
+
+        let (analytical_pos, analytical_vel) = eph.kepler2position_velocity(g10, t_gpst).unwrap();
+
+        let (numerical_pos, numerical_vel) = eph
+            .kepler2position_velocity_with_config(g10, t_gpst, cfg)
+            .unwrap();
+
+        assert_eq!(analytical_pos, numerical_pos);
+
+        let err_mm_s = (analytical_vel - numerical_vel).norm() * 1.0E6;
+        assert!(
+            err_mm_s < 1.0,
+            "failed for {} G10 velocity err={} mm/s",
+            t_gpst,
+            err_mm_s
+        );
+    }
+
+    // null step is rejected
+    let cfg = SolverConfig::default().with_velocity_method(VelocityMethod::FiniteDifference(0.0));
+
+    assert_eq!(
+        cfg.validate(),
+        Err(EphemerisError::InvalidFiniteDifferenceStep(0.0))
+    );
+
+    let t_gpst = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+    let (_, _, eph) = dut.nav_ephemeris_selection(g10, t_gpst).unwrap();
+
+    assert!(eph
+        .kepler2position_velocity_with_config(g10, t_gpst, cfg)
+        .is_none());
+
+    // null tolerance is rejected
+    let cfg = SolverConfig::default().with_tolerance(0.0);
+
+    assert_eq!(
+        cfg.validate(),
+        Err(EphemerisError::InvalidSolverTolerance(0.0))
+    );
+
+    assert!(eph.kepler2position_with_config(g10, t_gpst, cfg).is_none());
+}
+
+#[test]
+fn v3_irnss_kepler() {
+    init_logger();
+
+    let content = "     3.04           N: GNSS NAV DATA    I: IRNSS            RINEX VERSION / TYPE
+                                                            END OF HEADER
+I02 2021 01 01 00 00 00 4.291534423828E-04 1.136868377216E-12 0.000000000000E+00
+     0.000000000000E+00-2.262500000000E+01 1.753644200000E-09 1.203048900000E+00
+    -1.024454800000E-06 1.941275800000E-03 6.135553100000E-06 6.493245700000E+03
+     4.320000000000E+05 1.210719300000E-08-2.217283500000E+00-1.322478100000E-08
+     5.061953100000E-01 3.065312500000E+02-3.024341500000E+00-2.493103700000E-09
+     1.107189000000E-10 0.000000000000E+00 2.138000000000E+03 0.000000000000E+00
+     2.000000000000E+00 0.000000000000E+00-1.862645149231E-09 0.000000000000E+00
+     4.319700000000E+05
+";
+
+    let dut = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    let i02 = SV::from_str("I02").unwrap();
+
+    // IRNSS ICD (GPS-like) equations and constants, evaluated by hand
+    let (gm_m3_s2, omega_e) = (3.986005E14, 7.2921151467E-5);
+
+    for t_gpst in [
+        "2021-01-01T00:00:00 GPST",
+        "2021-01-01T00:15:00 GPST",
+        "2021-01-01T00:30:00 GPST",
+    ] {
+        let t_gpst = Epoch::from_str(t_gpst).unwrap();
+
+        let (_, toe, eph) = dut.nav_ephemeris_selection(i02, t_gpst).unwrap();
+
+        let t_k = (t_gpst - toe).to_seconds();
+        let reference_km = icd_position_km(eph, t_k, gm_m3_s2, omega_e);
+
+        let orbit = eph.kepler2position(i02, t_gpst).unwrap();
+
+        let pos_vel = orbit.to_cartesian_pos_vel();
+
+        let (x_err, y_err, z_err) = (
+            (pos_vel[0] - reference_km[0]).abs(),
+            (pos_vel[1] - reference_km[1]).abs(),
+            (pos_vel[2] - reference_km[2]).abs(),
+        );
+
+        assert!(
+            x_err < 1.0E-6,
+            "failed for {} I02(x) err={} km",
+            t_gpst,
+            x_err
+        );
+        assert!(
+            y_err < 1.0E-6,
+            "failed for {} I02(y) err={} km",
+            t_gpst,
+            y_err
+        );
+        assert!(
+            z_err < 1.0E-6,
+            "failed for {} I02(z) err={} km",
+            t_gpst,
+            z_err
+        );
+
+        // I02 is geosynchronous (~42164 km)
+        let r_km = (pos_vel[0].powi(2) + pos_vel[1].powi(2) + pos_vel[2].powi(2)).sqrt();
+
+        assert!(
+            (r_km - 42164.0).abs() < 300.0,
+            "{} I02 invalid orbit radius {} km",
+            t_gpst,
+            r_km
+        );
+    }
+}
+
+#[test]
+fn kepler_solver_debug_state() {
+    init_logger();
+
+    let g10 = SV::from_str("G10").unwrap();
+    let t_gpst = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let (_, _, eph) = dut.nav_ephemeris_selection(g10, t_gpst).unwrap();
+
+    let state = eph.solve_debug(g10, t_gpst, 30).unwrap();
+
+    assert!(state.converged);
+    assert!(state.iterations > 0 && state.iterations <= 30);
+
+    // GPS MEO: ~26560 km orbit radius, ~1.46E-4 rad/s mean motion
+    assert!(
+        (state.r_k - 26_560.0E3).abs() < 300.0E3,
+        "r_k={}",
+        state.r_k
+    );
+    assert!((state.n - 1.4585E-4).abs() < 1.0E-6, "n={}", state.n);
+    assert!(state.i_k.to_degrees() > 50.0 && state.i_k.to_degrees() < 60.0);
+
+    // Kepler equation is verified
+    let residual = state.e_k - eph.get_orbit_f64("e").unwrap() * state.e_k.sin() - state.m_k;
+    assert!(residual.abs() < 1.0E-9, "kepler residual={}", residual);
+
+    // consistent with the nominal solver
+    let orbit = eph.kepler2position(g10, t_gpst).unwrap();
+    let pos_km = orbit.to_cartesian_pos_vel();
+
+    assert_eq!(state.position_km.0, pos_km[0]);
+    assert_eq!(state.position_km.1, pos_km[1]);
+    assert_eq!(state.position_km.2, pos_km[2]);
+
+    let radius_km =
+        (state.position_km.0.powi(2) + state.position_km.1.powi(2) + state.position_km.2.powi(2))
+            .sqrt();
+
+    assert!((radius_km - state.r_k / 1.0E3).abs() < 1.0E-6);
+
+    // a single iteration is not enough to converge
+    let state = eph.solve_debug(g10, t_gpst, 1).unwrap();
+    assert!(!state.converged);
+    assert_eq!(state.iterations, 1);
+}
+
+#[test]
+fn kepler_non_physical_orbit() {
+    init_logger();
+
+    let g10 = SV::from_str("G10").unwrap();
+    let t_gpst = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let (_, _, eph) = dut.nav_ephemeris_selection(g10, t_gpst).unwrap();
+
+    assert!(eph.checked_kepler().is_ok());
+
+    // circular orbit is physical
+    let circular = eph.with_orbit("e", OrbitItem::from(0.0));
+    assert!(circular.checked_kepler().is_ok());
+
+    let state = circular.solve_debug(g10, t_gpst, 30).unwrap();
+    assert!(state.converged);
+    assert!(state.r_k.is_finite());
+
+    for (field, value, error) in [
+        ("e", -0.1, EphemerisError::NonPhysicalEccentricity(-0.1)),
+        ("e", 1.0, EphemerisError::NonPhysicalEccentricity(1.0)),
+        ("sqrta", 0.0, EphemerisError::NonPhysicalSemiMajorAxis(0.0)),
+    ] {
+        let corrupt = eph.with_orbit(field, OrbitItem::from(value));
+
+        assert_eq!(corrupt.checked_kepler(), Err(error));
+
+        // solver rejects this frame, rather than producing NaN
+        assert!(corrupt.kepler2position(g10, t_gpst).is_none());
+        assert!(corrupt.solve_debug(g10, t_gpst, 30).is_none());
+    }
+
+    let mut incomplete = eph.clone();
+    incomplete.orbits.remove("e");
+
+    assert_eq!(
+        incomplete.checked_kepler(),
+        Err(EphemerisError::MissingKeplerParameters)
+    );
+}
+
+/// Evaluates the ECEF position (in km) of this [Ephemeris] at t_k seconds past ToE,
+/// by hand, following IS-GPS-200 Tables 20-IV and 30-II, with these constants.
+fn icd_position_km(eph: &Ephemeris, t_k: f64, gm_m3_s2: f64, omega_e: f64) -> [f64; 3] {
+    let orbit = |field: &str| eph.get_orbit_f64(field).unwrap_or_default();
+
+    let (e, omega, omega0, omega_dot) = (
+        orbit("e"),
+        orbit("omega"),
+        orbit("omega0"),
+        orbit("omegaDot"),
+    );
+
+    let a_0 = orbit("sqrta").powi(2);
+    let a_k = a_0 + orbit("adot") * t_k;
+
+    // LNAV deltaN, or CNAV deltaN0 and its rate
+    let dn = orbit("deltaN") + orbit("deltaN0") + 0.5 * orbit("deltaN0Dot") * t_k;
+
+    let n_a = (gm_m3_s2 / a_0.powi(3)).sqrt() + dn;
+    let m_k = orbit("m0") + n_a * t_k;
+
+    // Kepler's equation, solved by Newton iterations
+    let mut e_k = m_k;
+    for _ in 0..10 {
+        e_k -= (e_k - e * e_k.sin() - m_k) / (1.0 - e * e_k.cos());
+    }
+
+    let v_k = ((1.0 - e.powi(2)).sqrt() * e_k.sin()).atan2(e_k.cos() - e);
+    let phi_k = v_k + omega;
+
+    // second harmonic corrections
+    let (sin_2phi, cos_2phi) = (2.0 * phi_k).sin_cos();
+    let du_k = orbit("cus") * sin_2phi + orbit("cuc") * cos_2phi;
+    let dr_k = orbit("crs") * sin_2phi + orbit("crc") * cos_2phi;
+    let di_k = orbit("cis") * sin_2phi + orbit("cic") * cos_2phi;
+
+    let u_k = phi_k + du_k;
+    let r_k = a_k * (1.0 - e * e_k.cos()) + dr_k;
+    let i_k = orbit("i0") + orbit("idot") * t_k + di_k;
+
+    let omega_k = omega0 + (omega_dot - omega_e) * t_k - omega_e * orbit("toe");
+
+    let (x_p, y_p) = (r_k * u_k.cos(), r_k * u_k.sin());
+
+    [
+        x_p * omega_k.cos() - y_p * i_k.cos() * omega_k.sin(),
+        x_p * omega_k.sin() + y_p * i_k.cos() * omega_k.cos(),
+        y_p * i_k.sin(),
+    ]
+    .map(|m| m * 1.0E-3)
+}
+
+#[test]
+fn kepler_solver_constants() {
+    init_logger();
+
+    let g10 = SV::from_str("G10").unwrap();
+    let toe = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+
+    let mut eph = Ephemeris::default();
+
+    // harmonic corrections are null
+    for (field, value) in [
+        ("crs", 0.0),
+        ("deltaN", 2.145446579149E-09),
+        ("m0", 1.032475437231),
+        ("cuc", 0.0),
+        ("e", 7.516302773729E-03),
+        ("cus", 0.0),
+        ("sqrta", 5.153623002625E+03),
+        ("cic", 0.0),
+        ("omega0", 2.179378003185),
+        ("cis", 0.0),
+        ("i0", 9.576713462938E-01),
+        ("crc", 0.0),
+        ("omega", -1.562017655373),
+        ("omegaDot", -2.667968273468E-09),
+        ("idot", -2.203663219130E-10),
+    ] {
+        eph = eph.with_orbit(field, OrbitItem::F64(value));
+    }
+
+    let eph = eph.with_toe(toe);
+
+    let t_k = 7200.0;
+    let t = toe + Duration::from_seconds(t_k);
+
+    let err_km = |pos_km: &[f64; 3], reference_km: &[f64; 3]| {
+        pos_km
+            .iter()
+            .zip(reference_km.iter())
+            .map(|(x, x_ref)| (x - x_ref).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    };
+
+    let position_km = |constants: SolverConstants| {
+        let pos_km = eph
+            .resolve_orbital_state_with_constants(g10, t, 30, constants)
+            .unwrap()
+            .radius_km;
+
+        [pos_km.x, pos_km.y, pos_km.z]
+    };
+
+    // IS-GPS-200 constants, by default
+    let defaults = SolverConstants::default();
+
+    let reference_km = icd_position_km(&eph, t_k, 3.986005E14, 7.2921151467E-5);
+    let err = err_km(&position_km(defaults), &reference_km);
+
+    assert!(err < 1.0E-6, "default constants: error {} km", err);
+
+    // WGS84 constants (NIMA TR8350.2)
+    let wgs84 = defaults.with_constants(
+        Constellation::GPS,
+        OrbitalConstants {
+            gm_m3_s2: 3.986004418E14,
+            omega_rad_s: 7.292115E-5,
+            ..defaults.gps
+        },
+    );
+
+    let wgs84_km = icd_position_km(&eph, t_k, 3.986004418E14, 7.292115E-5);
+    let err = err_km(&position_km(wgs84), &wgs84_km);
+
+    assert!(err < 1.0E-6, "WGS84 constants: error {} km", err);
+
+    // the constants do impact the solution (mostly GM, over 2 hours)
+    let delta_km = err_km(&reference_km, &wgs84_km);
+    assert!(
+        delta_km > 1.0E-3,
+        "constants had no impact ({} km)",
+        delta_km
+    );
+
+    let err = err_km(&position_km(wgs84), &reference_km);
+    assert!(err > 1.0E-3, "WGS84 constants were not applied");
+
+    // Galileo is not impacted
+    assert_eq!(wgs84.galileo, defaults.galileo);
+}
+
+#[test]
+fn kepler_semi_major_axis_rate() {
+    let g10 = SV::from_str("G10").unwrap();
+    let t = Epoch::from_str("2020-06-25T01:00:00 GPST").unwrap();
+
+    let (sqrt_a, dn) = (5.153623002625E+03_f64, 2.145446579149E-09);
+
+    let mut eph = Ephemeris::default();
+
+    for (field, value) in [
+        ("crs", -54.375),
+        ("deltaN", dn),
+        ("m0", 1.032475437231),
+        ("cuc", -1.853704452515E-06),
+        ("e", 7.516302773729E-03),
+        ("cus", 1.013278961182E-05),
+        ("sqrta", sqrt_a),
+        ("toe", 345600.0),
+        ("cic", -2.518296241760E-06),
+        ("omega0", 2.179378003185),
+        ("cis", -8.456408977509E-07),
+        ("i0", 9.576713462938E-01),
+        ("crc", -366.25),
+        ("omega", -1.562017655373),
+        ("omegaDot", -2.667968273468E-09),
+        ("idot", -2.203663219130E-10),
+    ] {
+        eph = eph.with_orbit(field, OrbitItem::F64(value));
+    }
+
+    let eph = eph.with_orbit("week", OrbitItem::U32(2111));
+
+    let a_0 = sqrt_a.powi(2);
+    let gm_m3_s2 = 3.9860050E14; // IS-GPS-200
+
+    let state = eph.solve_debug(g10, t, 30).unwrap();
+    assert_eq!(state.t_k, 3600.0);
+
+    let expected = (gm_m3_s2 / a_0.powi(3)).sqrt() + dn;
+    assert!((state.n - expected).abs() < 1.0E-18);
+
+    // CNAV Adot, as it is named in the database (m.s⁻¹)
+    let a_dot = 1.0E-2;
+    let state = eph
+        .with_orbit("adot", OrbitItem::F64(a_dot))
+        .solve_debug(g10, t, 30)
+        .unwrap();
+
+    // IS-GPS-200 30.3.3.1.3: n0 = sqrt(mu / A_0³) is not impacted
+    assert!(
+        (state.n - expected).abs() < 1.0E-18,
+        "n={:e} expecting {:e}",
+        state.n,
+        expected
+    );
+
+    // A_k = A_0 + Adot t_k
+    let a_k = a_0 + a_dot * state.t_k;
+    let without_rate = eph.solve_debug(g10, t, 30).unwrap();
+
+    // same anomalies: A_k only contributes to the orbit radius
+    assert_eq!(state.e_k, without_rate.e_k);
+    let expected = (a_k - a_0) * (1.0 - 7.516302773729E-03 * state.e_k.cos());
+
+    assert!(
+        ((state.r_k - without_rate.r_k) - expected).abs() < 1.0E-6,
+        "Adot not applied: dr={} m expecting {} m",
+        state.r_k - without_rate.r_k,
+        expected
+    );
+}
+
+#[test]
+fn kepler_reference_and_epoch() {
+    init_logger();
+
+    let g10 = SV::from_str("G10").unwrap();
+    let t_gpst = Epoch::from_str("2020-06-25T02:15:00 GPST").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let (toc, toe, eph) = dut.nav_ephemeris_selection(g10, t_gpst).unwrap();
+
+    let (at_toe, at_t) = eph
+        .resolve_reference_and_epoch(g10, toc, t_gpst, 30)
+        .unwrap();
+
+    assert_eq!(at_toe.epoch, toe);
+    assert_eq!(at_t.epoch, t_gpst);
+
+    // requested state is the regular solution
+    let orbit = eph.kepler2position(g10, t_gpst).unwrap();
+    let err_km = (at_t.radius_km - orbit.radius_km).norm();
+    assert!(err_km < 1.0E-6, "requested state differs by {} km", err_km);
+
+    // at ToE, the orbit radius is the semi major axis, within
+    // the eccentricity and the harmonic corrections (< 1km)
+    let kepler = eph.kepler().unwrap();
+    let a_km = kepler.a * 1.0E-3;
+    let r_km = at_toe.rmag_km();
+
+    assert!(
+        (r_km - a_km).abs() < a_km * kepler.e + 1.0,
+        "ToE radius {} km does not match orbit (a={} km, e={})",
+        r_km,
+        a_km,
+        kepler.e
+    );
+}
+
+#[test]
+fn kepler_beidou_cnav_mean_motion_rate() {
+    let c19 = SV::from_str("C19").unwrap();
+    let toe = Epoch::from_str("2023-03-12T01:00:00 BDT").unwrap();
+
+    let (sqrt_a, e, m0) = (
+        5.282629196167E+03_f64,
+        5.124568217434E-04,
+        5.123456789012E-01,
+    );
+    let (dn0, dn0_dot, a_dot) = (3.512345678901E-09, 1.0E-12, 1.0E-02);
+
+    let mut eph = Ephemeris::default();
+
+    // harmonic corrections are null, so the orbit radius is simply A_k (1 - e cos E_k)
+    for (field, value) in [
+        ("adot", a_dot),
+        ("crs", 0.0),
+        ("deltaN0", dn0),
+        ("m0", m0),
+        ("cuc", 0.0),
+        ("e", e),
+        ("cus", 0.0),
+        ("sqrta", sqrt_a),
+        ("cic", 0.0),
+        ("omega0", 1.234567890123),
+        ("cis", 0.0),
+        ("i0", 9.612345678901E-01),
+        ("crc", 0.0),
+        ("omega", 3.012345678901E-01),
+        ("omegaDot", -6.812345678901E-09),
+        ("idot", 0.0),
+        ("deltaN0Dot", dn0_dot),
+    ] {
+        eph = eph.with_orbit(field, OrbitItem::F64(value));
+    }
+
+    let eph = eph.with_toe(toe);
+
+    // BDS-SIS-ICD-B1C 7.2.4 (CGCS2000 constants)
+    let mu = 3.986004418E14;
+    let t_k = 3600.0;
+
+    let a_0 = sqrt_a.powi(2);
+    let a_k = a_0 + a_dot * t_k;
+
+    let n_0 = (mu / a_0.powi(3)).sqrt();
+    let n_a = n_0 + dn0 + 0.5 * dn0_dot * t_k;
+
+    let m_k = m0 + n_a * t_k;
+
+    // Kepler's equation, solved by Newton iterations
+    let mut e_k = m_k;
+    for _ in 0..10 {
+        e_k -= (e_k - e * e_k.sin() - m_k) / (1.0 - e * e_k.cos());
+    }
+
+    let r_k = a_k * (1.0 - e * e_k.cos());
+
+    let state = eph
+        .solve_debug(c19, toe + Duration::from_seconds(t_k), 30)
+        .unwrap();
+
+    assert!(state.converged);
+    assert_eq!(state.t_k, t_k);
+
+    assert!(
+        (state.n - n_a).abs() < 1.0E-18,
+        "mean motion rate not applied: n={:e} expecting {:e}",
+        state.n,
+        n_a
+    );
+
+    assert!(
+        (state.m_k - m_k).abs() < 1.0E-12,
+        "M_k={} expecting {}",
+        state.m_k,
+        m_k
+    );
+    assert!(
+        (state.e_k - e_k).abs() < 1.0E-12,
+        "E_k={} expecting {}",
+        state.e_k,
+        e_k
+    );
+    assert!(
+        (state.r_k - r_k).abs() < 1.0E-6,
+        "r_k={} m expecting {} m",
+        state.r_k,
+        r_k
+    );
+
+    // B-CNAV frames of a real file: MEO (~27900 km) or IGSO (~42160 km) orbit radius
+    let dut = Rinex::from_gzip_file("data/NAV/V4/BRD400DLR_S_20230710000_01D_MN.rnx.gz").unwrap();
+
+    let mut tested = 0;
+
+    for (k, eph) in dut.nav_ephemeris_frames_iter() {
+        if k.sv.constellation != Constellation::BeiDou || k.sv.is_beidou_geo() {
+            continue;
+        }
+
+        if !matches!(
+            k.msgtype,
+            NavMessageType::CNV1 | NavMessageType::CNV2 | NavMessageType::CNV3
+        ) {
+            continue;
+        }
+
+        // B-CNAV does not stream a week counter
+        let toe = eph.toe_with_msgtype(k.sv, k.msgtype, k.epoch).unwrap();
+        let eph = eph.with_toe(toe);
+
+        let state = eph
+            .solve_debug(k.sv, toe + Duration::from_seconds(3600.0), 30)
+            .unwrap();
+
+        assert!(state.converged);
+
+        assert!(
+            state.r_k > 27_000.0E3 && state.r_k < 43_000.0E3,
+            "{}({}) invalid orbit radius {} m",
+            k.epoch,
+            k.sv,
+            state.r_k
+        );
+
+        tested += 1;
+    }
+
+    assert!(tested > 0, "no BeiDou B-CNAV frame tested");
+}
+
+#[test]
+fn kepler_gps_cnav_mean_motion_rate() {
+    init_logger();
+
+    let g10 = SV::from_str("G10").unwrap();
+    let toe = Epoch::from_str("2023-03-12T02:00:00 GPST").unwrap();
+
+    let (sqrt_a, e, m0, omega) = (
+        5.153623002625E+03_f64,
+        7.516302773729E-03,
+        1.032475437231,
+        -1.562017655373,
+    );
+
+    let (omega0, omega_dot, i0) = (2.179378003185, -8.056408977509E-09, 9.576713462938E-01);
+    let (dn0, dn0_dot) = (4.512345678901E-09, 1.0E-12);
+
+    let mut cnav = Ephemeris::default();
+
+    // harmonic corrections and Adot are null
+    for (field, value) in [
+        ("adot", 0.0),
+        ("crs", 0.0),
+        ("deltaN0", dn0),
+        ("m0", m0),
+        ("cuc", 0.0),
+        ("e", e),
+        ("cus", 0.0),
+        ("sqrta", sqrt_a),
+        ("cic", 0.0),
+        ("omega0", omega0),
+        ("cis", 0.0),
+        ("i0", i0),
+        ("crc", 0.0),
+        ("omega", omega),
+        ("omegaDot", omega_dot),
+        ("idot", 0.0),
+        ("deltaN0Dot", dn0_dot),
+    ] {
+        cnav = cnav.with_orbit(field, OrbitItem::F64(value));
+    }
+
+    let cnav = cnav.with_toe(toe);
+
+    let without_rate = cnav.with_orbit("deltaN0Dot", OrbitItem::F64(0.0));
+
+    for t_k in [900.0, 1800.0, 3600.0] {
+        let t = toe + Duration::from_seconds(t_k);
+
+        let reference_km = icd_position_km(&cnav, t_k, 3.986005E14, 7.2921151467E-5);
+
+        let err_km = |eph: &Ephemeris| {
+            let pos_km = eph.kepler2position(g10, t).unwrap().radius_km;
+            ((pos_km.x - reference_km[0]).powi(2)
+                + (pos_km.y - reference_km[1]).powi(2)
+                + (pos_km.z - reference_km[2]).powi(2))
+            .sqrt()
+        };
+
+        let (err_km, err_without_km) = (err_km(&cnav), err_km(&without_rate));
+
+        assert!(
+            err_km < 1.0E-6,
+            "{}: CNAV differs from IS-GPS-200 by {} km",
+            t,
+            err_km
+        );
+
+        // the rate term is significant: 1/2 Δṅ t_k² along track
+        assert!(
+            err_without_km > err_km + 1.0E-3,
+            "{}: deltaN0Dot did not improve the orbit ({} km vs {} km)",
+            t,
+            err_km,
+            err_without_km
+        );
+    }
+
+    // CNAV frames of a real file are selected,
+    // and agree with the LNAV frames within a few meters
+    let dut = Rinex::from_gzip_file("data/NAV/V4/BRD400DLR_S_20230710000_01D_MN.rnx.gz").unwrap();
+
+    let mut tested = 0;
+
+    for (k, eph) in dut.nav_ephemeris_frames_iter() {
+        if !matches!(k.sv.constellation, Constellation::GPS | Constellation::QZSS) {
+            continue;
+        }
+
+        if !matches!(k.msgtype, NavMessageType::CNAV | NavMessageType::CNV2) {
+            continue;
+        }
+
+        let toe = eph.toe_with_msgtype(k.sv, k.msgtype, k.epoch).unwrap();
+        let t = toe + Duration::from_seconds(1800.0);
+
+        let candidates = dut.nav_ephemeris_selection_all(k.sv, t);
+
+        let (_, cnav_toe, cnav) = candidates
+            .iter()
+            .find(|(_, _, cand)| std::ptr::eq(*cand, eph))
+            .unwrap_or_else(|| panic!("{}({}) CNAV frame not selected", k.epoch, k.sv));
+
+        assert_eq!(*cnav_toe, toe);
+
+        // LNAV frames describe an IODE
+        let lnav = match candidates
+            .iter()
+            .find(|(_, _, cand)| cand.get_orbit_f64("iode").is_some())
+        {
+            Some((_, _, lnav)) => lnav,
+            None => continue,
+        };
+
+        let reference = lnav.kepler2position(k.sv, t).unwrap().radius_km;
+
+        let pos_km = cnav
+            .with_toe(toe)
+            .kepler2position(k.sv, t)
+            .unwrap()
+            .radius_km;
+
+        let err_km = (pos_km - reference).norm();
+
+        assert!(
+            err_km < 20.0E-3,
+            "{}({}) CNAV differs from LNAV by {} km",
+            k.epoch,
+            k.sv,
+            err_km
+        );
+
+        tested += 1;
+    }
+
+    assert!(tested > 0, "no CNAV frame tested");
+}
+
+#[test]
+#[ignore] // fetches the planetary kernels
+fn kepler_orbital_state_in_eci_frame() {
+    use anise::{constants::frames::EARTH_J2000, prelude::Almanac};
+
+    init_logger();
+
+    let almanac = Almanac::until_2035().unwrap();
+
+    let g10 = SV::from_str("G10").unwrap();
+    let t_gpst = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let (_, _, eph) = dut.nav_ephemeris_selection(g10, t_gpst).unwrap();
+
+    let ecef = eph.kepler2position(g10, t_gpst).unwrap();
+
+    let eci = eph
+        .resolve_orbital_state_in_frame(g10, t_gpst, EARTH_J2000, 30, &almanac)
+        .unwrap();
+
+    assert_eq!(eci.epoch, t_gpst);
+    assert_eq!(eci.frame.ephemeris_id, EARTH_J2000.ephemeris_id);
+    assert_eq!(eci.frame.orientation_id, EARTH_J2000.orientation_id);
+
+    // pure rotation: position magnitude is preserved
+    let err_km = (eci.rmag_km() - ecef.rmag_km()).abs();
+    assert!(err_km < 1.0E-6, "|r| differs by {} km", err_km);
+
+    // but the coordinates differ
+    assert!((eci.radius_km - ecef.radius_km).norm() > 1.0);
+}
@@ -1,12 +1,17 @@
 #[cfg(test)]
 mod test {
     use crate::{
-        prelude::{qc::Merge, Rinex},
+        prelude::{
+            qc::{Merge, MergePolicy},
+            Epoch, Observable, Rinex, SV,
+        },
         tests::toolkit::{generic_observation_rinex_test, TimeFrame},
     };
     use std::{
         //fs::remove_file as fs_remove_file,
+        io::BufReader,
         path::PathBuf,
+        str::FromStr,
     };
 
     #[test]
@@ -237,6 +242,145 @@ mod test {
         // let _ = fs_remove_file("ajac-merged.txt");
     }
 
+    #[test]
+    fn merge_all_obs_v3() {
+        // preferred receiver
+        let content =
+            "     3.05           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    1 C1C                                                  SYS / # / OBS TYPES
+  2024     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2024 01 01 00 00  0.0000000  0  1
+G01  20000000.000
+> 2024 01 01 00 00 30.0000000  0  1
+G01  20000030.000
+";
+
+        let rnx_a = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+        let content =
+            "     3.05           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    1 C1C                                                  SYS / # / OBS TYPES
+  2024     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2024 01 01 00 00 30.0000000  0  2
+G01  21000030.000
+G02  22000030.000
+> 2024 01 01 00 01  0.0000000  0  1
+G01  21000060.000
+";
+
+        let rnx_b = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+        let content =
+            "     3.05           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    1 C1C                                                  SYS / # / OBS TYPES
+  2024     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2024 01 01 00 01  0.0000000  0  1
+G01  23000060.000
+> 2024 01 01 00 01 30.0000000  0  1
+G01  23000090.000
+";
+
+        let rnx_c = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+        let files = [rnx_a, rnx_b, rnx_c];
+
+        let g01 = SV::from_str("G01").unwrap();
+        let g02 = SV::from_str("G02").unwrap();
+        let c1c = Observable::from_str("C1C").unwrap();
+
+        let expected_epochs = [
+            "2024-01-01T00:00:00 GPST",
+            "2024-01-01T00:00:30 GPST",
+            "2024-01-01T00:01:00 GPST",
+            "2024-01-01T00:01:30 GPST",
+        ]
+        .iter()
+        .map(|t| Epoch::from_str(t).unwrap())
+        .collect::<Vec<_>>();
+
+        let t_30s = expected_epochs[1];
+        let t_60s = expected_epochs[2];
+
+        for policy in [MergePolicy::GapFilling, MergePolicy::Combine] {
+            let merged = Rinex::merge_all(&files, policy).unwrap();
+
+            assert!(merged.is_merged());
+
+            // no duplicate epochs
+            assert_eq!(merged.epoch_iter().collect::<Vec<_>>(), expected_epochs);
+
+            for (k, sig) in merged.signal_observations_iter() {
+                assert_eq!(sig.observable, c1c);
+
+                if k.epoch == t_30s && sig.sv == g01 {
+                    assert_eq!(
+                        sig.value, 20000030.0,
+                        "{:?}: priority not respected",
+                        policy
+                    );
+                } else if k.epoch == t_60s {
+                    assert_eq!(
+                        sig.value, 21000060.0,
+                        "{:?}: priority not respected",
+                        policy
+                    );
+                } else if sig.sv == g02 {
+                    assert_eq!(policy, MergePolicy::Combine, "receivers should not mix");
+                }
+            }
+
+            let num_g02 = merged
+                .signal_observations_iter()
+                .filter(|(_, sig)| sig.sv == g02)
+                .count();
+
+            match policy {
+                MergePolicy::GapFilling => assert_eq!(num_g02, 0),
+                MergePolicy::Combine => assert_eq!(num_g02, 1),
+            }
+        }
+
+        assert!(Rinex::merge_all(&[], MergePolicy::default()).is_err());
+    }
+
+    #[test]
+    fn merge_obs_v3_signal_order() {
+        let rnx_a = merge_all_obs_v3_parse(
+            "> 2024 01 01 00 00  0.0000000  0  2
+G05  20000005.000
+G02  20000002.000
+",
+        );
+
+        let rnx_b = merge_all_obs_v3_parse(
+            "> 2024 01 01 00 00  0.0000000  0  2
+G03  21000003.000
+G01  21000001.000
+",
+        );
+
+        let sv_order = |rinex: &Rinex| {
+            rinex
+                .signal_observations_iter()
+                .map(|(_, sig)| sig.sv.prn)
+                .collect::<Vec<_>>()
+        };
+
+        // standalone normalization
+        assert_eq!(sv_order(&rnx_a), [5, 2]);
+        assert_eq!(sv_order(&rnx_a.normalize_signal_order()), [2, 5]);
+
+        // merge is normalized and does not depend on the order of operations
+        let ab = rnx_a.merge(&rnx_b).unwrap();
+        let ba = rnx_b.merge(&rnx_a).unwrap();
+
+        assert_eq!(sv_order(&ab), [1, 2, 3, 5]);
+        assert_eq!(sv_order(&ba), [1, 2, 3, 5]);
+    }
+
     // #[cfg(feature = "antex")]
     // use crate::antex::antenna::AntennaMatcher;
     // #[cfg(feature = "antex")]
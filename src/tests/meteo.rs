@@ -1,9 +1,9 @@
 use crate::{
-    prelude::Rinex,
+    prelude::{Epoch, Observable, Rinex},
     tests::toolkit::{generic_meteo_rinex_test, generic_rinex_comparison, TimeFrame},
 };
 
-use std::fs::remove_file;
+use std::{fs::remove_file, str::FromStr};
 
 #[test]
 fn v2_abvi0010_15m() {
@@ -100,6 +100,58 @@ fn v2_abvi0010_15m() {
     let _ = remove_file("v2_abvi0010_15m.txt");
 }
 
+#[test]
+#[cfg(feature = "meteo")]
+fn v2_abvi0010_15m_interpolation() {
+    let path: String = env!("CARGO_MANIFEST_DIR").to_owned() + "/data/MET/V2/abvi0010.15m";
+    let dut = Rinex::from_file(path).unwrap();
+
+    let t0 = Epoch::from_str("2015-01-01T00:00:00 UTC").unwrap();
+    let t1 = Epoch::from_str("2015-01-01T00:01:00 UTC").unwrap();
+    let halfway = Epoch::from_str("2015-01-01T00:00:30 UTC").unwrap();
+
+    let samples = dut
+        .meteo_observations_iter()
+        .filter_map(|(k, v)| {
+            if k.observable == Observable::Temperature && (k.epoch == t0 || k.epoch == t1) {
+                Some(*v)
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(samples.len(), 2);
+
+    let interpolated = dut
+        .meteo_interpolated(&Observable::Temperature, halfway)
+        .unwrap();
+
+    assert!((interpolated - (samples[0] + samples[1]) / 2.0).abs() < 1.0E-9);
+
+    // sampling instant
+    assert_eq!(
+        dut.meteo_interpolated(&Observable::Temperature, t0),
+        Some(samples[0])
+    );
+
+    // no extrapolation
+    let before = Epoch::from_str("2014-12-31T23:59:00 UTC").unwrap();
+    assert!(dut
+        .meteo_interpolated(&Observable::Temperature, before)
+        .is_none());
+
+    let after = Epoch::from_str("2015-01-02T00:01:00 UTC").unwrap();
+    assert!(dut
+        .meteo_interpolated(&Observable::Temperature, after)
+        .is_none());
+
+    // not observed
+    assert!(dut
+        .meteo_interpolated(&Observable::ZenithDryDelay, halfway)
+        .is_none());
+}
+
 #[test]
 #[cfg(feature = "flate2")]
 fn v3_pots00deu() {
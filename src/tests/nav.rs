@@ -1,12 +1,24 @@
 use crate::{
-    navigation::{NavFrameType, NavMessageType},
-    prelude::{Constellation, Epoch, Rinex, TimeScale, SV},
-    tests::toolkit::{generic_navigation_test, TimeFrame},
+    constants::{Wgs84, SPEED_OF_LIGHT_M_S},
+    navigation::{
+        gal::GalNavType, topocentric_az_el_range, Ephemeris, IonosphereModel, NavFrameType, NavKey,
+        NavMessageType, OrbitItem,
+    },
+    prelude::{Constellation, Duration, Epoch, Rinex, TimeScale, Version, SV},
+    tests::{
+        formatting::contains_exponent,
+        toolkit::{generic_navigation_test, TimeFrame},
+    },
 };
 
 use hifitime::Unit;
 
-use std::{path::PathBuf, str::FromStr};
+use std::{
+    collections::HashMap,
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+    str::FromStr,
+};
 
 #[test]
 fn v2_amel0010_21g() {
@@ -1382,3 +1394,1904 @@ fn nav_v2_iono_alphabeta_and_toe() {
     //     // }
     // }
 }
+
+#[test]
+#[cfg(feature = "nav")]
+#[cfg(feature = "flate2")]
+fn nav_sky_position() {
+    let g10 = SV::from_str("G10").unwrap();
+    let t = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    // ground station located in Denmark
+    let (azimuth_deg, elevation_deg, range_m) =
+        dut.sky_position(g10, t, 55.0, 10.0, 0.0, 30).unwrap();
+
+    assert!(
+        (azimuth_deg - 337.25).abs() < 0.5,
+        "G10 azimuth error: {}°",
+        azimuth_deg
+    );
+    assert!(
+        (elevation_deg - 3.42).abs() < 0.5,
+        "G10 elevation error: {}°",
+        elevation_deg
+    );
+    assert!(
+        (range_m - 25_552.5E3).abs() < 5.0E3,
+        "G10 range error: {}m",
+        range_m
+    );
+}
+
+#[test]
+#[cfg(feature = "nav")]
+#[cfg(feature = "flate2")]
+fn nav_topocentric_az_el_range() {
+    let t = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    // ground station located in Denmark
+    let (rx_lat_deg, rx_lon_deg, rx_alt_m) = (55.0, 10.0, 0.0);
+    let rx_ecef_km = Wgs84::geodetic2ecef_km(rx_lat_deg, rx_lon_deg, rx_alt_m);
+
+    let mut num_tests = 0;
+
+    for sv in dut.sv_iter() {
+        let sv_orbit = match dut.sv_orbit(sv, t) {
+            Some(orbit) => orbit,
+            None => continue,
+        };
+
+        // anise based reference
+        let (azimuth_ref, elevation_ref, range_ref) =
+            match dut.sky_position(sv, t, rx_lat_deg, rx_lon_deg, rx_alt_m, 30) {
+                Some(sky) => sky,
+                None => continue,
+            };
+
+        let sv_ecef_km = (
+            sv_orbit.radius_km.x,
+            sv_orbit.radius_km.y,
+            sv_orbit.radius_km.z,
+        );
+
+        let (azimuth_deg, elevation_deg, range_m) = topocentric_az_el_range(sv_ecef_km, rx_ecef_km);
+
+        let mut azimuth_err = (azimuth_deg - azimuth_ref).abs();
+        if azimuth_err > 180.0 {
+            azimuth_err = 360.0 - azimuth_err;
+        }
+
+        assert!(azimuth_err < 0.01, "{} azimuth error: {}°", sv, azimuth_err);
+        assert!(
+            (elevation_deg - elevation_ref).abs() < 0.01,
+            "{} elevation error: {}°",
+            sv,
+            elevation_deg - elevation_ref
+        );
+        assert!(
+            (range_m - range_ref).abs() < 1.0,
+            "{} range error: {}m",
+            sv,
+            range_m - range_ref
+        );
+
+        num_tests += 1;
+    }
+
+    assert!(num_tests > 0);
+}
+
+#[test]
+#[cfg(feature = "nav")]
+#[cfg(feature = "flate2")]
+fn nav_visible_satellites() {
+    let g10 = SV::from_str("G10").unwrap();
+    let t = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    // ground station located in Denmark
+    let visible = dut.visible_satellites(t, (55.0, 10.0, 0.0), 10.0, 30);
+
+    let num_visible = visible.len();
+    assert!(
+        (8..=40).contains(&num_visible),
+        "implausible number of satellites in sight: {}",
+        num_visible
+    );
+
+    for (sv, azelrange) in visible.iter() {
+        assert!(
+            azelrange.elevation_deg >= 10.0,
+            "{} below elevation mask",
+            sv
+        );
+        assert_ne!(*sv, g10, "G10 is below the elevation mask");
+    }
+
+    // G10 is in sight without elevation mask
+    let visible = dut.visible_satellites(t, (55.0, 10.0, 0.0), 0.0, 30);
+    assert!(visible.iter().any(|(sv, _)| *sv == g10));
+}
+
+#[test]
+#[cfg(feature = "nav")]
+#[cfg(feature = "flate2")]
+fn nav_visibility_passes() {
+    let g10 = SV::from_str("G10").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    // ground station located in Denmark
+    let passes = dut.visibility_passes(g10, (55.0, 10.0, 0.0), 10.0, 10.0 * Unit::Minute, 30);
+
+    assert!(
+        !passes.is_empty(),
+        "G10 should be in sight at least once a day"
+    );
+
+    for pass in passes.iter() {
+        assert!(pass.rise <= pass.set, "invalid pass {:?}", pass);
+        assert!(
+            pass.max_elevation_deg >= 10.0 && pass.max_elevation_deg <= 90.0,
+            "invalid max elevation {:?}",
+            pass
+        );
+    }
+
+    for window in passes.windows(2) {
+        assert!(window[0].set < window[1].rise, "overlapping passes");
+    }
+
+    // G10 is right above the horizon at 02:00 (sampling accuracy)
+    let t = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+    let step = 10.0 * Unit::Minute;
+    let passes = dut.visibility_passes(g10, (55.0, 10.0, 0.0), 0.0, step, 30);
+
+    assert!(
+        passes
+            .iter()
+            .any(|pass| pass.rise <= t + step && pass.set >= t - step),
+        "G10 should be in sight at {}",
+        t
+    );
+}
+
+#[test]
+#[cfg(feature = "nav")]
+#[cfg(feature = "flate2")]
+fn nav_ephemeris_selection_ambiguity() {
+    let g10 = SV::from_str("G10").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let toes = dut
+        .nav_ephemeris_frames_iter()
+        .filter(|(k, _)| k.sv == g10)
+        .filter_map(|(_, eph)| eph.toe(g10))
+        .collect::<Vec<_>>();
+
+    let mut num_ambiguous = 0;
+
+    for window in toes.windows(2) {
+        // half way between two publications: both frames apply, equally
+        let t = window[0] + (window[1] - window[0]) / 2;
+
+        let candidates = dut.nav_ephemeris_selection_all(g10, t);
+
+        assert!(candidates.len() >= 2, "{}: frames should overlap", t);
+
+        for (_, _, eph) in candidates.iter() {
+            assert!(eph.is_valid(g10, t));
+        }
+
+        // sorted by preference
+        for pair in candidates.windows(2) {
+            assert!((t - pair[0].1).abs() <= (t - pair[1].1).abs());
+        }
+
+        // the selection is the preferred candidate
+        let (toc, toe, _) = dut.nav_ephemeris_selection(g10, t).unwrap();
+        assert_eq!((toc, toe), (candidates[0].0, candidates[0].1));
+
+        if (t - candidates[0].1).abs() == (t - candidates[1].1).abs() {
+            // oldest frame comes first
+            assert!(candidates[0].0 < candidates[1].0);
+            num_ambiguous += 1;
+        }
+    }
+
+    assert!(num_ambiguous > 0, "no ambiguous selection was tested");
+
+    // no frame applies
+    let t = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+    assert!(dut.nav_ephemeris_selection_all(g10, t).is_empty());
+}
+
+#[test]
+#[cfg(feature = "nav")]
+fn v3_sbas_ephemeris_selection() {
+    let content = "     3.04           N: GNSS NAV DATA    M: Mixed            RINEX VERSION / TYPE
+                                                            END OF HEADER
+S23 2020 06 25 11 00 00 0.000000000000E+00 0.000000000000E+00 3.891680000000E+05
+     2.440047680000E+07 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
+     3.401426944000E+07 0.000000000000E+00 0.000000000000E+00 3.200000000000E+01
+     0.000000000000E+00 0.000000000000E+00 0.000000000000E+00 1.000000000000E+00
+S23 2020 06 25 15 00 00 0.000000000000E+00 0.000000000000E+00 3.891680000000E+05
+     2.440047680000E+07 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
+     3.401426944000E+07 0.000000000000E+00 0.000000000000E+00 3.200000000000E+01
+     0.000000000000E+00 0.000000000000E+00 0.000000000000E+00 1.000000000000E+00
+S23 2020 06 27 12 00 00 0.000000000000E+00 0.000000000000E+00 3.891680000000E+05
+     2.440047680000E+07 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
+     3.401426944000E+07 0.000000000000E+00 0.000000000000E+00 3.200000000000E+01
+     0.000000000000E+00 0.000000000000E+00 0.000000000000E+00 1.000000000000E+00
+";
+
+    let dut = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    let s23 = SV::from_str("S23").unwrap();
+    let t = Epoch::from_str("2020-06-25T12:00:00 GPST").unwrap();
+
+    let before = Epoch::from_str("2020-06-25T11:00:00 GPST").unwrap();
+    let after = Epoch::from_str("2020-06-25T15:00:00 GPST").unwrap();
+
+    // frames on both sides of t: closest ToC first, frames that expired are discarded
+    let candidates = dut
+        .nav_ephemeris_selection_all(s23, t)
+        .into_iter()
+        .map(|(toc, toe, _)| (toc, toe))
+        .collect::<Vec<_>>();
+
+    assert_eq!(candidates, vec![(before, before), (after, after)]);
+
+    // closer to the following frame
+    let t = Epoch::from_str("2020-06-25T14:00:00 GPST").unwrap();
+    let (toc, _, _) = dut.nav_ephemeris_selection(s23, t).unwrap();
+    assert_eq!(toc, after);
+
+    // no frame applies
+    let t = Epoch::from_str("2020-06-29T00:00:00 GPST").unwrap();
+    assert!(dut.nav_ephemeris_selection_all(s23, t).is_empty());
+}
+
+#[test]
+#[cfg(feature = "nav")]
+#[cfg(feature = "flate2")]
+fn nav_sv_validity_intervals() {
+    let g10 = SV::from_str("G10").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let intervals = dut.nav_sv_validity_intervals(g10);
+    assert!(!intervals.is_empty());
+
+    for (start, end) in intervals.iter() {
+        assert!(start < end);
+    }
+
+    for window in intervals.windows(2) {
+        assert!(window[0].1 < window[1].0, "intervals should be merged");
+    }
+
+    // create a 6 hour gap in the navigation message
+    let gap_start = Epoch::from_str("2020-06-25T08:00:00 GPST").unwrap();
+    let gap_end = Epoch::from_str("2020-06-25T14:00:00 GPST").unwrap();
+
+    let mut gapped = Rinex::new_navigation(Version::new(3, 4), Constellation::Mixed);
+
+    for (k, eph) in dut.nav_ephemeris_frames_iter() {
+        if k.sv == g10 && k.epoch >= gap_start && k.epoch <= gap_end {
+            continue;
+        }
+        gapped.add_ephemeris(*k, eph.clone());
+    }
+
+    let gapped_intervals = gapped.nav_sv_validity_intervals(g10);
+    assert_eq!(gapped_intervals.len(), intervals.len() + 1);
+
+    // the gap is not covered by any frame
+    let mid_gap = gap_start + (gap_end - gap_start) / 2;
+
+    assert!(gapped
+        .nav_sv_validity_intervals(g10)
+        .iter()
+        .all(|(start, end)| mid_gap < *start || mid_gap > *end));
+
+    assert!(gapped.nav_ephemeris_selection(g10, mid_gap).is_none());
+    assert!(dut.nav_ephemeris_selection(g10, mid_gap).is_some());
+
+    // no frames for this SV
+    let g99 = SV::from_str("G99").unwrap();
+    assert!(dut.nav_sv_validity_intervals(g99).is_empty());
+}
+
+#[test]
+#[cfg(feature = "nav")]
+#[cfg(feature = "flate2")]
+fn nav_ground_track() {
+    let g10 = SV::from_str("G10").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let start = dut.first_epoch().unwrap();
+    let end = dut.last_epoch().unwrap();
+
+    let track = dut.ground_track(g10, start, end, 10.0 * Unit::Minute, 30);
+
+    assert!(track.len() > 100, "G10 ground track is too short");
+
+    for window in track.windows(2) {
+        assert!(window[0].0 < window[1].0, "not in chronological order");
+    }
+
+    for (t, lat_deg, lon_deg) in track.iter() {
+        assert!(
+            lat_deg.abs() <= 56.0,
+            "{}: latitude {} exceeds GPS inclination",
+            t,
+            lat_deg
+        );
+        assert!(
+            *lon_deg > -180.0 && *lon_deg <= 180.0,
+            "{}: longitude {} not wrapped",
+            t,
+            lon_deg
+        );
+    }
+
+    // the orbit spans the whole latitude range within a day
+    let (min_lat, max_lat) = track
+        .iter()
+        .fold((90.0_f64, -90.0_f64), |(min, max), (_, lat, _)| {
+            (min.min(*lat), max.max(*lat))
+        });
+
+    assert!(min_lat < -50.0, "min latitude: {}", min_lat);
+    assert!(max_lat > 50.0, "max latitude: {}", max_lat);
+
+    // invalid step
+    assert!(dut
+        .ground_track(g10, start, end, Duration::ZERO, 30)
+        .is_empty());
+}
+
+#[test]
+#[cfg(feature = "nav")]
+#[cfg(feature = "flate2")]
+fn nav_dop() {
+    let t = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    // ground station located in Denmark
+    let dop = dut.compute_dop(t, (55.0, 10.0, 0.0), 10.0, 30).unwrap();
+
+    assert!(
+        dop.pdop > 1.0 && dop.pdop < 10.0,
+        "implausible PDOP: {}",
+        dop.pdop
+    );
+    assert!(dop.hdop <= dop.pdop);
+    assert!(dop.vdop <= dop.pdop);
+    assert!(dop.pdop <= dop.gdop);
+}
+
+#[test]
+fn v2_amel0010_21g_d_exponent_round_trip() {
+    let test_resource = env!("CARGO_MANIFEST_DIR").to_owned() + "/data/NAV/V2/amel0010.21g";
+
+    let dut = Rinex::from_file(&test_resource).unwrap();
+
+    let mut writer = BufWriter::new(Vec::new());
+    dut.format(&mut writer).unwrap();
+
+    let content = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+
+    // standard fortran exponent, header included
+    assert!(contains_exponent(&content, b'D'));
+    assert!(!contains_exponent(&content, b'E'));
+
+    let parsed = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    let mut num_tests = 0;
+
+    for ((k, eph), (parsed_k, parsed_eph)) in dut
+        .nav_ephemeris_frames_iter()
+        .zip(parsed.nav_ephemeris_frames_iter())
+    {
+        assert_eq!(k, parsed_k);
+        assert_eq!(eph.clock_bias, parsed_eph.clock_bias);
+        assert_eq!(eph.clock_drift, parsed_eph.clock_drift);
+        assert_eq!(eph.clock_drift_rate, parsed_eph.clock_drift_rate);
+
+        for (field, value) in eph.orbits.iter() {
+            assert_eq!(
+                parsed_eph.orbits.get(field),
+                Some(value),
+                "{} {}",
+                k.sv,
+                field
+            );
+        }
+
+        num_tests += 1;
+    }
+
+    assert_eq!(num_tests, 6);
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn v3_esbc00dnk_header_time_offsets() {
+    let dut = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    // V3 does not use system time frames
+    assert_eq!(dut.nav_system_time_frames_iter().count(), 0);
+
+    let gput = dut
+        .header_time_offsets()
+        .find(|offset| offset.lhs == TimeScale::GPST && offset.rhs == TimeScale::UTC)
+        .expect("missing GPUT definition");
+
+    // 2020-06-25 is GPST week 2111
+    let (week, nanos) = gput.t_ref;
+    assert!((2110..=2112).contains(&week), "invalid GPUT week {}", week);
+    assert!(nanos < 604_800_000_000_000);
+
+    // GPST-UTC (sub-leap) offset is a few ns at most
+    let (a0, a1, _) = gput.polynomial;
+    assert!(a0.abs() < 1.0E-6, "invalid GPUT offset {}", a0);
+    assert!(a1.abs() < 1.0E-9, "invalid GPUT drift {}", a1);
+
+    for (lhs, rhs) in [
+        (TimeScale::GST, TimeScale::UTC),
+        (TimeScale::GST, TimeScale::GPST),
+    ] {
+        assert!(
+            dut.header_time_offsets()
+                .any(|offset| offset.lhs == lhs && offset.rhs == rhs),
+            "missing {}/{} definition",
+            lhs,
+            rhs
+        );
+    }
+}
+
+#[test]
+fn v3_header_ionosphere_models() {
+    let content = "     3.04           N: GNSS NAV DATA    M: Mixed            RINEX VERSION / TYPE
+GPSA   1.1176D-08  2.9802D-08 -5.9605D-08 -1.1921D-07       IONOSPHERIC CORR
+GPSB   1.1469D+05  1.6384D+05 -6.5536D+04 -5.2429D+05       IONOSPHERIC CORR
+QZSB   9.8304D+04  1.3107D+05 -1.3107D+05 -2.6214D+05       IONOSPHERIC CORR
+QZSA   2.7940D-08 -7.4506D-09 -1.1921D-07  5.9605D-08       IONOSPHERIC CORR
+GAL    2.8250D+01  4.6875D-01  1.2207D-02  0.0000D+00       IONOSPHERIC CORR
+                                                            END OF HEADER
+";
+
+    let dut = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    assert_eq!(dut.header_ionosphere_models().count(), 3);
+
+    let (_, gps) = dut
+        .header_ionosphere_models()
+        .find(|(c, _)| **c == Constellation::GPS)
+        .expect("missing GPS model");
+
+    let kb = gps.as_klobuchar().expect("GPS should be a Klobuchar model");
+    assert_eq!(kb.alpha, (1.1176E-08, 2.9802E-08, -5.9605E-08, -1.1921E-07));
+    assert_eq!(kb.beta, (1.1469E+05, 1.6384E+05, -6.5536E+04, -5.2429E+05));
+
+    // B line preceding the A line
+    let (_, qzss) = dut
+        .header_ionosphere_models()
+        .find(|(c, _)| **c == Constellation::QZSS)
+        .expect("missing QZSS model");
+
+    let kb = qzss
+        .as_klobuchar()
+        .expect("QZSS should be a Klobuchar model");
+    assert_eq!(kb.alpha, (2.7940E-08, -7.4506E-09, -1.1921E-07, 5.9605E-08));
+    assert_eq!(kb.beta, (9.8304E+04, 1.3107E+05, -1.3107E+05, -2.6214E+05));
+
+    let (_, gal) = dut
+        .header_ionosphere_models()
+        .find(|(c, _)| **c == Constellation::Galileo)
+        .expect("missing Galileo model");
+
+    let ng = gal
+        .as_nequick_g()
+        .expect("Galileo should be a NequickG model");
+    assert_eq!(ng.a, (2.8250E+01, 4.6875E-01, 1.2207E-02));
+}
+
+#[test]
+#[cfg(feature = "nav")]
+fn v3_qzss_ephemeris_selection() {
+    let content = "     3.04           N: GNSS NAV DATA    M: Mixed            RINEX VERSION / TYPE
+                                                            END OF HEADER
+J01 2020 06 25 00 00 00-1.583695411682E-05-1.136868377216E-13 0.000000000000E+00
+     1.700000000000E+02-5.437500000000E+01 2.145446579149E-09 1.032475437231E+00
+    -1.853704452515E-06 7.516302773729E-02 1.013278961182E-05 6.493023002625E+03
+     3.456000000000E+05-2.518296241760E-06 2.179378003185E+00-8.456408977509E-07
+     7.176713462938E-01-3.662500000000E+02-1.562017655373E+00-2.667968273468E-09
+    -2.203663219130E-10 2.000000000000E+00 2.111000000000E+03 0.000000000000E+00
+     2.800000000000E+00 0.000000000000E+00-3.259629011154E-09 9.220000000000E+02
+     3.405000000000E+05 0.000000000000E+00
+";
+
+    let dut = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    let j01 = SV::from_str("J01").unwrap();
+    assert_eq!(dut.sv_iter().collect::<Vec<_>>(), vec![j01]);
+
+    let t = Epoch::from_str("2020-06-25T00:30:00 GPST").unwrap();
+
+    let (toc, _, eph) = dut
+        .nav_ephemeris_selection(j01, t)
+        .expect("J01 ephemeris selection failed");
+
+    assert_eq!(toc, Epoch::from_str("2020-06-25T00:00:00 QZSST").unwrap());
+
+    // external (193..=202) PRN numbering
+    let j193 = SV {
+        prn: 193,
+        constellation: Constellation::QZSS,
+    };
+
+    let (j193_toc, _, j193_eph) = dut
+        .nav_ephemeris_selection(j193, t)
+        .expect("J193 ephemeris selection failed");
+
+    assert_eq!(j193_toc, toc);
+    assert_eq!(j193_eph, eph);
+}
+
+#[test]
+fn v3_irnss_toe() {
+    let content = "     3.04           N: GNSS NAV DATA    I: IRNSS            RINEX VERSION / TYPE
+                                                            END OF HEADER
+I02 2021 01 01 00 00 00 4.291534423828E-04 1.136868377216E-12 0.000000000000E+00
+     0.000000000000E+00-2.262500000000E+01 1.753644200000E-09 1.203048900000E+00
+    -1.024454800000E-06 1.941275800000E-03 6.135553100000E-06 6.493245700000E+03
+     4.320000000000E+05 1.210719300000E-08-2.217283500000E+00-1.322478100000E-08
+     5.061953100000E-01 3.065312500000E+02-3.024341500000E+00-2.493103700000E-09
+     1.107189000000E-10 0.000000000000E+00 2.138000000000E+03 0.000000000000E+00
+     2.000000000000E+00 0.000000000000E+00-1.862645149231E-09 0.000000000000E+00
+     4.319700000000E+05
+";
+
+    let dut = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    let i02 = SV::from_str("I02").unwrap();
+
+    let (key, eph) = dut
+        .nav_ephemeris_frames_iter()
+        .next()
+        .expect("missing I02 ephemeris");
+
+    assert_eq!(key.sv, i02);
+    assert_eq!(eph.get_week(), Some(2138));
+
+    // IRNSS week is aligned to the GPS week
+    let toe = eph.toe(i02).expect("IRNSS ToE should be defined");
+    assert_eq!(toe, Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap());
+    assert_eq!(toe, key.epoch);
+
+    assert!(eph.is_valid(i02, toe + 1.0 * Unit::Hour));
+    assert!(!eph.is_valid(i02, toe + 3.0 * Unit::Hour));
+}
+
+#[test]
+fn v3_galileo_inav_fnav_selection() {
+    let content = "     3.04           N: GNSS NAV DATA    E: GALILEO          RINEX VERSION / TYPE
+                                                            END OF HEADER
+E01 2020 06 25 00 00 00-5.583695411682E-04-7.958078640513E-12 0.000000000000E+00
+     8.000000000000E+01-5.437500000000E+01 2.945446579149E-09 1.032475437231E+00
+    -1.853704452515E-06 3.516302773729E-04 1.013278961182E-05 5.440623002625E+03
+     3.456000000000E+05-2.518296241760E-08 2.179378003185E+00-8.456408977509E-08
+     9.576713462938E-01 1.662500000000E+02-1.562017655373E+00-5.667968273468E-09
+    -2.203663219130E-10 5.170000000000E+02 2.111000000000E+03 0.000000000000E+00
+     3.120000000000E+00 0.000000000000E+00-3.259629011154E-09-3.725290298462E-09
+     3.462000000000E+05 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
+E01 2020 06 25 00 10 00-5.583695411682E-04-7.958078640513E-12 0.000000000000E+00
+     8.100000000000E+01-5.437500000000E+01 2.945446579149E-09 1.032475437231E+00
+    -1.853704452515E-06 3.516302773729E-04 1.013278961182E-05 5.440623002625E+03
+     3.462000000000E+05-2.518296241760E-08 2.179378003185E+00-8.456408977509E-08
+     9.576713462938E-01 1.662500000000E+02-1.562017655373E+00-5.667968273468E-09
+    -2.203663219130E-10 2.580000000000E+02 2.111000000000E+03 0.000000000000E+00
+     3.120000000000E+00 0.000000000000E+00-3.259629011154E-09-3.725290298462E-09
+     3.468000000000E+05 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
+";
+
+    let dut = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    let e01 = SV::from_str("E01").unwrap();
+
+    let inav_toc = Epoch::from_str("2020-06-25T00:00:00 GST").unwrap();
+    let fnav_toc = Epoch::from_str("2020-06-25T00:10:00 GST").unwrap();
+
+    for (k, eph) in dut.nav_ephemeris_frames_iter() {
+        let nav_type = eph.galileo_nav_type(k.msgtype);
+        if k.epoch == inav_toc {
+            assert_eq!(nav_type, Some(GalNavType::INav));
+        } else {
+            assert_eq!(nav_type, Some(GalNavType::FNav));
+        }
+    }
+
+    let t = Epoch::from_str("2020-06-25T00:30:00 GST").unwrap();
+
+    // closest frame is the F/NAV frame
+    let (toc, _, _) = dut.nav_ephemeris_selection(e01, t).unwrap();
+    assert_eq!(toc, fnav_toc);
+
+    let (toc, _, eph) = dut
+        .nav_ephemeris_selection_galileo(e01, t, GalNavType::FNav)
+        .unwrap();
+    assert_eq!(toc, fnav_toc);
+    assert_eq!(eph.get_orbit_f64("iodnav"), Some(81.0));
+
+    let (toc, _, eph) = dut
+        .nav_ephemeris_selection_galileo(e01, t, GalNavType::INav)
+        .unwrap();
+    assert_eq!(toc, inav_toc);
+    assert_eq!(eph.get_orbit_f64("iodnav"), Some(80.0));
+
+    // I/NAV frame no longer valid: F/NAV is the only option
+    let t = Epoch::from_str("2020-06-25T03:05:00 GST").unwrap();
+
+    let (toc, _, _) = dut
+        .nav_ephemeris_selection_galileo(e01, t, GalNavType::INav)
+        .unwrap();
+    assert_eq!(toc, fnav_toc);
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn v3_esbc00dnk_retain_constellations() {
+    let dut = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let filter = [Constellation::GPS, Constellation::Galileo];
+    let retained = dut.retain_constellations(&filter);
+
+    let expected = dut
+        .sv_iter()
+        .filter(|sv| filter.contains(&sv.constellation))
+        .collect::<Vec<_>>();
+
+    assert!(!expected.is_empty());
+    assert_eq!(retained.sv_iter().collect::<Vec<_>>(), expected);
+
+    assert!(retained
+        .header
+        .ionod_corrections
+        .keys()
+        .all(|c| filter.contains(c)));
+
+    let g01 = SV::from_str("G01").unwrap();
+    let retained = dut.retain_satellites(&[g01]);
+
+    assert_eq!(retained.sv_iter().collect::<Vec<_>>(), vec![g01]);
+    assert!(retained.nav_ephemeris_frames_iter().count() > 0);
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn v3_mojn00dnk_ephemeris_updates() {
+    let dut = Rinex::from_gzip_file("data/NAV/V3/MOJN00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    for sv in dut.sv_iter() {
+        let frames = dut
+            .nav_ephemeris_frames_iter()
+            .filter_map(|(k, eph)| if k.sv == sv { Some(eph) } else { None })
+            .collect::<Vec<_>>();
+
+        // each frame is compared to the previous one
+        let num_sets = 1 + frames
+            .windows(2)
+            .filter(|pair| pair[1].differs_from(pair[0]))
+            .count();
+
+        assert!(num_sets <= frames.len());
+
+        for pair in frames.windows(2) {
+            assert!(!pair[0].differs_from(pair[0]), "{} differs from itself", sv);
+
+            // a new issue of data is a new set
+            if pair[1].iode_changed(pair[0]) {
+                assert!(pair[1].differs_from(pair[0]));
+            }
+        }
+
+        // GPS ephemerides are renewed every two hours
+        if sv.constellation == Constellation::GPS && frames.len() > 1 {
+            assert!(num_sets > 1, "{} did not broadcast new ephemeris", sv);
+        }
+    }
+}
+
+#[test]
+fn v2_amel0010_21g_glonass_is_valid_at() {
+    let test_resource = env!("CARGO_MANIFEST_DIR").to_owned() + "/data/NAV/V2/amel0010.21g";
+
+    let dut = Rinex::from_file(&test_resource).unwrap();
+
+    let (k, eph) = dut.nav_ephemeris_frames_iter().next().unwrap();
+    assert_eq!(k.sv.constellation, Constellation::Glonass);
+
+    let window = Ephemeris::validity_duration(Constellation::Glonass).unwrap();
+    let edge = window - 1.0 * Unit::Second;
+
+    // the frame's own tb is used
+    assert!(eph.is_valid_at(k, k.epoch));
+    assert!(eph.is_valid_at(k, k.epoch + edge));
+    assert!(eph.is_valid_at(k, k.epoch - edge));
+    assert!(!eph.is_valid_at(k, k.epoch + window));
+    assert!(!eph.is_valid_at(k, k.epoch - window));
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn esbcdnk_sbas_is_valid_at() {
+    let rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let (k, eph) = rinex
+        .nav_ephemeris_frames_iter()
+        .find(|(k, _)| k.sv.constellation.is_sbas())
+        .unwrap();
+
+    // GEO frames do not describe a ToE
+    assert!(!eph.is_valid(k.sv, k.epoch));
+
+    // but are valid around their ToC
+    let window = Ephemeris::validity_duration(k.sv.constellation).unwrap();
+    let edge = window - 1.0 * Unit::Second;
+
+    assert!(eph.is_valid_at(k, k.epoch));
+    assert!(eph.is_valid_at(k, k.epoch + 12.0 * Unit::Hour));
+    assert!(eph.is_valid_at(k, k.epoch + edge));
+    assert!(eph.is_valid_at(k, k.epoch - edge));
+    assert!(!eph.is_valid_at(k, k.epoch + window));
+    assert!(!eph.is_valid_at(k, k.epoch - window));
+    assert!(!eph.is_valid_at(k, k.epoch + 2.0 * Unit::Day));
+}
+
+#[test]
+fn v2_amel0010_21g_glonass_toe() {
+    let test_resource = env!("CARGO_MANIFEST_DIR").to_owned() + "/data/NAV/V2/amel0010.21g";
+
+    let dut = Rinex::from_file(&test_resource).unwrap();
+
+    let mut num_tests = 0;
+
+    for (k, eph) in dut.nav_ephemeris_frames_iter() {
+        // tb is ToC expressed in Moscow time
+        let tb = eph.glonass_tb_minutes().unwrap();
+        assert_eq!(
+            tb % 15,
+            0,
+            "{}({}) - tb should be a 15' multiple",
+            k.epoch,
+            k.sv
+        );
+
+        // ToC is ToE for Glonass
+        let toe = eph.glonass_toe(k.sv, k.epoch).unwrap();
+        assert_eq!(toe, k.epoch, "{}({}) - invalid ToE", k.epoch, k.sv);
+
+        // reference may be anywhere within the publication time frame
+        let toe = eph
+            .glonass_toe(k.sv, k.epoch + 20.0 * Unit::Minute)
+            .unwrap();
+        assert_eq!(toe, k.epoch, "{}({}) - invalid ToE", k.epoch, k.sv);
+
+        assert!(eph.is_valid(k.sv, k.epoch + 10.0 * Unit::Minute));
+        assert!(!eph.is_valid(k.sv, k.epoch + 1.0 * Unit::Hour));
+
+        // Not applicable to other constellations
+        let g01 = SV::from_str("G01").unwrap();
+        assert!(eph.glonass_toe(g01, k.epoch).is_none());
+
+        num_tests += 1;
+    }
+
+    assert_eq!(num_tests, 6);
+
+    // R01 was published on previous day (in UTC),
+    // but on the same day in Moscow time
+    let r01 = SV::from_str("R01").unwrap();
+    let t = Epoch::from_str("2020-12-31T23:50:00 UTC").unwrap();
+
+    let (toc, toe, eph) = dut.nav_ephemeris_selection(r01, t).unwrap();
+    assert_eq!(toc, Epoch::from_str("2020-12-31T23:45:00 UTC").unwrap());
+    assert_eq!(toe, toc);
+    assert_eq!(eph.glonass_tb_minutes(), Some(165));
+}
+
+#[test]
+#[cfg(all(feature = "clock", feature = "flate2"))]
+fn v3_esbc00dnk_clock_residual() {
+    let nav = Rinex::from_gzip_file(
+        env!("CARGO_MANIFEST_DIR").to_owned()
+            + "/data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz",
+    )
+    .unwrap();
+
+    let clk = Rinex::from_gzip_file(
+        env!("CARGO_MANIFEST_DIR").to_owned()
+            + "/data/CLK/V3/GRG0MGXFIN_20201770000_01D_30S_CLK.CLK.gz",
+    )
+    .unwrap();
+
+    let mut num_tests = 0;
+
+    for t in [
+        "2020-06-25T06:00:15 GPST",
+        "2020-06-25T12:00:15 GPST",
+        "2020-06-25T18:00:15 GPST",
+    ] {
+        let t = Epoch::from_str(t).unwrap();
+
+        for prn in 1..=32 {
+            let sv = SV::new(Constellation::GPS, prn);
+
+            if let Some(residual) = nav.nav_clock_residual(sv, t, &clk, 10) {
+                // broadcast clock is accurate to a few ns
+                assert!(
+                    residual.abs() < 50.0 * Unit::Nanosecond,
+                    "{}({}) - clock residual too large: {}",
+                    t,
+                    sv,
+                    residual,
+                );
+                num_tests += 1;
+            }
+        }
+    }
+
+    assert!(num_tests > 0, "no clock residual was evaluated");
+
+    // precise product does not extrapolate
+    let g01 = SV::from_str("G01").unwrap();
+    let t = Epoch::from_str("2020-06-26T12:00:00 GPST").unwrap();
+    assert!(nav.nav_clock_residual(g01, t, &clk, 10).is_none());
+}
+
+#[test]
+#[cfg(all(feature = "clock", feature = "flate2"))]
+fn v3_esbc00dnk_sisre() {
+    let nav = Rinex::from_gzip_file(
+        env!("CARGO_MANIFEST_DIR").to_owned()
+            + "/data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz",
+    )
+    .unwrap();
+
+    let clk = Rinex::from_gzip_file(
+        env!("CARGO_MANIFEST_DIR").to_owned()
+            + "/data/CLK/V3/GRG0MGXFIN_20201770000_01D_30S_CLK.CLK.gz",
+    )
+    .unwrap();
+
+    // This crate does not parse SP3 files: no precise orbit is available.
+    // The reference orbit is the neighbouring broadcast frame (different ToE),
+    // which is an independent set of Keplerian elements. This verifies the
+    // continuity of consecutive broadcast orbits, not their absolute accuracy.
+    let neighbour_orbit = |sv: SV, t: Epoch| {
+        let candidates = nav.nav_ephemeris_selection_all(sv, t);
+        let (_, selected_toe, _) = candidates.first()?;
+
+        let (_, _, eph) = candidates.iter().find(|(_, toe, _)| toe != selected_toe)?;
+
+        let pos_vel = eph.kepler2position(sv, t)?.to_cartesian_pos_vel();
+        Some((pos_vel[0], pos_vel[1], pos_vel[2]))
+    };
+
+    // broadcast position, offset by (radial, along track) meters.
+    // This only verifies the projection and weighting of the orbit error.
+    let offset_orbit = |offset_m: (f64, f64)| {
+        let nav = &nav;
+        move |sv: SV, t: Epoch| {
+            let pos_vel = nav.sv_orbit(sv, t)?.to_cartesian_pos_vel();
+            let (position, velocity) = (pos_vel.fixed_rows::<3>(0), pos_vel.fixed_rows::<3>(3));
+
+            let radial = position.normalize();
+            let along = position.cross(&velocity).normalize().cross(&radial);
+
+            let precise = position + (radial * offset_m.0 + along * offset_m.1) / 1.0E3;
+            Some((precise[0], precise[1], precise[2]))
+        }
+    };
+
+    let mut num_tests = 0;
+    let mut num_continuity_tests = 0;
+
+    for t in [
+        "2020-06-25T06:00:15 GPST",
+        "2020-06-25T12:00:15 GPST",
+        "2020-06-25T18:00:15 GPST",
+    ] {
+        let t = Epoch::from_str(t).unwrap();
+
+        for prn in 1..=32 {
+            let sv = SV::new(Constellation::GPS, prn);
+
+            let clock_m = match nav.nav_clock_residual(sv, t, &clk, 10) {
+                Some(residual) => residual.to_seconds() * SPEED_OF_LIGHT_M_S,
+                None => continue,
+            };
+
+            if let Some((x_km, y_km, z_km)) = neighbour_orbit(sv, t) {
+                let pos_vel = nav.sv_orbit(sv, t).unwrap().to_cartesian_pos_vel();
+
+                let orbit_error_m = ((pos_vel[0] - x_km).powi(2)
+                    + (pos_vel[1] - y_km).powi(2)
+                    + (pos_vel[2] - z_km).powi(2))
+                .sqrt()
+                    * 1.0E3;
+
+                // consecutive broadcast frames agree within a few meters
+                assert!(
+                    orbit_error_m < 10.0,
+                    "{}({}) - discontinuity: {}m",
+                    t,
+                    sv,
+                    orbit_error_m
+                );
+
+                let sisre = nav
+                    .sisre(sv, t, |_, _| Some((x_km, y_km, z_km)), &clk, 10)
+                    .unwrap();
+
+                // weights are <= 1: orbit contribution is bounded by the 3D error
+                assert!(
+                    (sisre - clock_m.abs()).abs() <= orbit_error_m + 1.0E-3,
+                    "{}({}) - sisre={} clock={} orbit={}",
+                    t,
+                    sv,
+                    sisre,
+                    clock_m,
+                    orbit_error_m
+                );
+
+                // broadcast quality: a few meters (refer to the clock residual test)
+                assert!(sisre < 15.0, "{}({}) - sisre too large: {}m", t, sv, sisre);
+
+                num_continuity_tests += 1;
+            }
+
+            // null orbit error: clock residual only
+            let sisre = nav
+                .sisre(sv, t, offset_orbit((0.0, 0.0)), &clk, 10)
+                .unwrap();
+
+            assert!(
+                (sisre - clock_m.abs()).abs() < 1.0E-3,
+                "{}({}) - sisre={} clock={}",
+                t,
+                sv,
+                sisre,
+                clock_m
+            );
+
+            // radial error is mostly retained
+            let sisre = nav
+                .sisre(sv, t, offset_orbit((1.0, 0.0)), &clk, 10)
+                .unwrap();
+            let expected = (-0.98 - clock_m).abs();
+            assert!((sisre - expected).abs() < 1.0E-3, "{}({})", t, sv);
+
+            // along track error is mostly projected out
+            let sisre = nav
+                .sisre(sv, t, offset_orbit((0.0, 7.0)), &clk, 10)
+                .unwrap();
+            let expected = (clock_m.powi(2) + 1.0).sqrt();
+            assert!((sisre - expected).abs() < 1.0E-3, "{}({})", t, sv);
+
+            num_tests += 1;
+        }
+    }
+
+    assert!(num_tests > 0, "no sisre was evaluated");
+    assert!(num_continuity_tests > 0, "no neighbouring frame was found");
+
+    // SBAS is not supported
+    let s23 = SV::from_str("S23").unwrap();
+    let t = Epoch::from_str("2020-06-25T12:00:15 GPST").unwrap();
+    assert!(nav
+        .sisre(s23, t, |_, _| Some((0.0, 0.0, 0.0)), &clk, 10)
+        .is_none());
+}
+
+#[test]
+fn v3_new_navigation_builder() {
+    let g10 = SV::from_str("G10").unwrap();
+    let toc = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+
+    let mut orbits = [
+        ("iode", 170.0),
+        ("crs", -54.375),
+        ("deltaN", 2.145446579149E-09),
+        ("m0", 1.032475437231),
+        ("cuc", -1.853704452515E-06),
+        ("e", 7.516302773729E-03),
+        ("cus", 1.013278961182E-05),
+        ("sqrta", 5.153623002625E+03),
+        ("toe", 345600.0),
+        ("cic", -2.518296241760E-06),
+        ("omega0", 2.179378003185),
+        ("cis", -8.456408977509E-07),
+        ("i0", 9.576713462938E-01),
+        ("crc", -366.25),
+        ("omega", -1.562017655373),
+        ("omegaDot", -2.667968273468E-09),
+        ("idot", -2.203663219130E-10),
+        ("l2Codes", 1.0),
+        ("accuracy", 2.0),
+        ("tgd", -3.259629011154E-09),
+        ("iodc", 426.0),
+        ("t_tm", 340500.0),
+        ("fitInt", 4.0),
+    ]
+    .into_iter()
+    .map(|(field, value)| (field.to_string(), OrbitItem::F64(value)))
+    .collect::<HashMap<_, _>>();
+
+    orbits.insert("week".to_string(), OrbitItem::U32(2111));
+
+    let ephemeris = Ephemeris {
+        clock_bias: -1.583695411682E-05,
+        clock_drift: -1.136868377216E-13,
+        clock_drift_rate: 0.0,
+        orbits,
+    };
+
+    let key = NavKey {
+        epoch: toc,
+        sv: g10,
+        msgtype: NavMessageType::LNAV,
+        frmtype: NavFrameType::Ephemeris,
+    };
+
+    let mut dut = Rinex::new_navigation(Version::new(3, 4), Constellation::GPS);
+    dut.add_ephemeris(key, ephemeris.clone());
+
+    assert_eq!(dut.header.version, Version::new(3, 4));
+    assert_eq!(dut.nav_ephemeris_frames_iter().count(), 1);
+
+    let mut writer = BufWriter::new(Vec::new());
+    dut.format(&mut writer).unwrap();
+
+    let content = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+
+    let parsed = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    assert!(parsed.is_navigation_rinex());
+    assert_eq!(parsed.header.constellation, Some(Constellation::GPS));
+    assert_eq!(parsed.sv_iter().collect::<Vec<_>>(), vec![g10]);
+
+    let (parsed_key, parsed_eph) = parsed
+        .nav_ephemeris_frames_iter()
+        .next()
+        .expect("missing G10 ephemeris");
+
+    assert_eq!(*parsed_key, key);
+    assert_eq!(parsed_eph.sv_clock(), ephemeris.sv_clock());
+
+    for (field, value) in ephemeris.orbits.iter() {
+        assert_eq!(parsed_eph.orbits.get(field), Some(value), "{}", field);
+    }
+
+    assert_eq!(parsed_eph.toe(g10), ephemeris.toe(g10));
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn nav_v4_ionosphere_models_iter() {
+    let fullpath = format!(
+        "{}/data/NAV/V4/BRD400DLR_S_20230710000_01D_MN.rnx.gz",
+        env!("CARGO_MANIFEST_DIR")
+    );
+
+    let rinex = Rinex::from_gzip_file(&fullpath).unwrap();
+
+    let (mut kb, mut ng, mut bd) = (0, 0, 0);
+
+    for (k, model) in rinex.nav_ionosphere_models_iter() {
+        assert_eq!(k.frmtype, NavFrameType::IonosphereModel);
+        match model {
+            IonosphereModel::Klobuchar(_) => kb += 1,
+            IonosphereModel::NequickG(_) => ng += 1,
+            IonosphereModel::Bdgim(_) => bd += 1,
+        }
+    }
+
+    let total = rinex.nav_ionosphere_models_iter().count();
+
+    assert!(total > 0, "no ionosphere model found");
+    assert_eq!(total, kb + ng + bd);
+
+    assert_eq!(kb, rinex.nav_klobuchar_models_iter().count());
+    assert_eq!(ng, rinex.nav_nequickg_models_iter().count());
+    assert_eq!(bd, rinex.nav_bdgim_models_iter().count());
+
+    // this file mixes several kinds of models
+    assert!(kb > 0, "no klobuchar model found");
+    assert!(ng > 0, "no nequick-g model found");
+}
+
+#[test]
+fn nav_gps_l1c_health_detail() {
+    let parse_cnv2_health = |value: &str| {
+        let health = OrbitItem::new(
+            "health",
+            "flag",
+            value,
+            &NavMessageType::CNV2,
+            Constellation::GPS,
+        )
+        .unwrap();
+
+        Ephemeris::default().with_orbit("health", health)
+    };
+
+    // healthy L1C signal
+    let ephemeris = parse_cnv2_health("0.000000000000E+00");
+    let detail = ephemeris.l1c_health_detail().unwrap();
+
+    assert!(detail.l1c_healthy);
+    assert_eq!(detail.spare_bits, 0);
+    assert!(detail.healthy());
+    assert!(ephemeris.sv_healthy());
+
+    // unhealthy L1C signal
+    let ephemeris = parse_cnv2_health("1.000000000000E+00");
+    let detail = ephemeris.l1c_health_detail().unwrap();
+
+    assert!(!detail.l1c_healthy);
+    assert_eq!(detail.spare_bits, 0);
+    assert!(!ephemeris.sv_healthy());
+
+    // non standard word: spare bits are retained but not trusted
+    let ephemeris = parse_cnv2_health("6.000000000000E+00");
+    let detail = ephemeris.l1c_health_detail().unwrap();
+
+    assert!(detail.l1c_healthy);
+    assert_eq!(detail.spare_bits, 0x6);
+    assert!(!detail.healthy());
+    assert!(!ephemeris.sv_healthy());
+
+    // LNAV frames do not carry the L1C health word
+    let health = OrbitItem::new(
+        "health",
+        "flag",
+        "0.000000000000E+00",
+        &NavMessageType::LNAV,
+        Constellation::GPS,
+    )
+    .unwrap();
+
+    let ephemeris = Ephemeris::default().with_orbit("health", health);
+    assert!(ephemeris.l1c_health_detail().is_none());
+}
+
+#[test]
+fn nav_glonass_health_detail() {
+    let parse_fdma = |key: &str, value: &str| {
+        OrbitItem::new(
+            key,
+            "flag",
+            value,
+            &NavMessageType::FDMA,
+            Constellation::Glonass,
+        )
+        .unwrap()
+    };
+
+    // GLO-M satellite, almanac is reported
+    let status = parse_fdma("status", "1.280000000000E+02");
+
+    // healthy: Bn=0, Cn=1, ln=0
+    let ephemeris = Ephemeris::default()
+        .with_orbit("health", parse_fdma("health", "0.000000000000E+00"))
+        .with_orbit("health2", parse_fdma("health2", "3.000000000000E+00"))
+        .with_orbit("status", status.clone());
+
+    let detail = ephemeris.glonass_health_detail().unwrap();
+
+    assert!(detail.bn_healthy);
+    assert_eq!(detail.cn_healthy, Some(true));
+    assert_eq!(detail.ln_healthy, Some(true));
+    assert!(detail.healthy());
+    assert!(ephemeris.sv_healthy());
+
+    // string 3 declared invalid: ln=1
+    let ephemeris = Ephemeris::default()
+        .with_orbit("health", parse_fdma("health", "0.000000000000E+00"))
+        .with_orbit("health2", parse_fdma("health2", "7.000000000000E+00"))
+        .with_orbit("status", status.clone());
+
+    let detail = ephemeris.glonass_health_detail().unwrap();
+
+    assert!(detail.bn_healthy);
+    assert_eq!(detail.cn_healthy, Some(true));
+    assert_eq!(detail.ln_healthy, Some(false));
+    assert!(!detail.healthy());
+    assert!(!ephemeris.sv_healthy());
+
+    // unhealthy ephemeris and almanac: Bn=1, Cn=0
+    let ephemeris = Ephemeris::default()
+        .with_orbit("health", parse_fdma("health", "1.000000000000E+00"))
+        .with_orbit("health2", parse_fdma("health2", "2.000000000000E+00"))
+        .with_orbit("status", status);
+
+    let detail = ephemeris.glonass_health_detail().unwrap();
+
+    assert!(!detail.bn_healthy);
+    assert_eq!(detail.cn_healthy, Some(false));
+    assert!(!detail.healthy());
+    assert!(!ephemeris.sv_healthy());
+
+    // legacy frame: Bn only
+    let ephemeris =
+        Ephemeris::default().with_orbit("health", parse_fdma("health", "0.000000000000E+00"));
+
+    let detail = ephemeris.glonass_health_detail().unwrap();
+
+    assert!(detail.bn_healthy);
+    assert!(detail.cn_healthy.is_none());
+    assert!(detail.ln_healthy.is_none());
+    assert!(detail.healthy());
+
+    // unexpected subsidary health word does not panic
+    let ephemeris = Ephemeris::default()
+        .with_orbit("health", parse_fdma("health", "0.000000000000E+00"))
+        .with_orbit("health2", OrbitItem::from(3.0));
+
+    assert!(ephemeris.sv_healthy());
+    assert!(ephemeris.glonass_health_detail().unwrap().healthy());
+
+    // not a Glonass frame
+    assert!(Ephemeris::default().glonass_health_detail().is_none());
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn nav_v4_cnav_toe() {
+    let fullpath = format!(
+        "{}/data/NAV/V4/BRD400DLR_S_20230710000_01D_MN.rnx.gz",
+        env!("CARGO_MANIFEST_DIR")
+    );
+
+    let rinex = Rinex::from_gzip_file(&fullpath).unwrap();
+
+    let (mut cnav, mut bds_cnav) = (0, 0);
+
+    for (k, eph) in rinex.nav_ephemeris_frames_iter() {
+        match (k.sv.constellation, k.msgtype) {
+            (
+                Constellation::GPS | Constellation::QZSS,
+                NavMessageType::CNAV | NavMessageType::CNV2,
+            ) => {
+                // no week counter in CNAV
+                assert!(eph.toe(k.sv).is_none());
+
+                let toe = eph.toe_with_msgtype(k.sv, k.msgtype, k.epoch).unwrap();
+                assert_eq!(toe, k.epoch, "{}({}) invalid CNAV ToE", k.epoch, k.sv);
+                assert_eq!(toe.time_scale, TimeScale::GPST);
+
+                cnav += 1;
+            },
+            (
+                Constellation::BeiDou,
+                NavMessageType::CNV1 | NavMessageType::CNV2 | NavMessageType::CNV3,
+            ) => {
+                let toe = eph.toe_with_msgtype(k.sv, k.msgtype, k.epoch).unwrap();
+
+                assert_eq!(toe.time_scale, TimeScale::BDT);
+
+                // seconds of week are preserved
+                let (_, nanos) = toe.to_time_of_week();
+                let toe_s = eph.get_orbit_f64("toe").unwrap();
+                assert_eq!(nanos, (toe_s * 1.0E9).round() as u64);
+
+                // week resolved next to ToC
+                assert!(
+                    (toe - k.epoch).abs() < 3.5 * Unit::Day,
+                    "{}({}) invalid BeiDou CNAV ToE {}",
+                    k.epoch,
+                    k.sv,
+                    toe
+                );
+
+                bds_cnav += 1;
+            },
+            _ => {
+                // other messages are not affected
+                assert_eq!(
+                    eph.toe_with_msgtype(k.sv, k.msgtype, k.epoch),
+                    eph.toe(k.sv)
+                );
+            },
+        }
+    }
+
+    assert!(cnav > 0, "no CNAV frames found");
+    assert!(bds_cnav > 0, "no BeiDou CNAV frames found");
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn nav_v4_cnav_selection() {
+    let fullpath = format!(
+        "{}/data/NAV/V4/BRD400DLR_S_20230710000_01D_MN.rnx.gz",
+        env!("CARGO_MANIFEST_DIR")
+    );
+
+    let rinex = Rinex::from_gzip_file(&fullpath).unwrap();
+
+    let mut tested = 0;
+
+    for (k, eph) in rinex.nav_ephemeris_frames_iter() {
+        let cnav = matches!(
+            (k.sv.constellation, k.msgtype),
+            (
+                Constellation::GPS,
+                NavMessageType::CNAV | NavMessageType::CNV2
+            ) | (
+                Constellation::BeiDou,
+                NavMessageType::CNV1 | NavMessageType::CNV2 | NavMessageType::CNV3
+            )
+        );
+
+        if !cnav {
+            continue;
+        }
+
+        let toe = eph.toe_with_msgtype(k.sv, k.msgtype, k.epoch).unwrap();
+        let t = toe + 10.0 * Unit::Minute;
+
+        assert!(eph.is_valid_with_msgtype(k.sv, k.msgtype, k.epoch, t));
+        assert!(!eph.is_valid_with_msgtype(k.sv, k.msgtype, k.epoch, toe + 1.0 * Unit::Day));
+
+        // CNAV frames are selectable
+        let candidates = rinex.nav_ephemeris_selection_all(k.sv, t);
+
+        assert!(
+            candidates
+                .iter()
+                .any(|(toc, cand_toe, _)| *toc == k.epoch && *cand_toe == toe),
+            "{}({}) {} frame not selected",
+            k.epoch,
+            k.sv,
+            k.msgtype
+        );
+
+        tested += 1;
+    }
+
+    assert!(tested > 0, "no CNAV frame tested");
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn v3_esbc00dnk_record_len() {
+    let dut = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    assert!(!dut.is_empty());
+
+    // one entry per frame
+    assert!(dut.record_len() >= dut.nav_ephemeris_frames_iter().count());
+    assert_eq!(dut.record_len(), dut.epoch_iter().count());
+
+    // several satellites share the same epoch
+    assert!(dut.epoch_count() > 0);
+    assert!(dut.epoch_count() < dut.record_len());
+
+    let null = Rinex::basic_nav();
+    assert!(null.is_empty());
+    assert_eq!(null.record_len(), 0);
+    assert_eq!(null.epoch_count(), 0);
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn v4_brd400dlr_system_time_offsets_formatting() {
+    let rinex = Rinex::from_gzip_file("data/NAV/V4/BRD400DLR_S_20230710000_01D_MN.rnx.gz").unwrap();
+
+    let offsets = rinex
+        .nav_system_time_frames_iter()
+        .map(|(k, v)| (*k, v.clone()))
+        .collect::<Vec<_>>();
+
+    assert!(!offsets.is_empty(), "BRD400DLR should contain STO frames");
+
+    // UTC identifier is preserved
+    assert!(offsets.iter().any(|(_, v)| v.utc.is_some()));
+
+    // STO frames only
+    let mut dut = rinex.clone();
+
+    dut.record
+        .as_mut_nav()
+        .unwrap()
+        .retain(|k, _| k.frmtype == NavFrameType::SystemTimeOffset);
+
+    let mut writer = BufWriter::new(Vec::new());
+    dut.format(&mut writer).unwrap();
+
+    let formatted = writer.into_inner().unwrap();
+
+    let content = String::from_utf8(formatted.clone()).unwrap();
+    assert!(content.contains("> STO "));
+
+    let mut reader = BufReader::new(formatted.as_slice());
+    let parsed = Rinex::parse(&mut reader).unwrap();
+
+    let reparsed = parsed
+        .nav_system_time_frames_iter()
+        .map(|(k, v)| (*k, v.clone()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(reparsed, offsets);
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn nav_mixed_and_single_constellation() {
+    let mixed = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    assert!(mixed.nav_is_mixed());
+
+    let constellations = mixed.nav_constellations();
+    assert!(constellations.len() > 1);
+    assert!(constellations.contains(&Constellation::GPS));
+    assert!(constellations.contains(&Constellation::Galileo));
+
+    let gps = Rinex::from_gzip_file("data/NAV/V2/cbw10010.21n.gz").unwrap();
+
+    assert!(!gps.nav_is_mixed());
+    assert_eq!(
+        gps.nav_constellations().into_iter().collect::<Vec<_>>(),
+        vec![Constellation::GPS]
+    );
+
+    // not a Navigation RINEX
+    let obs = Rinex::from_file("data/OBS/V3/DUTH0630.22O").unwrap();
+    assert!(!obs.nav_is_mixed());
+    assert!(obs.nav_constellations().is_empty());
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn nav_remove_constellation() {
+    // V4: frames of all kinds
+    let rinex = Rinex::from_gzip_file("data/NAV/V4/BRD400DLR_S_20230710000_01D_MN.rnx.gz").unwrap();
+
+    assert!(rinex.nav_constellations().contains(&Constellation::GPS));
+    assert!(rinex
+        .nav_system_time_frames_iter()
+        .any(|(k, _)| k.sv.constellation == Constellation::GPS));
+
+    let dut = rinex.remove_constellation(Constellation::GPS);
+
+    assert!(!dut.nav_constellations().contains(&Constellation::GPS));
+    assert!(dut
+        .sv_iter()
+        .all(|sv| sv.constellation != Constellation::GPS));
+
+    assert!(dut
+        .nav_ephemeris_frames_iter()
+        .all(|(k, _)| k.sv.constellation != Constellation::GPS));
+
+    assert!(dut
+        .nav_ionosphere_models_iter()
+        .all(|(k, _)| k.sv.constellation != Constellation::GPS));
+
+    assert!(dut
+        .nav_system_time_frames_iter()
+        .all(|(k, _)| k.sv.constellation != Constellation::GPS));
+
+    // other systems are preserved
+    assert!(dut.nav_constellations().contains(&Constellation::Galileo));
+    assert!(dut.nav_is_mixed());
+
+    // V3: header time offsets
+    let rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    assert!(rinex
+        .header_time_offsets()
+        .any(|offset| offset.lhs == TimeScale::GPST));
+
+    let mut dut = rinex.clone();
+    dut.remove_constellation_mut(Constellation::GPS);
+
+    assert!(dut
+        .header_time_offsets()
+        .all(|offset| offset.lhs != TimeScale::GPST));
+    assert!(!dut
+        .header
+        .ionod_corrections
+        .contains_key(&Constellation::GPS));
+    assert!(dut
+        .sv_iter()
+        .all(|sv| sv.constellation != Constellation::GPS));
+
+    // mono GNSS file
+    for constellation in rinex.nav_constellations() {
+        if constellation != Constellation::Galileo {
+            dut.remove_constellation_mut(constellation);
+        }
+    }
+
+    assert_eq!(dut.header.constellation, Some(Constellation::Galileo));
+    assert!(!dut.nav_is_mixed());
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn v4_brd400dlr_nav_frames_iter() {
+    let dut = Rinex::from_gzip_file("data/NAV/V4/BRD400DLR_S_20230710000_01D_MN.rnx.gz").unwrap();
+
+    let mut counts = HashMap::<NavFrameType, usize>::new();
+
+    for (k, frame) in dut.nav_frames_iter() {
+        match k.frmtype {
+            NavFrameType::Ephemeris => assert!(frame.as_ephemeris().is_some()),
+            NavFrameType::IonosphereModel => assert!(frame.as_ionosphere_model().is_some()),
+            NavFrameType::SystemTimeOffset => assert!(frame.as_system_time().is_some()),
+            NavFrameType::EarthOrientation => assert!(frame.as_earth_orientation().is_some()),
+        }
+
+        *counts.entry(k.frmtype).or_default() += 1;
+    }
+
+    assert_eq!(dut.nav_frames_iter().count(), dut.record_len());
+
+    assert_eq!(
+        counts
+            .get(&NavFrameType::Ephemeris)
+            .copied()
+            .unwrap_or_default(),
+        dut.nav_ephemeris_frames_iter().count()
+    );
+
+    assert_eq!(
+        counts
+            .get(&NavFrameType::IonosphereModel)
+            .copied()
+            .unwrap_or_default(),
+        dut.nav_ionosphere_models_iter().count()
+    );
+
+    assert_eq!(
+        counts
+            .get(&NavFrameType::SystemTimeOffset)
+            .copied()
+            .unwrap_or_default(),
+        dut.nav_system_time_frames_iter().count()
+    );
+
+    assert_eq!(
+        counts
+            .get(&NavFrameType::EarthOrientation)
+            .copied()
+            .unwrap_or_default(),
+        dut.nav_earth_orientation_frames_iter().count()
+    );
+
+    // heterogeneous V4 record
+    assert!(counts.get(&NavFrameType::Ephemeris).unwrap() > &0);
+    assert!(counts.get(&NavFrameType::SystemTimeOffset).unwrap() > &0);
+    assert!(counts.get(&NavFrameType::IonosphereModel).unwrap() > &0);
+
+    // not a Navigation RINEX
+    assert_eq!(Rinex::basic_obs().nav_frames_iter().count(), 0);
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn v4_brd400dlr_nav_retain_valid_at() {
+    let rinex = Rinex::from_gzip_file("data/NAV/V4/BRD400DLR_S_20230710000_01D_MN.rnx.gz").unwrap();
+
+    let t = Epoch::from_str("2023-03-12T12:00:00 GPST").unwrap();
+
+    let total = rinex.nav_ephemeris_frames_iter().count();
+
+    let valid = rinex
+        .nav_ephemeris_frames_iter()
+        .filter(|(k, eph)| eph.is_valid_at(k, t))
+        .count();
+
+    assert!(valid > 0, "no frame is valid at {}", t);
+    assert!(valid < total, "all frames are valid at {}", t);
+
+    let dut = rinex.nav_retain_valid_at(t);
+
+    // only frames covering this instant remain
+    assert_eq!(dut.nav_ephemeris_frames_iter().count(), valid);
+
+    // SBAS frames published within a day of this instant
+    let sbas_validity = Ephemeris::validity_duration(Constellation::SBAS).unwrap();
+
+    let sbas = rinex
+        .nav_ephemeris_frames_iter()
+        .filter(|(k, _)| k.sv.constellation.is_sbas() && (t - k.epoch).abs() < sbas_validity)
+        .count();
+
+    let retained_sbas = dut
+        .nav_ephemeris_frames_iter()
+        .filter(|(k, _)| k.sv.constellation.is_sbas())
+        .count();
+
+    assert_eq!(retained_sbas, sbas);
+
+    let total_sbas = rinex
+        .nav_ephemeris_frames_iter()
+        .filter(|(k, _)| k.sv.constellation.is_sbas())
+        .count();
+
+    if total_sbas > 0 {
+        assert!(retained_sbas < total_sbas, "all SBAS frames retained");
+    }
+
+    // GPS CNAV frames (ToE is ToC) within 2 hours of this instant
+    let gps_validity = Ephemeris::validity_duration(Constellation::GPS).unwrap();
+
+    let is_gps_cnav = |k: &NavKey| {
+        k.sv.constellation == Constellation::GPS
+            && matches!(k.msgtype, NavMessageType::CNAV | NavMessageType::CNV2)
+    };
+
+    let cnav = rinex
+        .nav_ephemeris_frames_iter()
+        .filter(|(k, _)| is_gps_cnav(k) && (t - k.epoch).abs() < gps_validity)
+        .count();
+
+    let retained_cnav = dut
+        .nav_ephemeris_frames_iter()
+        .filter(|(k, _)| is_gps_cnav(k))
+        .count();
+
+    assert!(cnav > 0, "no GPS CNAV frame valid at {}", t);
+    assert_eq!(retained_cnav, cnav);
+
+    for (k, eph) in dut.nav_ephemeris_frames_iter() {
+        assert!(eph.is_valid_at(k, t), "{}({}) is not valid", k.epoch, k.sv);
+    }
+
+    // other frames are preserved
+    assert_eq!(
+        dut.nav_ionosphere_models_iter().count(),
+        rinex.nav_ionosphere_models_iter().count()
+    );
+
+    assert_eq!(
+        dut.nav_system_time_frames_iter().count(),
+        rinex.nav_system_time_frames_iter().count()
+    );
+
+    // mutable variant
+    let mut mutated = rinex.clone();
+    mutated.nav_retain_valid_at_mut(t);
+    assert_eq!(mutated.record, dut.record);
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn v3_esbc00dnk_inter_system_bias() {
+    let dut = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    // this file declares GAGP (GST - GPST)
+    let gagp = dut
+        .header_time_offsets()
+        .find(|offset| offset.lhs == TimeScale::GST && offset.rhs == TimeScale::GPST)
+        .expect("missing GAGP definition");
+
+    let (a0, a1, a2) = gagp.polynomial;
+
+    for t in [
+        "2020-06-25T00:00:00 GPST",
+        "2020-06-25T12:00:00 GPST",
+        "2020-06-25T23:59:30 GPST",
+    ] {
+        let t = Epoch::from_str(t).unwrap();
+
+        let dt = (t.to_time_scale(TimeScale::GST) - gagp.t_ref_epoch()).to_seconds();
+        let expected = a0 + a1 * dt + a2 * dt.powi(2);
+
+        let gst_gpst = dut
+            .inter_system_bias(TimeScale::GST, TimeScale::GPST, t)
+            .unwrap();
+
+        assert!(
+            (gst_gpst.to_seconds() - expected).abs() < 1.0E-12,
+            "GST-GPST error @ {}: {}",
+            t,
+            gst_gpst
+        );
+
+        // reciprocal
+        let gpst_gst = dut
+            .inter_system_bias(TimeScale::GPST, TimeScale::GST, t)
+            .unwrap();
+
+        assert_eq!(gpst_gst, -gst_gpst);
+
+        // inter-system bias is a few nanoseconds at most
+        assert!(gpst_gst.abs().to_seconds() < 1.0E-6);
+    }
+
+    // BDT is not described
+    let t = Epoch::from_str("2020-06-25T12:00:00 GPST").unwrap();
+    assert!(dut
+        .inter_system_bias(TimeScale::GPST, TimeScale::BDT, t)
+        .is_none());
+
+    // not applicable
+    assert!(Rinex::basic_obs()
+        .inter_system_bias(TimeScale::GPST, TimeScale::GST, t)
+        .is_none());
+}
+
+#[test]
+#[cfg(all(feature = "rayon", feature = "flate2"))]
+fn v3_esbc00dnk_parallel_parsing() {
+    use flate2::read::GzDecoder;
+    use std::fs::File;
+
+    for path in [
+        "data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz",
+        "data/NAV/V4/BRD400DLR_S_20230710000_01D_MN.rnx.gz",
+    ] {
+        let serial = {
+            let fd = File::open(path).unwrap();
+            let mut reader = BufReader::new(GzDecoder::new(fd));
+            Rinex::parse(&mut reader).unwrap()
+        };
+
+        let parallel = {
+            let fd = File::open(path).unwrap();
+            let mut reader = BufReader::new(GzDecoder::new(fd));
+            Rinex::parse_parallel(&mut reader).unwrap()
+        };
+
+        assert!(serial.nav_ephemeris_frames_iter().count() > 0);
+
+        assert_eq!(parallel.header, serial.header, "{}: header mismatch", path);
+        assert_eq!(parallel.record, serial.record, "{}: record mismatch", path);
+        assert_eq!(
+            parallel.comments, serial.comments,
+            "{}: comments mismatch",
+            path
+        );
+    }
+}
+
+#[test]
+fn v3_mislabeled_constellation_consistency() {
+    use std::collections::BTreeSet;
+
+    // GPS file that actually contains Galileo and IRNSS frames
+    let content = "     3.04           N: GNSS NAV DATA    G: GPS              RINEX VERSION / TYPE
+                                                            END OF HEADER
+E01 2020 06 25 00 00 00-5.583695411682E-04-7.958078640513E-12 0.000000000000E+00
+     8.000000000000E+01-5.437500000000E+01 2.945446579149E-09 1.032475437231E+00
+    -1.853704452515E-06 3.516302773729E-04 1.013278961182E-05 5.440623002625E+03
+     3.456000000000E+05-2.518296241760E-08 2.179378003185E+00-8.456408977509E-08
+     9.576713462938E-01 1.662500000000E+02-1.562017655373E+00-5.667968273468E-09
+    -2.203663219130E-10 5.170000000000E+02 2.111000000000E+03 0.000000000000E+00
+     3.120000000000E+00 0.000000000000E+00-3.259629011154E-09-3.725290298462E-09
+     3.462000000000E+05 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
+I02 2021 01 01 00 00 00 4.291534423828E-04 1.136868377216E-12 0.000000000000E+00
+     0.000000000000E+00-2.262500000000E+01 1.753644200000E-09 1.203048900000E+00
+    -1.024454800000E-06 1.941275800000E-03 6.135553100000E-06 6.493245700000E+03
+     4.320000000000E+05 1.210719300000E-08-2.217283500000E+00-1.322478100000E-08
+     5.061953100000E-01 3.065312500000E+02-3.024341500000E+00-2.493103700000E-09
+     1.107189000000E-10 0.000000000000E+00 2.138000000000E+03 0.000000000000E+00
+     2.000000000000E+00 0.000000000000E+00-1.862645149231E-09 0.000000000000E+00
+     4.319700000000E+05
+";
+
+    let dut = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    let (declared, actual, extra, missing) = dut.constellation_consistency();
+
+    let found = BTreeSet::from([Constellation::Galileo, Constellation::IRNSS]);
+
+    assert_eq!(declared, BTreeSet::from([Constellation::GPS]));
+    assert_eq!(actual, found);
+    assert_eq!(extra, found);
+    assert_eq!(missing, BTreeSet::from([Constellation::GPS]));
+
+    // mixed files accept any constellation
+    let mut dut = dut;
+    dut.header.constellation = Some(Constellation::Mixed);
+
+    let (_, _, extra, missing) = dut.constellation_consistency();
+    assert!(extra.is_empty());
+    assert!(missing.is_empty());
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn v3_esbc00dnk_satellite_timeseries() {
+    let obs =
+        Rinex::from_gzip_file("data/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz").unwrap();
+
+    let nav = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let g01 = SV::from_str("G01").unwrap();
+
+    // NAV only
+    let series = nav.satellite_timeseries(g01);
+    assert!(series.observations.is_empty());
+    assert!(series.positions.is_empty());
+
+    assert_eq!(
+        series.ephemerides.len(),
+        nav.nav_ephemeris_frames_iter()
+            .filter(|(k, _)| k.sv == g01)
+            .count()
+    );
+
+    // OBS + NAV
+    let series = obs.satellite_timeseries_with_navigation(&nav, g01);
+
+    assert!(!series.observations.is_empty());
+    assert!(!series.positions.is_empty());
+    assert_eq!(
+        series.ephemerides.len(),
+        nav.satellite_timeseries(g01).ephemerides.len()
+    );
+
+    let epochs = series.observation_epochs();
+
+    for (t, (x_km, y_km, z_km)) in series.positions.iter() {
+        assert!(epochs.contains(t), "{} is not an observation epoch", t);
+
+        // GPS orbit radius
+        let radius_km = (x_km.powi(2) + y_km.powi(2) + z_km.powi(2)).sqrt();
+        assert!(
+            radius_km > 25_000.0 && radius_km < 27_500.0,
+            "{}: {} km",
+            t,
+            radius_km
+        );
+    }
+
+    // continuity: consecutive positions describe a GPS (ECEF) velocity,
+    // including across ephemeris frame transitions
+    let mut num_checks = 0;
+
+    for window in series.positions.windows(2) {
+        let (t0, (x0_km, y0_km, z0_km)) = window[0];
+        let (t1, (x1_km, y1_km, z1_km)) = window[1];
+
+        let dt_s = (t1 - t0).to_seconds();
+        assert!(dt_s > 0.0, "positions should be sorted");
+
+        if dt_s > 300.0 {
+            continue; // data gap
+        }
+
+        let distance_km =
+            ((x1_km - x0_km).powi(2) + (y1_km - y0_km).powi(2) + (z1_km - z0_km).powi(2)).sqrt();
+
+        let speed_km_s = distance_km / dt_s;
+
+        assert!(
+            speed_km_s > 2.0 && speed_km_s < 4.5,
+            "{}-{}: discontinuity ({} km/s)",
+            t0,
+            t1,
+            speed_km_s
+        );
+
+        num_checks += 1;
+    }
+
+    assert!(num_checks > 0, "no consecutive positions");
+}
@@ -1,13 +1,24 @@
 use crate::{
+    hardware::{Antenna, Receiver},
     observation::{EpochFlag, LliFlags, ObsKey, SignalObservation, SNR},
-    prelude::{Epoch, GeodeticMarker, Observable, Rinex, SV},
+    prelude::{
+        Constellation, DuplicateEpochPolicy, Duration, Epoch, FormattingError, GeodeticMarker,
+        Header, Observable, ParseWarning, ParsingError, ParsingOptions, Rinex, RinexType,
+        TimeScale, Version, SV,
+    },
+    record::Record,
     tests::toolkit::{
         generic_null_rinex_test, generic_observation_rinex_test, generic_rinex_comparison,
         SignalDataPoint, TimeFrame,
     },
 };
 
-use std::{fs::remove_file, path::Path, str::FromStr};
+use std::{
+    fs::remove_file,
+    io::{BufReader, BufWriter},
+    path::Path,
+    str::FromStr,
+};
 
 #[test]
 fn v2_aopr0010_17o() {
@@ -144,6 +155,48 @@ fn v2_rovn0010_21o() {
     let _ = remove_file("v2_rovn0010_21o.txt");
 }
 
+#[test]
+fn v3_terminating_flags() {
+    // LLI and SNR flags of the last signal terminate the line
+    let content = "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2022 03 04 00 00  0.0000000  0  2
+G01  20243517.560   106380022.5281
+G03  21243517.560   116380022.52817
+";
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    let g01 = SV::from_str("G01").unwrap();
+    let g03 = SV::from_str("G03").unwrap();
+    let l1c = Observable::from_str("L1C").unwrap();
+
+    let mut num_tests = 0;
+
+    for (_, sig) in rinex.signal_observations_iter() {
+        if sig.observable != l1c {
+            continue;
+        }
+
+        if sig.sv == g01 {
+            assert_eq!(sig.value, 106380022.528);
+            assert_eq!(sig.lli, Some(LliFlags::LOCK_LOSS));
+            assert_eq!(sig.snr, None);
+            num_tests += 1;
+        } else if sig.sv == g03 {
+            assert_eq!(sig.value, 116380022.528);
+            assert_eq!(sig.lli, Some(LliFlags::LOCK_LOSS));
+            assert_eq!(sig.snr, Some(SNR::from(7)));
+            num_tests += 1;
+        }
+    }
+
+    assert_eq!(num_tests, 2);
+}
+
 #[test]
 fn v3_duth0630() {
     let path = Path::new(env!("CARGO_MANIFEST_DIR"))
@@ -239,6 +292,35 @@ fn v4_kms300dnk_r_2022_v3crx() {
         );
 }
 
+#[test]
+fn v3_kms300dnk_crinex_decode_stats() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("CRNX")
+        .join("V3")
+        .join("KMS300DNK_R_20221591000_01H_30S_MO.crx");
+
+    let dut = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+    let stats = dut.crinex_decode_stats().unwrap();
+
+    assert_eq!(stats.epochs, 19);
+    assert_eq!(stats.epochs, dut.epoch_iter().count());
+
+    let file_size = std::fs::metadata(&path).unwrap().len() as usize;
+    assert!(stats.input_bytes > 0);
+    assert!(stats.input_bytes < file_size);
+    assert!(stats.output_bytes > stats.input_bytes);
+    assert!(stats.compression_ratio().unwrap() > 1.0);
+
+    // readable RINEX: nothing was decompressed
+    let dut = Rinex::from_file("data/OBS/V3/DUTH0630.22O").unwrap();
+    assert!(dut.crinex_decode_stats().is_none());
+
+    // not parsed from a CRINEX
+    let dut = dut.rnx2crnx();
+    assert!(dut.crinex_decode_stats().is_none());
+}
+
 #[test]
 #[ignore] // TODO
 fn v2_kosg0010_95o() {
@@ -636,3 +718,1922 @@ fn obs_null_substract() {
         generic_null_rinex_test(&diffed);
     }
 }
+
+#[test]
+#[cfg(feature = "flate2")]
+fn v3_esbc00dnk_time_window() {
+    let rinex =
+        Rinex::from_gzip_file("data/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz").unwrap();
+
+    let start = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+    let end = Epoch::from_str("2020-06-25T00:59:30 GPST").unwrap();
+
+    let dut = rinex.time_window(start, end);
+
+    // 1 hour at 30s sampling
+    assert_eq!(dut.epoch_iter().count(), 120);
+    assert_eq!(dut.first_epoch(), Some(start));
+    assert_eq!(dut.last_epoch(), Some(end));
+
+    let obs = dut.header.obs.as_ref().unwrap();
+    assert_eq!(obs.timeof_first_obs, Some(start));
+    assert_eq!(obs.timeof_last_obs, Some(end));
+}
+
+#[test]
+fn v3_special_events() {
+    let content = "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2022 03 04 00 00  0.0000000  0  1
+G01  20243517.560   106380022.528
+> 2022 03 04 00 00 30.0000000  4  1
+        0.1000        0.0000        0.0000                  ANTENNA: DELTA H/E/N
+> 2022 03 04 00 01  0.0000000  0  1
+G01  20243518.560   106380027.528
+";
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    let t0 = Epoch::from_str("2022-03-04T00:00:00 GPST").unwrap();
+    let t1 = Epoch::from_str("2022-03-04T00:00:30 GPST").unwrap();
+    let t2 = Epoch::from_str("2022-03-04T00:01:00 GPST").unwrap();
+
+    let flags = rinex.epoch_flags_iter().collect::<Vec<_>>();
+    assert_eq!(
+        flags,
+        vec![
+            (t0, EpochFlag::Ok),
+            (t1, EpochFlag::HeaderInformationFollows),
+            (t2, EpochFlag::Ok),
+        ]
+    );
+
+    let key = ObsKey {
+        epoch: t1,
+        flag: EpochFlag::HeaderInformationFollows,
+    };
+
+    let event = rinex.record.as_obs().unwrap().get(&key).unwrap();
+    assert!(event.signals.is_empty());
+    assert_eq!(
+        event.events,
+        vec![
+            "        0.1000        0.0000        0.0000                  ANTENNA: DELTA H/E/N"
+                .to_string()
+        ]
+    );
+
+    // special events are preserved
+    let mut writer = BufWriter::new(Vec::new());
+    rinex.format(&mut writer).unwrap();
+
+    let formatted = writer.into_inner().unwrap();
+    let mut reader = BufReader::new(formatted.as_slice());
+
+    let parsed = Rinex::parse(&mut reader).unwrap();
+    assert_eq!(parsed.record, rinex.record);
+}
+
+#[test]
+fn v3_special_events_crinex_round_trip() {
+    let content = "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2022 03 04 00 00  0.0000000  0  1
+G01  20243517.560   106380022.528
+> 2022 03 04 00 00 30.0000000  4  1
+        0.1000        0.0000        0.0000                  ANTENNA: DELTA H/E/N
+> 2022 03 04 00 01  0.0000000  0  1
+G01  20243518.560   106380027.528
+";
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    let crinex = rinex.rnx2crnx();
+    assert!(crinex.is_hatanaka());
+
+    let mut writer = BufWriter::new(Vec::new());
+    crinex.format(&mut writer).unwrap();
+
+    let formatted = writer.into_inner().unwrap();
+    let formatted = String::from_utf8(formatted).unwrap();
+
+    // event description and special records are not compressed
+    assert!(formatted.contains("> 2022 03 04 00 00 30.0000000  4  1\n"));
+    assert!(formatted.contains(
+        "        0.1000        0.0000        0.0000                  ANTENNA: DELTA H/E/N\n"
+    ));
+
+    // following epoch is not compressed either
+    assert!(formatted.contains("> 2022 03 04 00 01 00.0000000  0  1      G01\n"));
+
+    let mut reader = BufReader::new(formatted.as_bytes());
+    let parsed = Rinex::parse(&mut reader).unwrap();
+
+    assert!(parsed.is_hatanaka());
+    assert_eq!(parsed.record, rinex.record);
+}
+
+#[test]
+fn v2_record_events() {
+    let content = "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     2    C1    L1                                          # / TYPES OF OBSERV
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+ 22  3  4  0  0  0.0000000  0  1G01
+  20243517.560   106380022.528
+                            4  2
+        1.2500        0.0100       -0.0200                  ANTENNA: DELTA H/E/N
+DUTH                                                        MARKER NAME
+ 22  3  4  0  1  0.0000000  0  1G01
+  20243518.560   106380027.528
+";
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    // event is missing its timestamp: refers to previous epoch
+    let t0 = Epoch::from_str("2022-03-04T00:00:00 GPST").unwrap();
+
+    let events = rinex.record_events_iter().collect::<Vec<_>>();
+    assert_eq!(events.len(), 1);
+
+    let (key, event) = &events[0];
+    assert_eq!(key.epoch, t0);
+    assert_eq!(key.flag, EpochFlag::HeaderInformationFollows);
+    assert_eq!(event.flag, EpochFlag::HeaderInformationFollows);
+    assert_eq!(event.antenna_delta_hen, Some((1.25, 0.01, -0.02)));
+    assert_eq!(event.records.len(), 2);
+
+    let marker = event.geodetic_marker.as_ref().unwrap();
+    assert_eq!(marker.name, "DUTH");
+
+    // observations are not impacted
+    assert_eq!(rinex.signal_observations_iter().count(), 4);
+
+    // events are re-emitted
+    let mut writer = BufWriter::new(Vec::new());
+    rinex.format(&mut writer).unwrap();
+
+    let formatted = writer.into_inner().unwrap();
+    let mut reader = BufReader::new(formatted.as_slice());
+
+    let parsed = Rinex::parse(&mut reader).unwrap();
+    assert_eq!(parsed.record, rinex.record);
+
+    let parsed_events = parsed.record_events_iter().collect::<Vec<_>>();
+    assert_eq!(parsed_events, events);
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn v3_esbc00dnk_compression_introspection() {
+    let rinex =
+        Rinex::from_gzip_file("data/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz").unwrap();
+
+    assert!(rinex.is_gzip());
+    assert!(rinex.is_hatanaka());
+
+    let crinex_version = rinex.header.crinex_version().unwrap();
+    assert_eq!(crinex_version.major, 3);
+
+    // original specs are retained
+    let compressed = rinex.rnx2crnx();
+    assert_eq!(compressed.header.crinex_version(), Some(crinex_version));
+
+    let decompressed = rinex.crnx2rnx();
+    assert!(!decompressed.is_hatanaka());
+    assert!(decompressed.header.crinex_version().is_none());
+
+    let rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O").unwrap();
+    assert!(!rinex.is_gzip());
+    assert!(!rinex.is_hatanaka());
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn v3_cibg00idn_precision_round_trip() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("CRNX")
+        .join("V3")
+        .join("CIBG00IDN_R_20240100000_01D_30S_MO.crx.gz");
+
+    let fullpath = path.to_string_lossy();
+
+    let dut = Rinex::from_gzip_file(fullpath.as_ref()).unwrap().crnx2rnx();
+
+    let mut writer = BufWriter::new(Vec::new());
+    dut.format(&mut writer).unwrap();
+
+    let content = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+    let parsed = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    assert_eq!(
+        dut.signal_observations_iter().count(),
+        parsed.signal_observations_iter().count()
+    );
+
+    // F14.3: values are preserved up to half the last digit
+    for ((k, signal), (parsed_k, parsed_signal)) in dut
+        .signal_observations_iter()
+        .zip(parsed.signal_observations_iter())
+    {
+        assert_eq!(k, parsed_k);
+        assert_eq!(signal.sv, parsed_signal.sv);
+        assert_eq!(signal.observable, parsed_signal.observable);
+        assert_eq!(signal.lli, parsed_signal.lli);
+        assert_eq!(signal.snr, parsed_signal.snr);
+
+        let err = (signal.value - parsed_signal.value).abs();
+        assert!(
+            err <= 0.5E-3,
+            "{}({}) {} precision loss: {} vs {}",
+            signal.sv,
+            signal.observable,
+            k.epoch,
+            signal.value,
+            parsed_signal.value,
+        );
+    }
+}
+
+#[test]
+fn v3_duth0630_lenient_parsing() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("OBS")
+        .join("V3")
+        .join("DUTH0630.22O");
+
+    let content = std::fs::read_to_string(&path).unwrap();
+
+    let num_header_lines = content
+        .lines()
+        .position(|line| line.contains("END OF HEADER"))
+        .unwrap()
+        + 1;
+
+    // corrupt first signal line of first epoch
+    let corrupt_line = num_header_lines + 2;
+
+    let corrupted = content
+        .lines()
+        .enumerate()
+        .map(|(nth, line)| {
+            if nth + 1 == corrupt_line {
+                format!("#?!{}", &line[3..])
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    std::fs::write("v3_duth0630_corrupted.txt", corrupted).unwrap();
+
+    let (dut, warnings) = Rinex::from_file_lenient("v3_duth0630_corrupted.txt").unwrap();
+    let _ = remove_file("v3_duth0630_corrupted.txt");
+
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    assert_eq!(warnings[0].line, corrupt_line);
+
+    // best effort record
+    let reference = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+    assert_eq!(dut.epoch_iter().count(), reference.epoch_iter().count());
+
+    let first_epoch = reference.first_epoch().unwrap();
+
+    let num_signals = |rinex: &Rinex| {
+        rinex
+            .signal_observations_iter()
+            .filter(|(k, _)| k.epoch == first_epoch)
+            .count()
+    };
+
+    assert!(num_signals(&dut) < num_signals(&reference));
+
+    // untouched fixture is fully parsed
+    let (_, warnings) = Rinex::from_file_lenient(path.to_string_lossy().as_ref()).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+
+    // missing file is reported, not a panic
+    assert!(matches!(
+        Rinex::from_file_lenient("v3_duth0630_missing.txt"),
+        Err(ParsingError::InputError(_))
+    ));
+}
+
+/// Parses this content in a lenient manner, from memory.
+fn parse_lenient(content: &str) -> (Rinex, Vec<ParseWarning>) {
+    let num_header_lines = content
+        .lines()
+        .position(|line| line.contains("END OF HEADER"))
+        .unwrap()
+        + 1;
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let mut header = Header::parse(&mut reader).unwrap();
+
+    let (record, comments, warnings) =
+        Record::parse_lenient(&mut header, &mut reader, num_header_lines).unwrap();
+
+    let mut rinex = Rinex::new(header, record);
+    rinex.comments = comments;
+
+    (rinex, warnings)
+}
+
+#[test]
+fn v3_duth0630_truncated_final_epoch() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("OBS")
+        .join("V3")
+        .join("DUTH0630.22O");
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let lines = content.lines().collect::<Vec<_>>();
+
+    let reference = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+    let last_epoch = reference.last_epoch().unwrap();
+
+    // file was logged until the first signal of the final epoch, cut mid-line
+    let last_epoch_line = lines
+        .iter()
+        .rposition(|line| line.starts_with('>'))
+        .unwrap();
+    let cut_line = &lines[last_epoch_line + 1];
+
+    let mut truncated = lines[..last_epoch_line + 1].join("\n");
+    truncated.push('\n');
+    truncated.push_str(&cut_line[..cut_line.len() / 2]);
+
+    let (dut, warnings) = parse_lenient(&truncated);
+
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    assert_eq!(warnings[0].line, last_epoch_line + 1);
+    assert!(matches!(warnings[0].error, ParsingError::TruncatedEpoch));
+
+    // prior epochs are intact, partial epoch was dropped
+    assert_eq!(dut.epoch_iter().count(), reference.epoch_iter().count() - 1);
+
+    assert!(dut
+        .signal_observations_iter()
+        .all(|(k, _)| k.epoch != last_epoch));
+
+    assert!(dut.signal_observations_iter().eq(reference
+        .signal_observations_iter()
+        .filter(|(k, _)| k.epoch != last_epoch)));
+
+    // all signal lines are present, but the last one was cut within an observation:
+    // F14.3 value loses its last two decimals
+    let last_line = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .unwrap();
+    let cut_line = lines[last_line];
+
+    let nth_observable = (0..)
+        .take_while(|nth| 3 + 16 * nth + 14 <= cut_line.len())
+        .find(|nth| cut_line.as_bytes()[3 + 16 * nth + 13].is_ascii_digit())
+        .expect("last signal line has no observation");
+
+    let mut truncated = lines[..last_line].join("\n");
+    truncated.push('\n');
+    truncated.push_str(&cut_line[..3 + 16 * nth_observable + 12]);
+
+    let (dut, warnings) = parse_lenient(&truncated);
+
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    assert_eq!(warnings[0].line, last_epoch_line + 1);
+    assert!(matches!(warnings[0].error, ParsingError::TruncatedEpoch));
+    assert_eq!(dut.epoch_iter().count(), reference.epoch_iter().count() - 1);
+
+    // cut right after a complete observation: this cannot be told apart
+    // from blank trailing observations, the final epoch is preserved
+    let mut truncated = lines[..last_line].join("\n");
+    truncated.push('\n');
+    truncated.push_str(&cut_line[..3 + 16 * nth_observable + 14]);
+
+    let (dut, warnings) = parse_lenient(&truncated);
+
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(dut.last_epoch(), Some(last_epoch));
+
+    // descriptor itself may be cut
+    let mut truncated = lines[..last_epoch_line].join("\n");
+    truncated.push('\n');
+    truncated.push_str(&lines[last_epoch_line][..12]);
+
+    let mut reader = BufReader::new(truncated.as_bytes());
+    let dut = Rinex::parse(&mut reader).unwrap();
+
+    assert_eq!(dut.epoch_iter().count(), reference.epoch_iter().count() - 1);
+}
+
+#[test]
+fn v2_truncated_final_epoch() {
+    let header = "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     3    C1    L1    S1                                    # / TYPES OF OBSERV
+  2021     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+ 21  1  1  0  0  0.0000000  0  2G01G02
+  20243517.560   106380022.52808        45.000
+  21243517.560   111635022.52807        40.000
+ 21  1  1  0  0 30.0000000  0  2G01G02
+  20243749.370   106381240.12508        45.000
+";
+
+    // cut within the L1 observation of the last signal
+    let content = format!("{}  21243749.370   1116362", header);
+    let (dut, warnings) = parse_lenient(&content);
+
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    assert_eq!(warnings[0].line, 8);
+    assert!(matches!(warnings[0].error, ParsingError::TruncatedEpoch));
+    assert_eq!(dut.epoch_iter().count(), 1);
+
+    // complete final epoch
+    let content = format!("{}  21243749.370   111636240.12507        40.000\n", header);
+    let (dut, warnings) = parse_lenient(&content);
+
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(dut.epoch_iter().count(), 2);
+}
+
+#[test]
+fn v2_lenient_parsing() {
+    let content = "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     3    C1    L1    S1                                    # / TYPES OF OBSERV
+  2021     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+ 21  1  1  0  0  0.0000000  0  2G01G02
+  20243517.560   106380022.52808        45.000
+#?! corrupted signal line, that cannot be interpreted at all
+ 21  1  1  0  0 30.0000000  0  2G01G02
+  20243749.370   106381240.12508        45.000
+  21243749.370   111636240.12507        40.000
+";
+
+    let (dut, warnings) = parse_lenient(content);
+
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    assert_eq!(warnings[0].line, 7);
+    assert!(matches!(
+        warnings[0].error,
+        ParsingError::BadSignalObservationLine
+    ));
+
+    // best effort record
+    assert_eq!(dut.epoch_iter().count(), 2);
+
+    let g02 = SV::from_str("G02").unwrap();
+    let t0 = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+
+    assert!(dut
+        .signal_observations_iter()
+        .all(|(k, sig)| k.epoch != t0 || sig.sv != g02));
+
+    assert_eq!(dut.signal_observations_iter().count(), 3 + 3 + 3);
+}
+
+#[test]
+fn v3_leap_seconds() {
+    let content = "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+  2016    12    31    23    59    0.0000000     GPS         TIME OF FIRST OBS
+    17                                                      LEAP SECONDS
+                                                            END OF HEADER
+> 2016 12 31 23 59  0.0000000  0  1
+G01  20243517.560   106380022.528
+> 2017 01 01 00 00 30.0000000  4  1
+    18                                                      LEAP SECONDS
+> 2017 01 01 00 01  0.0000000  0  1
+G01  20243518.560   106380027.528
+";
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    assert_eq!(rinex.header.leap_seconds(), Some(17));
+
+    let t0 = Epoch::from_str("2016-12-31T23:59:00 GPST").unwrap();
+    let t1 = Epoch::from_str("2017-01-01T00:01:00 GPST").unwrap();
+
+    // header value applies until the update
+    assert_eq!(rinex.leap_seconds_at(t0), Some(17));
+    assert_eq!(rinex.leap_seconds_at(t1), Some(18));
+
+    // UTC conversion is consistent with the declared counts
+    for t in [t0, t1] {
+        let leap = rinex.leap_seconds_at(t).unwrap();
+        let utc = t.to_time_scale(TimeScale::UTC);
+
+        assert_eq!(
+            utc.to_gregorian(TimeScale::UTC),
+            (t - Duration::from_seconds(leap as f64)).to_gregorian(TimeScale::GPST),
+            "invalid UTC conversion at {}",
+            t
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "obs")]
+fn v3_duth0630_phase_range() {
+    let dut = Rinex::from_file("data/OBS/V3/DUTH0630.22O").unwrap();
+
+    let l1c = Observable::from_str("L1C").unwrap();
+    let c1c = Observable::from_str("C1C").unwrap();
+
+    let g01 = SV::from_str("G01").unwrap();
+
+    // hand computation
+    let lambda = 299_792_458.0 / 1575.42E6;
+
+    let mut num_tests = 0;
+
+    for ((t, phase_m), (ref_t, sig)) in dut.phase_range_iter(g01, &l1c).zip(
+        dut.phase_range_sampling_ok_iter()
+            .filter(|(_, sig)| sig.sv == g01 && sig.observable == l1c),
+    ) {
+        assert_eq!(t, ref_t);
+        let expected = sig.value * lambda;
+        assert!(
+            (phase_m - expected).abs() < 1.0E-6,
+            "{} error: {} m",
+            t,
+            phase_m - expected
+        );
+        num_tests += 1;
+    }
+
+    assert!(num_tests > 0, "no phase range for {}", g01);
+
+    // FDMA: each Glonass satellite has its own wavelength
+    for (sv, channel) in dut.header.glo_channels.iter() {
+        let lambda = 299_792_458.0 / ((1602.0 + *channel as f64 * 9.0 / 16.0) * 1.0E6);
+
+        for ((_, phase_m), (_, sig)) in dut.phase_range_iter(*sv, &l1c).zip(
+            dut.phase_range_sampling_ok_iter()
+                .filter(|(_, sig)| sig.sv == *sv && sig.observable == l1c),
+        ) {
+            assert!((phase_m - sig.value * lambda).abs() < 1.0E-6);
+        }
+    }
+
+    // not a phase measurement
+    assert_eq!(dut.phase_range_iter(g01, &c1c).count(), 0);
+}
+
+#[test]
+fn v3_duth0630_retain_constellations() {
+    let dut = Rinex::from_file("data/OBS/V3/DUTH0630.22O").unwrap();
+
+    let gps = dut.retain_constellations(&[Constellation::GPS]);
+
+    let expected = dut
+        .sv_iter()
+        .filter(|sv| sv.constellation == Constellation::GPS)
+        .collect::<Vec<_>>();
+
+    assert!(!expected.is_empty());
+    assert_eq!(gps.sv_iter().collect::<Vec<_>>(), expected);
+
+    let header = gps.header.obs.as_ref().unwrap();
+    assert!(header.codes.contains_key(&Constellation::GPS));
+    assert!(!header.codes.contains_key(&Constellation::Glonass));
+
+    let satellites = [SV::from_str("G01").unwrap(), SV::from_str("R01").unwrap()];
+
+    let retained = dut.retain_satellites(&satellites);
+
+    assert_eq!(retained.sv_iter().collect::<Vec<_>>(), satellites.to_vec());
+
+    // all glonass codes are still required by R01
+    let header = retained.header.obs.as_ref().unwrap();
+    assert!(header.codes.contains_key(&Constellation::Glonass));
+}
+
+#[test]
+fn v3_retain_sbas_constellations() {
+    let content = "     3.04           OBSERVATION DATA    M                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+S    2 C1C L1C                                              SYS / # / OBS TYPES
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2022 03 04 00 00  0.0000000  0  2
+G01  20243517.560   106380022.528
+S23  38137559.506   200409966.732
+";
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    let s23 = SV::from_str("S23").unwrap();
+    assert!(s23.constellation.is_sbas());
+
+    // SBAS vehicles and header definitions are retained,
+    // whether SBAS or a specific augmentation system is requested.
+    for constellation in [Constellation::SBAS, s23.constellation] {
+        let sbas = rinex.retain_constellations(&[constellation]);
+
+        assert_eq!(sbas.sv_iter().collect::<Vec<_>>(), vec![s23]);
+
+        let header = sbas.header.obs.as_ref().unwrap();
+        assert!(header.codes.contains_key(&Constellation::SBAS));
+        assert!(!header.codes.contains_key(&Constellation::GPS));
+    }
+
+    let gps = rinex.retain_constellations(&[Constellation::GPS]);
+
+    let header = gps.header.obs.as_ref().unwrap();
+    assert!(!header.codes.contains_key(&Constellation::SBAS));
+
+    // retaining a SBAS vehicle preserves the SBAS definitions
+    let retained = rinex.retain_satellites(&[s23]);
+
+    let header = retained.header.obs.as_ref().unwrap();
+    assert!(header.codes.contains_key(&Constellation::SBAS));
+}
+
+#[test]
+fn v4_observation_parsing() {
+    let content = "     4.00           OBSERVATION DATA    M                   RINEX VERSION / TYPE
+10.1000/example-doi                                         DOI
+CC BY 4.0                                                   LICENSE OF USE
+https://example.org/station                                 STATION INFORMATION
+G    3 C1C L1C S1C                                          SYS / # / OBS TYPES
+E    2 C1X L1X                                              SYS / # / OBS TYPES
+  2024     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2024 01 01 00 00  0.0000000  0  2       0.000123456789
+G01  20243517.560   106380022.528 7        45.000
+E05  23453517.120   123250011.25048
+> 2024 01 01 00 00 30.0000000  0  2
+G01  20243617.560   106380547.52816        42.000
+E05  23453617.120   123250536.250 8
+";
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    assert!(rinex.is_observation_rinex());
+    assert_eq!(rinex.header.version.major, 4);
+
+    // V4 header fields
+    assert_eq!(rinex.header.doi.as_deref(), Some("10.1000/example-doi"));
+    assert_eq!(rinex.header.license.as_deref(), Some("CC BY 4.0"));
+    assert_eq!(
+        rinex.header.station_url.as_deref(),
+        Some("https://example.org/station")
+    );
+
+    let t0 = Epoch::from_str("2024-01-01T00:00:00 GPST").unwrap();
+    let t1 = Epoch::from_str("2024-01-01T00:00:30 GPST").unwrap();
+
+    assert_eq!(rinex.epoch_iter().collect::<Vec<_>>(), vec![t0, t1]);
+
+    let clocks = rinex.clock_observations_iter().collect::<Vec<_>>();
+    assert_eq!(clocks.len(), 1);
+    assert_eq!(clocks[0].0.epoch, t0);
+    assert_eq!(clocks[0].1.offset_s, 0.000123456789);
+
+    let g01 = SV::from_str("G01").unwrap();
+    let e05 = SV::from_str("E05").unwrap();
+
+    let l1c = Observable::from_str("L1C").unwrap();
+    let s1c = Observable::from_str("S1C").unwrap();
+    let l1x = Observable::from_str("L1X").unwrap();
+
+    let signals = rinex
+        .signal_observations_iter()
+        .map(|(k, sig)| (k.epoch, sig.clone()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(signals.len(), 10);
+
+    for (t, sig) in signals.iter() {
+        if *t == t0 && sig.sv == g01 && sig.observable == l1c {
+            assert_eq!(sig.value, 106380022.528);
+            assert!(sig.lli.is_none());
+            assert_eq!(sig.snr, Some(SNR::from(7)));
+        } else if *t == t0 && sig.sv == g01 && sig.observable == s1c {
+            assert_eq!(sig.value, 45.0);
+            assert!(sig.snr.is_none());
+        } else if *t == t0 && sig.sv == e05 && sig.observable == l1x {
+            assert_eq!(sig.value, 123250011.250);
+            assert_eq!(sig.lli, Some(LliFlags::UNDER_ANTI_SPOOFING));
+            assert_eq!(sig.snr, Some(SNR::from(8)));
+        } else if *t == t1 && sig.sv == g01 && sig.observable == l1c {
+            assert_eq!(sig.value, 106380547.528);
+            assert_eq!(sig.lli, Some(LliFlags::LOCK_LOSS));
+            assert_eq!(sig.snr, Some(SNR::from(6)));
+        }
+    }
+
+    // V4 content is preserved
+    let mut writer = BufWriter::new(Vec::new());
+    rinex.format(&mut writer).unwrap();
+
+    let formatted = writer.into_inner().unwrap();
+    let mut reader = BufReader::new(formatted.as_slice());
+
+    let parsed = Rinex::parse(&mut reader).unwrap();
+    assert_eq!(parsed.header.version.major, 4);
+
+    let parsed_signals = parsed
+        .signal_observations_iter()
+        .map(|(k, sig)| (k.epoch, sig.clone()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(parsed_signals.len(), signals.len());
+
+    for signal in signals.iter() {
+        assert!(parsed_signals.contains(signal), "{:?} was lost", signal);
+    }
+}
+
+#[test]
+#[cfg(feature = "obs")]
+fn v3_derive_snr_from_ssi() {
+    let content = "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    3 C1C L1C S1C                                          SYS / # / OBS TYPES
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2022 03 04 00 00  0.0000000  0  2
+G01  20243517.560   106380022.528          45.250
+G03  21243517.560   116380022.528
+> 2022 03 04 00 00 30.0000000  0  1
+G01  20243518.560   106380027.528 5        22.000
+";
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    // SNR column is mostly absent
+    assert_eq!(
+        rinex
+            .signal_observations_iter()
+            .filter(|(_, sig)| sig.snr.is_some())
+            .count(),
+        1
+    );
+
+    let dut = rinex.derive_snr();
+
+    let t0 = Epoch::from_str("2022-03-04T00:00:00 GPST").unwrap();
+    let t1 = Epoch::from_str("2022-03-04T00:00:30 GPST").unwrap();
+
+    let g01 = SV::from_str("G01").unwrap();
+    let g03 = SV::from_str("G03").unwrap();
+
+    let mut num_tests = 0;
+
+    for (k, sig) in dut.signal_observations_iter() {
+        if sig.observable.is_ssi_observable() {
+            assert!(sig.snr.is_none(), "SSI observations should not be modified");
+            continue;
+        }
+
+        if sig.sv == g03 {
+            // no S observable
+            assert!(sig.snr.is_none());
+        } else if k.epoch == t0 {
+            assert_eq!(sig.sv, g01);
+            assert_eq!(sig.snr, Some(SNR::DbHz42_47));
+        } else if k.epoch == t1 {
+            if sig.observable.is_phase_range_observable() {
+                // preserved
+                assert_eq!(sig.snr, Some(SNR::from(5)));
+            } else {
+                assert_eq!(sig.snr, Some(SNR::DbHz18_23));
+            }
+        }
+
+        num_tests += 1;
+    }
+
+    assert_eq!(num_tests, 6);
+}
+
+#[test]
+#[cfg(feature = "obs")]
+fn v3_apply_dcb() {
+    use crate::observation::DcbSource;
+
+    let content = "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    4 C1C C1W L1C C2W                                      SYS / # / OBS TYPES
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2022 03 04 00 00  0.0000000  0  2
+G01  20243517.560    20243518.120   106380022.528    20243519.340
+G03  21243517.560    21243518.120   116380022.528    21243519.340
+";
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    let g01 = SV::from_str("G01").unwrap();
+    let c1c = Observable::from_str("C1C").unwrap();
+    let c1w = Observable::from_str("C1W").unwrap();
+
+    // G01: B(C1C) - B(C1W) = 2ns
+    let dcb = DcbSource::new().with_bias(g01, c1c.clone(), c1w.clone(), 2.0);
+
+    let dut = rinex.apply_dcb(&dcb);
+
+    let mut num_tests = 0;
+
+    for ((_, model), (_, sig)) in rinex
+        .signal_observations_iter()
+        .zip(dut.signal_observations_iter())
+    {
+        if sig.sv == g01 && sig.observable == c1c {
+            let expected = 20243517.560 - 2.0E-9 * 299_792_458.0;
+            assert!((sig.value - expected).abs() < 1.0E-6);
+            num_tests += 1;
+        } else {
+            // other SV, reference code and phase are untouched
+            assert_eq!(sig.value, model.value);
+        }
+    }
+
+    assert_eq!(num_tests, 1);
+}
+
+#[test]
+#[cfg(feature = "obs")]
+fn v3_lli_statistics() {
+    let content = "     3.04           OBSERVATION DATA    M                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+E    2 C1X L1X                                              SYS / # / OBS TYPES
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2022 03 04 00 00  0.0000000  0  3
+G01  20243517.560   106380022.5281
+G03  21243517.560   116380022.5283
+E05  23453517.120   123250011.2504
+> 2022 03 04 00 00 30.0000000  0  3
+G01  20243518.560   106380027.5282
+G03  21243518.560   116380027.528
+E05  23453518.120   123250016.250
+";
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    let stats = rinex.lli_statistics();
+
+    let gps = stats.constellation(Constellation::GPS).unwrap();
+    assert_eq!(gps.lock_loss, 2);
+    assert_eq!(gps.half_cycle_slip, 2);
+    assert_eq!(gps.under_anti_spoofing, 0);
+
+    let gal = stats.constellation(Constellation::Galileo).unwrap();
+    assert_eq!(gal.lock_loss, 0);
+    assert_eq!(gal.half_cycle_slip, 0);
+    assert_eq!(gal.under_anti_spoofing, 1);
+
+    assert!(stats.constellation(Constellation::Glonass).is_none());
+
+    let total = stats.total();
+    assert_eq!(total.lock_loss, 2);
+    assert_eq!(total.half_cycle_slip, 2);
+    assert_eq!(total.under_anti_spoofing, 1);
+}
+
+#[test]
+fn v3_new_observation_builder() {
+    let g01 = SV::from_str("G01").unwrap();
+    let e05 = SV::from_str("E05").unwrap();
+
+    let c1c = Observable::from_str("C1C").unwrap();
+    let l1c = Observable::from_str("L1C").unwrap();
+    let c1x = Observable::from_str("C1X").unwrap();
+
+    let t0 = Epoch::from_str("2024-01-01T00:00:00 GPST").unwrap();
+    let t1 = Epoch::from_str("2024-01-01T00:00:30 GPST").unwrap();
+
+    let template = Header::basic_obs().with_general_information("rinex", "test", "nav-solutions");
+
+    let mut dut = Rinex::new_observation(Version::new(3, 5), &template);
+
+    assert!(dut.is_observation_rinex());
+    assert_eq!(dut.header.version, Version::new(3, 5));
+
+    dut.add_signal_observations(
+        t0,
+        vec![
+            SignalObservation::new(g01, c1c.clone(), 20243517.560),
+            SignalObservation::new(g01, l1c.clone(), 106380022.528).with_snr(SNR::from(7)),
+            SignalObservation::new(e05, c1x.clone(), 23453517.120),
+        ],
+    );
+
+    dut.add_signal_observations(
+        t1,
+        vec![
+            SignalObservation::new(g01, c1c.clone(), 20243617.560),
+            SignalObservation::new(g01, l1c.clone(), 106380547.528),
+        ],
+    );
+
+    let obs_header = dut.header.obs.as_ref().unwrap();
+    assert_eq!(
+        obs_header.codes.get(&Constellation::GPS),
+        Some(&vec![c1c, l1c])
+    );
+    assert_eq!(
+        obs_header.codes.get(&Constellation::Galileo),
+        Some(&vec![c1x])
+    );
+    assert_eq!(obs_header.timeof_first_obs, Some(t0));
+    assert_eq!(obs_header.timeof_last_obs, Some(t1));
+
+    let signals = dut
+        .signal_observations_iter()
+        .map(|(k, sig)| (k.epoch, sig.clone()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(signals.len(), 5);
+
+    let mut writer = BufWriter::new(Vec::new());
+    dut.format(&mut writer).unwrap();
+
+    let formatted = writer.into_inner().unwrap();
+    let mut reader = BufReader::new(formatted.as_slice());
+
+    let parsed = Rinex::parse(&mut reader).unwrap();
+
+    assert!(parsed.is_observation_rinex());
+    assert_eq!(parsed.epoch_iter().collect::<Vec<_>>(), vec![t0, t1]);
+
+    let parsed_signals = parsed
+        .signal_observations_iter()
+        .map(|(k, sig)| (k.epoch, sig.clone()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(parsed_signals.len(), signals.len());
+
+    for signal in signals.iter() {
+        assert!(parsed_signals.contains(signal), "{:?} was lost", signal);
+    }
+}
+
+#[test]
+fn v3_receiver_clock_offset() {
+    let content = "     3.05           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+  2024     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2024 01 01 00 00  0.0000000  0  1       0.000123456789
+G01  20243517.560   106380022.528
+> 2024 01 01 00 00 30.0000000  0  1
+G01  20243617.560   106380547.528
+> 2024 01 01 00 01  0.0000000  0  1      -0.000000987654
+G01  20243717.560   106381072.528
+";
+
+    let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    let t0 = Epoch::from_str("2024-01-01T00:00:00 GPST").unwrap();
+    let t2 = Epoch::from_str("2024-01-01T00:01:00 GPST").unwrap();
+
+    // epochs without clock state are not listed
+    assert_eq!(
+        rinex.receiver_clock_iter().collect::<Vec<_>>(),
+        vec![
+            (t0, Duration::from_seconds(0.000123456789)),
+            (t2, Duration::from_seconds(-0.000000987654)),
+        ],
+    );
+}
+
+#[test]
+fn v3_observations_at_nearest_epoch() {
+    let content = "     3.05           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+  2024     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2024 01 01 00 00  0.0000000  0  1
+G01  20243517.560   106380022.528
+> 2024 01 01 00 00 30.0000000  0  2
+G01  20243617.560   106380547.528
+G03  21243617.560   111635547.528
+> 2024 01 01 00 01  0.0000000  0  1
+G01  20243717.560   106381072.528
+";
+
+    let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    let g01 = SV::from_str("G01").unwrap();
+    let g03 = SV::from_str("G03").unwrap();
+    let l1c = Observable::from_str("L1C").unwrap();
+
+    let t0 = Epoch::from_str("2024-01-01T00:00:00 GPST").unwrap();
+    let t1 = Epoch::from_str("2024-01-01T00:00:30 GPST").unwrap();
+    let t2 = Epoch::from_str("2024-01-01T00:01:00 GPST").unwrap();
+
+    // exact epoch
+    let signals = rinex.observations_at(t1).unwrap();
+    assert_eq!(signals.len(), 4);
+
+    let l1c_g03 = signals
+        .iter()
+        .find(|sig| sig.sv == g03 && sig.observable == l1c)
+        .unwrap();
+    assert_eq!(l1c_g03.value, 111635547.528);
+
+    assert!(rinex
+        .observations_at(t0)
+        .unwrap()
+        .iter()
+        .all(|sig| sig.sv == g01));
+
+    // between samples
+    let t = Epoch::from_str("2024-01-01T00:00:20 GPST").unwrap();
+    assert!(rinex.observations_at(t).is_none());
+    assert_eq!(rinex.nearest_epoch(t), Some(t1));
+
+    let t = Epoch::from_str("2024-01-01T00:00:10 GPST").unwrap();
+    assert_eq!(rinex.nearest_epoch(t), Some(t0));
+
+    // exactly in between: earliest wins
+    let t = Epoch::from_str("2024-01-01T00:00:45 GPST").unwrap();
+    assert_eq!(rinex.nearest_epoch(t), Some(t1));
+
+    assert_eq!(rinex.nearest_epoch(t1), Some(t1));
+
+    // outside of the time frame
+    let t = Epoch::from_str("2023-12-31T23:00:00 GPST").unwrap();
+    assert_eq!(rinex.nearest_epoch(t), Some(t0));
+
+    let t = Epoch::from_str("2024-01-01T01:00:00 GPST").unwrap();
+    assert_eq!(rinex.nearest_epoch(t), Some(t2));
+}
+
+#[test]
+fn v3_per_system_observables_mapping() {
+    // GPS and Galileo declare a different number of observables, in different orders,
+    // including the special channel number pseudo observable
+    let content = "     3.05           OBSERVATION DATA    M                   RINEX VERSION / TYPE
+G    4 C1C L1C D1C S1C                                      SYS / # / OBS TYPES
+E    5 L5Q C5Q X1  L1C C1C                                  SYS / # / OBS TYPES
+  2024     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2024 01 01 00 00  0.0000000  0  2
+G01  20243517.560   106380022.528       -1234.567          45.000
+E05  89652340.123    23453517.120           7.000   123250011.250    23453518.340
+";
+
+    let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    let obs_header = rinex.header.obs.as_ref().unwrap();
+    assert_eq!(obs_header.codes.get(&Constellation::GPS).unwrap().len(), 4);
+    assert_eq!(
+        obs_header.codes.get(&Constellation::Galileo).unwrap().len(),
+        5
+    );
+
+    let signals = rinex
+        .signal_observations_iter()
+        .map(|(_, sig)| (sig.sv, sig.observable.to_string(), sig.value))
+        .collect::<Vec<_>>();
+
+    assert_eq!(signals.len(), 9);
+
+    let g01 = SV::from_str("G01").unwrap();
+    let e05 = SV::from_str("E05").unwrap();
+
+    for (sv, observable, value) in [
+        (g01, "C1C", 20243517.560),
+        (g01, "L1C", 106380022.528),
+        (g01, "D1C", -1234.567),
+        (g01, "S1C", 45.000),
+        (e05, "L5Q", 89652340.123),
+        (e05, "C5Q", 23453517.120),
+        (e05, "X1", 7.000),
+        (e05, "L1C", 123250011.250),
+        (e05, "C1C", 23453518.340),
+    ] {
+        assert!(
+            signals.contains(&(sv, observable.to_string(), value)),
+            "{}({}) should be {}",
+            sv,
+            observable,
+            value,
+        );
+    }
+}
+
+#[test]
+fn v3_header_mut_rebuild_from_record() {
+    let g01 = SV::from_str("G01").unwrap();
+    let e05 = SV::from_str("E05").unwrap();
+
+    let c1c = Observable::from_str("C1C").unwrap();
+    let l1c = Observable::from_str("L1C").unwrap();
+    let c1x = Observable::from_str("C1X").unwrap();
+
+    let t0 = Epoch::from_str("2024-01-01T00:00:00 GPST").unwrap();
+    let t1 = Epoch::from_str("2024-01-01T00:00:30 GPST").unwrap();
+    let t2 = Epoch::from_str("2024-01-01T00:01:00 GPST").unwrap();
+
+    let mut dut = Rinex::new_observation(Version::new(3, 5), &Header::basic_obs());
+
+    dut.add_signal_observations(
+        t0,
+        vec![
+            SignalObservation::new(g01, c1c.clone(), 20243517.560),
+            SignalObservation::new(g01, l1c.clone(), 106380022.528),
+        ],
+    );
+
+    dut.add_signal_observations(
+        t1,
+        vec![
+            SignalObservation::new(g01, c1c.clone(), 20243617.560),
+            SignalObservation::new(g01, l1c.clone(), 106380547.528),
+        ],
+    );
+
+    // guarded header modifications
+    dut.header_mut().observer = Some("test".to_string());
+    assert_eq!(dut.header.observer, Some("test".to_string()));
+
+    dut.header_mut().rinex_type = RinexType::MeteoData;
+    assert_eq!(dut.header.rinex_type, RinexType::ObservationData);
+
+    dut.header_mut().version = Version::new(2, 11);
+    assert_eq!(dut.header.version, Version::new(3, 5));
+
+    dut.header_mut().version = Version::new(4, 0);
+    assert_eq!(dut.header.version, Version::new(4, 0));
+
+    // direct record modifications: header is now outdated
+    let rec = dut.record.as_mut_obs().unwrap();
+
+    rec.remove(&ObsKey::new_ok(t0));
+
+    for (_, observations) in rec.iter_mut() {
+        observations.signals.retain(|sig| sig.observable != l1c);
+    }
+
+    rec.entry(ObsKey::new_ok(t2))
+        .or_default()
+        .signals
+        .extend(vec![
+            SignalObservation::new(g01, c1c.clone(), 20243717.560),
+            SignalObservation::new(e05, c1x.clone(), 23453517.120),
+        ]);
+
+    let obs_header = dut.header.obs.as_ref().unwrap();
+    assert_eq!(obs_header.timeof_first_obs, Some(t0));
+    assert_eq!(dut.header.constellation, Some(Constellation::GPS));
+
+    dut.rebuild_header_from_record();
+
+    assert_eq!(dut.header.constellation, Some(Constellation::Mixed));
+
+    let obs_header = dut.header.obs.as_ref().unwrap();
+    assert_eq!(obs_header.timeof_first_obs, Some(t1));
+    assert_eq!(obs_header.timeof_last_obs, Some(t2));
+    assert_eq!(obs_header.codes.len(), 2);
+
+    assert_eq!(obs_header.codes.get(&Constellation::GPS), Some(&vec![c1c]));
+    assert_eq!(
+        obs_header.codes.get(&Constellation::Galileo),
+        Some(&vec![c1x])
+    );
+}
+
+#[test]
+fn v3_append_to_file() {
+    let g01 = SV::from_str("G01").unwrap();
+    let e05 = SV::from_str("E05").unwrap();
+
+    let c1c = Observable::from_str("C1C").unwrap();
+    let l1c = Observable::from_str("L1C").unwrap();
+    let c1x = Observable::from_str("C1X").unwrap();
+    let c5q = Observable::from_str("C5Q").unwrap();
+
+    let t0 = Epoch::from_str("2024-01-01T00:00:00 GPST").unwrap();
+    let t1 = Epoch::from_str("2024-01-01T00:00:30 GPST").unwrap();
+    let t2 = Epoch::from_str("2024-01-01T00:01:00 GPST").unwrap();
+
+    let template = Header::basic_obs().with_general_information("rinex", "test", "nav-solutions");
+
+    let mut first = Rinex::new_observation(Version::new(3, 5), &template);
+
+    first.add_signal_observations(
+        t0,
+        vec![
+            SignalObservation::new(g01, c1c.clone(), 20243517.560),
+            SignalObservation::new(g01, l1c.clone(), 106380022.528),
+            SignalObservation::new(e05, c1x.clone(), 23453517.120),
+        ],
+    );
+
+    let mut second = Rinex::new_observation(Version::new(3, 5), &template);
+
+    second.add_signal_observations(
+        t1,
+        vec![
+            SignalObservation::new(g01, c1c.clone(), 20243617.560),
+            SignalObservation::new(e05, c1x.clone(), 23453617.120),
+        ],
+    );
+
+    second.add_signal_observations(
+        t2,
+        vec![
+            SignalObservation::new(g01, l1c.clone(), 106380547.528),
+            SignalObservation::new(e05, c1x.clone(), 23453717.120),
+        ],
+    );
+
+    let _ = remove_file("v3_append_to_file.txt");
+
+    // file does not exist: header is generated
+    first.append_to_file("v3_append_to_file.txt").unwrap();
+    second.append_to_file("v3_append_to_file.txt").unwrap();
+
+    // new observable, not described by the existing file
+    let mut incompatible = Rinex::new_observation(Version::new(3, 5), &template);
+
+    incompatible.add_signal_observations(
+        t2,
+        vec![
+            SignalObservation::new(g01, c1c.clone(), 20243717.560),
+            SignalObservation::new(e05, c5q, 23453717.120),
+        ],
+    );
+
+    assert!(matches!(
+        incompatible.append_to_file("v3_append_to_file.txt"),
+        Err(FormattingError::IncompatibleHeader),
+    ));
+
+    let parsed = Rinex::from_file("v3_append_to_file.txt").unwrap();
+    let _ = remove_file("v3_append_to_file.txt");
+
+    assert_eq!(parsed.epoch_iter().collect::<Vec<_>>(), vec![t0, t1, t2]);
+
+    // header was not rewritten
+    let obs_header = parsed.header.obs.as_ref().unwrap();
+    assert_eq!(obs_header.timeof_first_obs, Some(t0));
+
+    let signals = parsed
+        .signal_observations_iter()
+        .map(|(k, sig)| (k.epoch, sig.sv, sig.observable.clone(), sig.value))
+        .collect::<Vec<_>>();
+
+    assert_eq!(signals.len(), 7);
+
+    for (epoch, sv, observable, value) in [
+        (t1, g01, c1c.clone(), 20243617.560),
+        (t1, e05, c1x.clone(), 23453617.120),
+        (t2, g01, l1c.clone(), 106380547.528),
+        (t2, e05, c1x.clone(), 23453717.120),
+    ] {
+        assert!(
+            signals
+                .iter()
+                .any(|(t, s, obs, v)| *t == epoch && *s == sv && *obs == observable && *v == value),
+            "missing {}({}) {}",
+            epoch,
+            sv,
+            observable
+        );
+    }
+}
+
+#[test]
+fn v3_record_len_and_emptiness() {
+    let header = "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+";
+
+    let mut reader = BufReader::new(header.as_bytes());
+    let empty = Rinex::parse(&mut reader).unwrap();
+
+    assert!(empty.is_empty());
+    assert_eq!(empty.record_len(), 0);
+    assert_eq!(empty.epoch_count(), 0);
+    assert!(empty.first_epoch().is_none());
+
+    let content = format!(
+        "{}> 2022 03 04 00 00  0.0000000  0  1
+G01  20243517.560   106380022.528
+> 2022 03 04 00 00 30.0000000  4  1
+        0.1000        0.0000        0.0000                  ANTENNA: DELTA H/E/N
+> 2022 03 04 00 01  0.0000000  0  1
+G01  20243518.560   106380027.528
+",
+        header
+    );
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    assert!(!rinex.is_empty());
+    assert_eq!(rinex.record_len(), 3);
+    assert_eq!(rinex.epoch_count(), 3);
+
+    let dut = Rinex::from_file("data/OBS/V3/DUTH0630.22O").unwrap();
+    assert!(!dut.is_empty());
+    assert_eq!(dut.epoch_count(), dut.epoch_iter().count());
+    assert_eq!(dut.record_len(), dut.epoch_count());
+}
+
+#[test]
+fn v3_duplicate_epoch_policies() {
+    let content = "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2022 03 04 00 00  0.0000000  0  1
+G01  20243517.560   106380022.528
+> 2022 03 04 00 00  0.0000000  0  2
+G01                 106380099.528
+G02  21243517.560
+> 2022 03 04 00 00 30.0000000  0  1
+G01  20243518.560   106380027.528
+";
+
+    let g01 = SV::from_str("G01").unwrap();
+    let g02 = SV::from_str("G02").unwrap();
+
+    let c1c = Observable::from_str("C1C").unwrap();
+    let l1c = Observable::from_str("L1C").unwrap();
+
+    let t0 = Epoch::from_str("2022-03-04T00:00:00 GPST").unwrap();
+    let t1 = Epoch::from_str("2022-03-04T00:00:30 GPST").unwrap();
+
+    let signals_at = |rinex: &Rinex, t: Epoch| {
+        rinex
+            .signal_observations_iter()
+            .filter(|(k, _)| k.epoch == t)
+            .map(|(_, sig)| (sig.sv, sig.observable.clone(), sig.value))
+            .collect::<Vec<_>>()
+    };
+
+    // default: latest occurrence wins
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    assert_eq!(rinex.epoch_iter().collect::<Vec<_>>(), vec![t0, t1]);
+    assert_eq!(
+        signals_at(&rinex, t0),
+        vec![
+            (g01, l1c.clone(), 106380099.528),
+            (g02, c1c.clone(), 21243517.560)
+        ]
+    );
+
+    let options = ParsingOptions::default();
+    assert_eq!(options.duplicate_epochs, DuplicateEpochPolicy::Overwrite);
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let overwritten = Rinex::parse_with_options(&mut reader, &options).unwrap();
+    assert_eq!(overwritten.record, rinex.record);
+
+    // merge: signals are combined
+    let options = options.with_duplicate_epoch_policy(DuplicateEpochPolicy::Merge);
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let merged = Rinex::parse_with_options(&mut reader, &options).unwrap();
+
+    assert_eq!(merged.epoch_iter().collect::<Vec<_>>(), vec![t0, t1]);
+    assert_eq!(
+        signals_at(&merged, t0),
+        vec![
+            (g01, c1c.clone(), 20243517.560),
+            (g01, l1c.clone(), 106380099.528),
+            (g02, c1c.clone(), 21243517.560),
+        ]
+    );
+
+    assert_eq!(signals_at(&merged, t1).len(), 2);
+
+    // error: file is rejected
+    let options = options.with_duplicate_epoch_policy(DuplicateEpochPolicy::Error);
+
+    let mut reader = BufReader::new(content.as_bytes());
+    assert!(matches!(
+        Rinex::parse_with_options(&mut reader, &options),
+        Err(ParsingError::DuplicateEpoch)
+    ));
+}
+
+#[test]
+#[cfg(feature = "obs")]
+fn v3_duth0630_snr_statistics_per_sv() {
+    let dut = Rinex::from_file("data/OBS/V3/DUTH0630.22O").unwrap();
+
+    let s1c = Observable::from_str("S1C").unwrap();
+    let l1c = Observable::from_str("L1C").unwrap();
+
+    let stats = dut.snr_statistics_per_sv(&s1c);
+    assert!(!stats.is_empty());
+
+    for (sv, (mean, std)) in stats.iter() {
+        assert!(
+            *mean > 30.0 && *mean < 50.0,
+            "{}: implausible mean C/N0 {} dB-Hz",
+            sv,
+            mean
+        );
+        assert!(
+            *std >= 0.0 && *std < 20.0,
+            "{}: implausible std {}",
+            sv,
+            std
+        );
+    }
+
+    // explicit C/N0 is preferred
+    let phase_stats = dut.snr_statistics_per_sv(&l1c);
+
+    for (sv, (mean, std)) in phase_stats.iter() {
+        if let Some((ssi_mean, ssi_std)) = stats.get(sv) {
+            assert!((mean - ssi_mean).abs() < 1.0E-6, "{}", sv);
+            assert!((std - ssi_std).abs() < 1.0E-6, "{}", sv);
+        }
+    }
+
+    // not an Observation RINEX
+    assert!(Rinex::basic_nav().snr_statistics_per_sv(&s1c).is_empty());
+}
+
+#[test]
+#[cfg(feature = "obs")]
+fn v3_duth0630_observation_matrix() {
+    use std::collections::BTreeSet;
+
+    let dut = Rinex::from_file("data/OBS/V3/DUTH0630.22O").unwrap();
+
+    let c1c = Observable::from_str("C1C").unwrap();
+
+    let (epochs, satellites, grid) = dut.to_observation_matrix(&c1c);
+
+    let expected_epochs = dut.epoch_iter().collect::<BTreeSet<_>>();
+    assert_eq!(epochs, expected_epochs.into_iter().collect::<Vec<_>>());
+    assert_eq!(grid.len(), epochs.len());
+
+    let expected_satellites = dut
+        .signal_observations_iter()
+        .filter(|(_, sig)| sig.observable == c1c)
+        .map(|(_, sig)| sig.sv)
+        .collect::<BTreeSet<_>>();
+
+    assert_eq!(
+        satellites,
+        expected_satellites.into_iter().collect::<Vec<_>>()
+    );
+
+    for row in grid.iter() {
+        assert_eq!(row.len(), satellites.len());
+    }
+
+    // each value is correctly placed
+    let mut num_values = 0;
+
+    for (k, sig) in dut.signal_observations_iter() {
+        if sig.observable != c1c {
+            continue;
+        }
+
+        let row = epochs.iter().position(|t| *t == k.epoch).unwrap();
+        let col = satellites.iter().position(|sv| *sv == sig.sv).unwrap();
+
+        assert_eq!(grid[row][col], Some(sig.value));
+        num_values += 1;
+    }
+
+    let num_some = grid
+        .iter()
+        .flatten()
+        .filter(|value| value.is_some())
+        .count();
+    assert_eq!(num_some, num_values);
+
+    // not observed
+    let l5q = Observable::from_str("L5Q").unwrap();
+    let (epochs, satellites, grid) = dut.to_observation_matrix(&l5q);
+
+    assert_eq!(grid.len(), epochs.len());
+    assert!(satellites.is_empty());
+    assert!(grid.iter().all(|row| row.is_empty()));
+}
+
+#[test]
+fn v2_nsat0010_21o_satellites_continuation() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("OBS")
+        .join("V2")
+        .join("nsat0010.21o");
+
+    let dut = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+    let t0 = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+    let t1 = Epoch::from_str("2021-01-01T00:00:30 GPST").unwrap();
+
+    assert_eq!(dut.epoch_iter().collect::<Vec<_>>(), vec![t0, t1]);
+
+    let c1 = Observable::from_str("C1").unwrap();
+
+    for (t, num_sat) in [(t0, 14), (t1, 26)] {
+        // satellites use a blank system identifier and wrap over several lines
+        let satellites = dut
+            .signal_observations_iter()
+            .filter(|(k, sig)| k.epoch == t && sig.observable == c1)
+            .map(|(_, sig)| sig.sv)
+            .collect::<Vec<_>>();
+
+        let expected = (1..=num_sat)
+            .map(|prn| SV::new(Constellation::GPS, prn))
+            .collect::<Vec<_>>();
+
+        assert_eq!(satellites, expected, "{}: bad satellites", t);
+
+        assert_eq!(
+            dut.signal_observations_iter()
+                .filter(|(k, _)| k.epoch == t)
+                .count(),
+            2 * num_sat as usize,
+        );
+    }
+
+    // last satellite of the continuation line
+    let g14 = SV::new(Constellation::GPS, 14);
+
+    let value = dut
+        .signal_observations_iter()
+        .find(|(k, sig)| k.epoch == t0 && sig.sv == g14 && sig.observable == c1)
+        .map(|(_, sig)| sig.value)
+        .unwrap();
+
+    assert_eq!(value, 21728395.046);
+}
+
+#[test]
+#[cfg(all(feature = "obs", feature = "nav", feature = "flate2"))]
+fn v3_esbc00dnk_observation_weights() {
+    use crate::prelude::obs::WeightModel;
+
+    let obs =
+        Rinex::from_gzip_file("data/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz").unwrap();
+
+    let nav = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    // Esbjerg (Denmark)
+    let (rx_lat_deg, rx_lon_deg, rx_alt_m) = (55.47, 8.45, 0.0);
+
+    let weights = obs.observation_weights(
+        &nav,
+        (rx_lat_deg, rx_lon_deg, rx_alt_m),
+        WeightModel::Elevation,
+        30,
+    );
+
+    assert!(!weights.is_empty(), "no weights were generated");
+
+    let c1c = Observable::from_str("C1C").unwrap();
+
+    // first valid epoch
+    let (t0, _, _) = weights.keys().next().unwrap().clone();
+
+    let mut sky = weights
+        .iter()
+        .filter_map(|((t, sv, observable), weight)| {
+            if *t == t0 && *observable == c1c {
+                let (_, elev_deg, _) =
+                    nav.sky_position(*sv, *t, rx_lat_deg, rx_lon_deg, rx_alt_m, 30)?;
+                Some((*sv, elev_deg, *weight))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    assert!(sky.len() > 2, "not enough satellites in sight");
+
+    for (sv, elev_deg, weight) in sky.iter() {
+        assert!(*elev_deg > 0.0, "{} is below the horizon", sv);
+        assert!(*weight > 0.0 && *weight <= 1.0, "{} invalid weight", sv);
+    }
+
+    // higher elevation satellites have larger weights
+    sky.sort_by(|(_, a, _), (_, b, _)| a.total_cmp(b));
+
+    for pair in sky.windows(2) {
+        let (sv_low, _, w_low) = pair[0];
+        let (sv_high, _, w_high) = pair[1];
+        assert!(
+            w_high >= w_low,
+            "{} is higher than {} but has lower weight",
+            sv_high,
+            sv_low
+        );
+    }
+
+    // SNR model: stronger signals have larger weights
+    let weights = obs.observation_weights(
+        &nav,
+        (rx_lat_deg, rx_lon_deg, rx_alt_m),
+        WeightModel::Snr,
+        30,
+    );
+
+    assert!(!weights.is_empty(), "no weights were generated");
+
+    assert!(
+        weights
+            .keys()
+            .all(|(_, _, observable)| !observable.is_ssi_observable()),
+        "SSI observables should not be weighted"
+    );
+
+    assert!(WeightModel::snr_weight(45.0) > WeightModel::snr_weight(30.0));
+    assert!(WeightModel::elevation_weight(60.0) > WeightModel::elevation_weight(10.0));
+    assert!((WeightModel::elevation_weight(90.0) - 1.0).abs() < 1.0E-9);
+}
+
+#[test]
+fn v3_duth0630_crlf_line_endings() {
+    let lf = std::fs::read_to_string("data/OBS/V3/DUTH0630.22O").unwrap();
+    assert!(!lf.contains('\r'));
+
+    let model = Rinex::parse(&mut BufReader::new(lf.as_bytes())).unwrap();
+
+    // Windows style
+    let crlf = lf.replace('\n', "\r\n");
+    let dut = Rinex::parse(&mut BufReader::new(crlf.as_bytes())).unwrap();
+
+    assert_eq!(dut.header, model.header);
+    assert_eq!(dut.comments, model.comments);
+    assert_eq!(dut.record, model.record);
+
+    // legacy Mac style
+    let cr = lf.replace('\n', "\r");
+    let dut = Rinex::parse(&mut BufReader::new(cr.as_bytes())).unwrap();
+
+    assert_eq!(dut.header, model.header);
+    assert_eq!(dut.comments, model.comments);
+    assert_eq!(dut.record, model.record);
+}
+
+#[test]
+#[cfg(feature = "obs")]
+fn v3_carrier_phase_arcs() {
+    let content = "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2022 03 04 00 00  0.0000000  0  1
+G01  20243517.560   106380022.528
+> 2022 03 04 00 00 30.0000000  0  1
+G01  20243518.560   106380027.528
+> 2022 03 04 00 01  0.0000000  0  1
+G01  20243519.560   106381022.5281
+> 2022 03 04 00 01 30.0000000  0  1
+G01  20243520.560   106381027.528
+> 2022 03 04 00 02  0.0000000  0  1
+G01  20243521.560   106381032.528
+> 2022 03 04 00 02 30.0000000  0  1
+G01  20243522.560   106390032.5281
+";
+
+    let dut = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    let g01 = SV::from_str("G01").unwrap();
+    let l1c = Observable::from_str("L1C").unwrap();
+    let c1c = Observable::from_str("C1C").unwrap();
+
+    let lambda = 299_792_458.0 / 1575.42E6;
+
+    let arcs = dut.carrier_phase_arc_iter(g01, &l1c).collect::<Vec<_>>();
+
+    // arcs break at each lock loss
+    assert_eq!(arcs.len(), 3);
+
+    let starts = arcs.iter().map(|arc| arc[0].0).collect::<Vec<_>>();
+
+    assert_eq!(
+        starts,
+        [
+            Epoch::from_str("2022-03-04T00:00:00 GPST").unwrap(),
+            Epoch::from_str("2022-03-04T00:01:00 GPST").unwrap(),
+            Epoch::from_str("2022-03-04T00:02:30 GPST").unwrap(),
+        ]
+    );
+
+    assert_eq!(
+        arcs.iter().map(|arc| arc.len()).collect::<Vec<_>>(),
+        [2, 3, 1]
+    );
+
+    for (arc, expected_cycles) in arcs
+        .iter()
+        .zip([vec![0.0, 5.0], vec![0.0, 5.0, 10.0], vec![0.0]])
+    {
+        for ((t, phase_m), cycles) in arc.iter().zip(expected_cycles) {
+            assert!(
+                (phase_m - cycles * lambda).abs() < 1.0E-6,
+                "{} error: {} m",
+                t,
+                phase_m - cycles * lambda
+            );
+        }
+    }
+
+    // not a phase measurement
+    assert_eq!(dut.carrier_phase_arc_iter(g01, &c1c).count(), 0);
+}
+
+#[test]
+fn v3_duth0630_station_metadata_edition() {
+    let mut rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O").unwrap();
+
+    rinex.rename_station_mut("ESBC", Some("10118M001"));
+    rinex.set_agency_mut("Curation Agency");
+    rinex.set_operator_mut("Some Operator");
+
+    rinex.set_receiver_mut(
+        Receiver::default()
+            .with_model("SEPT POLARX5")
+            .with_serial_number("3013312")
+            .with_firmware("5.3.2"),
+    );
+
+    rinex.set_antenna_mut(
+        Antenna::default()
+            .with_model("LEIAR25.R4      LEIT")
+            .with_serial_number("725103"),
+    );
+
+    let mut writer = BufWriter::new(Vec::new());
+    rinex.format(&mut writer).unwrap();
+
+    let formatted = writer.into_inner().unwrap();
+    let mut reader = BufReader::new(formatted.as_slice());
+
+    let parsed = Rinex::parse(&mut reader).unwrap();
+
+    let marker = parsed.header.geodetic_marker.as_ref().unwrap();
+    assert_eq!(marker.name, "ESBC");
+    assert_eq!(marker.number(), Some("10118M001".to_string()));
+
+    assert_eq!(parsed.header.agency, Some("Curation Agency".to_string()));
+    assert_eq!(parsed.header.observer, Some("Some Operator".to_string()));
+
+    let rcvr = parsed.header.rcvr.as_ref().unwrap();
+    assert_eq!(rcvr.model, "SEPT POLARX5");
+    assert_eq!(rcvr.sn, "3013312");
+    assert_eq!(rcvr.firmware, "5.3.2");
+
+    let antenna = parsed.header.rcvr_antenna.as_ref().unwrap();
+    assert_eq!(antenna.model, "LEIAR25.R4      LEIT");
+    assert_eq!(antenna.sn, "725103");
+
+    // record is preserved
+    assert_eq!(parsed.record, rinex.record);
+
+    // RINEX fields are limited in width
+    rinex.set_operator_mut("An operator with a very long name");
+    assert_eq!(
+        rinex.header.observer,
+        Some("An operator with a v".to_string())
+    );
+}
+
+#[test]
+fn v3_receiver_clock_offset_applied() {
+    let content = "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+     1                                                      RCV CLOCK OFFS APPL
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2022 03 04 00 00  0.0000000  0  1       0.000123456789
+G01  20243517.560   106380022.528
+> 2022 03 04 00 00 30.0000000  0  1
+G01  20243518.560   106380027.528
+";
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    assert!(rinex.receiver_clock_is_applied());
+
+    let t0 = Epoch::from_str("2022-03-04T00:00:00 GPST").unwrap();
+
+    // offset is still reported
+    assert_eq!(
+        rinex.receiver_clock_iter().collect::<Vec<_>>(),
+        vec![(t0, Duration::from_seconds(0.000123456789))]
+    );
+
+    // flag is preserved
+    let mut writer = BufWriter::new(Vec::new());
+    rinex.format(&mut writer).unwrap();
+
+    let formatted = writer.into_inner().unwrap();
+    let mut reader = BufReader::new(formatted.as_slice());
+
+    let parsed = Rinex::parse(&mut reader).unwrap();
+    assert!(parsed.receiver_clock_is_applied());
+
+    // default value
+    let content = content.replace(
+        "     1                                                      RCV CLOCK OFFS APPL\n",
+        "",
+    );
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+    assert!(!rinex.receiver_clock_is_applied());
+}
+
+#[test]
+fn v3_rcvc0630_receiver_clock_offset_applied() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("OBS")
+        .join("V3")
+        .join("RCVC0630.22O");
+
+    let dut = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+    assert!(dut.receiver_clock_is_applied());
+
+    let t0 = Epoch::from_str("2022-03-04T00:00:00 GPST").unwrap();
+    let t2 = Epoch::from_str("2022-03-04T00:01:00 GPST").unwrap();
+
+    let offsets = dut.receiver_clock_iter().collect::<Vec<_>>();
+
+    assert_eq!(offsets.len(), 3);
+    assert_eq!(offsets[0], (t0, Duration::from_seconds(0.000123456789)));
+    assert_eq!(offsets[2], (t2, Duration::from_seconds(0.000123476789)));
+}
+
+#[test]
+fn v3_gps_satellite_timeseries() {
+    let content = "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2022 03 04 00 00  0.0000000  0  2
+G01  20243517.560   106380022.528
+G03  21243517.560   116380022.528
+> 2022 03 04 00 00 30.0000000  0  1
+G01  20243518.560
+> 2022 03 04 00 01  0.0000000  0  1
+G03  21243519.560   116380032.528
+";
+
+    let mut reader = BufReader::new(content.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    let t0 = Epoch::from_str("2022-03-04T00:00:00 GPST").unwrap();
+    let t1 = Epoch::from_str("2022-03-04T00:00:30 GPST").unwrap();
+
+    let g01 = SV::from_str("G01").unwrap();
+    let c1c = Observable::from_str("C1C").unwrap();
+    let l1c = Observable::from_str("L1C").unwrap();
+
+    let series = rinex.satellite_timeseries(g01);
+
+    assert_eq!(series.sv, g01);
+    assert_eq!(series.observations.len(), 2);
+
+    assert_eq!(
+        series.observations[&c1c],
+        vec![(t0, 20243517.560), (t1, 20243518.560)]
+    );
+
+    assert_eq!(series.observations[&l1c], vec![(t0, 106380022.528)]);
+
+    assert_eq!(
+        series.observation_epochs().into_iter().collect::<Vec<_>>(),
+        vec![t0, t1]
+    );
+
+    // single OBS RINEX
+    assert!(series.ephemerides.is_empty());
+    assert!(series.positions.is_empty());
+
+    // not observed
+    let g02 = SV::from_str("G02").unwrap();
+    assert!(rinex.satellite_timeseries(g02).is_empty());
+}
@@ -2,7 +2,8 @@
 #[cfg(test)]
 mod decimation {
     use crate::prelude::*;
-    use qc_traits::{Decimate, DecimationFilter};
+    use qc_traits::{Decimate, DecimationFilter, DecimationFilterType};
+    use std::io::BufWriter;
     use std::path::Path;
     #[test]
     #[cfg(feature = "flate2")]
@@ -37,6 +38,46 @@ mod decimation {
     }
     #[test]
     #[cfg(feature = "flate2")]
+    fn obs_sampling_interval_decimation() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("CRNX")
+            .join("V3")
+            .join("ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz");
+
+        let fullpath = path.to_string_lossy();
+        let mut rinex = Rinex::from_gzip_file(fullpath.as_ref()).unwrap();
+
+        let dt_30s = Duration::from_seconds(30.0);
+        assert_eq!(rinex.header.sampling_interval(), Some(dt_30s));
+        assert_eq!(rinex.dominant_sampling_interval(), Some(dt_30s));
+
+        rinex.decimate_mut(&DecimationFilter::duration(Duration::from_seconds(45.0)));
+
+        let dt_60s = Duration::from_seconds(60.0);
+        assert_eq!(rinex.header.sampling_interval(), Some(dt_60s));
+        assert_eq!(rinex.dominant_sampling_interval(), Some(dt_60s));
+
+        rinex.decimate_mut(&DecimationFilter {
+            item: None,
+            filter: DecimationFilterType::Modulo(2),
+        });
+
+        let dt_120s = Duration::from_seconds(120.0);
+        assert_eq!(rinex.header.sampling_interval(), Some(dt_120s));
+        assert_eq!(rinex.dominant_sampling_interval(), Some(dt_120s));
+
+        // declared interval is formatted accordingly
+        let mut writer = BufWriter::new(Vec::new());
+        rinex.format(&mut writer).unwrap();
+
+        let content = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert!(content
+            .lines()
+            .any(|line| line.starts_with("   120.000") && line.ends_with("INTERVAL")));
+    }
+    #[test]
+    #[cfg(feature = "flate2")]
     fn meteo_dt_decimation() {
         let path = Path::new(env!("CARGO_MANIFEST_DIR"))
             .join("data")
@@ -327,4 +327,167 @@ mod test {
         let carriers = dut.carrier_iter().sorted().collect::<Vec<_>>();
         assert_eq!(carriers, vec![Carrier::G2(None)]);
     }
+
+    #[test]
+    fn obs_time_bounds_v3_duth0630() {
+        let rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O").unwrap();
+
+        let t0 = Epoch::from_str("2022-03-04T00:00:00 GPST").unwrap();
+        let t1 = Epoch::from_str("2022-03-04T00:28:30 GPST").unwrap();
+        let t2 = Epoch::from_str("2022-03-04T00:57:00 GPST").unwrap();
+
+        let mask = Filter::mask(MaskOperand::GreaterThan, FilterItem::EpochItem(t0));
+        let dut = rinex.filter(&mask);
+
+        let obs = dut.header.obs.as_ref().unwrap();
+        assert_eq!(obs.timeof_first_obs, Some(t1));
+        assert_eq!(obs.timeof_last_obs, Some(t2));
+
+        // manual invocation, on untouched header
+        let mut dut = rinex.clone();
+        dut.recompute_time_bounds_mut();
+
+        let obs = dut.header.obs.as_ref().unwrap();
+        assert_eq!(obs.timeof_first_obs, Some(t0));
+        assert_eq!(obs.timeof_last_obs, Some(t2));
+    }
+
+    #[test]
+    fn obs_observable_snr_mask_v3_duth0630() {
+        use crate::observation::SNR;
+        use crate::prelude::processing::ObservableSNRMask;
+
+        let mut rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O").unwrap();
+
+        let c1c = Observable::from_str("C1C").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+
+        // even PRNs are weak signals, odd PRNs are strong signals
+        for (_, signal) in rinex.signal_observations_iter_mut() {
+            if signal.sv.prn % 2 == 0 {
+                signal.snr = Some(SNR::DbHz18_23);
+            } else {
+                signal.snr = Some(SNR::DbHz36_41);
+            }
+        }
+
+        let mask = ObservableSNRMask::new(c1c.clone(), SNR::DbHz30_35, MaskOperand::GreaterEquals);
+        let dut = rinex.observable_snr_mask(&mask);
+
+        let total_c1c = rinex
+            .signal_observations_iter()
+            .filter(|(_, sig)| sig.observable == c1c)
+            .count();
+
+        let total_l1c = rinex
+            .signal_observations_iter()
+            .filter(|(_, sig)| sig.observable == l1c)
+            .count();
+
+        let masked_c1c = dut
+            .signal_observations_iter()
+            .filter(|(_, sig)| sig.observable == c1c)
+            .count();
+
+        assert!(masked_c1c > 0);
+        assert!(masked_c1c < total_c1c);
+
+        for (_, sig) in dut.signal_observations_iter() {
+            if sig.observable == c1c {
+                assert_eq!(sig.sv.prn % 2, 1, "{} C1C should have been masked", sig.sv);
+            }
+        }
+
+        // L1C is preserved regardless of SNR
+        let masked_l1c = dut
+            .signal_observations_iter()
+            .filter(|(_, sig)| sig.observable == l1c)
+            .count();
+
+        assert_eq!(masked_l1c, total_l1c);
+
+        // missing SNR is dropped
+        for (_, signal) in rinex.signal_observations_iter_mut() {
+            signal.snr = None;
+        }
+
+        let dut = rinex.observable_snr_mask(&mask);
+
+        assert_eq!(
+            dut.signal_observations_iter()
+                .filter(|(_, sig)| sig.observable == c1c)
+                .count(),
+            0
+        );
+
+        assert_eq!(
+            dut.signal_observations_iter()
+                .filter(|(_, sig)| sig.observable == l1c)
+                .count(),
+            total_l1c
+        );
+    }
+
+    #[test]
+    fn obs_event_epochs_masking() {
+        use crate::observation::{EpochFlag, ObsKey, SNR};
+        use crate::prelude::processing::ObservableSNRMask;
+        use std::io::BufReader;
+
+        let content =
+            "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+  2022     3     4     0     0    0.0000000     GPS         TIME OF FIRST OBS
+                                                            END OF HEADER
+> 2022 03 04 00 00  0.0000000  0  1
+G01  20243517.560   106380022.528
+> 2022 03 04 00 00 30.0000000  4  1
+        0.1000        0.0000        0.0000                  ANTENNA: DELTA H/E/N
+> 2022 03 04 00 01  0.0000000  0  1
+G01  20243518.560   106380027.528
+";
+
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+        let t1 = Epoch::from_str("2022-03-04T00:00:30 GPST").unwrap();
+
+        let event = ObsKey {
+            epoch: t1,
+            flag: EpochFlag::HeaderInformationFollows,
+        };
+
+        // signal masks preserve the event
+        let g01 = SV::from_str("G01").unwrap();
+
+        for mask in [
+            Filter::mask(MaskOperand::NotEquals, FilterItem::SvItem(vec![g01])),
+            Filter::mask(
+                MaskOperand::Equals,
+                FilterItem::ConstellationItem(vec![Constellation::Galileo]),
+            ),
+        ] {
+            let dut = rinex.filter(&mask);
+            let rec = dut.record.as_obs().unwrap();
+            assert_eq!(rec.len(), 1, "signals should have been masked");
+            assert!(rec.contains_key(&event), "event should have been preserved");
+        }
+
+        let mask = ObservableSNRMask::new(
+            Observable::from_str("C1C").unwrap(),
+            SNR::DbHz30_35,
+            MaskOperand::GreaterEquals,
+        );
+
+        let dut = rinex.observable_snr_mask(&mask);
+        let rec = dut.record.as_obs().unwrap();
+        assert_eq!(rec.len(), 3);
+        assert!(rec.contains_key(&event), "event should have been preserved");
+
+        // epoch masks apply to events
+        let mask = Filter::mask(MaskOperand::NotEquals, FilterItem::EpochItem(t1));
+        let dut = rinex.filter(&mask);
+        let rec = dut.record.as_obs().unwrap();
+        assert_eq!(rec.len(), 2);
+        assert!(!rec.contains_key(&event));
+    }
 }
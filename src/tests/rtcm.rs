@@ -1,8 +1,13 @@
 use crate::{
-    navigation::Ephemeris,
-    prelude::{Constellation, Rinex},
+    navigation::{Ephemeris, OrbitItem, SkipReason},
+    prelude::{Constellation, Epoch, OutputFormat, ParsingError, Rinex, RinexType, SV},
+    tests::toolkit::{assert_ephemeris_roundtrip, RoundtripFormat},
 };
 
+use itertools::Itertools;
+
+use std::{f64::consts::PI, str::FromStr};
+
 // NAV (V3) to RTCM
 #[test]
 #[cfg(feature = "nav")]
@@ -19,6 +24,12 @@ fn esbcdnk_ephv3_to_rtcm() {
         match k.sv.constellation {
             Constellation::GPS => {
                 if let Some(msg) = ephemeris.to_rtcm_gps1019(k.epoch, k.sv) {
+                    assert!(assert_ephemeris_roundtrip(
+                        ephemeris,
+                        k.epoch,
+                        k.sv,
+                        RoundtripFormat::Rtcm,
+                    ));
                     gps1019 += 1;
                 }
             },
@@ -59,6 +70,198 @@ fn esbcdnk_ephv3_to_rtcm() {
     assert_eq!(qzss1044, 15);
 }
 
+/// G10 ephemeris published in ESBC00DNK_R_20201770000_01D_MN (2020-06-25T00:00:00 GPST).
+/// Angular fields are expressed in radians, like RINEX does.
+fn esbcdnk_g10_ephemeris() -> Ephemeris {
+    let mut ephemeris = Ephemeris {
+        clock_bias: -1.583695411682E-05,
+        clock_drift: -1.136868377216E-13,
+        clock_drift_rate: 0.0,
+        ..Default::default()
+    };
+
+    for (field, value) in [
+        ("iode", 170.0),
+        ("crs", -54.375),
+        ("deltaN", 2.145446579149E-09),
+        ("m0", 1.032475437231),
+        ("cuc", -1.853704452515E-06),
+        ("e", 7.516302773729E-03),
+        ("cus", 1.013278961182E-05),
+        ("sqrta", 5.153623002625E+03),
+        ("toe", 345600.0),
+        ("cic", -2.518296241760E-06),
+        ("omega0", 2.179378003185),
+        ("cis", -8.456408977509E-07),
+        ("i0", 9.576713462938E-01),
+        ("crc", -366.25),
+        ("omega", -1.562017655373),
+        ("omegaDot", -2.667968273468E-09),
+        ("idot", -2.203663219130E-10),
+        ("l2Codes", 1.0),
+        ("l2p", 0.0),
+        ("accuracy", 2.0),
+        ("health", 0.0),
+        ("tgd", -3.259629011154E-09),
+        ("iodc", 426.0),
+        ("t_tm", 340500.0),
+        ("fitInt", 4.0),
+    ] {
+        ephemeris = ephemeris.with_orbit(field, OrbitItem::F64(value));
+    }
+
+    ephemeris.with_orbit("week", OrbitItem::U32(2111))
+}
+
+#[test]
+fn rtcm_gps1019_semicircles() {
+    let g10 = SV::from_str("G10").unwrap();
+    let toc = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+
+    let ephemeris = esbcdnk_g10_ephemeris();
+
+    let msg = ephemeris.to_rtcm_gps1019(toc, g10).unwrap();
+
+    // RTCM streams angular terms in semicircles
+    for (field, semicircles) in [
+        ("m0", msg.m0_sc),
+        ("i0", msg.i0_sc),
+        ("omega0", msg.omega0_sc),
+        ("omega", msg.omega_sc),
+        ("omegaDot", msg.omegadot_sc_s),
+        ("idot", msg.idot_sc_s),
+        ("deltaN", msg.delta_n_sc_s as f64),
+    ] {
+        let expected = ephemeris.get_orbit_f64(field).unwrap() / PI;
+        let err = (semicircles - expected).abs();
+        assert!(
+            err <= expected.abs() * 1.0E-6,
+            "{}: encoded {} semicircles, expecting {}",
+            field,
+            semicircles,
+            expected
+        );
+    }
+
+    // decoder converts back to radians
+    let (sv, decoded) = Ephemeris::from_rtcm_gps1019(&msg, 2111);
+    assert_eq!(sv, g10);
+
+    for field in ["m0", "i0", "omega0", "omega", "omegaDot", "idot", "deltaN"] {
+        let expected = ephemeris.get_orbit_f64(field).unwrap();
+        let decoded = decoded.get_orbit_f64(field).unwrap();
+        assert!(
+            (decoded - expected).abs() <= expected.abs() * 1.0E-6,
+            "{}: decoded {} rad, expecting {}",
+            field,
+            decoded,
+            expected
+        );
+    }
+}
+
+#[test]
+fn rtcm_gps1019_toe_time_of_week() {
+    let g10 = SV::from_str("G10").unwrap();
+    let toc = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+
+    let ephemeris = esbcdnk_g10_ephemeris();
+
+    let msg = ephemeris.to_rtcm_gps1019(toc, g10).unwrap();
+
+    // ToE is streamed in seconds of week
+    assert_eq!(msg.toe_s, 345600.0);
+
+    let (_, decoded) = Ephemeris::from_rtcm_gps1019(&msg, 2111);
+
+    assert_eq!(decoded.toe(g10), ephemeris.toe(g10));
+    assert_eq!(
+        decoded.toe(g10),
+        Some(Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap())
+    );
+}
+
+#[test]
+fn rtcm_gps1019_ura_fit_interval_l2_codes_week() {
+    let g10 = SV::from_str("G10").unwrap();
+    let toc = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+
+    let ephemeris = esbcdnk_g10_ephemeris();
+
+    let msg = ephemeris.to_rtcm_gps1019(toc, g10).unwrap();
+
+    // 10-bit week counter
+    assert_eq!(msg.gps_week_number, 2111 % 1024);
+
+    // 2.0m accuracy is URA index 0 (<= 2.4m)
+    assert_eq!(msg.ura_index, 0);
+
+    // 4h fit interval
+    assert_eq!(msg.fit_interval_ind, 0);
+
+    // C/A code on L2
+    assert_eq!(msg.code_on_l2_ind, 1);
+
+    let ephemeris = ephemeris
+        .with_orbit("accuracy", OrbitItem::F64(5.0))
+        .with_orbit("fitInt", OrbitItem::F64(6.0))
+        .with_orbit("l2Codes", OrbitItem::F64(2.0));
+
+    let msg = ephemeris.to_rtcm_gps1019(toc, g10).unwrap();
+
+    // 5.0m accuracy is URA index 3 (4.85m < 5.0m <= 6.85m)
+    assert_eq!(msg.ura_index, 3);
+
+    // more than 4h
+    assert_eq!(msg.fit_interval_ind, 1);
+
+    assert_eq!(msg.code_on_l2_ind, 2);
+
+    // same rules apply to QZSS 1044
+    let j01 = SV::from_str("J01").unwrap();
+    let qzss = ephemeris.to_rtcm_qzss1044(toc, j01).unwrap();
+    assert_eq!(qzss.qzss_week_number, 2111 % 1024);
+    assert_eq!(qzss.fit_interval_ind, 1);
+
+    let (_, decoded) = Ephemeris::from_rtcm_gps1019(&msg, 2111);
+    assert_eq!(decoded.get_orbit_f64("accuracy"), Some(6.85));
+    assert_eq!(decoded.get_orbit_f64("l2Codes"), Some(2.0));
+}
+
+#[test]
+fn rtcm_gps1019_fit_interval() {
+    let g10 = SV::from_str("G10").unwrap();
+    let toc = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+
+    let ephemeris = esbcdnk_g10_ephemeris();
+
+    // IS-GPS-200 Table 20-XII
+    for (fit_int, iodc, expected) in [
+        (4.0, 426.0, 4.0),
+        (6.0, 426.0, 6.0),
+        (8.0, 240.0, 8.0),
+        (14.0, 496.0, 14.0),
+        (26.0, 1021.0, 26.0),
+        (50.0, 504.0, 50.0),
+        (74.0, 752.0, 74.0),
+        (98.0, 757.0, 98.0),
+    ] {
+        let ephemeris = ephemeris
+            .with_orbit("fitInt", OrbitItem::F64(fit_int))
+            .with_orbit("iodc", OrbitItem::F64(iodc));
+
+        let msg = ephemeris.to_rtcm_gps1019(toc, g10).unwrap();
+        let (_, decoded) = Ephemeris::from_rtcm_gps1019(&msg, 2111);
+
+        assert_eq!(
+            decoded.get_orbit_f64("fitInt"),
+            Some(expected),
+            "invalid fit interval for IODC={}",
+            iodc
+        );
+    }
+}
+
 // GLO (V2) to RTCM
 #[test]
 #[ignore]
@@ -91,3 +294,145 @@ fn esbcdnk_nav3_to_ubx() {
 
     let rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
 }
+
+// RTCM2RNX (NAV)
+#[test]
+#[cfg(feature = "flate2")]
+fn esbcdnk_rtcm_stream_to_nav() {
+    let rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let mut rtcm = Vec::new();
+    rinex.convert(OutputFormat::Rtcm, &mut rtcm).unwrap();
+
+    // recording date
+    let reference = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+
+    let decoded =
+        Rinex::from_rtcm_stream(rtcm.as_slice(), RinexType::NavigationData, reference).unwrap();
+
+    assert!(decoded.is_navigation_rinex());
+    assert_eq!(decoded.header.constellation, Some(Constellation::GPS));
+
+    // only GPS ephemeris are decoded
+    let gps_sv = rinex
+        .nav_ephemeris_frames_iter()
+        .filter_map(|(k, eph)| {
+            if k.sv.constellation == Constellation::GPS {
+                eph.to_rtcm_gps1019(k.epoch, k.sv).map(|_| k.sv)
+            } else {
+                None
+            }
+        })
+        .unique()
+        .count();
+
+    assert_eq!(decoded.sv_iter().count(), gps_sv);
+    assert_eq!(decoded.nav_ephemeris_frames_iter().count(), 253);
+
+    for (k, eph) in decoded.nav_ephemeris_frames_iter() {
+        assert_eq!(k.sv.constellation, Constellation::GPS);
+        assert!(eph.get_orbit_f64("sqrta").is_some());
+        assert!(eph.toe(k.sv).is_some());
+
+        // week counter is resolved
+        assert!(
+            rinex
+                .nav_ephemeris_frames_iter()
+                .any(|(orig, _)| orig.sv == k.sv && orig.epoch == k.epoch),
+            "{}({}) invalid ToC",
+            k.epoch,
+            k.sv
+        );
+    }
+
+    // any reference within half a week, or 512 weeks for the week counter, is fine
+    for reference in ["2020-06-28T12:00:00 GPST", "2029-12-31T00:00:00 GPST"] {
+        let reference = Epoch::from_str(reference).unwrap();
+
+        let other =
+            Rinex::from_rtcm_stream(rtcm.as_slice(), RinexType::NavigationData, reference).unwrap();
+
+        assert_eq!(
+            other.nav_ephemeris_frames_iter().collect::<Vec<_>>(),
+            decoded.nav_ephemeris_frames_iter().collect::<Vec<_>>(),
+        );
+    }
+
+    // MSM messages only
+    let decoded =
+        Rinex::from_rtcm_stream(rtcm.as_slice(), RinexType::ObservationData, reference).unwrap();
+
+    assert!(decoded.is_observation_rinex());
+    assert_eq!(decoded.epoch_iter().count(), 0);
+}
+
+#[test]
+fn rtcm_stream_non_supported_type() {
+    let reference = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+
+    match Rinex::from_rtcm_stream([].as_slice(), RinexType::MeteoData, reference) {
+        Err(ParsingError::NonSupportedRtcmType) => {},
+        _ => panic!("meteo rinex should not be supported"),
+    }
+}
+
+// RNX2RTCM skipped frames
+#[test]
+#[cfg(feature = "flate2")]
+fn esbcdnk_rtcm_skipped_report() {
+    let rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let total_sbas = rinex
+        .nav_ephemeris_frames_iter()
+        .filter(|(k, _)| k.sv.constellation.is_sbas())
+        .count();
+
+    assert!(total_sbas > 0, "test file should contain SBAS frames");
+
+    let mut streamer = rinex.rnx2rtcm().unwrap();
+    let streamed = streamer.by_ref().count();
+
+    assert!(streamed > 0);
+
+    let report = streamer.skipped_report();
+
+    // SBAS (1043) is not supported yet
+    let sbas = report
+        .iter()
+        .filter(|(_, sv, _)| sv.constellation.is_sbas())
+        .collect::<Vec<_>>();
+
+    assert_eq!(sbas.len(), total_sbas);
+
+    for (_, _, reason) in sbas {
+        assert_eq!(*reason, SkipReason::NonSupportedConstellation);
+    }
+
+    // nothing was silently dropped
+    assert_eq!(
+        streamed + report.len(),
+        rinex.nav_ephemeris_frames_iter().count()
+    );
+}
+
+#[test]
+fn esbcdnk_rtcm_estimated_message_count() {
+    let rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let mut streamer = rinex.rnx2rtcm().unwrap();
+    let estimated = streamer.estimated_message_count();
+
+    let streamed = streamer.by_ref().count();
+
+    assert!(streamed > 0);
+    assert!(streamed <= estimated);
+
+    // the estimate only misses the frames that could not be encoded
+    let missing_fields = streamer
+        .skipped_report()
+        .iter()
+        .filter(|(_, _, reason)| *reason == SkipReason::MissingFields)
+        .count();
+
+    assert_eq!(streamed + missing_fields, estimated);
+}
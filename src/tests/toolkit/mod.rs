@@ -13,6 +13,10 @@ mod observation;
 #[cfg(feature = "nav")]
 mod nav;
 
+// Binary formats reciprocity
+#[cfg(any(feature = "binex", feature = "rtcm", feature = "ublox"))]
+mod roundtrip;
+
 // Meteo RINEX dedicated tests
 #[cfg(feature = "meteo")]
 mod meteo;
@@ -28,6 +32,9 @@ pub use nav::{
     generic_comparison as generic_navigation_comparison, generic_test as generic_navigation_test,
 };
 
+#[cfg(any(feature = "binex", feature = "rtcm", feature = "ublox"))]
+pub use roundtrip::{assert_ephemeris_roundtrip, Format as RoundtripFormat};
+
 #[cfg(feature = "meteo")]
 pub use meteo::{generic_comparison as generic_meteo_comparison, generic_meteo_rinex_test};
 
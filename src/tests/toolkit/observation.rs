@@ -313,7 +313,9 @@ pub fn generic_observation_epoch_decoding_test(
     // PARSE
     let mut obs = Observations::default();
 
-    let key = parse_epoch(&header, content, ts, &mut obs).unwrap();
+    let mut skipped_lines = Vec::new();
+    let key = parse_epoch(&header, content, ts, &mut obs, None, &mut skipped_lines).unwrap();
+    assert!(skipped_lines.is_empty());
 
     assert_eq!(key.epoch, key_epoch);
     assert_eq!(key.flag, key_flag);
@@ -0,0 +1,214 @@
+//! [Ephemeris] reciprocity (encoding then decoding) through binary formats
+use crate::{
+    navigation::Ephemeris,
+    prelude::{Epoch, SV},
+};
+
+#[cfg(any(feature = "rtcm", feature = "ublox"))]
+use crate::prelude::Constellation;
+
+#[cfg(feature = "rtcm")]
+use rtcm_rs::{msg::message::Message, next_msg_frame, MessageBuilder};
+
+#[cfg(feature = "ublox")]
+use ublox::{packetref_proto23::PacketRef, Parser, UbxPacket};
+
+#[cfg(any(feature = "rtcm", feature = "ublox"))]
+use std::f64::consts::PI;
+
+/// Binary [Format]s we can encode [Ephemeris] to, and decode from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// BINEX ephemeris frames, mirrored at the frame level.
+    /// Most fields are single precision floats.
+    #[cfg(feature = "binex")]
+    Binex,
+    /// RTCM ephemeris messages. Only GPS (1019) may be decoded at the moment.
+    #[cfg(feature = "rtcm")]
+    Rtcm,
+    /// UBX-MGA-EPH frames (GPS, QZSS, BeiDou and Galileo).
+    #[cfg(feature = "ublox")]
+    Ubx,
+}
+
+/// Quantization step (one LSB, in RINEX units) of the fields that are streamed
+/// as scaled integers. We retain the coarsest step of IS-GPS-200, Galileo OS-SIS-ICD
+/// and BDS-SIS-ICD, so one table applies to all constellations.
+/// Fields that should be transmitted exactly have a null step.
+#[cfg(any(feature = "rtcm", feature = "ublox"))]
+fn icd_resolution(field: &str) -> Option<f64> {
+    match field {
+        "clock_bias" | "tgd" => Some(2.0_f64.powi(-31)),
+        "clock_drift" => Some(2.0_f64.powi(-43)),
+        "clock_drift_rate" => Some(2.0_f64.powi(-55)),
+        "crs" | "crc" => Some(2.0_f64.powi(-5)),
+        "cuc" | "cus" | "cic" | "cis" => Some(2.0_f64.powi(-29)),
+        "e" => Some(2.0_f64.powi(-33)),
+        "sqrta" => Some(2.0_f64.powi(-19)),
+        "m0" | "omega0" | "omega" | "i0" => Some(2.0_f64.powi(-31) * PI),
+        "deltaN" | "idot" | "omegaDot" => Some(2.0_f64.powi(-43) * PI),
+        "toe" => Some(60.0),
+        "iode" | "iodc" | "iodnav" | "health" | "l2p" | "l2Codes" | "week" => Some(0.0),
+        _ => None,
+    }
+}
+
+impl Format {
+    /// Fields that this [Format] does not carry (yet), but the decoder
+    /// still defines, as a placeholder or in a lossy form.
+    fn not_carried(&self) -> &'static [&'static str] {
+        match self {
+            #[cfg(feature = "binex")]
+            Self::Binex => &["week"],
+            // accuracy is quantized to the URA index, t_tm is not streamed
+            // and the fit interval is reduced to a flag.
+            #[cfg(feature = "rtcm")]
+            Self::Rtcm => &["accuracy", "t_tm", "fitInt"],
+            // MGA-GAL only describes I/NAV: E5a health and the data source are lost.
+            #[cfg(feature = "ublox")]
+            Self::Ubx => &["week", "iodnav", "sisa", "source", "health"],
+        }
+    }
+
+    /// Maximal absolute error we tolerate on this field, after a round trip.
+    /// Single precision floats are tolerated a relative error (any format),
+    /// scaled integers are tolerated one LSB (see [icd_resolution]).
+    fn tolerance(&self, field: &str, value: f64) -> f64 {
+        let single_precision = value.abs() * f32::EPSILON as f64;
+        match self {
+            #[cfg(feature = "binex")]
+            Self::Binex => single_precision,
+            #[cfg(feature = "rtcm")]
+            Self::Rtcm => single_precision + icd_resolution(field).unwrap_or_default(),
+            #[cfg(feature = "ublox")]
+            Self::Ubx => single_precision + icd_resolution(field).unwrap_or_default(),
+        }
+    }
+
+    /// Encodes, then decodes this [Ephemeris].
+    /// Returns None when this [SV] is not supported by this [Format].
+    fn mirror(&self, eph: &Ephemeris, toc: Epoch, sv: SV) -> Option<(SV, Ephemeris)> {
+        match self {
+            #[cfg(feature = "binex")]
+            Self::Binex => {
+                let frame = eph.to_binex(toc, sv)?;
+                let decoded = Ephemeris::from_binex(frame).unwrap_or_else(|| {
+                    panic!("{}({}) failed to decode BINEX frame", toc, sv);
+                });
+                Some(decoded)
+            },
+            #[cfg(feature = "rtcm")]
+            Self::Rtcm => {
+                let message = match sv.constellation {
+                    Constellation::GPS => Message::Msg1019(eph.to_rtcm_gps1019(toc, sv)?),
+                    _ => return None, // decoding not supported yet
+                };
+
+                let mut builder = MessageBuilder::new();
+
+                let bytes = builder.build_message(&message).unwrap_or_else(|_| {
+                    panic!("{}({}) failed to encode RTCM message", toc, sv);
+                });
+
+                let (_, frame) = next_msg_frame(bytes);
+
+                let frame = frame.unwrap_or_else(|| {
+                    panic!("{}({}) did not encode a valid RTCM frame", toc, sv);
+                });
+
+                match frame.get_message() {
+                    Message::Msg1019(msg) => {
+                        let (week, _) = toc.to_time_of_week();
+                        Some(Ephemeris::from_rtcm_gps1019(&msg, week))
+                    },
+                    _ => panic!("{}({}) did not encode a RTCM 1019 message", toc, sv),
+                }
+            },
+            #[cfg(feature = "ublox")]
+            Self::Ubx => {
+                let bytes = match sv.constellation {
+                    Constellation::GPS | Constellation::QZSS => {
+                        eph.to_ubx_mga_gps_qzss(toc, sv)?.to_vec()
+                    },
+                    Constellation::BeiDou => eph.to_ubx_mga_bds(toc, sv)?.to_vec(),
+                    Constellation::Galileo => eph.to_ubx_mga_gal(toc, sv)?.to_vec(),
+                    _ => return None, // TODO: Glonass reciprocity
+                };
+
+                let mut parser: Parser<Vec<u8>> = Parser::default();
+                let mut packets = parser.consume_ubx(&bytes);
+
+                match packets.next() {
+                    Some(Ok(UbxPacket::Proto23(packet))) => match packet {
+                        PacketRef::MgaGpsEph(ubx) => {
+                            if sv.constellation == Constellation::QZSS {
+                                Some(Ephemeris::from_ubx_mga_qzss(ubx))
+                            } else {
+                                Some(Ephemeris::from_ubx_mga_gps(ubx))
+                            }
+                        },
+                        PacketRef::MgaBdsEph(ubx) => Some(Ephemeris::from_ubx_mga_bds(ubx)),
+                        PacketRef::MgaGalEph(ubx) => Some(Ephemeris::from_ubx_mga_gal(ubx)),
+                        _ => panic!("{}({}) did not encode a UBX-MGA-EPH frame", toc, sv),
+                    },
+                    _ => panic!("{}({}) did not encode a valid UBX frame", toc, sv),
+                }
+            },
+        }
+    }
+
+    fn assert_field(&self, toc: Epoch, sv: SV, field: &str, model: f64, dut: f64) {
+        let tolerance = self.tolerance(field, model);
+        assert!(
+            (dut - model).abs() <= tolerance,
+            "{}({}) {:?} \"{}\": decoded {:e} but encoded {:e} (tolerance {:e})",
+            toc,
+            sv,
+            self,
+            field,
+            dut,
+            model,
+            tolerance,
+        );
+    }
+}
+
+/// Encodes this [Ephemeris] to [Format], decodes it back and verifies that
+/// the [SV], the clock terms and each orbital field the [Format] carries
+/// were preserved, within the [Format] tolerance.
+/// Returns false when this [SV] (or [Ephemeris]) could not be encoded
+/// to this [Format]: nothing was tested.
+pub fn assert_ephemeris_roundtrip(eph: &Ephemeris, toc: Epoch, sv: SV, format: Format) -> bool {
+    let (decoded_sv, decoded) = match format.mirror(eph, toc, sv) {
+        Some(mirrored) => mirrored,
+        None => return false,
+    };
+
+    assert_eq!(decoded_sv, sv, "{}({}) {:?} invalid SV", toc, sv, format);
+
+    for (field, model, dut) in [
+        ("clock_bias", eph.clock_bias, decoded.clock_bias),
+        ("clock_drift", eph.clock_drift, decoded.clock_drift),
+        (
+            "clock_drift_rate",
+            eph.clock_drift_rate,
+            decoded.clock_drift_rate,
+        ),
+    ] {
+        format.assert_field(toc, sv, field, model, dut);
+    }
+
+    let not_carried = format.not_carried();
+
+    for (field, dut) in decoded.orbits.iter() {
+        if not_carried.contains(&field.as_str()) {
+            continue;
+        }
+
+        if let Some(model) = eph.orbits.get(field) {
+            format.assert_field(toc, sv, field, model.as_f64(), dut.as_f64());
+        }
+    }
+
+    true
+}
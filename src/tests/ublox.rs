@@ -1,15 +1,141 @@
 use std::io::Read;
 
 use crate::{
-    navigation::Ephemeris,
-    prelude::{Constellation, Rinex},
+    navigation::{Ephemeris, OrbitItem, SkipReason},
+    observation::LliFlags,
+    prelude::{Constellation, Epoch, Observable, Rinex, TimeScale, SV},
+    tests::toolkit::{assert_ephemeris_roundtrip, RoundtripFormat},
 };
 
+use std::str::FromStr;
+
 use ublox::{
     mga_bds_eph::MgaBdsEphRef, mga_glo_eph::MgaGloEphRef, mga_gps_eph::MgaGpsEphRef,
     packetref_proto23::PacketRef, Parser, UbxPacket,
 };
 
+/// Builds a GPS [Ephemeris] with all fields MGA-GPS-EPH requires.
+/// Angular fields are expressed in radians, like RINEX does.
+fn gps_mga_ephemeris(m0_rad: f64) -> Ephemeris {
+    let mut ephemeris = Ephemeris::default();
+
+    for (field, value) in [
+        ("toe", 345600.0),
+        ("tgd", -1.1641532182693E-08),
+        ("iodc", 45.0),
+        ("health", 0.0),
+        ("cuc", -2.1196901798248E-06),
+        ("cus", 5.4277107119560E-06),
+        ("cic", 1.2665987014771E-07),
+        ("cis", -3.7252902984619E-08),
+        ("crc", 2.4121875000000E+02),
+        ("crs", -4.0687500000000E+01),
+        ("e", 1.1755236750469E-02),
+        ("sqrta", 5.1536713142395E+03),
+        ("omega0", -2.5819032103342E+00),
+        ("omega", 8.8289568852064E-01),
+        ("omegaDot", -8.1374461033113E-09),
+        ("deltaN", 4.6216639341136E-09),
+        ("m0", m0_rad),
+        ("i0", 9.6360153298290E-01),
+        ("idot", -1.7143571247551E-10),
+    ] {
+        ephemeris = ephemeris.with_orbit(field, OrbitItem::F64(value));
+    }
+
+    ephemeris
+}
+
+#[test]
+fn ubx_mga_gps_m0_semicircles() {
+    let sv = SV::from_str("G01").unwrap();
+    let toc = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+
+    // pi/4 rad is exactly 0.25 semicircle
+    let ephemeris = gps_mga_ephemeris(std::f64::consts::FRAC_PI_4);
+
+    let bytes = ephemeris.to_ubx_mga_gps_qzss(toc, sv).unwrap();
+
+    let mut parser: Parser<Vec<u8>> = Parser::default();
+    let mut packets = parser.consume_ubx(&bytes);
+
+    match packets.next() {
+        Some(Ok(UbxPacket::Proto23(PacketRef::MgaGpsEph(ubx)))) => {
+            let m0_semicircles = ubx.m0_semicircles();
+            assert!(
+                (m0_semicircles - 0.25).abs() < 1.0E-9,
+                "invalid m0: {} semicircles",
+                m0_semicircles
+            );
+        },
+        _ => panic!("did not encode a valid MGA-GPS-EPH frame"),
+    }
+}
+
+#[test]
+fn ubx_mga_gps_m0_reencoding() {
+    let sv = SV::from_str("G01").unwrap();
+    let toc = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+
+    let m0_rad = 1.0;
+    let ephemeris = gps_mga_ephemeris(m0_rad);
+
+    // MGA-GPS-EPH resolution on m0 is 2^-31 semicircle
+    let resolution = 2.0_f64.powi(-31) * std::f64::consts::PI;
+
+    let mut parser: Parser<Vec<u8>> = Parser::default();
+
+    let mut decode = |bytes: &[u8]| {
+        let mut packets = parser.consume_ubx(bytes);
+        match packets.next() {
+            Some(Ok(UbxPacket::Proto23(PacketRef::MgaGpsEph(ubx)))) => {
+                Ephemeris::from_ubx_mga_gps(ubx)
+            },
+            _ => panic!("did not encode a valid MGA-GPS-EPH frame"),
+        }
+    };
+
+    // decode
+    let bytes = ephemeris.to_ubx_mga_gps_qzss(toc, sv).unwrap();
+    let (decoded_sv, decoded) = decode(&bytes);
+    assert_eq!(decoded_sv, sv);
+
+    let decoded_m0 = decoded.get_orbit_f64("m0").unwrap();
+    assert!((decoded_m0 - m0_rad).abs() <= resolution);
+
+    // re-encode
+    let bytes = decoded.to_ubx_mga_gps_qzss(toc, sv).unwrap();
+    let (_, reencoded) = decode(&bytes);
+
+    let reencoded_m0 = reencoded.get_orbit_f64("m0").unwrap();
+    assert!(
+        (reencoded_m0 - m0_rad).abs() <= resolution,
+        "m0 not recovered: {} rad",
+        reencoded_m0
+    );
+
+    // IS-GPS-200 Table 20-III scale factors (semicircles, or semicircles.s⁻¹)
+    for (field, scale) in [
+        ("omega0", 2.0_f64.powi(-31)),
+        ("omega", 2.0_f64.powi(-31)),
+        ("omegaDot", 2.0_f64.powi(-43)),
+        ("deltaN", 2.0_f64.powi(-43)),
+        ("i0", 2.0_f64.powi(-31)),
+        ("idot", 2.0_f64.powi(-43)),
+    ] {
+        let resolution = scale * std::f64::consts::PI;
+        let model = ephemeris.get_orbit_f64(field).unwrap();
+        let dut = reencoded.get_orbit_f64(field).unwrap();
+        assert!(
+            (dut - model).abs() <= resolution,
+            "\"{}\" not recovered: {} rad (resolution {:e} rad)",
+            field,
+            dut,
+            resolution
+        );
+    }
+}
+
 // MGA-EPH-XXX
 #[test]
 #[cfg(feature = "nav")]
@@ -39,13 +165,16 @@ fn esbcdnk_ephv3_to_ubx_mga() {
                             match packet {
                                 PacketRef::MgaGpsEph(encoded) => {
                                     // run mirror OP
-                                    let (decoded_sv, decoded_eph) =
-                                        Ephemeris::from_ubx_mga_gps(encoded);
+                                    let (decoded_sv, _) = Ephemeris::from_ubx_mga_gps(encoded);
 
                                     assert_eq!(decoded_sv, k.sv);
 
-                                    // TODO: testbench
-                                    // assert_eq!(decoded_eph, ephemeris.clone());
+                                    assert!(assert_ephemeris_roundtrip(
+                                        ephemeris,
+                                        k.epoch,
+                                        k.sv,
+                                        RoundtripFormat::Ubx,
+                                    ));
 
                                     gps += 1;
                                 },
@@ -69,13 +198,16 @@ fn esbcdnk_ephv3_to_ubx_mga() {
                             match packet {
                                 PacketRef::MgaGpsEph(encoded) => {
                                     // run mirror OP
-                                    let (decoded_sv, decoded_eph) =
-                                        Ephemeris::from_ubx_mga_qzss(encoded);
+                                    let (decoded_sv, _) = Ephemeris::from_ubx_mga_qzss(encoded);
 
                                     assert_eq!(decoded_sv, k.sv);
 
-                                    // TODO: testbench
-                                    // assert_eq!(decoded_eph, ephemeris.clone());
+                                    assert!(assert_ephemeris_roundtrip(
+                                        ephemeris,
+                                        k.epoch,
+                                        k.sv,
+                                        RoundtripFormat::Ubx,
+                                    ));
 
                                     qzss += 1;
                                 },
@@ -99,13 +231,16 @@ fn esbcdnk_ephv3_to_ubx_mga() {
                             match packet {
                                 PacketRef::MgaBdsEph(encoded) => {
                                     // run mirror OP
-                                    let (decoded_sv, decoded_eph) =
-                                        Ephemeris::from_ubx_mga_bds(encoded);
+                                    let (decoded_sv, _) = Ephemeris::from_ubx_mga_bds(encoded);
 
                                     assert_eq!(decoded_sv, k.sv);
 
-                                    // TODO: testbench
-                                    // assert_eq!(decoded_eph, ephemeris.clone());
+                                    assert!(assert_ephemeris_roundtrip(
+                                        ephemeris,
+                                        k.epoch,
+                                        k.sv,
+                                        RoundtripFormat::Ubx,
+                                    ));
 
                                     bds += 1;
                                 },
@@ -134,8 +269,18 @@ fn esbcdnk_ephv3_to_ubx_mga() {
 
                                     assert_eq!(decoded_sv, k.sv);
 
-                                    // TODO: testbench
-                                    // assert_eq!(decoded_eph, ephemeris.clone());
+                                    let source = decoded_eph.orbits.get("source").unwrap();
+                                    assert!(source.as_galileo_data_source().is_some());
+
+                                    let health = decoded_eph.orbits.get("health").unwrap();
+                                    assert!(health.as_galileo_health_flag().is_some());
+
+                                    assert!(assert_ephemeris_roundtrip(
+                                        ephemeris,
+                                        k.epoch,
+                                        k.sv,
+                                        RoundtripFormat::Ubx,
+                                    ));
 
                                     gal += 1;
                                 },
@@ -168,6 +313,77 @@ fn esbcdnk_ephv3_to_ubx_mga() {
     println!("UBX-MGA-GAL: {:4} GAL frames", gal);
 }
 
+#[test]
+fn ubx_mga_gps_semicircles() {
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    let sv = SV::from_str("G10").unwrap();
+    let toc = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+
+    let mut ephemeris = Ephemeris::default();
+
+    // angular terms are chosen to be exact semicircle fractions
+    for (field, value) in [
+        ("toe", 345600.0),
+        ("tgd", -3.259629011154E-09),
+        ("iodc", 426.0),
+        ("health", 0.0),
+        ("cuc", -1.853704452515E-06),
+        ("cus", 1.013278961182E-05),
+        ("cic", -2.518296241760E-06),
+        ("cis", -8.456408977509E-07),
+        ("crc", -366.25),
+        ("crs", -54.375),
+        ("e", 7.516302773729E-03),
+        ("sqrta", 5.153623002625E+03),
+        ("omega0", -FRAC_PI_2),
+        ("omega", FRAC_PI_4),
+        ("omegaDot", -PI * 2.0_f64.powi(-30)),
+        ("deltaN", PI * 2.0_f64.powi(-30)),
+        ("m0", -FRAC_PI_4),
+        ("i0", 0.3 * PI),
+        ("idot", PI * 2.0_f64.powi(-35)),
+    ] {
+        ephemeris = ephemeris.with_orbit(field, OrbitItem::F64(value));
+    }
+
+    let bytes = ephemeris.to_ubx_mga_gps_qzss(toc, sv).unwrap();
+
+    let mut parser: Parser<Vec<u8>> = Parser::default();
+    let mut packets = parser.consume_ubx(&bytes);
+
+    let ubx = match packets.next() {
+        Some(Ok(UbxPacket::Proto23(PacketRef::MgaGpsEph(ubx)))) => ubx,
+        _ => panic!("did not encode a valid MGA-GPS-EPH frame"),
+    };
+
+    // UBX streams angular terms in semicircles
+    assert!((ubx.omega0_semicircles() + 0.5).abs() < 1.0E-9);
+    assert!((ubx.omega_semicircles() - 0.25).abs() < 1.0E-9);
+    assert!((ubx.m0_semicircles() + 0.25).abs() < 1.0E-9);
+    assert!((ubx.i0_semicircles() - 0.3).abs() < 1.0E-9);
+
+    // decoder converts back to radians, including i0
+    let (decoded_sv, decoded) = Ephemeris::from_ubx_mga_gps(ubx);
+    assert_eq!(decoded_sv, sv);
+
+    for field in ["omega0", "omega", "omegaDot", "deltaN", "m0", "i0", "idot"] {
+        let expected = ephemeris.get_orbit_f64(field).unwrap();
+
+        let decoded = decoded
+            .get_orbit_f64(field)
+            .unwrap_or_else(|| panic!("{} not decoded", field));
+
+        assert!(
+            (decoded - expected).abs() <= expected.abs() * 1.0E-6,
+            "{}: decoded {} rad, expecting {}",
+            field,
+            decoded,
+            expected
+        );
+    }
+}
+
 // MGA-EPH-GLO
 #[test]
 #[ignore]
@@ -303,6 +519,114 @@ fn esbcdnk_nav3_to_ubx() {
     );
 }
 
+// RNX2UBX with a buffer smaller than a MGA-BDS-EPH frame
+#[test]
+fn esbcdnk_nav3_to_ubx_small_buffer() {
+    let rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let stream = |buffer: &mut [u8]| {
+        let mut streamer = rinex.rnx2ubx();
+        let mut bytes = Vec::new();
+
+        loop {
+            match streamer.read(buffer) {
+                Ok(0) => break,
+                Ok(size) => {
+                    assert!(size <= buffer.len());
+                    bytes.extend_from_slice(&buffer[..size]);
+                },
+                Err(e) => panic!("streaming error: {}", e),
+            }
+        }
+
+        bytes
+    };
+
+    let mut small = [0; 64];
+    let mut large = [0; 2048];
+
+    let small_stream = stream(&mut small);
+    let large_stream = stream(&mut large);
+
+    assert!(!small_stream.is_empty());
+    assert_eq!(small_stream, large_stream);
+
+    // all frames were forwarded and are complete
+    let mut mga_bds_eph = 0;
+    let mut parser: Parser<Vec<u8>> = Parser::default();
+    let mut packets = parser.consume_ubx(&small_stream);
+
+    while let Some(packet) = packets.next() {
+        match packet {
+            Ok(UbxPacket::Proto23(PacketRef::MgaBdsEph(_))) => mga_bds_eph += 1,
+            Ok(_) => {},
+            Err(e) => panic!("invalid UBX content: {}", e),
+        }
+    }
+
+    assert_eq!(mga_bds_eph, 360);
+}
+
+#[test]
+fn esbcdnk_nav3_to_ubx_estimated_frame_count() {
+    let rinex = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz").unwrap();
+
+    let mut buffer = [0; 1024];
+    let mut streamer = rinex.rnx2ubx();
+    let estimated = streamer.estimated_frame_count();
+
+    let mut bytes = Vec::new();
+
+    loop {
+        match streamer.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(size) => bytes.extend_from_slice(&buffer[..size]),
+            Err(e) => panic!("streaming error: {}", e),
+        }
+    }
+
+    let mut frames = 0;
+    let mut parser: Parser<Vec<u8>> = Parser::default();
+    let mut packets = parser.consume_ubx(&bytes);
+
+    while let Some(packet) = packets.next() {
+        assert!(packet.is_ok(), "invalid UBX content");
+        frames += 1;
+    }
+
+    assert!(frames > 0);
+    assert!(frames <= estimated);
+
+    // the estimate only misses the frames that could not be encoded
+    let missing_fields = streamer
+        .skipped_report()
+        .iter()
+        .filter(|(_, _, reason)| *reason == SkipReason::MissingFields)
+        .count();
+
+    assert_eq!(frames + missing_fields, estimated);
+
+    // Galileo is not supported: frames are reported
+    let num_gal = rinex
+        .nav_ephemeris_frames_iter()
+        .filter(|(k, _)| k.sv.constellation == Constellation::Galileo)
+        .count();
+
+    assert!(num_gal > 0);
+
+    let skipped_gal = streamer
+        .skipped_report()
+        .iter()
+        .filter(|(_, sv, _)| sv.constellation == Constellation::Galileo)
+        .collect::<Vec<_>>();
+
+    assert_eq!(skipped_gal.len(), num_gal);
+
+    for (_, _, reason) in skipped_gal {
+        assert_eq!(*reason, SkipReason::NonSupportedConstellation);
+    }
+}
+
 // MGA-TIM-XXX
 #[test]
 fn esbcdnk_timv4_to_ubx_mga() {
@@ -321,3 +645,190 @@ fn esbcdnk_timv4_to_ubx_mga() {
         }
     }
 }
+
+/// Forges one UBX-RXM-RAWX frame.
+/// Measurements are (gnssId, svId, sigId, pseudo range, phase, doppler, cno),
+/// each one is tracked with the (lock time in ms, trkStat) of the same index.
+fn forge_rxm_rawx(
+    tow: f64,
+    week: u16,
+    meas: &[(u8, u8, u8, f64, f64, f32, u8)],
+    tracking: &[(u16, u8)],
+) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(16 + 32 * meas.len());
+
+    payload.extend_from_slice(&tow.to_le_bytes());
+    payload.extend_from_slice(&week.to_le_bytes());
+    payload.push(18); // leap
+    payload.push(meas.len() as u8);
+    payload.push(0x01); // rec stat
+    payload.push(0x01); // version
+    payload.extend_from_slice(&[0, 0]);
+
+    for ((gnss_id, sv_id, sig_id, pr, cp, dop, cno), (lock_ms, trk_stat)) in
+        meas.iter().zip(tracking.iter())
+    {
+        payload.extend_from_slice(&pr.to_le_bytes());
+        payload.extend_from_slice(&cp.to_le_bytes());
+        payload.extend_from_slice(&dop.to_le_bytes());
+        payload.extend_from_slice(&[*gnss_id, *sv_id, *sig_id, 0]);
+        payload.extend_from_slice(&lock_ms.to_le_bytes());
+        payload.extend_from_slice(&[*cno, 0x01, 0x01, 0x01, *trk_stat, 0]);
+    }
+
+    let mut frame = vec![0xb5, 0x62, 0x02, 0x15];
+    frame.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+    frame.extend_from_slice(&payload);
+
+    let (mut ck_a, mut ck_b) = (0u8, 0u8);
+    for byte in frame[2..].iter() {
+        ck_a = ck_a.wrapping_add(*byte);
+        ck_b = ck_b.wrapping_add(ck_a);
+    }
+
+    frame.push(ck_a);
+    frame.push(ck_b);
+    frame
+}
+
+// RXM-RAWX to OBS RINEX
+#[test]
+fn ubx_rxm_rawx_to_obs() {
+    let mut stream = Vec::new();
+
+    stream.extend(forge_rxm_rawx(
+        86400.0,
+        2300,
+        &[
+            (0, 1, 0, 20243517.560, 106380022.528, -1234.567, 45),
+            (0, 1, 3, 20243519.120, 82893470.125, -961.975, 38),
+            (2, 5, 0, 23453517.120, 123250011.250, 650.125, 42),
+            (6, 4, 0, 21154321.450, 113059842.375, 320.500, 40),
+        ],
+        &[(1000, 0x07); 4],
+    ));
+
+    // unknown signal is dropped, invalid phase (trkStat) is dropped
+    stream.extend(forge_rxm_rawx(
+        86401.0,
+        2300,
+        &[
+            (0, 1, 0, 20243749.370, 106381240.125, -1234.750, 45),
+            (0, 1, 15, 20243749.370, 106381240.125, -1234.750, 45),
+            (2, 5, 0, 23453388.240, 123250023.500, 650.250, 42),
+        ],
+        &[(2000, 0x07), (2000, 0x07), (2000, 0x01)],
+    ));
+
+    let rinex = Rinex::from_ubx_stream(stream.as_slice()).unwrap();
+
+    assert!(rinex.is_observation_rinex());
+    assert_eq!(rinex.header.constellation, Some(Constellation::Mixed));
+
+    let t0 = Epoch::from_time_of_week(2300, 86_400_000_000_000, TimeScale::GPST);
+    let t1 = Epoch::from_time_of_week(2300, 86_401_000_000_000, TimeScale::GPST);
+
+    assert_eq!(rinex.epoch_iter().collect::<Vec<_>>(), vec![t0, t1]);
+    assert_eq!(rinex.sv_iter().count(), 3);
+
+    let g01 = SV::from_str("G01").unwrap();
+    let e05 = SV::from_str("E05").unwrap();
+
+    let c1c = Observable::from_str("C1C").unwrap();
+    let l2l = Observable::from_str("L2L").unwrap();
+    let l1c = Observable::from_str("L1C").unwrap();
+
+    let signals = rinex
+        .signal_observations_iter()
+        .map(|(k, sig)| (k.epoch, sig.sv, sig.observable.clone(), sig.value))
+        .collect::<Vec<_>>();
+
+    // 4 observations per signal, except for the last one
+    assert_eq!(signals.len(), 4 * 4 + 4 + 3);
+
+    assert!(signals.contains(&(t0, g01, c1c.clone(), 20243517.560)));
+    assert!(signals.contains(&(t0, g01, l2l, 82893470.125)));
+    assert!(signals.contains(&(t1, e05, c1c, 23453388.240)));
+    assert!(!signals
+        .iter()
+        .any(|(t, sv, obs, _)| *t == t1 && *sv == e05 && *obs == l1c));
+}
+
+// RXM-RAWX tracking status to LLI
+#[test]
+fn ubx_rxm_rawx_tracking_status() {
+    let mut stream = Vec::new();
+
+    // G01: locked, G02: half cycle unresolved, G03: pseudo range invalid
+    stream.extend(forge_rxm_rawx(
+        100.0,
+        2300,
+        &[
+            (0, 1, 0, 20243517.560, 106380022.528, -1234.567, 45),
+            (0, 2, 0, 21243517.560, 111635022.528, -234.567, 40),
+            (0, 3, 0, 22243517.560, 116890022.528, 734.567, 35),
+        ],
+        &[(5000, 0x07), (5000, 0x03), (5000, 0x06)],
+    ));
+
+    // G01: lock time reset, G02: half cycle resolved, G03: lock time is null
+    stream.extend(forge_rxm_rawx(
+        101.0,
+        2300,
+        &[
+            (0, 1, 0, 20243749.370, 106381240.125, -1234.750, 45),
+            (0, 2, 0, 21243749.370, 111636240.125, -234.750, 40),
+            (0, 3, 0, 22243749.370, 116891240.125, 734.750, 35),
+        ],
+        &[(500, 0x07), (6000, 0x07), (0, 0x07)],
+    ));
+
+    // G01: half cycle subtracted, G03: locked
+    stream.extend(forge_rxm_rawx(
+        102.0,
+        2300,
+        &[
+            (0, 1, 0, 20243981.180, 106382457.722, -1234.933, 45),
+            (0, 3, 0, 22243981.180, 116892457.722, 734.933, 35),
+        ],
+        &[(1500, 0x0f), (1000, 0x07)],
+    ));
+
+    let rinex = Rinex::from_ubx_stream(stream.as_slice()).unwrap();
+
+    let t = |tow: u64| Epoch::from_time_of_week(2300, tow * 1_000_000_000, TimeScale::GPST);
+
+    let c1c = Observable::from_str("C1C").unwrap();
+    let l1c = Observable::from_str("L1C").unwrap();
+
+    let lli = |t: Epoch, sv: &str| {
+        let sv = SV::from_str(sv).unwrap();
+        rinex
+            .signal_observations_iter()
+            .find(|(k, sig)| k.epoch == t && sig.sv == sv && sig.observable == l1c)
+            .and_then(|(_, sig)| sig.lli)
+            .unwrap_or_else(|| panic!("missing {}({}) L1C", t, sv))
+    };
+
+    assert_eq!(lli(t(100), "G01"), LliFlags::OK_OR_UNKNOWN);
+    assert_eq!(lli(t(100), "G02"), LliFlags::HALF_CYCLE_SLIP);
+    assert_eq!(lli(t(100), "G03"), LliFlags::OK_OR_UNKNOWN);
+
+    assert_eq!(lli(t(101), "G01"), LliFlags::LOCK_LOSS);
+    assert_eq!(lli(t(101), "G02"), LliFlags::OK_OR_UNKNOWN);
+    assert_eq!(lli(t(101), "G03"), LliFlags::LOCK_LOSS);
+
+    assert_eq!(lli(t(102), "G01"), LliFlags::LOCK_LOSS);
+    assert_eq!(lli(t(102), "G03"), LliFlags::OK_OR_UNKNOWN);
+
+    // invalid pseudo range is dropped
+    let g03 = SV::from_str("G03").unwrap();
+
+    assert!(!rinex
+        .signal_observations_iter()
+        .any(|(k, sig)| k.epoch == t(100) && sig.sv == g03 && sig.observable == c1c));
+
+    assert!(rinex
+        .signal_observations_iter()
+        .any(|(k, sig)| k.epoch == t(101) && sig.sv == g03 && sig.observable == c1c));
+}
@@ -0,0 +1,140 @@
+//! Per satellite time series
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::{
+    navigation::Ephemeris,
+    prelude::{Epoch, Observable, Rinex, SV},
+};
+
+/// [SatelliteTimeSeries] gathers everything a [Rinex] describes about one [SV],
+/// which is typically the starting point of per satellite analysis.
+/// Refer to [Rinex::satellite_timeseries].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SatelliteTimeSeries {
+    /// [SV] described by this series
+    pub sv: SV,
+    /// Signal observations, per [Observable], in chronological order.
+    /// Only defined for Observation RINEX.
+    pub observations: BTreeMap<Observable, Vec<(Epoch, f64)>>,
+    /// [Ephemeris] frames, indexed by time of clock, in chronological order.
+    /// Only defined for Navigation RINEX.
+    pub ephemerides: Vec<(Epoch, Ephemeris)>,
+    /// Resolved ECEF (x, y, z) coordinates (in km), at each observation [Epoch].
+    /// Only defined when combining Observation and Navigation RINEX,
+    /// see [Rinex::satellite_timeseries_with_navigation].
+    pub positions: Vec<(Epoch, (f64, f64, f64))>,
+}
+
+impl SatelliteTimeSeries {
+    /// Returns the [Epoch]s at which this [SV] was observed, in chronological order.
+    pub fn observation_epochs(&self) -> BTreeSet<Epoch> {
+        self.observations
+            .values()
+            .flat_map(|series| series.iter().map(|(t, _)| *t))
+            .collect()
+    }
+
+    /// Returns true if this series does not contain anything.
+    pub fn is_empty(&self) -> bool {
+        self.observations.is_empty() && self.ephemerides.is_empty() && self.positions.is_empty()
+    }
+}
+
+impl Rinex {
+    /// Extracts the complete [SatelliteTimeSeries] of this [SV] from this [Rinex]:
+    /// - signal observations, per [Observable], for Observation RINEX
+    /// - [Ephemeris] frames, for Navigation RINEX.
+    ///
+    /// A single [Rinex] cannot resolve positions: combine your Observation RINEX
+    /// with a Navigation RINEX using [Self::satellite_timeseries_with_navigation].
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    ///
+    /// let rinex = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// let g01 = SV::from_str("G01").unwrap();
+    /// let series = rinex.satellite_timeseries(g01);
+    ///
+    /// for (observable, observations) in series.observations.iter() {
+    ///     for (epoch, value) in observations.iter() {
+    ///         // per observable analysis
+    ///     }
+    /// }
+    /// ```
+    pub fn satellite_timeseries(&self, sv: SV) -> SatelliteTimeSeries {
+        let mut observations = BTreeMap::<Observable, Vec<(Epoch, f64)>>::new();
+
+        for (k, signal) in self.signal_observations_iter() {
+            if signal.sv == sv {
+                observations
+                    .entry(signal.observable.clone())
+                    .or_default()
+                    .push((k.epoch, signal.value));
+            }
+        }
+
+        let ephemerides = self
+            .nav_ephemeris_frames_iter()
+            .filter_map(|(k, eph)| {
+                if k.sv == sv {
+                    Some((k.epoch, eph.clone()))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        SatelliteTimeSeries {
+            sv,
+            observations,
+            ephemerides,
+            positions: Default::default(),
+        }
+    }
+
+    /// Extracts the [SatelliteTimeSeries] of this [SV], combining this Observation [Rinex]
+    /// with a Navigation [Rinex]: observations are picked up from this [Rinex], ephemerides
+    /// from the Navigation [Rinex], and the position is resolved at each observation [Epoch]
+    /// (see [Self::sv_orbit]). Epochs for which no [Ephemeris] applies are not resolved.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    ///
+    /// let obs = Rinex::from_gzip_file("data/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz")
+    ///     .unwrap();
+    ///
+    /// let nav = Rinex::from_gzip_file("data/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    ///
+    /// let g01 = SV::from_str("G01").unwrap();
+    /// let series = obs.satellite_timeseries_with_navigation(&nav, g01);
+    ///
+    /// for (epoch, (x_km, y_km, z_km)) in series.positions.iter() {
+    ///     // position at observation epoch
+    /// }
+    /// ```
+    #[cfg(feature = "nav")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "nav")))]
+    pub fn satellite_timeseries_with_navigation(&self, nav: &Self, sv: SV) -> SatelliteTimeSeries {
+        let mut series = self.satellite_timeseries(sv);
+        series.ephemerides = nav.satellite_timeseries(sv).ephemerides;
+
+        series.positions = series
+            .observation_epochs()
+            .into_iter()
+            .filter_map(|t| {
+                let orbit = nav.sv_orbit(sv, t)?;
+                let pos_km = orbit.radius_km;
+                Some((t, (pos_km.x, pos_km.y, pos_km.z)))
+            })
+            .collect();
+
+        series
+    }
+}
@@ -0,0 +1,195 @@
+//! UBX stream to [Rinex] decoding
+use std::{collections::HashMap, io::Read, str::FromStr};
+
+use crate::{
+    observation::{LliFlags, SignalObservation, SNR},
+    prelude::{
+        Constellation, Duration, Epoch, Header, Observable, ParsingError, Rinex, TimeScale,
+        Version, SV,
+    },
+};
+
+use ublox::{packetref_proto23::PacketRef, Parser, UbxPacket};
+
+/// RXM-RAWX trkStat: pseudo range is valid
+const TRK_STAT_PR_VALID: u8 = 0x01;
+
+/// RXM-RAWX trkStat: carrier phase is valid
+const TRK_STAT_CP_VALID: u8 = 0x02;
+
+/// RXM-RAWX trkStat: half cycle ambiguity is resolved
+const TRK_STAT_HALF_CYC: u8 = 0x04;
+
+/// RXM-RAWX trkStat: half cycle was subtracted from the carrier phase
+const TRK_STAT_SUB_HALF_CYC: u8 = 0x08;
+
+/// Identifies the [SV] from UBX (gnssId, svId) identifiers.
+fn ubx_sv(gnss_id: u8, sv_id: u8) -> Option<SV> {
+    match gnss_id {
+        0 => Some(SV::new(Constellation::GPS, sv_id)),
+        1 if sv_id >= 120 => Some(SV::new(Constellation::SBAS, sv_id - 100)),
+        2 => Some(SV::new(Constellation::Galileo, sv_id)),
+        3 => Some(SV::new(Constellation::BeiDou, sv_id)),
+        5 => Some(SV::new(Constellation::QZSS, sv_id)),
+        6 if sv_id != 255 => Some(SV::new(Constellation::Glonass, sv_id)),
+        7 => Some(SV::new(Constellation::IRNSS, sv_id)),
+        _ => None,
+    }
+}
+
+/// Converts UBX (gnssId, sigId) identifiers to RINEX (V3) frequency and
+/// tracking code, for example "1C" for GPS L1 C/A.
+fn ubx_signal_code(gnss_id: u8, sig_id: u8) -> Option<&'static str> {
+    match (gnss_id, sig_id) {
+        (0, 0) => Some("1C"),
+        (0, 3) => Some("2L"),
+        (0, 4) => Some("2S"),
+        (0, 6) => Some("5I"),
+        (0, 7) => Some("5Q"),
+        (1, 0) => Some("1C"),
+        (2, 0) => Some("1C"),
+        (2, 1) => Some("1B"),
+        (2, 3) => Some("5I"),
+        (2, 4) => Some("5Q"),
+        (2, 5) => Some("7I"),
+        (2, 6) => Some("7Q"),
+        (3, 0) | (3, 1) => Some("2I"),
+        (3, 2) | (3, 3) => Some("7I"),
+        (3, 5) => Some("1P"),
+        (3, 7) => Some("5P"),
+        (5, 0) => Some("1C"),
+        (5, 1) => Some("1Z"),
+        (5, 4) => Some("2S"),
+        (5, 5) => Some("2L"),
+        (5, 8) => Some("5I"),
+        (5, 9) => Some("5Q"),
+        (6, 0) => Some("1C"),
+        (6, 2) => Some("2C"),
+        (7, 0) => Some("5A"),
+        _ => None,
+    }
+}
+
+impl Rinex {
+    /// Decodes a U-Blox receiver stream (UBX protocol) into an Observation [Rinex].
+    /// Each `RXM-RAWX` message defines one [Epoch] (expressed in GPST), from which
+    /// we generate the pseudo range, carrier phase, doppler and C/N0 observations
+    /// of each tracked signal. Pseudo ranges and carrier phases are only generated
+    /// when the tracking status (trkStat) declares them valid.
+    /// Carrier phases are tagged with [LliFlags]:
+    /// - [LliFlags::LOCK_LOSS] when the lock time was reset since the previous epoch,
+    ///   or when the half cycle correction was toggled
+    /// - [LliFlags::HALF_CYCLE_SLIP] when the half cycle ambiguity is not resolved.
+    ///
+    /// All other UBX messages are currently ignored.
+    ///
+    /// ```
+    /// use rinex::prelude::Rinex;
+    ///
+    /// // any readable interface: serial port, log file..
+    /// let stream = std::io::empty();
+    ///
+    /// let rinex = Rinex::from_ubx_stream(stream)
+    ///     .unwrap();
+    ///
+    /// assert!(rinex.is_observation_rinex());
+    /// assert_eq!(rinex.epoch_iter().count(), 0);
+    /// ```
+    pub fn from_ubx_stream<R: Read>(mut reader: R) -> Result<Self, ParsingError> {
+        let mut rinex = Self::new_observation(Version::new(3, 5), &Header::basic_obs());
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        let mut parser: Parser<Vec<u8>> = Parser::default();
+        let mut packets = parser.consume_ubx(&bytes);
+
+        // previous (epoch, half cycle subtracted) of each carrier phase
+        let mut tracking = HashMap::<(SV, &'static str), (Epoch, bool)>::new();
+
+        while let Some(packet) = packets.next() {
+            let rawx = match packet {
+                Ok(UbxPacket::Proto23(PacketRef::RxmRawx(rawx))) => rawx,
+                _ => continue,
+            };
+
+            let tow_nanos = (rawx.rcv_tow() * 1.0E9).round() as u64;
+            let epoch = Epoch::from_time_of_week(rawx.week() as u32, tow_nanos, TimeScale::GPST);
+
+            let mut observations = Vec::with_capacity(rawx.num_meas() as usize * 4);
+
+            for meas in rawx.measurements() {
+                let sv = match ubx_sv(meas.gnss_id, meas.sv_id) {
+                    Some(sv) => sv,
+                    None => continue,
+                };
+
+                let code = match ubx_signal_code(meas.gnss_id, meas.sig_id) {
+                    Some(code) => code,
+                    None => continue, // non supported signal
+                };
+
+                let snr = SNR::from(meas.cno as f64);
+
+                let pr_valid = meas.trk_stat & TRK_STAT_PR_VALID > 0;
+                let cp_valid = meas.trk_stat & TRK_STAT_CP_VALID > 0;
+                let half_cyc = meas.trk_stat & TRK_STAT_HALF_CYC > 0;
+                let sub_half_cyc = meas.trk_stat & TRK_STAT_SUB_HALF_CYC > 0;
+
+                let mut lli = LliFlags::OK_OR_UNKNOWN;
+
+                if cp_valid {
+                    let lock_time = Duration::from_milliseconds(meas.lock_time as f64);
+
+                    let lock_loss = match tracking.get(&(sv, code)) {
+                        Some((prev_epoch, prev_sub_half_cyc)) => {
+                            lock_time < epoch - *prev_epoch || *prev_sub_half_cyc != sub_half_cyc
+                        },
+                        None => meas.lock_time == 0,
+                    };
+
+                    if lock_loss {
+                        lli |= LliFlags::LOCK_LOSS;
+                    }
+
+                    if !half_cyc {
+                        lli |= LliFlags::HALF_CYCLE_SLIP;
+                    }
+
+                    tracking.insert((sv, code), (epoch, sub_half_cyc));
+                } else {
+                    tracking.remove(&(sv, code));
+                }
+
+                for (prefix, value, valid) in [
+                    ('C', meas.pr_mes, pr_valid),
+                    ('L', meas.cp_mes, cp_valid),
+                    ('D', meas.do_mes as f64, true),
+                    ('S', meas.cno as f64, true),
+                ] {
+                    if !valid {
+                        continue; // not resolved
+                    }
+
+                    if let Ok(observable) = Observable::from_str(&format!("{}{}", prefix, code)) {
+                        let mut observation = SignalObservation::new(sv, observable, value);
+
+                        if prefix == 'L' {
+                            observation.snr = Some(snr);
+                            observation.lli = Some(lli);
+                        }
+
+                        observations.push(observation);
+                    }
+                }
+            }
+
+            if !observations.is_empty() {
+                rinex.add_signal_observations(epoch, observations);
+            }
+        }
+
+        rinex.rebuild_header_from_record();
+        Ok(rinex)
+    }
+}
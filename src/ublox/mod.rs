@@ -1,5 +1,9 @@
-use crate::prelude::Rinex;
+use crate::{
+    navigation::SkipReason,
+    prelude::{Epoch, Rinex, SV},
+};
 
+mod decoding;
 mod nav;
 use nav::Streamer as NavStreamer;
 
@@ -27,6 +31,16 @@ impl Rinex {
     ///
     /// The stream content is RINEX dependent, and we currently only truly support NAV RINEX.
     ///
+    /// Ephemeris frames are only encoded for GPS, QZSS, BeiDou and Glonass.
+    /// Galileo (MGA-GAL-EPH) is not supported yet, and neither are SBAS and IRNSS:
+    /// those frames are not streamed, but reported with
+    /// [SkipReason::NonSupportedConstellation] in [RNX2UBX::skipped_report].
+    ///
+    /// Note that we cannot generate AssistNow Offline (MGA-ANO) assistance:
+    /// its 64 byte data block is a proprietary u-blox format, which is not described
+    /// in the interface description and cannot be derived from broadcast ephemerides.
+    /// Stream MGA-EPH frames (with up to date ephemerides) for cold start assistance.
+    ///
     /// RINEX NAV (V3) example:
     /// ```
     /// use std::io::Read;
@@ -53,11 +67,10 @@ impl Rinex {
     ///         Ok(size) => {
     ///             // example: decode all forwarded packets
     ///             // "size" is the total number of bytes, not the number of UBX frames
-    ///             // note that we only encode complete frames, not partial frames.
+    ///             // note that the last frame may be partial: it is completed by the next read.
     ///         },
     ///         Err(e) => {
-    ///             // we wind up here on buffering or system errors.
-    ///             // For example, the complete frame would not fit in the buffer.
+    ///             // we wind up here on system errors.
     ///             break;
     ///         },
     ///     }
@@ -77,14 +90,37 @@ pub struct RNX2UBX<'a> {
     streamer: TypeDependentStreamer<'a>,
 }
 
+impl<'a> RNX2UBX<'a> {
+    /// Returns the number of UBX frames this [RNX2UBX] should produce
+    /// once fully consumed, for buffer sizing or progress reporting.
+    /// This is an upper bound: frames that miss a mandatory field are only
+    /// identified while encoding, and will eventually appear in the [Self::skipped_report].
+    pub fn estimated_frame_count(&self) -> usize {
+        match &self.streamer {
+            TypeDependentStreamer::NAV(streamer) => streamer.estimated_count(),
+        }
+    }
+
+    /// Returns the frames that could not be encoded to UBX so far, as (Epoch, SV, reason).
+    /// Since [RNX2UBX] is a [Read]er, this is best inspected after the last read.
+    pub fn skipped_report(&self) -> &[(Epoch, SV, SkipReason)] {
+        match &self.streamer {
+            TypeDependentStreamer::NAV(streamer) => streamer.skipped_report(),
+        }
+    }
+}
+
 impl<'a> std::io::Read for RNX2UBX<'a> {
-    /// Fills proposed mutable buffer with as many complete UBX frames as possible.
+    /// Fills proposed mutable buffer with UBX frames.
     ///
     /// ## Inputs
     /// - buffer: mutable user buffer to which we write the UBX bytes.
     /// You can then transmit them or decode them using the UBX [Parser].
-    /// We will not encode partial frame, but will postpone the pending frame
-    /// that will not fit into a successive read that will need to be invoked later on.
+    /// Any buffer size makes progress: a frame that does not fit is split, its remainder
+    /// is buffered internally and forwarded by the next read(s). Frames are therefore
+    /// not aligned to read boundaries and should be fed to a streaming UBX [Parser].
+    /// Buffers smaller than 96 bytes (our largest frame, MGA-BDS-EPH) will
+    /// need several reads to forward a single frame.
     /// As per stardards, we return Ok(0) once the [Rinex] file has been fully consumed.
     fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
         match &mut self.streamer {
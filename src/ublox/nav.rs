@@ -1,117 +1,137 @@
 use crate::{
-    navigation::{Ephemeris, NavKey},
-    prelude::{Constellation, Rinex},
+    navigation::{Ephemeris, NavKey, SkipReason},
+    prelude::{Constellation, Epoch, Rinex, SV},
 };
 
-use std::io::{Error, ErrorKind};
+/// Returns true if we can encode ephemeris of this [Constellation].
+/// Must remain consistent with `Streamer::read`.
+fn supported_constellation(constellation: Constellation) -> bool {
+    matches!(
+        constellation,
+        Constellation::GPS | Constellation::QZSS | Constellation::BeiDou | Constellation::Glonass
+    )
+}
 
 pub struct Streamer<'a> {
+    /// [Rinex] being streamed
+    rinex: &'a Rinex,
+
     /// Pending bytes
     pending_size: usize,
 
-    /// Pending frame
+    /// Offset of the first pending byte
+    pending_offset: usize,
+
+    /// Pending frame (or remainder of it).
+    /// Largest frame we generate is MGA-BDS-EPH (96 bytes).
     buffer: [u8; 1024],
 
     /// Iterator
     ephemeris_iter: Box<dyn Iterator<Item = (&'a NavKey, &'a Ephemeris)> + 'a>,
+
+    /// Frames that could not be streamed
+    skipped: Vec<(Epoch, SV, SkipReason)>,
 }
 
 impl<'a> Streamer<'a> {
     pub fn new(rinex: &'a Rinex) -> Self {
         Self {
+            rinex,
             pending_size: 0,
+            pending_offset: 0,
             buffer: [0; 1024],
             ephemeris_iter: rinex.nav_ephemeris_frames_iter(),
+            skipped: Vec::new(),
         }
     }
+
+    /// Number of frames this [Streamer] should produce, when fully consumed.
+    pub fn estimated_count(&self) -> usize {
+        self.rinex
+            .nav_ephemeris_frames_iter()
+            .filter(|(k, _)| supported_constellation(k.sv.constellation))
+            .count()
+    }
+
+    /// Frames that were skipped so far.
+    pub fn skipped_report(&self) -> &[(Epoch, SV, SkipReason)] {
+        &self.skipped
+    }
 }
 
 impl<'a> std::io::Read for Streamer<'a> {
     fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        if buffer.is_empty() {
+            return Ok(0);
+        }
+
         let mut size = 0;
-        let mut size_avail = buffer.len();
 
         if self.pending_size > 0 {
-            if size_avail < self.pending_size {
-                return Err(Error::new(ErrorKind::StorageFull, "would not fit"));
-            } else {
-                size += self.pending_size;
-                size_avail -= self.pending_size;
-                buffer[..self.pending_size].copy_from_slice(&self.buffer[..self.pending_size]);
-                self.pending_size = 0;
+            // flush what we can: frames may span several reads
+            size = self.pending_size.min(buffer.len());
+
+            buffer[..size]
+                .copy_from_slice(&self.buffer[self.pending_offset..self.pending_offset + size]);
+
+            self.pending_size -= size;
+            self.pending_offset += size;
+
+            if self.pending_size > 0 {
+                return Ok(size);
             }
         }
 
         loop {
-            match self.ephemeris_iter.next() {
-                Some((key, ephemeris)) => match key.sv.constellation {
-                    Constellation::GPS => {
-                        if let Some(bytes) = ephemeris.to_ubx_mga_gps_qzss(key.epoch, key.sv) {
-                            let new_len = bytes.len();
-
-                            if size_avail > new_len {
-                                buffer[size..size + new_len].copy_from_slice(&bytes);
-                                size += new_len;
-                                size_avail -= new_len;
-                            } else {
-                                self.pending_size = new_len;
-                                self.buffer[..new_len].copy_from_slice(&bytes);
-                                return Ok(size);
-                            }
-                        }
-                    },
-                    Constellation::QZSS => {
-                        if let Some(bytes) = ephemeris.to_ubx_mga_gps_qzss(key.epoch, key.sv) {
-                            let new_len = bytes.len();
-
-                            if size_avail > new_len {
-                                buffer[size..size + new_len].copy_from_slice(&bytes);
-                                size += new_len;
-                                size_avail -= new_len;
-                            } else {
-                                self.pending_size = new_len;
-                                self.buffer[..new_len].copy_from_slice(&bytes);
-                                return Ok(size);
-                            }
-                        }
-                    },
-                    Constellation::BeiDou => {
-                        if let Some(bytes) = ephemeris.to_ubx_mga_bds(key.epoch, key.sv) {
-                            let new_len = bytes.len();
-
-                            if size_avail > new_len {
-                                buffer[size..size + new_len].copy_from_slice(&bytes);
-                                size += new_len;
-                                size_avail -= new_len;
-                            } else {
-                                self.pending_size = new_len;
-                                self.buffer[..new_len].copy_from_slice(&bytes);
-                                return Ok(size);
-                            }
-                        }
-                    },
-                    Constellation::Glonass => {
-                        if let Some(bytes) = ephemeris.to_ubx_mga_glo(key.sv) {
-                            let new_len = bytes.len();
-
-                            if size_avail > new_len {
-                                buffer[size..size + new_len].copy_from_slice(&bytes);
-                                size += new_len;
-                                size_avail -= new_len;
-                            } else {
-                                self.pending_size = new_len;
-                                self.buffer[..new_len].copy_from_slice(&bytes);
-                                return Ok(size);
-                            }
-                        }
-                    },
-                    _ => {
-                        // frame not supported
-                    },
-                },
+            let (key, ephemeris) = match self.ephemeris_iter.next() {
+                Some(entry) => entry,
                 None => {
                     return Ok(size);
                 },
+            };
+
+            let bytes = match key.sv.constellation {
+                Constellation::GPS | Constellation::QZSS => ephemeris
+                    .to_ubx_mga_gps_qzss(key.epoch, key.sv)
+                    .map(|bytes| bytes.to_vec()),
+                Constellation::BeiDou => ephemeris
+                    .to_ubx_mga_bds(key.epoch, key.sv)
+                    .map(|bytes| bytes.to_vec()),
+                Constellation::Glonass => {
+                    ephemeris.to_ubx_mga_glo(key.sv).map(|bytes| bytes.to_vec())
+                },
+                _ => {
+                    // frame not supported
+                    self.skipped
+                        .push((key.epoch, key.sv, SkipReason::NonSupportedConstellation));
+                    continue;
+                },
+            };
+
+            let bytes = match bytes {
+                Some(bytes) => bytes,
+                None => {
+                    self.skipped
+                        .push((key.epoch, key.sv, SkipReason::MissingFields));
+                    continue;
+                },
+            };
+
+            let new_len = bytes.len();
+            let size_avail = buffer.len() - size;
+
+            if size_avail >= new_len {
+                buffer[size..size + new_len].copy_from_slice(&bytes);
+                size += new_len;
+            } else {
+                // stream the beginning of this frame, retain the remainder
+                buffer[size..].copy_from_slice(&bytes[..size_avail]);
+
+                self.pending_offset = 0;
+                self.pending_size = new_len - size_avail;
+                self.buffer[..self.pending_size].copy_from_slice(&bytes[size_avail..]);
+
+                return Ok(buffer.len());
             }
         }
     }